@@ -0,0 +1,40 @@
+//! Procedural macros for the `glob` crate.
+//!
+//! Don't depend on this crate directly, enable the `macros` feature of the `glob` crate instead and
+//! use the re-exported [`glob!`] macro.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, LitStr};
+
+/// validates a glob pattern at compile time and expands to a lazily parsed
+/// `&'static ParsedGlobString<'static>`.
+///
+/// The pattern is parsed on first use only, and parsing cannot fail at runtime, since the macro
+/// already rejects malformed patterns during compilation:
+/// ```
+/// use glob::glob;
+/// assert!(glob!("*.y?ml").matches_partially("deployment.yaml"));
+/// ```
+/// ```compile_fail
+/// use glob::glob;
+/// let pattern = glob!("Foo\\n"); // unknown escape sequence
+/// ```
+#[proc_macro]
+pub fn glob(input: TokenStream) -> TokenStream {
+    let literal = parse_macro_input!(input as LitStr);
+    let pattern = literal.value();
+    if let Err(error) = glob_parser::parse_events(&pattern, |_| {}) {
+        let message = format!("invalid glob pattern: {}", error);
+        return syn::Error::new(literal.span(), message).to_compile_error().into();
+    }
+    let expanded = quote! {
+        {
+            static PATTERN: ::std::sync::OnceLock<::glob::ParsedGlobString<'static>> = ::std::sync::OnceLock::new();
+            PATTERN.get_or_init(|| {
+                ::glob::ParsedGlobString::try_from(#literal).expect("pattern was validated by glob!")
+            })
+        }
+    };
+    return expanded.into();
+}
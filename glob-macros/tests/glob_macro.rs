@@ -0,0 +1,36 @@
+use glob::{glob, ParsedGlobString};
+
+#[test]
+fn test_glob_macro_matches() {
+    assert!(glob!("*.y?ml").matches_partially("path/to/deployment.yaml"));
+    assert!(!glob!("*.y?ml").matches_partially("path/to/deployment.json"));
+}
+
+#[test]
+fn test_glob_macro_with_escapes() {
+    assert!(glob!("\\*\\?\\\\").matches_partially("*?\\"));
+}
+
+#[test]
+fn test_glob_macro_returns_same_static_each_time() {
+    fn pattern() -> &'static ParsedGlobString<'static> {
+        glob!("thesis-*.pdf")
+    }
+    assert!(std::ptr::eq(pattern(), pattern()));
+}
+
+#[test]
+fn test_glob_match_takes_first_matching_arm() {
+    let kind = |path: &str| glob::glob_match!(path => {
+        "Cargo.*" => 0,
+        "*.toml" | "*.lock" => 1,
+        "*" => 2,
+        _ => 3,
+    });
+    assert_eq!(kind("Cargo.toml"), 0);
+    assert_eq!(kind("rustfmt.toml"), 1);
+    assert_eq!(kind("src/lib.rs"), 2);
+    assert_eq!(kind(""), 2);
+    let empty = glob::glob_match!(String::from("x") => { _ => "default" });
+    assert_eq!(empty, "default");
+}
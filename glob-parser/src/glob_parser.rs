@@ -0,0 +1,788 @@
+use std::cell::RefCell;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::iter::FusedIterator;
+use std::ops::Range;
+use Token::{MinLengthWildcard, ExactLengthWildcard, Literal, WordBoundary};
+use GlobParseError::{UnknownEscapeSequence, UnterminatedEscapeSequence};
+use crate::multislice::MultiSlice;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token<'g> {
+    ExactLengthWildcard(usize), // length
+    MinLengthWildcard(usize), // minimum length
+    Literal(MultiSlice<'g>),
+    WordBoundary, // matches the empty string between a word character and a non-word character
+}
+
+impl<'g> Token<'g> {
+    pub fn into_owned(self) -> Token<'static> {
+        match self {
+            ExactLengthWildcard(length) => ExactLengthWildcard(length),
+            MinLengthWildcard(length) => MinLengthWildcard(length),
+            Literal(multi_slice) => Literal(multi_slice.into_owned()),
+            WordBoundary => WordBoundary,
+        }
+    }
+}
+
+/// returned if parsing a glob string fails, e.g.:
+/// ```
+/// # use glob::ParsedGlobString;
+/// # use glob::GlobParseError;
+/// let pattern = ParsedGlobString::try_from("Foo\\n");
+/// assert!(pattern.is_err());
+/// assert_eq!(pattern.unwrap_err(), GlobParseError::UnknownEscapeSequence(3, "\\n"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GlobParseError<'g> {
+    /// returned when there is an unsupported escape sequence, i.e. a (unescaped) backslash
+    /// any character other than `*`, `?`, `\` or `b`. Encapsulates the index at which the escape
+    /// sequence is found in the pattern string and the escape sequence itself.
+    UnknownEscapeSequence(usize, &'g str), //index, escape sequence
+    /// returned when there is an unescaped backslash at the end of the pattern string. Encapsulates
+    /// the index at which the offending backslash is in the pattern string.
+    UnterminatedEscapeSequence(usize), // index
+}
+
+/// identifies the kind of a [`GlobParseError`], e.g. to look up a translated error message.
+///
+/// New kinds of errors may be added along with new pattern syntax, so matches on this enum need a
+/// wildcard arm.
+/// ```
+/// use glob::{ErrorCode, ParsedGlobString};
+/// let error = ParsedGlobString::try_from("*.txt\\").unwrap_err();
+/// let message = match error.code() {
+///     ErrorCode::UnterminatedEscapeSequence => format!("Das Muster endet mit `{}`.", error.fragment()),
+///     _ => error.to_string(),
+/// };
+/// assert_eq!(message, "Das Muster endet mit `\\`.");
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    /// see [`GlobParseError::UnknownEscapeSequence`].
+    UnknownEscapeSequence,
+    /// see [`GlobParseError::UnterminatedEscapeSequence`].
+    UnterminatedEscapeSequence,
+}
+
+impl<'g> GlobParseError<'g> {
+    /// returns the kind of this error.
+    pub fn code(&self) -> ErrorCode {
+        return match self {
+            UnknownEscapeSequence(_, _) => ErrorCode::UnknownEscapeSequence,
+            UnterminatedEscapeSequence(_) => ErrorCode::UnterminatedEscapeSequence,
+        };
+    }
+
+    /// returns the byte index in the pattern string at which the offending part starts.
+    pub fn position(&self) -> usize {
+        return match self {
+            UnknownEscapeSequence(index, _) | UnterminatedEscapeSequence(index) => *index,
+        };
+    }
+
+    /// returns the offending part of the pattern string.
+    pub fn fragment(&self) -> &'g str {
+        return match self {
+            UnknownEscapeSequence(_, sequence) => sequence,
+            UnterminatedEscapeSequence(_) => "\\",
+        };
+    }
+}
+
+impl<'g> Display for GlobParseError<'g> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnknownEscapeSequence(index, sequence) => write!(f, "unknown escape sequence `{}` at index {}", sequence, index),
+            UnterminatedEscapeSequence(index) => write!(f, "unterminated escape sequence at index {}", index),
+        }
+    }
+}
+
+impl<'g> Error for GlobParseError<'g> {}
+
+/// a [`GlobParseError`] that owns a copy of the offending part of the pattern string instead of
+/// borrowing it, so it can be propagated with `?` out of functions that own the pattern string.
+/// ```
+/// use glob::{OwnedGlobParseError, ParsedGlobString};
+/// fn count_matches(pattern: String, lines: &[&str]) -> Result<usize, OwnedGlobParseError> {
+///     let pattern = ParsedGlobString::try_from(pattern.as_str())?;
+///     return Ok(lines.iter().filter(|line| pattern.matches_partially(line)).count());
+/// }
+/// let error = count_matches(String::from("*\\n"), &[]).unwrap_err();
+/// assert_eq!(error.to_string(), "unknown escape sequence `\\n` at index 1");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OwnedGlobParseError {
+    code: ErrorCode,
+    position: usize,
+    fragment: String,
+}
+
+impl OwnedGlobParseError {
+    /// returns the kind of this error.
+    pub fn code(&self) -> ErrorCode {
+        return self.code;
+    }
+
+    /// returns the byte index in the pattern string at which the offending part starts.
+    pub fn position(&self) -> usize {
+        return self.position;
+    }
+
+    /// returns the offending part of the pattern string.
+    pub fn fragment(&self) -> &str {
+        return &self.fragment;
+    }
+}
+
+impl<'g> GlobParseError<'g> {
+    /// copies the offending part of the pattern string into an error that doesn't borrow it.
+    pub fn into_owned(self) -> OwnedGlobParseError {
+        return OwnedGlobParseError { code: self.code(), position: self.position(), fragment: String::from(self.fragment()) };
+    }
+}
+
+impl<'g> From<GlobParseError<'g>> for OwnedGlobParseError {
+    fn from(error: GlobParseError<'g>) -> Self {
+        return error.into_owned();
+    }
+}
+
+impl Display for OwnedGlobParseError {
+    /// formats the error like the [`GlobParseError`] it was created from.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.code {
+            ErrorCode::UnknownEscapeSequence => write!(f, "unknown escape sequence `{}` at index {}", self.fragment, self.position),
+            ErrorCode::UnterminatedEscapeSequence => write!(f, "unterminated escape sequence at index {}", self.position),
+        }
+    }
+}
+
+impl Error for OwnedGlobParseError {}
+
+/// which wildcard a [`ParseEvent::Wildcard`] stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WildcardKind {
+    /// `*`, zero or more characters.
+    Star,
+    /// `?`, exactly one character.
+    QuestionMark,
+}
+
+/// a part of a pattern recognized by [`parse_events`], with its byte range in the pattern string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseEvent<'g> {
+    /// a run of characters without special meaning, which are matched literally.
+    Literal { text: &'g str, span: Range<usize> },
+    /// a wildcard.
+    Wildcard { kind: WildcardKind, span: Range<usize> },
+    /// an escape sequence like `\*`, whose `character` is matched literally.
+    Escape { character: char, span: Range<usize> },
+    /// the escape sequence `\b`, which matches a word boundary.
+    WordBoundary { span: Range<usize> },
+}
+
+/// selects which pattern syntax the parser accepts, so patterns written for an earlier version of
+/// this crate keep parsing exactly as they did when new syntax is added.
+///
+/// [`extended`](Self::extended) is everything this version of the crate supports, and is what
+/// `ParsedGlobString::try_from` uses; it grows as new syntax
+/// is added. [`basic`](Self::basic) is the syntax of the first release and never changes. Each
+/// addition can be switched on and off on its own, so a corpus of patterns can pin exactly the
+/// syntax it was written for with `GlobBuilder::syntax`. Syntax
+/// that is switched off is an error, just like before it was added.
+/// ```
+/// use glob::{GlobBuilder, GlobParseError, Syntax};
+/// assert!(GlobBuilder::new("\\bid").syntax(Syntax::extended()).build().unwrap().is_match("id=1"));
+/// let error = GlobBuilder::new("\\bid").syntax(Syntax::basic()).build().unwrap_err();
+/// assert_eq!(error, GlobParseError::UnknownEscapeSequence(0, "\\b"));
+/// assert_eq!(Syntax::basic().word_boundaries(true), Syntax::extended());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Syntax {
+    word_boundaries: bool,
+}
+
+impl Syntax {
+    /// returns the syntax of the first release: the wildcards `*` and `?`, and the escape
+    /// sequences `\*`, `\?` and `\\`.
+    pub const fn basic() -> Self {
+        return Syntax { word_boundaries: false };
+    }
+
+    /// returns all syntax this version of the crate supports.
+    pub const fn extended() -> Self {
+        return Syntax { word_boundaries: true };
+    }
+
+    /// sets whether the escape sequence `\b` matches a word boundary.
+    pub const fn word_boundaries(mut self, yes: bool) -> Self {
+        self.word_boundaries = yes;
+        return self;
+    }
+
+    /// checks if the escape sequence `\b` matches a word boundary.
+    pub const fn has_word_boundaries(&self) -> bool {
+        return self.word_boundaries;
+    }
+}
+
+impl Default for Syntax {
+    fn default() -> Self {
+        return Syntax::extended();
+    }
+}
+
+/// parses a pattern and passes each part of it to `sink` as soon as it is recognized, from left to
+/// right, without building the tokens of a `ParsedGlobString`. This is
+/// the basis for tools like formatters and translators to other pattern syntaxes.
+///
+/// Unlike the tokens, the events follow the pattern string exactly: consecutive wildcards are
+/// reported one by one and escape sequences separately from the literals around them. If the
+/// pattern is malformed, the events before the error have been passed to `sink` already.
+/// ```
+/// use glob::{parse_events, ParseEvent, WildcardKind};
+/// let mut events = Vec::new();
+/// parse_events("*.tx\\?", |event| events.push(event)).unwrap();
+/// assert_eq!(events, vec![
+///     ParseEvent::Wildcard { kind: WildcardKind::Star, span: 0..1 },
+///     ParseEvent::Literal { text: ".tx", span: 1..4 },
+///     ParseEvent::Escape { character: '?', span: 4..6 },
+/// ]);
+/// ```
+pub fn parse_events<'g>(pattern: &'g str, sink: impl FnMut(ParseEvent<'g>)) -> Result<(), GlobParseError<'g>> {
+    return parse_events_with(pattern, Syntax::extended(), sink);
+}
+
+/// parses a pattern with the given syntax like [`parse_events`] does.
+pub fn parse_events_with<'g>(pattern: &'g str, syntax: Syntax, sink: impl FnMut(ParseEvent<'g>)) -> Result<(), GlobParseError<'g>> {
+    return scan(pattern, syntax, sink, Result::Err);
+}
+
+/// the parser behind [`parse_events_with`] and [`parse_partial`]. `on_error` decides whether
+/// parsing stops at an error, by returning it, or continues after the offending part.
+fn scan<'g>(pattern: &'g str, syntax: Syntax, mut sink: impl FnMut(ParseEvent<'g>), mut on_error: impl FnMut(GlobParseError<'g>) -> Result<(), GlobParseError<'g>>) -> Result<(), GlobParseError<'g>> {
+    let mut tokenize = tokenize_with(pattern, syntax);
+    while let Option::Some(part) = tokenize.next_part() {
+        match part {
+            Ok(event) => sink(event),
+            Err(error) => on_error(error)?,
+        }
+    }
+    return Result::Ok(());
+}
+
+/// iterates over the parts of a pattern, see [`tokenize`].
+#[derive(Debug, Clone)]
+pub struct Tokenize<'g> {
+    pattern: &'g str,
+    syntax: Syntax,
+    position: usize, // where the next part starts
+    failed: bool,
+}
+
+impl<'g> Tokenize<'g> {
+    /// recognizes the part at the current position and moves past it, also if it is malformed.
+    fn next_part(&mut self) -> Option<Result<ParseEvent<'g>, GlobParseError<'g>>> {
+        let start = self.position;
+        let rest = &self.pattern[start..];
+        let c = rest.chars().next()?;
+        if !matches!(c, '*' | '?' | '\\') {
+            let end = rest.find(['*', '?', '\\']).map_or(self.pattern.len(), |offset| start + offset);
+            self.position = end;
+            return Some(Ok(ParseEvent::Literal { text: &self.pattern[start..end], span: start..end }));
+        }
+        self.position = start + 1;
+        let kind = match c {
+            '*' => WildcardKind::Star,
+            '?' => WildcardKind::QuestionMark,
+            _ => {
+                let Option::Some(escaped) = rest[1..].chars().next() else {
+                    return Some(Err(UnterminatedEscapeSequence(start)));
+                };
+                self.position = start + 1 + escaped.len_utf8();
+                let span = start..self.position;
+                return Some(match escaped {
+                    '*' | '?' | '\\' => Ok(ParseEvent::Escape { character: escaped, span: span }),
+                    'b' if self.syntax.word_boundaries => Ok(ParseEvent::WordBoundary { span: span }),
+                    _ => Err(UnknownEscapeSequence(start, &self.pattern[span])),
+                });
+            },
+        };
+        return Some(Ok(ParseEvent::Wildcard { kind: kind, span: start..start + 1 }));
+    }
+}
+
+impl<'g> Iterator for Tokenize<'g> {
+    type Item = Result<ParseEvent<'g>, GlobParseError<'g>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let part = self.next_part();
+        self.failed = matches!(part, Option::Some(Err(_)));
+        return part;
+    }
+}
+
+impl<'g> FusedIterator for Tokenize<'g> {}
+
+/// returns an iterator over the parts of a pattern, which recognizes each part only when it is
+/// requested. It yields the same parts as [`parse_events`] passes to its sink, and the error if
+/// the pattern is malformed, after which it ends. Consumers that look at a pattern once, like
+/// validators and converters, need neither a callback nor the tokens of a
+/// `ParsedGlobString`.
+/// ```
+/// use glob::{tokenize, ParseEvent, WildcardKind};
+/// let mut parts = tokenize("*.t\\x");
+/// assert_eq!(parts.next(), Some(Ok(ParseEvent::Wildcard { kind: WildcardKind::Star, span: 0..1 })));
+/// assert_eq!(parts.next(), Some(Ok(ParseEvent::Literal { text: ".t", span: 1..3 })));
+/// assert_eq!(parts.next().unwrap().unwrap_err().to_string(), "unknown escape sequence `\\x` at index 3");
+/// assert_eq!(parts.next(), None);
+/// let has_wildcards = tokenize("src/*.rs").any(|part| matches!(part, Ok(ParseEvent::Wildcard { .. })));
+/// assert!(has_wildcards);
+/// ```
+pub fn tokenize(pattern: &str) -> Tokenize<'_> {
+    return tokenize_with(pattern, Syntax::extended());
+}
+
+/// returns an iterator over the parts of a pattern with the given syntax like [`tokenize`] does.
+pub fn tokenize_with(pattern: &str, syntax: Syntax) -> Tokenize<'_> {
+    return Tokenize { pattern: pattern, syntax: syntax, position: 0, failed: false };
+}
+
+/// a part of a pattern recognized by [`parse_partial`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartialToken<'g> {
+    /// a well-formed part, as [`parse_events`] reports it.
+    Event(ParseEvent<'g>),
+    /// a malformed part, like an unknown escape sequence. Its error is in
+    /// [`PartialParse::errors`] at the position of this token among the error tokens.
+    Error { span: Range<usize> },
+}
+
+/// the result of [`parse_partial`]: the parts of a pattern in order, with the malformed ones
+/// replaced by [`PartialToken::Error`], and the errors in the same order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialParse<'g> {
+    tokens: Vec<PartialToken<'g>>,
+    errors: Vec<GlobParseError<'g>>,
+}
+
+impl<'g> PartialParse<'g> {
+    /// returns the parts of the pattern in order.
+    pub fn tokens(&self) -> &[PartialToken<'g>] {
+        return &self.tokens;
+    }
+
+    /// returns the errors in the order they occur in the pattern string.
+    pub fn errors(&self) -> &[GlobParseError<'g>] {
+        return &self.errors;
+    }
+
+    /// checks if the pattern is well-formed, i.e. if there are no errors.
+    pub fn is_ok(&self) -> bool {
+        return self.errors.is_empty();
+    }
+}
+
+/// parses a pattern like [`parse_events_with`] does, but doesn't stop at the first error: each
+/// malformed part becomes a [`PartialToken::Error`] and parsing continues after it. Editors can
+/// highlight the whole pattern and report all of its errors at once while it is being typed.
+/// ```
+/// use glob::{parse_partial, GlobParseError, ParseEvent, PartialToken, Syntax, WildcardKind};
+/// let parsed = parse_partial("\\x*\\", Syntax::extended());
+/// assert_eq!(parsed.tokens(), &[
+///     PartialToken::Error { span: 0..2 },
+///     PartialToken::Event(ParseEvent::Wildcard { kind: WildcardKind::Star, span: 2..3 }),
+///     PartialToken::Error { span: 3..4 },
+/// ]);
+/// assert_eq!(parsed.errors(), &[
+///     GlobParseError::UnknownEscapeSequence(0, "\\x"),
+///     GlobParseError::UnterminatedEscapeSequence(3),
+/// ]);
+/// ```
+pub fn parse_partial(pattern: &str, syntax: Syntax) -> PartialParse<'_> {
+    let tokens = RefCell::new(Vec::new());
+    let mut errors = Vec::new();
+    let result = scan(pattern, syntax, |event| tokens.borrow_mut().push(PartialToken::Event(event)), |error| {
+        let span = error.position()..error.position() + error.fragment().len();
+        tokens.borrow_mut().push(PartialToken::Error { span: span });
+        errors.push(error);
+        return Result::Ok(());
+    });
+    debug_assert!(result.is_ok());
+    return PartialParse { tokens: tokens.into_inner(), errors: errors };
+}
+
+/// returns the byte ranges of the parts of `pattern` between the occurrences of `delimiter` that
+/// aren't escaped with a backslash, so tools that split lists of patterns or paths into components
+/// treat escapes like the parser does. A backslash escapes any character here, and the parts keep
+/// their escape sequences. Splitting never fails; the parts are parsed, and checked, separately.
+/// ```
+/// use glob::split_pattern;
+/// let list = r"*.rs,\\,*.t\,x,";
+/// let parts: Vec<_> = split_pattern(list, ',').into_iter().map(|span| &list[span]).collect();
+/// assert_eq!(parts, vec!["*.rs", r"\\", r"*.t\,x", ""]);
+/// ```
+pub fn split_pattern(pattern: &str, delimiter: char) -> Vec<Range<usize>> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut escaped = false;
+    for (index, c) in pattern.char_indices() {
+        if c == delimiter && !escaped {
+            parts.push(start..index);
+            start = index + c.len_utf8();
+            continue;
+        }
+        escaped = c == '\\' && !escaped;
+    }
+    parts.push(start..pattern.len());
+    return parts;
+}
+
+fn merge_wildcard_tokens<'g>(token1: Token, token2: Token) -> Token<'g> {
+    match (token1, token2) {
+        (ExactLengthWildcard(length1), ExactLengthWildcard(length2)) => ExactLengthWildcard(length1 + length2),
+        (MinLengthWildcard(min_length1) | ExactLengthWildcard(min_length1), MinLengthWildcard(min_length2) | ExactLengthWildcard(min_length2)) => {
+            MinLengthWildcard(min_length1 + min_length2)
+        },
+        (token1, token2) => panic!("one of the tokens is not a wildcard: {:?}, {:?}", token1, token2),
+    }
+}
+
+fn append_wildcard_to_token_sequence<'g>(token_sequence : &mut Vec<Token<'g>>, token: Token<'g>) {
+    let last_token : Option<Token<'g>> = token_sequence.pop();
+    match last_token {
+        Option::None => token_sequence.push(token),
+        Option::Some(last_token) => match last_token {
+            Literal(_) | WordBoundary => {
+                token_sequence.push(last_token);
+                token_sequence.push(token);
+            },
+            wildcard => token_sequence.push(merge_wildcard_tokens(wildcard, token)),
+        },
+    }
+}
+fn append_literal_to_token_sequence<'g>(token_sequence: &mut Vec<Token<'g>>, literal: &'g str) {
+    let last_token = token_sequence.last_mut();
+    match last_token {
+        Option::None => {
+            let literal_token = Literal(MultiSlice::from(literal));
+            token_sequence.push(literal_token);
+        },
+        Option::Some(last_token) => match last_token {
+            Literal(multi_slice) => multi_slice.push(literal),
+            ExactLengthWildcard(_) | MinLengthWildcard(_) | WordBoundary => {
+                token_sequence.push(Literal(MultiSlice::from(literal)))
+            }
+        }
+    }
+}
+
+/// appends a token that was parsed from `span` of the pattern string to tokens parsed from the part
+/// before it, merging adjacent literals and adjacent wildcards like the parser does.
+pub fn append_token<'g>(tokens: &mut Vec<Token<'g>>, spans: &mut Vec<Range<usize>>, token: Token<'g>, span: Range<usize>) {
+    let merged = match (tokens.last_mut(), token) {
+        (Option::Some(Literal(last)), Literal(literal)) => {
+            last.append(literal);
+            true
+        },
+        (Option::Some(last @ (ExactLengthWildcard(_) | MinLengthWildcard(_))), wildcard @ (ExactLengthWildcard(_) | MinLengthWildcard(_))) => {
+            *last = merge_wildcard_tokens(last.clone(), wildcard);
+            true
+        },
+        (_, token) => {
+            tokens.push(token);
+            false
+        },
+    };
+    match spans.last_mut() {
+        Option::Some(last) if merged => last.end = span.end,
+        _ => spans.push(span),
+    }
+}
+
+/// parses a pattern string with the extended syntax into tokens.
+pub fn parse_glob_string(str: &str) -> Result<Vec<Token<'_>>, GlobParseError<'_>> {
+    return parse_glob_string_with_spans(str, Syntax::extended()).map(|(tokens, _)| tokens);
+}
+
+/// parses a pattern string into tokens and the byte range of the pattern string each token was
+/// parsed from.
+pub fn parse_glob_string_with_spans(str: &str, syntax: Syntax) -> Result<(Vec<Token<'_>>, Vec<Range<usize>>), GlobParseError<'_>> {
+    let mut output = Vec::new();
+    let mut spans: Vec<Range<usize>> = Vec::new();
+    parse_events_with(str, syntax, |event| {
+        let span = match event {
+            ParseEvent::Literal { text, span } => {
+                append_literal_to_token_sequence(&mut output, text);
+                span
+            },
+            ParseEvent::Escape { span, .. } => {
+                // the escaped character is the last one of the escape sequence
+                append_literal_to_token_sequence(&mut output, &str[span.start + 1..span.end]);
+                span
+            },
+            ParseEvent::Wildcard { kind: WildcardKind::Star, span } => {
+                append_wildcard_to_token_sequence(&mut output, MinLengthWildcard(0));
+                span
+            },
+            ParseEvent::Wildcard { kind: WildcardKind::QuestionMark, span } => {
+                append_wildcard_to_token_sequence(&mut output, ExactLengthWildcard(1));
+                span
+            },
+            ParseEvent::WordBoundary { span } => {
+                output.push(WordBoundary);
+                span
+            },
+        };
+        // the event either started a new token or was merged into the last one
+        if spans.len() == output.len() {
+            if let Option::Some(last) = spans.last_mut() {
+                last.end = span.end;
+            }
+        } else {
+            spans.push(span);
+        }
+    })?;
+    return Result::Ok((output, spans));
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{ErrorCode, GlobParseError, OwnedGlobParseError};
+    use super::GlobParseError::*;
+    use super::{Token};
+    use super::{parse_events, parse_glob_string, parse_partial, split_pattern, tokenize, tokenize_with, ParseEvent, PartialToken, Syntax, WildcardKind};
+    use super::Token::{Literal, MinLengthWildcard, ExactLengthWildcard, WordBoundary};
+    use core::iter::zip;
+    use super::MultiSlice;
+
+    fn test_single_token(glob_string: &str, token: Token) {
+        let mut tokens = Vec::new();
+        tokens.push(token);
+        test_multiple_tokens(glob_string, &tokens);
+    }
+
+    fn test_multiple_tokens(glob_string : &str, tokens: &[Token]) {
+        let result = parse_glob_string(glob_string);
+        assert!(result.is_ok());
+        match result {
+            Ok(token_sequence) => {
+                assert_eq!(token_sequence.len(), tokens.len());
+                for (actual, expected) in zip(token_sequence.into_iter(), tokens.into_iter()) {
+                    assert_eq!(&actual, expected);
+                }
+            },
+            Err(_) => panic!("previous assert should prevent this"),
+        }
+    }
+
+    fn test_parse_failure(glob_string : &str, expected_error : GlobParseError) {
+        let result = parse_glob_string(glob_string);
+        assert!(result.is_err());
+        match result {
+            Ok(_) => panic!("previous assert should prevent this"),
+            Err(err) => assert_eq!(err, expected_error),
+        }
+    }
+
+    #[test]
+    fn test_parse_only_literal() {
+        test_single_token("abc", Literal(MultiSlice::from("abc")));
+    }
+
+    #[test]
+    fn test_parse_only_asterisk() {
+        test_single_token("*", MinLengthWildcard(0));
+    }
+
+    #[test]
+    fn test_parse_only_question_mark() {
+        test_single_token("?", ExactLengthWildcard(1));
+    }
+
+    #[test]
+    fn test_parse_multiple_wildcards() {
+        test_single_token("?*?**?", MinLengthWildcard(3));
+    }
+
+    #[test]
+    fn test_mixed_wildcard_literal_wildcard() {
+        test_multiple_tokens("*.yam?", &[Token::MinLengthWildcard(0), Token::Literal(MultiSlice::from(".yam")), Token::ExactLengthWildcard(1)]);
+    }
+
+    #[test]
+    fn test_escaped_asterisk() {
+        test_single_token("abc\\*def", Token::Literal(MultiSlice::from("abc*def")));
+    }
+
+    #[test]
+    fn test_escaped_question_mark() {
+        test_multiple_tokens("Hello *, how are you\\?", &[Literal(MultiSlice::from("Hello ")), Token::MinLengthWildcard(0), Token::Literal(MultiSlice::from(", how are you?"))]);
+    }
+
+    #[test]
+    fn test_escaped_backslash() {
+        test_single_token("\\\\", Token::Literal(MultiSlice::from("\\")));
+    }
+
+    #[test]
+    fn test_failure_with_single_backslash() {
+        test_parse_failure("\\", UnterminatedEscapeSequence(0));
+    }
+
+    #[test]
+    fn test_failure_with_backslash_at_end() {
+        test_parse_failure("abc\\", UnterminatedEscapeSequence(3));
+    }
+
+    #[test]
+    fn test_failure_with_wildcards_and_backslash_at_end() {
+        test_parse_failure("*-page-*.txt\\", UnterminatedEscapeSequence(12));
+    }
+
+    #[test]
+    fn test_failure_with_uneven_number_of_backslashes_at_end() {
+        test_parse_failure("a\\\\\\", UnterminatedEscapeSequence(3));
+    }
+
+    #[test]
+    fn test_success_with_two_backslashes_at_end() {
+        test_single_token("a\\\\", Token::Literal(MultiSlice::from("a\\")));
+    }
+
+    #[test]
+    fn test_success_with_even_number_of_backslashes_at_end() {
+        test_multiple_tokens("a\\\\\\\\", &[Literal(MultiSlice::from("a\\\\"))]);
+    }
+
+    #[test]
+    fn test_failure_with_illegal_escape_sequence() {
+        test_parse_failure("\\n", UnknownEscapeSequence(0, "\\n"));
+    }
+
+    #[test]
+    fn test_word_boundary() {
+        test_multiple_tokens("\\bfoo*\\b*", &[WordBoundary, Literal(MultiSlice::from("foo")), MinLengthWildcard(0), WordBoundary, MinLengthWildcard(0)]);
+        test_multiple_tokens("a\\b\\bc", &[Literal(MultiSlice::from("a")), WordBoundary, WordBoundary, Literal(MultiSlice::from("c"))]);
+        test_single_token("\\\\b", Literal(MultiSlice::from("\\b")));
+    }
+
+    #[test]
+    fn test_non_ascii_literal() {
+        test_multiple_tokens("grüße-*.txt", &[Literal(MultiSlice::from("grüße-")), MinLengthWildcard(0), Literal(MultiSlice::from(".txt"))]);
+    }
+
+    #[test]
+    fn test_failure_with_non_ascii_escape_sequence() {
+        test_parse_failure("ä\\ö", UnknownEscapeSequence(2, "\\ö"));
+    }
+
+    #[test]
+    fn test_error_accessors() {
+        let error = parse_glob_string("ä\\ö").unwrap_err();
+        assert_eq!((error.code(), error.position(), error.fragment()), (ErrorCode::UnknownEscapeSequence, 2, "\\ö"));
+        let error = parse_glob_string("a\\").unwrap_err();
+        assert_eq!((error.code(), error.position(), error.fragment()), (ErrorCode::UnterminatedEscapeSequence, 1, "\\"));
+    }
+
+    #[test]
+    fn test_owned_error() {
+        for pattern in ["ä\\ö", "a\\"] {
+            let error = parse_glob_string(pattern).unwrap_err();
+            let owned = OwnedGlobParseError::from(parse_glob_string(pattern).unwrap_err());
+            assert_eq!((owned.code(), owned.position(), owned.fragment()), (error.code(), error.position(), error.fragment()));
+            assert_eq!(owned.to_string(), error.to_string());
+        }
+    }
+
+    #[test]
+    fn test_error_display() {
+        assert_eq!(UnknownEscapeSequence(3, "\\n").to_string(), "unknown escape sequence `\\n` at index 3");
+        assert_eq!(UnterminatedEscapeSequence(7).to_string(), "unterminated escape sequence at index 7");
+    }
+
+    #[test]
+    fn test_parse_events() {
+        let mut events = Vec::new();
+        parse_events("??\\\\b\\bä", |event| events.push(event)).unwrap();
+        assert_eq!(events, vec![
+            ParseEvent::Wildcard { kind: WildcardKind::QuestionMark, span: 0..1 },
+            ParseEvent::Wildcard { kind: WildcardKind::QuestionMark, span: 1..2 },
+            ParseEvent::Escape { character: '\\', span: 2..4 },
+            ParseEvent::Literal { text: "b", span: 4..5 },
+            ParseEvent::WordBoundary { span: 5..7 },
+            ParseEvent::Literal { text: "ä", span: 7..9 },
+        ]);
+    }
+
+    #[test]
+    fn test_parse_events_until_error() {
+        let mut events = Vec::new();
+        assert_eq!(parse_events("a*\\n", |event| events.push(event)), Err(UnknownEscapeSequence(2, "\\n")));
+        assert_eq!(events, vec![ParseEvent::Literal { text: "a", span: 0..1 }, ParseEvent::Wildcard { kind: WildcardKind::Star, span: 1..2 }]);
+    }
+
+    #[test]
+    fn test_tokenize_agrees_with_parse_events() {
+        for pattern in ["", "abc", "a*?b", "\\*x\\b", "ä\\\\ö?", "a\\", "x\\qy", "**\\?"] {
+            let mut events = Vec::new();
+            let result = parse_events(pattern, |event| events.push(Ok(event)));
+            events.extend(result.err().map(Err));
+            assert_eq!(tokenize(pattern).collect::<Vec<_>>(), events, "{:?}", pattern);
+        }
+        let parts: Vec<_> = tokenize_with("\\b\\b", Syntax::basic()).collect();
+        assert_eq!(parts, vec![Err(UnknownEscapeSequence(0, "\\b"))]);
+    }
+
+    #[test]
+    fn test_parse_partial() {
+        let parsed = parse_partial("a\\nb\\b\\", Syntax::basic());
+        assert_eq!(parsed.tokens(), &[
+            PartialToken::Event(ParseEvent::Literal { text: "a", span: 0..1 }),
+            PartialToken::Error { span: 1..3 },
+            PartialToken::Event(ParseEvent::Literal { text: "b", span: 3..4 }),
+            PartialToken::Error { span: 4..6 },
+            PartialToken::Error { span: 6..7 },
+        ]);
+        assert_eq!(parsed.errors(), &[UnknownEscapeSequence(1, "\\n"), UnknownEscapeSequence(4, "\\b"), UnterminatedEscapeSequence(6)]);
+        assert!(!parsed.is_ok());
+        let parsed = parse_partial("ä\\\\*", Syntax::extended());
+        assert!(parsed.is_ok());
+        assert_eq!(parsed.tokens().len(), 3);
+    }
+
+    #[test]
+    fn test_split_pattern() {
+        assert_eq!(split_pattern("a,b", ','), vec![0..1, 2..3]);
+        assert_eq!(split_pattern(",", ','), vec![0..0, 1..1]);
+        assert_eq!(split_pattern("a\\\\\\,b", ','), vec![0..6]);
+        assert_eq!(split_pattern("ä→ö", '→'), vec![0..2, 5..7]);
+    }
+
+    #[test]
+    fn test_wild_mixture() {
+        let glob_str = "ab\\*c-*-?-???-?*?-de\\\\f-gh\\?i.foobar\\*?";
+        let tokens = [
+            Literal(MultiSlice::from("ab*c-")),
+            MinLengthWildcard(0), // *
+            Literal(MultiSlice::from("-")),
+            ExactLengthWildcard(1), // ?
+            Literal(MultiSlice::from("-")),
+            ExactLengthWildcard(3), // ???
+            Literal(MultiSlice::from("-")),
+            MinLengthWildcard(2), // ?*?
+            Literal(MultiSlice::from("-de\\f-gh?i.foobar*")),
+            ExactLengthWildcard(1) // ?
+        ];
+        test_multiple_tokens(glob_str, &tokens);
+    }
+
+}
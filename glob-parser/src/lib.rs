@@ -0,0 +1,10 @@
+//! The pattern parser of the `glob` crate.
+//!
+//! This crate only exists so that the `glob` crate and the `glob-macros` crate can share the
+//! parser without a dependency cycle. Don't depend on it directly, the `glob` crate re-exports
+//! everything that is meant to be public.
+
+mod glob_parser;
+pub mod multislice;
+
+pub use glob_parser::*;
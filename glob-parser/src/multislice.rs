@@ -1,10 +1,12 @@
+use std::borrow::Cow;
 use std::ops::Index;
 use std::cmp::min;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Default)]
 pub struct MultiSlice<'g> {
-    slices: Vec<&'g str>,
-    total_length: usize // combined length of all slices
+    slices: Vec<Cow<'g, str>>,
+    total_length: usize, // combined length of all slices
+    joined: Option<String>, // all slices joined into one string, if there are several
 }
 
 impl<'g> MultiSlice<'g> {
@@ -12,38 +14,94 @@ impl<'g> MultiSlice<'g> {
     pub fn new() -> Self {
         return MultiSlice {
             slices: vec!(),
-            total_length: 0
+            total_length: 0,
+            joined: None,
         };
     }
 
     pub fn push(&mut self, slice: &'g str) {
-        self.slices.push(slice);
+        self.push_cow(Cow::Borrowed(slice));
+    }
+
+    fn push_cow(&mut self, slice: Cow<'g, str>) {
+        match (&mut self.joined, self.slices.first()) {
+            (Option::Some(joined), _) => joined.push_str(&slice),
+            (Option::None, Option::Some(first)) => self.joined = Some([first.as_ref(), slice.as_ref()].concat()),
+            (Option::None, Option::None) => {},
+        }
         self.total_length += slice.len();
+        self.slices.push(slice);
     }
 
-    pub fn get(&self, index: usize) -> Option<&'g str> { // could be an implementation of SliceIndex, but that's nightly-only
-        self.slices.get(index).and_then(|slice| Some(*slice))
+    #[allow(dead_code)]
+    pub fn get(&self, index: usize) -> Option<&str> { // could be an implementation of SliceIndex, but that's nightly-only
+        self.slices.get(index).map(|slice| slice.as_ref())
     }
 
-    fn get_next_non_empty_slice(&self, index: usize) -> Option<(usize, &'g str)> {
+    fn get_next_non_empty_slice(&self, index: usize) -> Option<(usize, &str)> {
         for (i, string) in self.slices[index..].iter().enumerate() {
-            if string.len() > 0 {
+            if !string.is_empty() {
                 return Some((i + index, string));
             }
         }
         return None;
     }
 
+    /// copies all slices into a single owned string, so the result no longer borrows from the
+    /// pattern string.
+    pub fn into_owned(self) -> MultiSlice<'static> {
+        let joined = self.joined.unwrap_or_else(|| self.slices.concat());
+        return MultiSlice {
+            slices: vec!(Cow::Owned(joined)),
+            total_length: self.total_length,
+            joined: None,
+        };
+    }
+
+    /// appends the slices of `other`, so the result matches what both match one after the other.
+    pub fn append(&mut self, other: MultiSlice<'g>) {
+        for slice in other.slices {
+            self.push_cow(slice);
+        }
+    }
+
+    /// returns the number of bytes allocated on the heap for the slices, not counting the pattern
+    /// string that borrowed slices point into.
+    pub fn heap_size(&self) -> usize {
+        let owned = self.slices.iter().map(|slice| match slice {
+            Cow::Borrowed(_) => 0,
+            Cow::Owned(string) => string.capacity(),
+        });
+        let joined = self.joined.as_ref().map_or(0, String::capacity);
+        return self.slices.capacity() * std::mem::size_of::<Cow<'g, str>>() + owned.sum::<usize>() + joined;
+    }
+
     pub fn get_combined_length(&self) -> usize {
         return self.total_length;
     }
 
+    /// returns the slices joined into one string, which is kept up to date as slices are added.
+    pub fn joined(&self) -> &str {
+        return match &self.joined {
+            Option::Some(joined) => joined,
+            Option::None => self.slices.first().map_or("", |slice| slice.as_ref()),
+        };
+    }
+
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        return self.slices.iter().flat_map(|slice| slice.chars());
+    }
+
     pub fn matches_string_start(&self, string: &str) -> bool {
+        return self.matches_bytes_start(string.as_bytes());
+    }
+
+    pub fn matches_bytes_start(&self, bytes: &[u8]) -> bool {
         let mut i = 0;
-        let string_len = string.len();
+        let bytes_len = bytes.len();
         for slice in &self.slices {
             let slice_len = slice.len();
-            if slice_len > string_len - i || **slice != string[i..i + slice_len] {
+            if slice_len > bytes_len - i || bytes[i..i + slice_len] != *slice.as_bytes() {
                 return false;
             }
             i += slice_len;
@@ -57,7 +115,7 @@ impl<'g> MultiSlice<'g> {
 }
 
 impl<'g> Index<usize> for MultiSlice<'g> {
-    type Output = &'g str;
+    type Output = str;
     fn index(&self, index: usize) -> &Self::Output {
         return &self.slices[index];
     }
@@ -82,10 +140,11 @@ impl<'g> From<&'g str> for MultiSlice<'g> {
 // FIXME: this doesn't work for fixed-length arrays
 impl<'g> From<&[&'g str]> for MultiSlice<'g> {
     fn from(slices: &[&'g str]) -> MultiSlice<'g> {
-        return MultiSlice {
-            slices: Vec::from(slices),
-            total_length: slices.iter().map(|slice| slice.len()).sum(),
+        let mut multi_slice = MultiSlice::new();
+        for slice in slices {
+            multi_slice.push(slice);
         }
+        return multi_slice;
     }
 }
 
@@ -93,7 +152,7 @@ impl<'g> PartialEq<str> for MultiSlice<'g> {
     fn eq(&self, other: &str) -> bool {
         let mut position = 0;
         for slice in &self.slices {
-            if **slice == other[position..position + other.len()] {
+            if *slice == other[position..position + other.len()] {
                 position += other.len();
             } else {
                 return false;
@@ -113,9 +172,7 @@ impl<'g> PartialEq<MultiSlice<'g>> for MultiSlice<'g> {
         loop {
             let left_slice = self.get_next_non_empty_slice(left_slice_no);
             let right_slice = other.get_next_non_empty_slice(right_slice_no);
-            if left_slice.is_none() && !right_slice.is_none() {
-                return false
-            } else if right_slice.is_none() && !left_slice.is_none() {
+            if left_slice.is_none() != right_slice.is_none() {
                 return false
             } else if left_slice.is_none() && right_slice.is_none() {
                 return true
@@ -133,13 +190,13 @@ impl<'g> PartialEq<MultiSlice<'g>> for MultiSlice<'g> {
                 return false
             } else {
                 if chars_to_be_compared == chars_remaining_left {
-                    left_slice_no = left_slice_no + 1;
+                    left_slice_no += 1;
                     left_slice_index = 0;
                 } else {
                     left_slice_index += chars_to_be_compared;
                 }
                 if chars_to_be_compared == chars_remaining_right {
-                    right_slice_no = right_slice_no + 1;
+                    right_slice_no += 1;
                     right_slice_index = 0;
                 } else {
                     right_slice_index += chars_to_be_compared;
@@ -174,7 +231,7 @@ impl<'g, 's> Iterator for AllMultiSliceOccurencesIterator<'g, 's> {
             Option::None => {
                 let current_search_position = self.next_search_position;
                 if current_search_position <= self.string.len() {
-                    self.next_search_position += 1;
+                    self.next_search_position += self.string[current_search_position..].chars().next().map_or(1, char::len_utf8);
                     return Some(current_search_position);
                 } else {
                     return None;
@@ -191,7 +248,8 @@ impl<'g, 's> Iterator for AllMultiSliceOccurencesIterator<'g, 's> {
                         },
                         Some(index) => {
                             let absolute_position = current_search_position + index;
-                            self.next_search_position = absolute_position + 1;
+                            // the slice is non-empty, so it starts with a character at `absolute_position`
+                            self.next_search_position = absolute_position + self.string[absolute_position..].chars().next().unwrap().len_utf8();
                             if self.slices.matches_string_start(&self.string[absolute_position..]) {
                                 return Some(absolute_position);
                             }
@@ -463,6 +521,26 @@ mod tests {
         assert_eq!(occurences.as_slice(), &[]);
     }
 
+    #[test]
+    fn test_joined() {
+        assert_eq!(MultiSlice::new().joined(), "");
+        assert_eq!(MultiSlice::from("ab").joined(), "ab");
+        let mut ms = MultiSlice::from(["ab", "*"].as_slice());
+        ms.append(MultiSlice::from("c"));
+        assert_eq!(ms.joined(), "ab*c");
+        assert_eq!(ms.clone().into_owned().joined(), "ab*c");
+    }
+
+    #[test]
+    fn test_find_all_occurences_with_non_ascii_characters() {
+        let ms = MultiSlice::from("é");
+        let occurences : Vec<usize> = ms.find_all_occurences_in("aéé").collect();
+        assert_eq!(occurences.as_slice(), &[1, 3]);
+        let ms = MultiSlice::new();
+        let occurences : Vec<usize> = ms.find_all_occurences_in("éa").collect();
+        assert_eq!(occurences.as_slice(), &[0, 2, 3]);
+    }
+
     #[test]
     fn test_find_all_occurences_with_non_empty_slice_and_matching_string() {
         let ms = MultiSlice::from("Hello, World");
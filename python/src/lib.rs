@@ -0,0 +1,67 @@
+//! Python bindings for the `glob` crate.
+//!
+//! Build and install the module with [maturin](https://www.maturin.rs/), e.g. `maturin develop`
+//! in this directory, then use it from Python:
+//! ```python
+//! from libglob import Pattern
+//! pattern = Pattern("*.y?ml")
+//! assert pattern.matches("path/to/deployment.yaml")
+//! assert pattern.find("a.yaml b") == (0, 6)
+//! assert pattern.filter(["a.yaml", "b.json"]) == ["a.yaml"]
+//! ```
+//! The matching semantics are exactly those of the Rust library, so Python scripts and Rust
+//! services agree on which strings a pattern matches. The tests in `tests/` run with `pytest`
+//! after `maturin develop`.
+
+use pyo3::pymodule;
+
+#[pymodule]
+mod libglob {
+    use glob::ParsedGlobString;
+    use pyo3::exceptions::PyValueError;
+    use pyo3::prelude::*;
+
+    /// A parsed glob pattern.
+    #[pyclass(frozen)]
+    struct Pattern {
+        pattern: String,
+        parsed: ParsedGlobString<'static>,
+    }
+
+    /// converts a byte offset into `string` to the character offset Python uses for indexing.
+    fn char_offset(string: &str, byte_offset: usize) -> usize {
+        return string[..byte_offset].chars().count();
+    }
+
+    #[pymethods]
+    impl Pattern {
+        /// parses the pattern, raising a `ValueError` if it is malformed.
+        #[new]
+        fn new(pattern: &str) -> PyResult<Self> {
+            let parsed = ParsedGlobString::try_from(pattern)
+                .map_err(|error| PyValueError::new_err(error.to_string()))?
+                .into_owned();
+            return Ok(Pattern { pattern: String::from(pattern), parsed: parsed });
+        }
+
+        /// checks if the pattern occurs anywhere in the given string.
+        fn matches(&self, string: &str) -> bool {
+            return self.parsed.matches_partially(string);
+        }
+
+        /// returns the `(start, end)` character offsets of the leftmost occurrence of the pattern,
+        /// or `None` if it doesn't occur in the given string.
+        fn find(&self, string: &str) -> Option<(usize, usize)> {
+            return self.parsed.find(string).map(|range| (char_offset(string, range.start), char_offset(string, range.end)));
+        }
+
+        /// returns the strings the pattern occurs in, in their original order.
+        fn filter(&self, strings: Vec<String>) -> Vec<String> {
+            return strings.into_iter().filter(|string| self.parsed.matches_partially(string)).collect();
+        }
+
+        fn __repr__(&self) -> String {
+            return format!("Pattern({:?})", self.pattern);
+        }
+    }
+}
@@ -0,0 +1,33 @@
+"""Smoke tests of the Python bindings; run `maturin develop` and then `pytest tests` in the
+`python` directory."""
+
+import pytest
+
+from libglob import Pattern
+
+
+def test_matches():
+    pattern = Pattern("*.y?ml")
+    assert pattern.matches("path/to/deployment.yaml")
+    assert pattern.matches("values.yaml.bak")
+    assert not pattern.matches("values.json")
+
+
+def test_find_returns_character_offsets():
+    assert Pattern("*.y?ml").find("a.yaml b") == (0, 6)
+    assert Pattern("c*").find("éé café") == (3, 4)
+    assert Pattern("x").find("abc") is None
+
+
+def test_filter_keeps_the_order():
+    assert Pattern("*.rs").filter(["lib.rs", "README.md", "main.rs"]) == ["lib.rs", "main.rs"]
+    assert Pattern("*.rs").filter([]) == []
+
+
+def test_malformed_pattern_raises_value_error():
+    with pytest.raises(ValueError, match="unterminated escape sequence"):
+        Pattern("a\\")
+
+
+def test_repr():
+    assert repr(Pattern("*.txt")) == 'Pattern("*.txt")'
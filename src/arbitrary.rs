@@ -0,0 +1,67 @@
+use std::ops::ControlFlow;
+use arbitrary::{Arbitrary, Result, Unstructured};
+use glob_parser::Token;
+use crate::{Anchor, ParsedGlobString, WildcardKind};
+
+/// the largest number of literals, wildcards and word boundaries in a generated pattern.
+const MAX_PARTS: u32 = 16;
+
+/// generates a pattern from the parts it consists of instead of from a pattern string, so every
+/// generated pattern is valid, and fuzz targets exercise the matcher rather than the error paths of
+/// the parser. Requires the `arbitrary` feature.
+///
+/// The pattern is a sequence of literals, `*`, `?` and `\b`, and has one of the anchors. The other
+/// options are the default ones; use [`GlobBuilder`](crate::GlobBuilder) with the
+/// [`Display`](std::fmt::Display) output of the pattern to match it with other options.
+/// ```
+/// use arbitrary::{Arbitrary, Unstructured};
+/// use glob::ParsedGlobString;
+/// let mut data = Unstructured::new(b"\x03\x01\x00\x02ab\x01\x02\x03");
+/// let pattern = ParsedGlobString::arbitrary(&mut data).unwrap();
+/// let pattern_string = pattern.to_string();
+/// assert_eq!(ParsedGlobString::try_from(pattern_string.as_str()).unwrap().dump(), pattern.dump());
+/// ```
+/// A fuzz target for `cargo fuzz` can take patterns as its input directly:
+/// ```ignore
+/// fuzz_target!(|input: (ParsedGlobString<'static>, String)| {
+///     let (pattern, haystack) = input;
+///     assert_eq!(pattern.matches_partially(&haystack), pattern.find(&haystack).is_some());
+/// });
+/// ```
+impl<'a> Arbitrary<'a> for ParsedGlobString<'static> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut pattern = ParsedGlobString::try_from("").expect("the empty pattern is valid").into_owned();
+        pattern.anchor = *u.choose(&[Anchor::None, Anchor::Start, Anchor::End, Anchor::Both])?;
+        u.arbitrary_loop(None, Some(MAX_PARTS), |u| {
+            match u.int_in_range(0..=3)? {
+                0 => pattern.push_literal(<&str>::arbitrary(u)?),
+                1 => pattern.push_wildcard(WildcardKind::Star),
+                2 => pattern.push_wildcard(WildcardKind::QuestionMark),
+                _ => pattern.push_token(Token::WordBoundary),
+            }
+            return Ok(ControlFlow::Continue(()));
+        })?;
+        return Ok(pattern);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arbitrary::{Arbitrary, Unstructured};
+    use crate::ParsedGlobString;
+
+    #[test]
+    fn test_generated_patterns_are_valid() {
+        let data: Vec<u8> = (0..4096u32).map(|i| (i.wrapping_mul(2654435761) >> 13) as u8).collect();
+        let mut u = Unstructured::new(&data);
+        for _ in 0..100 {
+            let Ok(pattern) = ParsedGlobString::arbitrary(&mut u) else {
+                break;
+            };
+            let pattern_string = pattern.to_string();
+            assert_eq!(ParsedGlobString::try_from(pattern_string.as_str()).unwrap().dump(), pattern.dump(), "{}", pattern);
+            let haystack = String::arbitrary(&mut u).unwrap_or_default();
+            assert_eq!(pattern.matches_partially(&haystack), pattern.find(&haystack).is_some(), "{} in {:?}", pattern, haystack);
+        }
+    }
+}
@@ -0,0 +1,426 @@
+//! A command line interface to the glob library, for testing patterns and for shell pipelines.
+//!
+//! The exit status is 0 if anything matched, 1 if nothing matched and 2 if an error occurred, like
+//! `grep`'s.
+
+use std::io::{self, BufRead, BufWriter, Write};
+use std::path::Path;
+use std::process::ExitCode;
+use glob::{Anchor, Captures, GlobWalker, ParsedGlobString};
+
+const USAGE: &str = "\
+usage: glob match PATTERN STRING            check if PATTERN occurs in STRING
+       glob filter [OPTIONS] PATTERN        print the lines of stdin that PATTERN occurs in
+       glob find PATTERN [DIR]              print the paths below DIR (default: .) that match PATTERN
+
+options:
+  --json                    print one JSON object per match, with the haystack, the byte range of
+                            the match and the captured wildcard contents
+
+filter options:
+  -v, --invert              select the lines that don't match
+  -c, --count               only print the number of selected lines
+  --anchored=start|end|both only match at the start, the end, or the whole line
+  -z, --null                lines are terminated by NUL bytes instead of newlines, in input and output";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FilterOptions {
+    invert: bool,
+    count: bool,
+    anchoring: Anchor,
+    null_delimited: bool,
+}
+
+impl Default for FilterOptions {
+    fn default() -> Self {
+        return FilterOptions { invert: false, count: false, anchoring: Anchor::None, null_delimited: false };
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    Match { pattern: String, string: String },
+    Filter { pattern: String, options: FilterOptions },
+    Find { pattern: String, root: String },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Invocation {
+    command: Command,
+    json: bool,
+}
+
+/// splits the arguments into options (starting with `-`) and operands. Everything after `--` is
+/// an operand.
+fn split_options(arguments: &[String]) -> (Vec<&str>, Vec<&String>) {
+    let mut options = Vec::new();
+    let mut operands = Vec::new();
+    let mut only_operands = false;
+    for argument in arguments {
+        if only_operands || !argument.starts_with('-') || argument == "-" {
+            operands.push(argument);
+        } else if argument == "--" {
+            only_operands = true;
+        } else {
+            options.push(argument.as_str());
+        }
+    }
+    return (options, operands);
+}
+
+fn parse_filter_options(options: &[&str]) -> Result<FilterOptions, String> {
+    let mut filter_options = FilterOptions::default();
+    for option in options {
+        match *option {
+            "-v" | "--invert" => filter_options.invert = true,
+            "-c" | "--count" => filter_options.count = true,
+            "-z" | "--null" => filter_options.null_delimited = true,
+            "--anchored=start" => filter_options.anchoring = Anchor::Start,
+            "--anchored=end" => filter_options.anchoring = Anchor::End,
+            "--anchored=both" => filter_options.anchoring = Anchor::Both,
+            _ => return Err(format!("unknown option {}", option)),
+        }
+    }
+    return Ok(filter_options);
+}
+
+fn parse_arguments(arguments: &[String]) -> Result<Invocation, String> {
+    let (subcommand, arguments) = match arguments.split_first() {
+        Option::None => return Err(String::from("missing subcommand")),
+        Option::Some((subcommand, arguments)) => (subcommand.as_str(), arguments),
+    };
+    let (mut options, operands) = split_options(arguments);
+    let json = options.contains(&"--json");
+    options.retain(|option| *option != "--json");
+    if subcommand != "filter" {
+        if let Some(option) = options.first() {
+            return Err(format!("unknown option {}", option));
+        }
+    }
+    let command = parse_command(subcommand, &options, &operands)?;
+    return Ok(Invocation { command: command, json: json });
+}
+
+fn parse_command(subcommand: &str, options: &[&str], operands: &[&String]) -> Result<Command, String> {
+    match (subcommand, operands) {
+        ("match", [pattern, string]) => Ok(Command::Match { pattern: String::clone(pattern), string: String::clone(string) }),
+        ("filter", [pattern]) => Ok(Command::Filter { pattern: String::clone(pattern), options: parse_filter_options(options)? }),
+        ("find", [pattern]) => Ok(Command::Find { pattern: String::clone(pattern), root: String::from(".") }),
+        ("find", [pattern, root]) => Ok(Command::Find { pattern: String::clone(pattern), root: String::clone(root) }),
+        ("match" | "filter" | "find", _) => Err(format!("wrong number of arguments for {}", subcommand)),
+        _ => Err(format!("unknown subcommand {}", subcommand)),
+    }
+}
+
+fn write_json_string(output: &mut impl Write, string: &str) -> io::Result<()> {
+    output.write_all(b"\"")?;
+    for c in string.chars() {
+        match c {
+            '"' => output.write_all(b"\\\"")?,
+            '\\' => output.write_all(b"\\\\")?,
+            '\n' => output.write_all(b"\\n")?,
+            '\r' => output.write_all(b"\\r")?,
+            '\t' => output.write_all(b"\\t")?,
+            c if c.is_control() => write!(output, "\\u{:04x}", c as u32)?,
+            c => write!(output, "{}", c)?,
+        }
+    }
+    return output.write_all(b"\"");
+}
+
+/// writes a JSON object describing a match (or, if `captures` is `None`, a haystack selected
+/// without a match) as a single line. `path` is added for matches of the `find` subcommand.
+fn write_json_match(output: &mut impl Write, haystack: &str, path: Option<&Path>, captures: Option<&Captures>) -> io::Result<()> {
+    output.write_all(b"{\"haystack\":")?;
+    write_json_string(output, haystack)?;
+    if let Some(path) = path {
+        output.write_all(b",\"path\":")?;
+        write_json_string(output, &path.to_string_lossy())?;
+    }
+    match captures {
+        Option::None => output.write_all(b",\"range\":null,\"captures\":[]")?,
+        Option::Some(captures) => {
+            let range = captures.range();
+            write!(output, ",\"range\":[{},{}],\"captures\":[", range.start, range.end)?;
+            for (i, range) in captures.ranges().enumerate() {
+                if i > 0 {
+                    output.write_all(b",")?;
+                }
+                write!(output, "{{\"range\":[{},{}],\"text\":", range.start, range.end)?;
+                write_json_string(output, &haystack[range])?;
+                output.write_all(b"}")?;
+            }
+            output.write_all(b"]")?;
+        }
+    }
+    return output.write_all(b"}\n");
+}
+
+fn run_match(pattern: &ParsedGlobString, string: &str, json: bool, output: &mut impl Write) -> io::Result<bool> {
+    let captures = pattern.captures(string);
+    if json {
+        if let Some(captures) = &captures {
+            write_json_match(output, string, None, Some(captures))?;
+        }
+    } else {
+        writeln!(output, "{}", if captures.is_some() { "match" } else { "no match" })?;
+    }
+    return Ok(captures.is_some());
+}
+
+/// writes the lines of `input` selected by `pattern` and `options` to `output`, or only their
+/// number. Lines don't need to be valid UTF-8, invalid sequences are matched as replacement
+/// characters but written unchanged.
+fn run_filter(pattern: &ParsedGlobString, options: &FilterOptions, json: bool, input: &mut impl BufRead, output: &mut impl Write) -> io::Result<bool> {
+    let delimiter = if options.null_delimited { b'\0' } else { b'\n' };
+    let mut selected_lines: usize = 0;
+    let mut line = Vec::new();
+    while input.read_until(delimiter, &mut line)? > 0 {
+        let original = line.strip_suffix(&[delimiter]).unwrap_or(&line);
+        let content = if options.null_delimited { original } else { original.strip_suffix(b"\r").unwrap_or(original) };
+        let haystack = String::from_utf8_lossy(content);
+        let captures = pattern.captures_anchored(&haystack, options.anchoring);
+        if captures.is_some() != options.invert {
+            selected_lines += 1;
+            if json && !options.count {
+                write_json_match(output, &haystack, None, captures.as_ref())?;
+            } else if !options.count {
+                output.write_all(original)?;
+                output.write_all(&[delimiter])?;
+            }
+        }
+        line.clear();
+    }
+    if json && options.count {
+        writeln!(output, "{{\"count\":{}}}", selected_lines)?;
+    } else if options.count {
+        writeln!(output, "{}", selected_lines)?;
+    }
+    return Ok(selected_lines > 0);
+}
+
+/// returns the path relative to `root` with components separated by `/`, which is what the walker
+/// matches the pattern against.
+fn relative_path_string(path: &Path, root: &Path) -> String {
+    let relative_path = path.strip_prefix(root).unwrap_or(path);
+    let components: Vec<_> = relative_path.components().map(|component| component.as_os_str().to_string_lossy()).collect();
+    return components.join("/");
+}
+
+fn run_find(pattern: &ParsedGlobString, root: &str, json: bool, output: &mut impl Write) -> io::Result<bool> {
+    let mut any_match = false;
+    for path in GlobWalker::new(Path::new(root), pattern) {
+        match path {
+            Ok(path) => {
+                any_match = true;
+                if json {
+                    let relative_path = relative_path_string(&path, Path::new(root));
+                    let captures = pattern.captures_anchored(&relative_path, Anchor::Both);
+                    write_json_match(output, &relative_path, Some(&path), captures.as_ref())?;
+                } else {
+                    writeln!(output, "{}", path.display())?;
+                }
+            },
+            Err(error) => eprintln!("glob: {}", error),
+        }
+    }
+    return Ok(any_match);
+}
+
+fn run(invocation: &Invocation) -> Result<bool, String> {
+    let pattern = match &invocation.command {
+        Command::Match { pattern, .. } | Command::Filter { pattern, .. } | Command::Find { pattern, .. } => pattern,
+    };
+    let pattern = ParsedGlobString::try_from(pattern.as_str()).map_err(|error| format!("invalid pattern: {}", error))?;
+    let mut output = BufWriter::new(io::stdout().lock());
+    let json = invocation.json;
+    let result = match &invocation.command {
+        Command::Match { string, .. } => run_match(&pattern, string, json, &mut output),
+        Command::Filter { options, .. } => run_filter(&pattern, options, json, &mut io::stdin().lock(), &mut output),
+        Command::Find { root, .. } => run_find(&pattern, root, json, &mut output),
+    };
+    return result.and_then(|any_match| output.flush().map(|_| any_match)).map_err(|error| error.to_string());
+}
+
+fn main() -> ExitCode {
+    let arguments: Vec<String> = std::env::args().skip(1).collect();
+    let invocation = match parse_arguments(&arguments) {
+        Ok(invocation) => invocation,
+        Err(message) => {
+            eprintln!("glob: {}\n{}", message, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    match run(&invocation) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(message) => {
+            eprintln!("glob: {}", message);
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glob::ParsedGlobString;
+    use glob::Anchor;
+    use super::{parse_arguments, run_filter, run_find, run_match, Command, FilterOptions, Invocation};
+
+    fn arguments(arguments: &[&str]) -> Vec<String> {
+        return arguments.iter().map(|argument| String::from(*argument)).collect();
+    }
+
+    fn parse_command(arguments_: &[&str]) -> Result<Command, String> {
+        return parse_arguments(&arguments(arguments_)).map(|invocation| invocation.command);
+    }
+
+    fn run_filter_to_string(pattern: &str, options: FilterOptions, json: bool, input: &str) -> (bool, String) {
+        let pattern = ParsedGlobString::try_from(pattern).unwrap();
+        let mut output = Vec::new();
+        let any_match = run_filter(&pattern, &options, json, &mut input.as_bytes(), &mut output).unwrap();
+        return (any_match, String::from_utf8(output).unwrap());
+    }
+
+    fn filter_with(pattern: &str, options: FilterOptions, input: &str) -> (bool, String) {
+        return run_filter_to_string(pattern, options, false, input);
+    }
+
+    fn filter(pattern: &str, input: &str) -> (bool, String) {
+        return filter_with(pattern, FilterOptions::default(), input);
+    }
+
+    #[test]
+    fn test_parse_subcommands() {
+        assert_eq!(parse_command(&["match", "*.rs", "lib.rs"]), Ok(Command::Match { pattern: String::from("*.rs"), string: String::from("lib.rs") }));
+        assert_eq!(parse_command(&["filter", "*.rs"]), Ok(Command::Filter { pattern: String::from("*.rs"), options: FilterOptions::default() }));
+        assert_eq!(parse_command(&["find", "*.rs"]), Ok(Command::Find { pattern: String::from("*.rs"), root: String::from(".") }));
+        assert_eq!(parse_command(&["find", "*.rs", "src"]), Ok(Command::Find { pattern: String::from("*.rs"), root: String::from("src") }));
+    }
+
+    #[test]
+    fn test_parse_filter_options() {
+        let expected_options = FilterOptions { invert: true, count: true, anchoring: Anchor::End, null_delimited: true };
+        assert_eq!(
+            parse_command(&["filter", "-v", "--count", "*.rs", "--anchored=end", "-z"]),
+            Ok(Command::Filter { pattern: String::from("*.rs"), options: expected_options })
+        );
+        assert_eq!(
+            parse_command(&["filter", "--", "-v"]),
+            Ok(Command::Filter { pattern: String::from("-v"), options: FilterOptions::default() })
+        );
+    }
+
+    #[test]
+    fn test_parse_json_option() {
+        let expected_command = Command::Match { pattern: String::from("*.rs"), string: String::from("lib.rs") };
+        assert_eq!(parse_arguments(&arguments(&["match", "--json", "*.rs", "lib.rs"])), Ok(Invocation { command: expected_command, json: true }));
+        assert!(!parse_arguments(&arguments(&["find", "*.rs"])).unwrap().json);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_arguments(&arguments(&[])).is_err());
+        assert!(parse_arguments(&arguments(&["match", "*.rs"])).is_err());
+        assert!(parse_arguments(&arguments(&["grep", "*.rs"])).is_err());
+        assert!(parse_arguments(&arguments(&["filter", "--anchored=middle", "*.rs"])).is_err());
+        assert!(parse_arguments(&arguments(&["match", "-v", "*.rs", "lib.rs"])).is_err());
+    }
+
+    #[test]
+    fn test_match() {
+        let pattern = ParsedGlobString::try_from("*.rs").unwrap();
+        let mut output = Vec::new();
+        assert!(run_match(&pattern, "lib.rs", false, &mut output).unwrap());
+        assert!(!run_match(&pattern, "lib.py", false, &mut output).unwrap());
+        assert_eq!(String::from_utf8(output).unwrap(), "match\nno match\n");
+    }
+
+    #[test]
+    fn test_match_json() {
+        let pattern = ParsedGlobString::try_from("[*,*]").unwrap();
+        let mut output = Vec::new();
+        assert!(run_match(&pattern, "x = [\"a\", 2];", true, &mut output).unwrap());
+        assert!(!run_match(&pattern, "x = 1;", true, &mut output).unwrap());
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "{\"haystack\":\"x = [\\\"a\\\", 2];\",\"range\":[4,12],\"captures\":[{\"range\":[5,8],\"text\":\"\\\"a\\\"\"},{\"range\":[9,11],\"text\":\" 2\"}]}\n"
+        );
+    }
+
+    #[test]
+    fn test_filter_json() {
+        let (any_match, output) = run_filter_to_string("ERROR?", FilterOptions::default(), true, "INFO\nERROR\t1\n");
+        assert!(any_match);
+        assert_eq!(output, "{\"haystack\":\"ERROR\\t1\",\"range\":[0,6],\"captures\":[{\"range\":[5,6],\"text\":\"\\t\"}]}\n");
+        let inverted = FilterOptions { invert: true, ..FilterOptions::default() };
+        let (_, output) = run_filter_to_string("ERROR", inverted, true, "INFO\nERROR\n");
+        assert_eq!(output, "{\"haystack\":\"INFO\",\"range\":null,\"captures\":[]}\n");
+        let count = FilterOptions { count: true, ..FilterOptions::default() };
+        let (_, output) = run_filter_to_string("ERROR", count, true, "INFO\nERROR\n");
+        assert_eq!(output, "{\"count\":1}\n");
+    }
+
+    #[test]
+    fn test_filter() {
+        assert_eq!(filter("ERROR*disk", "INFO ok\nERROR: disk full\r\nERROR: cpu\n"), (true, String::from("ERROR: disk full\r\n")));
+        assert_eq!(filter("WARN", "INFO ok\nERROR: disk full"), (false, String::new()));
+    }
+
+    #[test]
+    fn test_filter_keeps_carriage_returns() {
+        assert_eq!(filter("*full", "disk full\r\ncpu\r\n"), (true, String::from("disk full\r\n")));
+        let options = FilterOptions { invert: true, ..FilterOptions::default() };
+        assert_eq!(filter_with("*full", options, "disk full\r\ncpu\r\n"), (true, String::from("cpu\r\n")));
+    }
+
+    #[test]
+    fn test_filter_last_line_without_newline() {
+        assert_eq!(filter("b", "a\nb"), (true, String::from("b\n")));
+    }
+
+    #[test]
+    fn test_filter_invert() {
+        let options = FilterOptions { invert: true, ..FilterOptions::default() };
+        assert_eq!(filter_with("DEBUG", options, "DEBUG a\nINFO b\nDEBUG c\n"), (true, String::from("INFO b\n")));
+        assert_eq!(filter_with("*", options, "a\nb\n"), (false, String::new()));
+    }
+
+    #[test]
+    fn test_filter_count() {
+        let options = FilterOptions { count: true, ..FilterOptions::default() };
+        assert_eq!(filter_with("DEBUG", options, "DEBUG a\nINFO b\nDEBUG c\n"), (true, String::from("2\n")));
+        assert_eq!(filter_with("WARN", options, "DEBUG a\n"), (false, String::from("0\n")));
+    }
+
+    #[test]
+    fn test_filter_anchored() {
+        let input = "src/lib.rs\nlib.rs.orig\nlib.rs\n";
+        let anchored = |anchoring| FilterOptions { anchoring: anchoring, ..FilterOptions::default() };
+        assert_eq!(filter_with("lib.rs", anchored(Anchor::Start), input), (true, String::from("lib.rs.orig\nlib.rs\n")));
+        assert_eq!(filter_with("lib.rs", anchored(Anchor::End), input), (true, String::from("src/lib.rs\nlib.rs\n")));
+        assert_eq!(filter_with("lib.rs", anchored(Anchor::Both), input), (true, String::from("lib.rs\n")));
+    }
+
+    #[test]
+    fn test_filter_null_delimited() {
+        let options = FilterOptions { null_delimited: true, ..FilterOptions::default() };
+        assert_eq!(filter_with("*.rs", options, "a.rs\0b\nc.rs\0d.txt\0"), (true, String::from("a.rs\0b\nc.rs\0")));
+    }
+
+    #[test]
+    fn test_find() {
+        let root = std::env::temp_dir().join(format!("libglob-cli-test-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/lib.rs"), "").unwrap();
+        std::fs::write(root.join("Cargo.toml"), "").unwrap();
+        let pattern = ParsedGlobString::try_from("*.rs").unwrap();
+        let mut output = Vec::new();
+        assert!(run_find(&pattern, root.to_str().unwrap(), false, &mut output).unwrap());
+        assert_eq!(String::from_utf8(output).unwrap(), format!("{}\n", root.join("src/lib.rs").display()));
+        let mut output = Vec::new();
+        assert!(run_find(&pattern, root.to_str().unwrap(), true, &mut output).unwrap());
+        assert!(String::from_utf8(output).unwrap().starts_with("{\"haystack\":\"src/lib.rs\",\"path\":"));
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,126 @@
+use std::ops::Range;
+use crate::matcher::{find_match_with_captures_from, next_search_position};
+use crate::ParsedGlobString;
+
+/// how many bytes at the start of a file [`is_binary`] looks at, like git does.
+pub const BINARY_DETECTION_LIMIT: usize = 8000;
+
+/// selects what the file scanning helpers do with binary files, which [`is_binary`] tells apart
+/// from text files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BinaryDetection {
+    /// binary files are not searched, like with `grep --binary-files=without-match`.
+    Skip,
+    /// binary files are searched like text files, like with `grep --text`.
+    MatchBytes,
+    /// binary files are searched only to tell whether the pattern occurs in them, like grep does
+    /// by default when it prints "binary file matches".
+    #[default]
+    Report,
+}
+
+/// the result of scanning the contents of a file with [`scan_bytes`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FileScan {
+    /// the byte ranges of the non-overlapping occurrences of the pattern. Binary files without an
+    /// occurrence have no ranges with [`BinaryDetection::Report`].
+    Matches(Vec<Range<usize>>),
+    /// the file is binary and the pattern occurs in it, with [`BinaryDetection::Report`].
+    BinaryMatches,
+    /// the file is binary and wasn't searched, with [`BinaryDetection::Skip`].
+    SkippedBinary,
+}
+
+impl FileScan {
+    /// checks if the pattern occurs in the file. Skipped files don't match.
+    pub fn is_match(&self) -> bool {
+        return match self {
+            FileScan::Matches(ranges) => !ranges.is_empty(),
+            FileScan::BinaryMatches => true,
+            FileScan::SkippedBinary => false,
+        };
+    }
+}
+
+/// checks if `contents` look like the contents of a binary file, because there is a NUL byte in
+/// their first [`BINARY_DETECTION_LIMIT`] bytes. Text in UTF-16 counts as binary too.
+/// ```
+/// use glob::is_binary;
+/// assert!(is_binary(b"\x7fELF\x02\x01\x01\x00"));
+/// assert!(!is_binary("grüße\n".as_bytes()));
+/// ```
+pub fn is_binary(contents: &[u8]) -> bool {
+    return contents[..contents.len().min(BINARY_DETECTION_LIMIT)].contains(&0);
+}
+
+/// returns the byte ranges of the non-overlapping occurrences of `pattern` in `bytes`.
+pub(crate) fn find_all_in(bytes: &[u8], pattern: &ParsedGlobString<'_>) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut position = Some(0);
+    while let Option::Some(from) = position {
+        let Option::Some((range, _)) = find_match_with_captures_from(pattern.tokens(), bytes, from, false, false, pattern.config()) else {
+            break;
+        };
+        position = next_search_position(bytes, &range);
+        ranges.push(range);
+    }
+    return ranges;
+}
+
+/// searches the contents of a file for `pattern` like [`find_in`](ParsedGlobString::find_in)
+/// does, and handles binary files as `binary` selects.
+/// ```
+/// use glob::{scan_bytes, BinaryDetection, FileScan, ParsedGlobString};
+/// let pattern = ParsedGlobString::try_from("GLIBC_*.").unwrap();
+/// let executable = b"\x7fELF\x02\x01\x01\x00...GLIBC_2.34\x00";
+/// assert_eq!(scan_bytes(executable, &pattern, BinaryDetection::Report), FileScan::BinaryMatches);
+/// assert_eq!(scan_bytes(executable, &pattern, BinaryDetection::Skip), FileScan::SkippedBinary);
+/// assert_eq!(scan_bytes(executable, &pattern, BinaryDetection::MatchBytes), FileScan::Matches(vec![11..19]));
+/// ```
+pub fn scan_bytes(contents: &[u8], pattern: &ParsedGlobString<'_>, binary: BinaryDetection) -> FileScan {
+    if binary == BinaryDetection::MatchBytes || !is_binary(contents) {
+        return FileScan::Matches(find_all_in(contents, pattern));
+    }
+    if binary == BinaryDetection::Skip {
+        return FileScan::SkippedBinary;
+    }
+    if pattern.find_in(contents).is_some() {
+        return FileScan::BinaryMatches;
+    }
+    return FileScan::Matches(Vec::new());
+}
+
+#[cfg(test)]
+#[allow(clippy::single_range_in_vec_init)] // vectors of match ranges
+mod tests {
+    use crate::ParsedGlobString;
+    use super::{is_binary, scan_bytes, BinaryDetection, FileScan, BINARY_DETECTION_LIMIT};
+
+    #[test]
+    fn test_is_binary() {
+        assert!(!is_binary(b""));
+        assert!(!is_binary(b"\xff\xfe not UTF-8 but no NUL"));
+        assert!(is_binary(b"a\0b"));
+        let mut late = vec![b'a'; BINARY_DETECTION_LIMIT];
+        late.push(0);
+        assert!(!is_binary(&late));
+        late[BINARY_DETECTION_LIMIT - 1] = 0;
+        assert!(is_binary(&late));
+    }
+
+    #[test]
+    fn test_scan_bytes() {
+        let pattern = ParsedGlobString::try_from("key=?").unwrap();
+        let text = b"key=1 key=2";
+        for binary in [BinaryDetection::Skip, BinaryDetection::MatchBytes, BinaryDetection::Report] {
+            assert_eq!(scan_bytes(text, &pattern, binary), FileScan::Matches(vec![0..5, 6..11]));
+        }
+        let data = b"\0\0key=\0";
+        assert_eq!(scan_bytes(data, &pattern, BinaryDetection::Report), FileScan::BinaryMatches);
+        assert_eq!(scan_bytes(data, &pattern, BinaryDetection::MatchBytes), FileScan::Matches(vec![2..7]));
+        assert!(!scan_bytes(data, &pattern, BinaryDetection::Skip).is_match());
+        let missing = ParsedGlobString::try_from("secret").unwrap();
+        assert_eq!(scan_bytes(data, &missing, BinaryDetection::Report), FileScan::Matches(vec![]));
+        assert!(!scan_bytes(data, &missing, BinaryDetection::Report).is_match());
+    }
+}
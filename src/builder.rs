@@ -0,0 +1,255 @@
+use glob_parser::{parse_glob_string_with_spans, Syntax};
+use crate::matcher::MatchConfig;
+use crate::optimize::repeat_tokens;
+use crate::prefilter::LiteralCheck;
+use crate::{Anchor, CaseFold, DefaultCaseFold, GlobParseError, MatchOptions, ParsedGlobString, WildcardPolicy};
+
+/// creates a [`ParsedGlobString`] with options that [`try_from`](ParsedGlobString::try_from) does
+/// not offer.
+///
+/// Options that are not set keep the behavior of [`ParsedGlobString::try_from`].
+/// ```
+/// use glob::GlobBuilder;
+/// let pattern = GlobBuilder::new("ERROR*failed").wildcards_match_newline(false).build().unwrap();
+/// assert!(pattern.matches_partially("ERROR: disk check failed\n"));
+/// assert!(!pattern.matches_partially("ERROR: disk full\ncheck failed"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct GlobBuilder<'g> {
+    pattern: &'g str,
+    config: MatchConfig,
+    anchor: Anchor,
+    repetitions: usize,
+    syntax: Syntax,
+}
+
+impl<'g> GlobBuilder<'g> {
+    /// creates a builder for the given pattern string.
+    pub fn new(pattern: &'g str) -> Self {
+        return GlobBuilder { pattern: pattern, config: MatchConfig::default(), anchor: Anchor::None, repetitions: 1, syntax: Syntax::extended() };
+    }
+
+    /// sets whether wildcards match line breaks (`\n`). Enabled by default; disable it when matching
+    /// multi-line text like logs, so a match never spans several lines.
+    pub fn wildcards_match_newline(mut self, yes: bool) -> Self {
+        self.config.wildcards_match_newline = yes;
+        return self;
+    }
+
+    /// sets which alignment of the wildcards [`find`](ParsedGlobString::find) and
+    /// [`captures`](ParsedGlobString::captures) report if there are several, see
+    /// [`WildcardPolicy`]. Defaults to [`WildcardPolicy::Lazy`].
+    pub fn wildcard_policy(mut self, policy: WildcardPolicy) -> Self {
+        self.config.wildcard_policy = policy;
+        return self;
+    }
+
+    /// sets whether [`find`](ParsedGlobString::find) and [`captures`](ParsedGlobString::captures)
+    /// report the longest of the matches starting at the leftmost possible position, like POSIX
+    /// regular expressions do. The [`WildcardPolicy`] then only decides the captures if several
+    /// alignments give the longest match. Disabled by default, since finding the longest match
+    /// requires trying all alignments of the wildcards.
+    /// ```
+    /// use glob::GlobBuilder;
+    /// let pattern = GlobBuilder::new("<*>?").leftmost_longest(true).build().unwrap();
+    /// assert_eq!(pattern.find("<a>b<c>d"), Some(0..8));
+    /// ```
+    pub fn leftmost_longest(mut self, yes: bool) -> Self {
+        self.config.leftmost_longest = yes;
+        return self;
+    }
+
+    /// limits the work of each search to `max_steps` steps of the matcher. A search that exceeds the
+    /// budget behaves as if the pattern didn't match, so all methods of the pattern fail closed for
+    /// patterns or strings from untrusted sources. Use
+    /// [`match_with_budget`](ParsedGlobString::match_with_budget) to tell the two cases apart.
+    /// ```
+    /// use glob::GlobBuilder;
+    /// let pattern = GlobBuilder::new("*a*a*a*a*b").step_budget(100_000).build().unwrap();
+    /// assert!(!pattern.matches_partially(&"a".repeat(1000)));
+    /// assert!(pattern.matches_partially("aaaab"));
+    /// ```
+    pub fn step_budget(mut self, max_steps: u64) -> Self {
+        self.config.step_budget = Some(max_steps);
+        return self;
+    }
+
+    /// sets whether literal parts of the pattern match regardless of case, using
+    /// [`DefaultCaseFold`]. Disabled by default.
+    /// ```
+    /// use glob::GlobBuilder;
+    /// let pattern = GlobBuilder::new("*.jpg").case_insensitive(true).build().unwrap();
+    /// assert!(pattern.matches_completely("Holiday.JPG"));
+    /// ```
+    pub fn case_insensitive(mut self, yes: bool) -> Self {
+        self.config.case_fold = if yes { Some(&DefaultCaseFold) } else { None };
+        return self;
+    }
+
+    /// makes literal parts of the pattern match regardless of case, using the given folding, e.g.
+    /// for the rules of a locale.
+    /// ```
+    /// use glob::{GlobBuilder, TurkishCaseFold};
+    /// let pattern = GlobBuilder::new("IŞIK*").case_fold(&TurkishCaseFold).build().unwrap();
+    /// assert!(pattern.matches_completely("ışık.png"));
+    /// assert!(!pattern.matches_completely("isik.png"));
+    /// ```
+    pub fn case_fold(mut self, fold: &'static dyn CaseFold) -> Self {
+        self.config.case_fold = Some(fold);
+        return self;
+    }
+
+    /// sets all options covered by [`MatchOptions`] at once, replacing earlier calls of
+    /// [`case_insensitive`](Self::case_insensitive), [`case_fold`](Self::case_fold) and
+    /// [`wildcards_match_newline`](Self::wildcards_match_newline). The anchor of the options is
+    /// used by [`is_match`](ParsedGlobString::is_match).
+    pub fn options(mut self, options: &MatchOptions) -> Self {
+        self.config = options.apply_to(self.config);
+        self.anchor = options.anchor;
+        return self;
+    }
+
+    /// makes the pattern match `count` copies of the pattern string one after the other, for
+    /// fixed-format identifiers that would be long and error-prone to write out. Wildcards and
+    /// literals where the copies meet are merged, as if the repeated string had been parsed. A
+    /// count of 0 gives the empty pattern.
+    /// ```
+    /// use glob::GlobBuilder;
+    /// let serial = GlobBuilder::new("????-").repeat(3).build().unwrap();
+    /// assert_eq!(serial.to_string(), "????-????-????-");
+    /// assert!(serial.matches_at_start("AB12-CD34-EF56-GH78"));
+    /// assert!(!serial.matches_at_start("AB12-CD34-EF5"));
+    /// ```
+    pub fn repeat(mut self, count: usize) -> Self {
+        self.repetitions = count;
+        return self;
+    }
+
+    /// sets the syntax the pattern string is parsed with, see [`Syntax`]. Defaults to
+    /// [`Syntax::extended`], the syntax [`ParsedGlobString::try_from`] accepts.
+    pub fn syntax(mut self, syntax: Syntax) -> Self {
+        self.syntax = syntax;
+        return self;
+    }
+
+    /// parses the pattern string and returns the configured pattern or an error.
+    pub fn build(&self) -> Result<ParsedGlobString<'g>, GlobParseError<'g>> {
+        let (mut tokens, mut spans) = parse_glob_string_with_spans(self.pattern, self.syntax)?;
+        if self.repetitions != 1 {
+            tokens = repeat_tokens(&tokens, self.repetitions);
+            spans = Vec::new();
+        }
+        let literal_check = LiteralCheck::new(&tokens);
+        return Ok(ParsedGlobString { tokens: tokens, config: self.config, anchor: self.anchor, literal_check: literal_check, spans: spans });
+    }
+
+    /// parses the pattern string like [`build`](Self::build), but copies it into `arena` first, so
+    /// the pattern borrows its literals from the arena instead of from the original string.
+    /// Requires the `arena` feature.
+    ///
+    /// Services that parse many patterns at startup can keep them in one arena: the pattern strings
+    /// are stored one after the other in its memory and freed at once when it is dropped, and the
+    /// literals need no strings of their own, as they would with
+    /// [`into_owned`](ParsedGlobString::into_owned). The list of tokens of each pattern is still
+    /// allocated on the heap.
+    /// ```
+    /// use bumpalo::Bump;
+    /// use glob::GlobBuilder;
+    /// let arena = Bump::new();
+    /// let patterns: Vec<_> = ["*.rs", "*.TOML"].iter()
+    ///     .map(|pattern| GlobBuilder::new(pattern).case_insensitive(true).build_in(&arena).unwrap())
+    ///     .collect();
+    /// assert!(patterns[1].matches_completely("Cargo.toml"));
+    /// ```
+    #[cfg(feature = "arena")]
+    pub fn build_in<'a>(&self, arena: &'a bumpalo::Bump) -> Result<ParsedGlobString<'a>, GlobParseError<'a>> {
+        let builder = GlobBuilder { pattern: arena.alloc_str(self.pattern), config: self.config, anchor: self.anchor, repetitions: self.repetitions, syntax: self.syntax };
+        return builder.build();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Anchor, GlobParseError, Syntax, TurkishCaseFold, WildcardPolicy};
+    use super::GlobBuilder;
+
+    #[test]
+    fn test_defaults_match_try_from() {
+        let pattern = GlobBuilder::new("a*b").build().unwrap();
+        assert!(pattern.matches_completely("a\nb"));
+    }
+
+    #[test]
+    fn test_wildcards_dont_match_newline() {
+        let pattern = GlobBuilder::new("a*b").wildcards_match_newline(false).build().unwrap();
+        assert!(!pattern.matches_completely("a\nb"));
+        assert!(!pattern.matches_at_start("a\nb"));
+        assert!(!pattern.matches_at_end("a\nb"));
+        assert_eq!(pattern.find("a\nb, a-b"), Some(5..8));
+        assert_eq!(pattern.captures_anchored("a\nab", Anchor::End).unwrap().range(), 2..4);
+        assert!(pattern.into_owned().matches_partially("a\nab"));
+    }
+
+    #[test]
+    fn test_build_reports_parse_errors() {
+        assert_eq!(GlobBuilder::new("a\\").build().unwrap_err(), GlobParseError::UnterminatedEscapeSequence(1));
+    }
+
+    #[test]
+    fn test_greedy_captures() {
+        let pattern = GlobBuilder::new("*/*").wildcard_policy(WildcardPolicy::Greedy).build().unwrap();
+        let captures = pattern.captures_anchored("src/bin/glob.rs", Anchor::Both).unwrap();
+        assert_eq!(captures.iter().collect::<Vec<_>>(), vec!["src/bin", "glob.rs"]);
+    }
+
+    #[test]
+    fn test_leftmost_longest() {
+        let pattern = GlobBuilder::new("<*>").leftmost_longest(true).build().unwrap();
+        assert_eq!(pattern.find("x <a> <b> y"), Some(2..9));
+        assert_eq!(pattern.captures("x <a> <b> y").unwrap().get(0), Some("a> <b"));
+        assert_eq!(GlobBuilder::new("<*>").build().unwrap().find("x <a> <b> y"), Some(2..5));
+    }
+
+    #[test]
+    fn test_case_insensitive() {
+        let pattern = GlobBuilder::new("readme.*").case_insensitive(true).build().unwrap();
+        assert!(pattern.matches_at_start("README.md"));
+        assert!(pattern.matches_partially("docs/ReadMe.txt"));
+        assert_eq!(pattern.find("docs/ReadMe.txt"), Some(5..12));
+        assert!(!GlobBuilder::new("readme.*").case_insensitive(true).case_insensitive(false).build().unwrap().matches_partially("README.md"));
+    }
+
+    #[test]
+    fn test_turkish_case_fold() {
+        let pattern = GlobBuilder::new("*İ*").case_fold(&TurkishCaseFold).build().unwrap();
+        assert!(pattern.matches_completely("izmir"));
+        assert!(!pattern.matches_completely("IZMIR"));
+        let pattern = GlobBuilder::new("*i*").case_insensitive(true).build().unwrap();
+        assert!(pattern.matches_completely("IZMIR"));
+    }
+
+    #[test]
+    fn test_repeat() {
+        let pattern = GlobBuilder::new("?").repeat(5).build().unwrap();
+        assert_eq!(pattern.dump(), "0 exact-length-wildcard 5\n");
+        let pattern = GlobBuilder::new("ab\\*").repeat(2).build().unwrap();
+        assert_eq!(pattern.dump(), "0 literal \"ab*ab*\"\n");
+        let pattern = GlobBuilder::new("*.").repeat(2).case_insensitive(true).build().unwrap();
+        assert!(pattern.matches_completely("A.B."));
+        assert!(!pattern.matches_completely("A.B"));
+        let pattern = GlobBuilder::new("x*").repeat(0).build().unwrap();
+        assert!(pattern.matches_completely(""));
+        assert!(!pattern.matches_completely("x"));
+    }
+
+    #[test]
+    fn test_syntax() {
+        let pattern = GlobBuilder::new("\\bcat\\*").syntax(Syntax::basic().word_boundaries(true)).build().unwrap();
+        assert!(pattern.matches_partially("a cat*"));
+        assert!(!pattern.matches_partially("concat*"));
+        assert_eq!(GlobBuilder::new("a\\b").syntax(Syntax::basic()).build().unwrap_err(), GlobParseError::UnknownEscapeSequence(1, "\\b"));
+        assert!(GlobBuilder::new("\\?\\\\").syntax(Syntax::basic()).repeat(2).build().unwrap().matches_completely("?\\?\\"));
+        assert!(!Syntax::basic().has_word_boundaries());
+        assert_eq!(Syntax::default(), Syntax::extended());
+    }
+}
@@ -0,0 +1,400 @@
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+/// which bytes a [`BytePattern`] may contain, see [`BytePattern::parse_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ByteValidation {
+    /// any bytes (the default).
+    #[default]
+    Any,
+    /// the pattern must be valid UTF-8.
+    Utf8,
+    /// the pattern must only contain ASCII characters.
+    Ascii,
+}
+
+/// returned if parsing a [`BytePattern`] fails. Encapsulates the byte index in the pattern at which
+/// the offending part starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BytePatternError {
+    /// a backslash is followed by a byte other than `*`, `?`, `\` or `b`. Encapsulates the index of
+    /// the backslash and the byte after it.
+    UnknownEscapeSequence(usize, u8),
+    /// the pattern ends with an unescaped backslash.
+    UnterminatedEscapeSequence(usize),
+    /// the pattern is not valid UTF-8, but [`ByteValidation::Utf8`] was requested.
+    InvalidUtf8(usize),
+    /// the pattern contains a byte that isn't ASCII, but [`ByteValidation::Ascii`] was requested.
+    NonAscii(usize),
+    /// the pattern contains a character that can't be encoded in Latin-1, see
+    /// [`BytePattern::parse_latin1`].
+    NotLatin1(usize),
+}
+
+impl BytePatternError {
+    /// returns the byte index in the pattern at which the offending part starts.
+    pub fn position(&self) -> usize {
+        return match self {
+            BytePatternError::UnknownEscapeSequence(index, _) | BytePatternError::UnterminatedEscapeSequence(index)
+                | BytePatternError::InvalidUtf8(index) | BytePatternError::NonAscii(index) | BytePatternError::NotLatin1(index) => *index,
+        };
+    }
+}
+
+impl Display for BytePatternError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BytePatternError::UnknownEscapeSequence(index, byte) => {
+                write!(f, "unknown escape sequence `\\{}` at index {}", std::ascii::escape_default(*byte), index)
+            },
+            BytePatternError::UnterminatedEscapeSequence(index) => write!(f, "unterminated escape sequence at index {}", index),
+            BytePatternError::InvalidUtf8(index) => write!(f, "invalid UTF-8 at index {}", index),
+            BytePatternError::NonAscii(index) => write!(f, "non-ASCII byte at index {}", index),
+            BytePatternError::NotLatin1(index) => write!(f, "character at index {} is not in Latin-1", index),
+        }
+    }
+}
+
+impl Error for BytePatternError {}
+
+/// a case folding table for text encoded in Latin-1 (ISO 8859-1), see
+/// [`BytePattern::case_fold`]. It maps the uppercase letters `A`–`Z` and `À`–`Þ`, except `×`, to
+/// their lowercase counterparts, and all other bytes to themselves.
+pub const LATIN1_CASE_FOLD: [u8; 256] = latin1_case_fold();
+
+const fn latin1_case_fold() -> [u8; 256] {
+    let mut table = [0; 256];
+    let mut byte = 0;
+    while byte < 256 {
+        let is_upper = (byte >= 0x41 && byte <= 0x5a) || (byte >= 0xc0 && byte <= 0xde && byte != 0xd7);
+        table[byte] = if is_upper { byte as u8 + 0x20 } else { byte as u8 };
+        byte += 1;
+    }
+    return table;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ByteToken<'g> {
+    Literal(Cow<'g, [u8]>),
+    Wildcard(usize), // exact length in bytes
+    WordBoundary, // between an ASCII word character and another byte
+}
+
+/// a run of tokens without `*`, which matches a fixed number of bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Segment<'g> {
+    min_gap: Option<usize>, // the minimum number of bytes before the segment, or `None` for the first one
+    tokens: Vec<ByteToken<'g>>,
+    length: usize,
+}
+
+fn is_word_byte(byte: u8) -> bool {
+    return byte.is_ascii_alphanumeric() || byte == b'_';
+}
+
+fn is_word_boundary(haystack: &[u8], position: usize) -> bool {
+    let before = position > 0 && is_word_byte(haystack[position - 1]);
+    let after = haystack.get(position).is_some_and(|byte| is_word_byte(*byte));
+    return before != after;
+}
+
+impl<'g> Segment<'g> {
+    fn new(min_gap: Option<usize>) -> Self {
+        return Segment { min_gap: min_gap, tokens: Vec::new(), length: 0 };
+    }
+
+    fn push_literal(&mut self, literal: &'g [u8]) {
+        self.length += literal.len();
+        if let Option::Some(ByteToken::Literal(last)) = self.tokens.last_mut() {
+            last.to_mut().extend_from_slice(literal);
+            return;
+        }
+        self.tokens.push(ByteToken::Literal(Cow::Borrowed(literal)));
+    }
+
+    fn push_wildcard(&mut self) {
+        self.length += 1;
+        if let Option::Some(ByteToken::Wildcard(length)) = self.tokens.last_mut() {
+            *length += 1;
+            return;
+        }
+        self.tokens.push(ByteToken::Wildcard(1));
+    }
+
+    /// returns the offset after the segment if it matches at `position`, comparing the literals after
+    /// mapping their bytes with `case_fold`, if given.
+    fn match_at(&self, haystack: &[u8], position: usize, case_fold: Option<&[u8; 256]>) -> Option<usize> {
+        let mut end = position;
+        for token in &self.tokens {
+            match token {
+                ByteToken::Literal(literal) => {
+                    let candidate = haystack.get(end..end + literal.len())?;
+                    let matches = match case_fold {
+                        Option::None => candidate == literal.as_ref(),
+                        Option::Some(table) => candidate.iter().zip(literal.iter()).all(|(a, b)| table[*a as usize] == table[*b as usize]),
+                    };
+                    if !matches {
+                        return None;
+                    }
+                    end += literal.len();
+                },
+                ByteToken::Wildcard(length) => {
+                    end += length;
+                    if end > haystack.len() {
+                        return None;
+                    }
+                },
+                ByteToken::WordBoundary => {
+                    if !is_word_boundary(haystack, end) {
+                        return None;
+                    }
+                },
+            }
+        }
+        return Some(end);
+    }
+
+    fn into_owned(self) -> Segment<'static> {
+        let tokens = self.tokens.into_iter().map(|token| match token {
+            ByteToken::Literal(literal) => ByteToken::Literal(Cow::Owned(literal.into_owned())),
+            ByteToken::Wildcard(length) => ByteToken::Wildcard(length),
+            ByteToken::WordBoundary => ByteToken::WordBoundary,
+        }).collect();
+        return Segment { min_gap: self.min_gap, tokens: tokens, length: self.length };
+    }
+}
+
+/// a pattern given as bytes, e.g. read from a file that isn't guaranteed to be UTF-8, that is
+/// matched against bytes.
+///
+/// The syntax is the one of [`ParsedGlobString`](crate::ParsedGlobString), but wildcards match
+/// bytes instead of characters, so `?` matches one byte of a multi-byte UTF-8 character, and `\b`
+/// matches between an ASCII letter, digit or `_` and any other byte. Matches are found like with
+/// the default options: the leftmost one, with wildcards matching as few bytes as possible.
+/// ```
+/// use glob::BytePattern;
+/// let pattern = BytePattern::parse(b"*.log\xff").unwrap();
+/// assert!(pattern.matches_completely(b"app.log\xff"));
+/// assert_eq!(pattern.find(b"see app.log\xff!"), Some(0..12));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BytePattern<'g> {
+    segments: Vec<Segment<'g>>, // never empty
+    case_fold: Option<&'static [u8; 256]>,
+}
+
+impl<'g> BytePattern<'g> {
+    /// parses the pattern, allowing any bytes in it.
+    pub fn parse(pattern: &'g [u8]) -> Result<Self, BytePatternError> {
+        return BytePattern::parse_with(pattern, ByteValidation::Any);
+    }
+
+    /// parses the pattern after checking that it only contains the bytes that `validation` allows.
+    /// ```
+    /// use glob::{BytePattern, BytePatternError, ByteValidation};
+    /// assert_eq!(BytePattern::parse_with(b"caf\xe9", ByteValidation::Utf8), Err(BytePatternError::InvalidUtf8(3)));
+    /// assert_eq!(BytePattern::parse_with("café".as_bytes(), ByteValidation::Ascii), Err(BytePatternError::NonAscii(3)));
+    /// ```
+    pub fn parse_with(pattern: &'g [u8], validation: ByteValidation) -> Result<Self, BytePatternError> {
+        match validation {
+            ByteValidation::Any => {},
+            ByteValidation::Utf8 => {
+                if let Err(error) = std::str::from_utf8(pattern) {
+                    return Err(BytePatternError::InvalidUtf8(error.valid_up_to()));
+                }
+            },
+            ByteValidation::Ascii => {
+                if let Option::Some(index) = pattern.iter().position(|byte| !byte.is_ascii()) {
+                    return Err(BytePatternError::NonAscii(index));
+                }
+            },
+        }
+        let mut segments = vec![Segment::new(None)];
+        let mut literal_start = None;
+        let mut i = 0;
+        while i < pattern.len() {
+            let byte = pattern[i];
+            if !matches!(byte, b'*' | b'?' | b'\\') {
+                literal_start.get_or_insert(i);
+                i += 1;
+                continue;
+            }
+            let segment = segments.last_mut().expect("there is always a segment");
+            if let Option::Some(start) = literal_start.take() {
+                segment.push_literal(&pattern[start..i]);
+            }
+            match byte {
+                b'*' => {
+                    // the `?` right before the `*` become part of the gap before the next segment
+                    let mut gap = 0;
+                    if let Option::Some(ByteToken::Wildcard(length)) = segment.tokens.last() {
+                        gap = *length;
+                        segment.length -= gap;
+                        segment.tokens.pop();
+                    }
+                    match segment.min_gap {
+                        Option::Some(min_gap) if segment.tokens.is_empty() => segment.min_gap = Some(min_gap + gap),
+                        _ => segments.push(Segment::new(Some(gap))),
+                    }
+                },
+                b'?' => segment.push_wildcard(),
+                _ => match pattern.get(i + 1) {
+                    Option::None => return Err(BytePatternError::UnterminatedEscapeSequence(i)),
+                    Option::Some(b'b') => {
+                        segment.tokens.push(ByteToken::WordBoundary);
+                        i += 1;
+                    },
+                    Option::Some(b'*' | b'?' | b'\\') => {
+                        segment.push_literal(&pattern[i + 1..i + 2]);
+                        i += 1;
+                    },
+                    Option::Some(escaped) => return Err(BytePatternError::UnknownEscapeSequence(i, *escaped)),
+                },
+            }
+            i += 1;
+        }
+        if let Option::Some(start) = literal_start {
+            segments.last_mut().expect("there is always a segment").push_literal(&pattern[start..]);
+        }
+        return Ok(BytePattern { segments: segments, case_fold: None });
+    }
+
+    /// parses a pattern for text encoded in Latin-1 (ISO 8859-1), like old log archives, so they can
+    /// be scanned without converting them to UTF-8. Each character of the pattern is encoded as the
+    /// byte with the same value; error indices refer to `pattern`.
+    /// ```
+    /// use glob::{BytePattern, BytePatternError, LATIN1_CASE_FOLD};
+    /// let pattern = BytePattern::parse_latin1("*café*").unwrap().case_fold(&LATIN1_CASE_FOLD);
+    /// assert!(pattern.matches_completely(b"CAF\xc9 au lait"));
+    /// assert_eq!(BytePattern::parse_latin1("€*"), Err(BytePatternError::NotLatin1(0)));
+    /// ```
+    pub fn parse_latin1(pattern: &str) -> Result<BytePattern<'static>, BytePatternError> {
+        let mut encoded = Vec::with_capacity(pattern.len());
+        for (index, c) in pattern.char_indices() {
+            match u8::try_from(c) {
+                Ok(byte) => encoded.push(byte),
+                Err(_) => return Err(BytePatternError::NotLatin1(index)),
+            }
+        }
+        // each byte of the encoded pattern stands for one character of `pattern`
+        let position = |encoded_index: usize| pattern.char_indices().nth(encoded_index).map_or(pattern.len(), |(index, _)| index);
+        return match BytePattern::parse(&encoded) {
+            Ok(parsed) => Ok(parsed.into_owned()),
+            Err(BytePatternError::UnknownEscapeSequence(index, byte)) => Err(BytePatternError::UnknownEscapeSequence(position(index), byte)),
+            Err(BytePatternError::UnterminatedEscapeSequence(index)) => Err(BytePatternError::UnterminatedEscapeSequence(position(index))),
+            Err(error) => Err(error),
+        };
+    }
+
+    /// makes literals match case-insensitively: two bytes match if `table` maps them to the same
+    /// byte, like [`LATIN1_CASE_FOLD`] does for Latin-1 letters. Wildcards and word boundaries are
+    /// not affected.
+    pub fn case_fold(mut self, table: &'static [u8; 256]) -> Self {
+        self.case_fold = Some(table);
+        return self;
+    }
+
+    /// returns the end of the match that starts at `start`, with the last segment at the end of
+    /// `haystack` if `anchor_end` is set.
+    ///
+    /// Each segment has a fixed length, so placing it as early as possible leaves the most room for
+    /// the following ones and finds the shortest match.
+    fn match_from(&self, haystack: &[u8], start: usize, anchor_end: bool) -> Option<usize> {
+        let mut position = start;
+        let last = self.segments.len() - 1;
+        for (i, segment) in self.segments.iter().enumerate() {
+            position = match segment.min_gap {
+                Option::None => segment.match_at(haystack, position, self.case_fold)?,
+                Option::Some(gap) => {
+                    let earliest = position + gap;
+                    let latest = haystack.len().checked_sub(segment.length)?;
+                    if earliest > latest {
+                        return None;
+                    }
+                    if anchor_end && i == last {
+                        segment.match_at(haystack, latest, self.case_fold)?
+                    } else {
+                        (earliest..=latest).find_map(|at| segment.match_at(haystack, at, self.case_fold))?
+                    }
+                },
+            };
+        }
+        if anchor_end && position != haystack.len() {
+            return None;
+        }
+        return Some(position);
+    }
+
+    /// finds the leftmost occurrence of this pattern in `haystack` and returns its byte range.
+    pub fn find(&self, haystack: &[u8]) -> Option<Range<usize>> {
+        // a pattern starting with `*` matches at the start if it matches anywhere
+        let last_start = if self.segments[0].tokens.is_empty() { 0 } else { haystack.len() };
+        return (0..=last_start).find_map(|start| self.match_from(haystack, start, false).map(|end| start..end));
+    }
+
+    /// checks if this pattern occurs anywhere in `haystack`.
+    pub fn matches_partially(&self, haystack: &[u8]) -> bool {
+        return self.find(haystack).is_some();
+    }
+
+    /// checks if this pattern matches all of `haystack`.
+    pub fn matches_completely(&self, haystack: &[u8]) -> bool {
+        return self.match_from(haystack, 0, true).is_some();
+    }
+
+    /// converts the pattern into one that doesn't borrow the bytes it was parsed from.
+    pub fn into_owned(self) -> BytePattern<'static> {
+        return BytePattern { segments: self.segments.into_iter().map(Segment::into_owned).collect(), case_fold: self.case_fold };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BytePattern, BytePatternError, LATIN1_CASE_FOLD};
+
+    fn pattern(pattern: &[u8]) -> BytePattern<'_> {
+        return BytePattern::parse(pattern).unwrap();
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(pattern(b"a?c").find(b"xxabcabc"), Some(2..5));
+        assert_eq!(pattern(b"*").find(b"abc"), Some(0..0));
+        assert_eq!(pattern(b"?*?").find(b"abc"), Some(0..2));
+        assert_eq!(pattern(b"a*b*c").find(b"xaxbxbc"), Some(1..7));
+        assert_eq!(pattern(b"a*b*c").find(b"xaxbx"), None);
+        assert_eq!(pattern(b"").find(b""), Some(0..0));
+        assert_eq!(pattern(b"\\bab").find(b"cab ab"), Some(4..6));
+    }
+
+    #[test]
+    fn test_matches_completely() {
+        assert!(pattern(b"*.rs").matches_completely(b"lib.rs"));
+        assert!(!pattern(b"*.rs").matches_completely(b"lib.rs.orig"));
+        assert!(pattern(b"*a*a").matches_completely(b"aaa"));
+        assert!(!pattern(b"?*?").matches_completely(b"a"));
+        assert!(pattern(b"?**?").matches_completely(b"\xc3\xa9"));
+        assert!(pattern(b"a\\*\\?\\\\").matches_completely(b"a*?\\"));
+        assert!(pattern(b"\xff*").matches_completely(b"\xff\xfe"));
+    }
+
+    #[test]
+    fn test_latin1() {
+        let pattern = BytePattern::parse_latin1("straße-?.log").unwrap();
+        assert!(pattern.matches_completely(b"stra\xdfe-1.log"));
+        assert!(!pattern.matches_completely("straße-1.log".as_bytes()));
+        let folded = pattern.case_fold(&LATIN1_CASE_FOLD);
+        assert!(folded.matches_completely(b"STRA\xdfE-1.LOG"));
+        assert_eq!(LATIN1_CASE_FOLD[0xd7], 0xd7);
+        assert_eq!(BytePattern::parse_latin1("ä\\x"), Err(BytePatternError::UnknownEscapeSequence(2, b'x')));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(BytePattern::parse(b"ab\\"), Err(BytePatternError::UnterminatedEscapeSequence(2)));
+        assert_eq!(BytePattern::parse(b"a\\n"), Err(BytePatternError::UnknownEscapeSequence(1, b'n')));
+        assert_eq!(BytePattern::parse(b"a\\\xff").unwrap_err().to_string(), "unknown escape sequence `\\\\xff` at index 1");
+    }
+}
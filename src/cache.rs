@@ -0,0 +1,110 @@
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+use crate::lru::LruCache;
+use crate::{GlobParseError, ParsedGlobString};
+
+/// the number of compiled patterns kept by the global pattern cache unless changed with
+/// [`set_pattern_cache_capacity`].
+pub const DEFAULT_PATTERN_CACHE_CAPACITY: usize = 256;
+
+type PatternCache = LruCache<String, Arc<ParsedGlobString<'static>>>;
+
+static PATTERN_CACHE: OnceLock<Mutex<PatternCache>> = OnceLock::new();
+
+fn pattern_cache() -> MutexGuard<'static, PatternCache> {
+    let cache = PATTERN_CACHE.get_or_init(|| Mutex::new(LruCache::new(DEFAULT_PATTERN_CACHE_CAPACITY)));
+    // the cache is always left in a consistent state, so a panic elsewhere doesn't invalidate it
+    return cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+}
+
+/// returns the compiled form of `pattern`, parsing it only if it is not in the global pattern cache.
+///
+/// The cache is shared by all threads and holds at most [`DEFAULT_PATTERN_CACHE_CAPACITY`]
+/// patterns (see [`set_pattern_cache_capacity`]); the least recently used pattern is evicted first.
+/// Patterns that fail to parse are not cached.
+/// ```
+/// # use glob::cached_pattern;
+/// let pattern = cached_pattern("*.yaml").unwrap();
+/// assert!(pattern.matches_partially("deployment.yaml"));
+/// ```
+pub fn cached_pattern<'g>(pattern: &'g str) -> Result<Arc<ParsedGlobString<'static>>, GlobParseError<'g>> {
+    if let Some(parsed) = pattern_cache().get(pattern) {
+        return Ok(Arc::clone(parsed));
+    }
+    // parse without holding the lock, other threads may use the cache in the meantime
+    let parsed = Arc::new(ParsedGlobString::try_from(pattern)?.into_owned());
+    let mut cache = pattern_cache();
+    // another thread may have cached the pattern while this one parsed it
+    if let Some(cached) = cache.get(pattern) {
+        return Ok(Arc::clone(cached));
+    }
+    cache.insert(String::from(pattern), Arc::clone(&parsed));
+    return Ok(parsed);
+}
+
+/// checks if the given pattern occurs anywhere in the given string, using the global pattern cache.
+///
+/// This is the cached counterpart of [`pattern_matches_partially`](crate::pattern_matches_partially).
+pub fn pattern_matches_cached<'g>(pattern: &'g str, string: &str) -> Result<bool, GlobParseError<'g>> {
+    return cached_pattern(pattern).map(|parsed| parsed.matches_partially(string));
+}
+
+/// changes how many compiled patterns the global pattern cache holds. Shrinking the cache evicts
+/// the least recently used patterns immediately; a capacity of 0 disables caching.
+pub fn set_pattern_cache_capacity(capacity: usize) {
+    pattern_cache().set_capacity(capacity);
+}
+
+/// removes all patterns from the global pattern cache.
+pub fn clear_pattern_cache() {
+    pattern_cache().clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+    use crate::GlobParseError;
+    use super::{cached_pattern, pattern_matches_cached};
+
+    #[test]
+    fn test_cached_pattern_is_reused() {
+        let first = cached_pattern("cache-test-*.rs").unwrap();
+        let second = cached_pattern("cache-test-*.rs").unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+    }
+
+    #[test]
+    fn test_pattern_matches_cached() {
+        assert_eq!(pattern_matches_cached("*.toml", "Cargo.toml"), Ok(true));
+        assert_eq!(pattern_matches_cached("*.toml", "Cargo.lock"), Ok(false));
+    }
+
+    #[test]
+    fn test_parse_error_is_returned() {
+        assert_eq!(pattern_matches_cached("\\n", "foo"), Err(GlobParseError::UnknownEscapeSequence(0, "\\n")));
+    }
+
+    #[test]
+    fn test_cache_is_usable_from_multiple_threads() {
+        let handles: Vec<_> = (0..4).map(|i| thread::spawn(move || {
+            let pattern = format!("thread-{}-*", i % 2);
+            cached_pattern(&pattern).unwrap().matches_partially("thread-1-foo")
+        })).collect();
+        let results: Vec<bool> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+        assert_eq!(results, vec![false, true, false, true]);
+    }
+
+    #[test]
+    fn test_concurrent_misses_return_the_same_pattern() {
+        let barrier = Arc::new(Barrier::new(4));
+        let handles: Vec<_> = (0..4).map(|_| {
+            let barrier = Arc::clone(&barrier);
+            thread::spawn(move || {
+                barrier.wait();
+                cached_pattern("concurrent-miss-*").unwrap()
+            })
+        }).collect();
+        let patterns: Vec<_> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+        assert!(patterns.iter().all(|pattern| Arc::ptr_eq(pattern, &patterns[0])));
+    }
+}
@@ -0,0 +1,113 @@
+use std::ops::Range;
+use std::sync::{Mutex, MutexGuard};
+use crate::lru::LruCache;
+use crate::{Anchor, ParsedGlobString};
+
+/// a pattern that remembers its results for recently matched strings.
+///
+/// Useful when the same strings are matched against the same pattern over and over again, like file
+/// paths in a build system. The results for at most `capacity` distinct strings are kept; the least
+/// recently used result is evicted first. The matcher can be shared between threads.
+/// ```
+/// use glob::{Anchor, CachedMatcher, ParsedGlobString};
+/// let pattern = ParsedGlobString::try_from("src/*.rs").unwrap();
+/// let matcher = CachedMatcher::new(pattern, Anchor::Both, 1024);
+/// assert!(matcher.is_match("src/lib.rs")); // matches and stores the result
+/// assert!(matcher.is_match("src/lib.rs")); // looks up the stored result
+/// assert!(!matcher.is_match("src/lib.rs.orig"));
+/// ```
+#[derive(Debug)]
+pub struct CachedMatcher<'g> {
+    pattern: ParsedGlobString<'g>,
+    anchor: Anchor,
+    results: Mutex<LruCache<String, Option<Range<usize>>>>,
+}
+
+impl<'g> CachedMatcher<'g> {
+    /// creates a matcher that finds `pattern` anchored as requested and keeps the results for at
+    /// most `capacity` strings.
+    pub fn new(pattern: ParsedGlobString<'g>, anchor: Anchor, capacity: usize) -> Self {
+        return CachedMatcher { pattern: pattern, anchor: anchor, results: Mutex::new(LruCache::new(capacity)) };
+    }
+
+    fn results(&self) -> MutexGuard<'_, LruCache<String, Option<Range<usize>>>> {
+        // the cache is always left in a consistent state, so a panic elsewhere doesn't invalidate it
+        return self.results.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    }
+
+    /// finds the leftmost occurrence of the pattern in the given string, anchored as requested, and
+    /// returns its byte range.
+    pub fn find(&self, string: &str) -> Option<Range<usize>> {
+        if let Some(result) = self.results().get(string) {
+            return result.clone();
+        }
+        // match without holding the lock, other threads may use the cache in the meantime
+        let result = self.pattern.captures_anchored(string, self.anchor).map(|captures| captures.range());
+        self.results().insert(String::from(string), result.clone());
+        return result;
+    }
+
+    /// checks if the pattern occurs in the given string, anchored as requested.
+    pub fn is_match(&self, string: &str) -> bool {
+        return self.find(string).is_some();
+    }
+
+    /// returns the pattern.
+    pub fn pattern(&self) -> &ParsedGlobString<'g> {
+        return &self.pattern;
+    }
+
+    /// forgets all stored results.
+    pub fn clear(&self) {
+        self.results().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::thread;
+    use crate::{Anchor, ParsedGlobString};
+    use super::CachedMatcher;
+
+    fn matcher(pattern: &str, anchor: Anchor, capacity: usize) -> CachedMatcher<'_> {
+        return CachedMatcher::new(ParsedGlobString::try_from(pattern).unwrap(), anchor, capacity);
+    }
+
+    #[test]
+    fn test_cached_results_equal_fresh_results() {
+        let matcher = matcher("*.o", Anchor::End, 2);
+        for _ in 0..2 {
+            assert_eq!(matcher.find("build/main.o"), Some(0..12));
+            assert_eq!(matcher.find("build/main.c"), None);
+            assert!(matcher.is_match("lib.o"));
+        }
+        assert_eq!(matcher.results().len(), 2);
+    }
+
+    #[test]
+    fn test_anchor_is_respected() {
+        let matcher = matcher("lib", Anchor::Start, 8);
+        assert!(matcher.is_match("libglob"));
+        assert!(!matcher.is_match("glob-lib"));
+        assert!(matcher.pattern().matches_partially("glob-lib"));
+    }
+
+    #[test]
+    fn test_clear() {
+        let matcher = matcher("*", Anchor::None, 8);
+        matcher.is_match("a");
+        matcher.clear();
+        assert_eq!(matcher.results().len(), 0);
+    }
+
+    #[test]
+    fn test_shared_between_threads() {
+        let matcher = Arc::new(CachedMatcher::new(ParsedGlobString::try_from("*.rs").unwrap().into_owned(), Anchor::End, 16));
+        let threads: Vec<_> = (0..4).map(|i| {
+            let matcher = Arc::clone(&matcher);
+            thread::spawn(move || matcher.is_match(&format!("src/file{}.rs", i % 2)))
+        }).collect();
+        assert!(threads.into_iter().all(|thread| thread.join().unwrap()));
+    }
+}
@@ -0,0 +1,30 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// the error returned when a long-running operation stopped early because its cancellation flag
+/// was set. Walkers and searches yield it wrapped in an [`io::Error`] of kind
+/// [`Interrupted`](io::ErrorKind::Interrupted).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cancelled;
+
+impl Display for Cancelled {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the operation was cancelled")
+    }
+}
+
+impl Error for Cancelled {}
+
+impl From<Cancelled> for io::Error {
+    fn from(cancelled: Cancelled) -> Self {
+        return io::Error::new(io::ErrorKind::Interrupted, cancelled);
+    }
+}
+
+/// checks if the cancellation flag, if there is one, is set.
+pub(crate) fn is_cancelled(flag: &Option<Arc<AtomicBool>>) -> bool {
+    return flag.as_ref().is_some_and(|flag| flag.load(Ordering::Relaxed));
+}
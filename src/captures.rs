@@ -0,0 +1,114 @@
+use std::ops::Range;
+
+/// the result of a successful match, including the parts of the string matched by the wildcards.
+///
+/// Each wildcard token of the pattern captures the part of the string it matched. Consecutive
+/// wildcards like `?*` form a single token and therefore a single capture, so the pattern
+/// `[*,?*]` has two captures. All ranges are byte ranges into the matched string.
+/// ```
+/// use glob::ParsedGlobString;
+/// let pattern = ParsedGlobString::try_from("[*,?*]").unwrap();
+/// let captures = pattern.captures("x = [1, 23];").unwrap();
+/// assert_eq!(captures.range(), 4..11);
+/// assert_eq!(captures.as_str(), "[1, 23]");
+/// assert_eq!(captures.get(0), Some("1"));
+/// assert_eq!(captures.get(1), Some(" 23"));
+/// assert_eq!(captures.get(2), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Captures<'s> {
+    string: &'s str,
+    range: Range<usize>,
+    wildcard_ranges: Vec<Range<usize>>,
+}
+
+impl<'s> Captures<'s> {
+
+    pub(crate) fn new(string: &'s str, range: Range<usize>, wildcard_ranges: Vec<Range<usize>>) -> Self {
+        return Captures { string: string, range: range, wildcard_ranges: wildcard_ranges };
+    }
+
+    /// returns the string the pattern was matched against.
+    pub fn haystack(&self) -> &'s str {
+        return self.string;
+    }
+
+    /// returns the byte range of the whole match.
+    pub fn range(&self) -> Range<usize> {
+        return self.range.clone();
+    }
+
+    /// returns the matched part of the string.
+    pub fn as_str(&self) -> &'s str {
+        return &self.string[self.range.clone()];
+    }
+
+    /// returns the number of captures, i.e. the number of wildcard tokens in the pattern.
+    pub fn len(&self) -> usize {
+        return self.wildcard_ranges.len();
+    }
+
+    /// checks if there are no captures, i.e. if the pattern has no wildcards.
+    pub fn is_empty(&self) -> bool {
+        return self.wildcard_ranges.is_empty();
+    }
+
+    /// returns the part of the string matched by the `index`-th wildcard token.
+    pub fn get(&self, index: usize) -> Option<&'s str> {
+        return self.get_range(index).map(|range| &self.string[range]);
+    }
+
+    /// returns the byte range matched by the `index`-th wildcard token.
+    pub fn get_range(&self, index: usize) -> Option<Range<usize>> {
+        return self.wildcard_ranges.get(index).cloned();
+    }
+
+    /// iterates over the parts of the string matched by the wildcard tokens, in pattern order.
+    pub fn iter(&self) -> impl Iterator<Item = &'s str> + '_ {
+        return self.wildcard_ranges.iter().map(|range| &self.string[range.clone()]);
+    }
+
+    /// iterates over the byte ranges matched by the wildcard tokens, in pattern order.
+    pub fn ranges(&self) -> impl Iterator<Item = Range<usize>> + '_ {
+        return self.wildcard_ranges.iter().cloned();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Anchor, ParsedGlobString};
+
+    #[test]
+    fn test_captures_without_wildcards() {
+        let pattern = ParsedGlobString::try_from("lib").unwrap();
+        let captures = pattern.captures("src/lib.rs").unwrap();
+        assert_eq!(captures.range(), 4..7);
+        assert!(captures.is_empty());
+    }
+
+    #[test]
+    fn test_captures_iter() {
+        let pattern = ParsedGlobString::try_from("*/*.??").unwrap();
+        let captures = pattern.captures_anchored("src/lib.rs", Anchor::Both).unwrap();
+        assert_eq!(captures.iter().collect::<Vec<_>>(), vec!["src", "lib", "rs"]);
+        assert_eq!(captures.ranges().collect::<Vec<_>>(), vec![0..3, 4..7, 8..10]);
+        assert_eq!(captures.haystack(), "src/lib.rs");
+    }
+
+    #[test]
+    fn test_no_captures_without_match() {
+        let pattern = ParsedGlobString::try_from("*.rs").unwrap();
+        assert!(pattern.captures("lib.py").is_none());
+        assert!(pattern.captures_anchored("lib.rs.orig", Anchor::End).is_none());
+    }
+
+    #[test]
+    fn test_anchored_captures() {
+        let pattern = ParsedGlobString::try_from("a*").unwrap();
+        assert_eq!(pattern.captures_anchored("banana", Anchor::None).unwrap().range(), 1..2);
+        assert_eq!(pattern.captures_anchored("banana", Anchor::End).unwrap().range(), 1..6);
+        assert!(pattern.captures_anchored("banana", Anchor::Start).is_none());
+        assert_eq!(pattern.captures_anchored("apple", Anchor::Start).unwrap().get(0), Some(""));
+        assert_eq!(pattern.captures_anchored("apple", Anchor::Both).unwrap().get(0), Some("pple"));
+    }
+}
@@ -0,0 +1,114 @@
+use std::fmt::Debug;
+use std::panic::RefUnwindSafe;
+
+/// maps characters to a canonical case for case-insensitive matching: two characters match
+/// case-insensitively if they are folded to the same character.
+///
+/// Implement this trait for locale-specific rules and pass a `'static` reference to
+/// [`GlobBuilder::case_fold`](crate::GlobBuilder::case_fold):
+/// ```
+/// use glob::{CaseFold, DefaultCaseFold, GlobBuilder};
+///
+/// /// ignores case and the umlaut dots, so `Müller` matches `Muller`.
+/// #[derive(Debug)]
+/// struct IgnoreUmlauts;
+///
+/// impl CaseFold for IgnoreUmlauts {
+///     fn fold(&self, c: char) -> char {
+///         return match DefaultCaseFold.fold(c) {
+///             'ä' => 'a',
+///             'ö' => 'o',
+///             'ü' => 'u',
+///             c => c,
+///         };
+///     }
+/// }
+///
+/// let pattern = GlobBuilder::new("Muller*").case_fold(&IgnoreUmlauts).build().unwrap();
+/// assert!(pattern.matches_completely("MÜLLER GmbH"));
+/// ```
+pub trait CaseFold: Debug + Send + Sync + RefUnwindSafe {
+    /// returns the canonical case of `c`.
+    fn fold(&self, c: char) -> char;
+
+    /// returns whether this folding maps every ASCII character to its ASCII lowercase form, like
+    /// [`char::to_ascii_lowercase`]. If it does, matching compares ASCII characters of the string
+    /// without calling [`fold`](Self::fold). Defaults to `false`, which is always correct.
+    fn folds_ascii_to_lowercase(&self) -> bool {
+        return false;
+    }
+}
+
+/// folds characters to lowercase with the locale-independent mappings of Unicode. Characters whose
+/// lowercase form consists of several characters, like `İ`, are left unchanged.
+/// ```
+/// use glob::{CaseFold, DefaultCaseFold};
+/// assert_eq!(DefaultCaseFold.fold('Ä'), 'ä');
+/// assert_eq!(DefaultCaseFold.fold('I'), 'i');
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DefaultCaseFold;
+
+impl CaseFold for DefaultCaseFold {
+    fn fold(&self, c: char) -> char {
+        let mut lowercase = c.to_lowercase();
+        return match (lowercase.next(), lowercase.next()) {
+            (Option::Some(folded), Option::None) => folded,
+            _ => c,
+        };
+    }
+
+    fn folds_ascii_to_lowercase(&self) -> bool {
+        return true;
+    }
+}
+
+/// folds characters like [`DefaultCaseFold`], but with the rules of Turkish and Azerbaijani for
+/// the letter i: `I` is the uppercase form of the dotless `ı` and `İ` is the uppercase form of `i`.
+/// ```
+/// use glob::{CaseFold, TurkishCaseFold};
+/// assert_eq!(TurkishCaseFold.fold('I'), 'ı');
+/// assert_eq!(TurkishCaseFold.fold('İ'), 'i');
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TurkishCaseFold;
+
+impl CaseFold for TurkishCaseFold {
+    fn fold(&self, c: char) -> char {
+        return match c {
+            'I' => 'ı',
+            'İ' => 'i',
+            c => DefaultCaseFold.fold(c),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CaseFold, DefaultCaseFold, TurkishCaseFold};
+
+    #[test]
+    fn test_default_case_fold() {
+        assert_eq!(DefaultCaseFold.fold('A'), 'a');
+        assert_eq!(DefaultCaseFold.fold('a'), 'a');
+        assert_eq!(DefaultCaseFold.fold('Σ'), 'σ');
+        assert_eq!(DefaultCaseFold.fold('İ'), 'İ');
+        assert_eq!(DefaultCaseFold.fold('1'), '1');
+    }
+
+    #[test]
+    fn test_folds_ascii_to_lowercase() {
+        assert!((0..128u8).map(char::from).all(|c| DefaultCaseFold.fold(c) == c.to_ascii_lowercase()));
+        assert!(DefaultCaseFold.folds_ascii_to_lowercase());
+        assert!(!TurkishCaseFold.folds_ascii_to_lowercase());
+    }
+
+    #[test]
+    fn test_turkish_case_fold() {
+        assert_eq!(TurkishCaseFold.fold('I'), 'ı');
+        assert_eq!(TurkishCaseFold.fold('ı'), 'ı');
+        assert_eq!(TurkishCaseFold.fold('İ'), 'i');
+        assert_eq!(TurkishCaseFold.fold('i'), 'i');
+        assert_eq!(TurkishCaseFold.fold('Ş'), 'ş');
+    }
+}
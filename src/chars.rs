@@ -0,0 +1,176 @@
+use std::cell::Cell;
+use std::ops::Range;
+use glob_parser::Token;
+use crate::matcher::MatchConfig;
+use crate::{Anchor, ParsedGlobString, WildcardPolicy};
+
+fn is_word_character(c: char) -> bool {
+    return c.is_alphanumeric() || c == '_';
+}
+
+/// matches a token sequence against a slice of characters by trying all alignments of the
+/// wildcards, like the matcher for strings does.
+struct CharMatcher<'a, 'g> {
+    tokens: &'a [Token<'g>],
+    haystack: &'a [char],
+    anchor_end: bool,
+    config: &'a MatchConfig,
+    longest: Cell<Option<usize>>, // end of the longest match so far
+}
+
+impl<'a, 'g> CharMatcher<'a, 'g> {
+    fn wildcard_matches(&self, position: usize) -> bool {
+        return self.haystack.get(position).is_some_and(|c| self.config.wildcard_matches(*c));
+    }
+
+    fn literal_matches(&self, expected: char, position: usize) -> bool {
+        return match (self.haystack.get(position), self.config.case_fold) {
+            (Option::None, _) => false,
+            (Option::Some(c), Option::None) => *c == expected,
+            (Option::Some(c), Option::Some(fold)) => fold.fold(*c) == fold.fold(expected),
+        };
+    }
+
+    fn is_word_boundary(&self, position: usize) -> bool {
+        let before = position > 0 && is_word_character(self.haystack[position - 1]);
+        let after = self.haystack.get(position).is_some_and(|c| is_word_character(*c));
+        return before != after;
+    }
+
+    fn match_end(&self, position: usize) -> Option<usize> {
+        if self.anchor_end && position != self.haystack.len() {
+            return None;
+        }
+        if self.config.leftmost_longest && position != self.haystack.len() {
+            if self.longest.get().is_none_or(|end| position > end) {
+                self.longest.set(Some(position));
+            }
+            return None;
+        }
+        return Some(position);
+    }
+
+    /// tries to match the tokens starting at `token_index` at `position` and returns the index at
+    /// which the match ends.
+    fn match_here(&self, token_index: usize, position: usize) -> Option<usize> {
+        let token = match self.tokens.get(token_index) {
+            Option::None => return self.match_end(position),
+            Option::Some(token) => token,
+        };
+        match token {
+            Token::Literal(literal) => {
+                let mut end = position;
+                for expected in literal.chars() {
+                    if !self.literal_matches(expected, end) {
+                        return None;
+                    }
+                    end += 1;
+                }
+                return self.match_here(token_index + 1, end);
+            },
+            Token::ExactLengthWildcard(length) => {
+                if !(position..position + length).all(|i| self.wildcard_matches(i)) {
+                    return None;
+                }
+                return self.match_here(token_index + 1, position + length);
+            },
+            Token::WordBoundary => {
+                if !self.is_word_boundary(position) {
+                    return None;
+                }
+                return self.match_here(token_index + 1, position);
+            },
+            Token::MinLengthWildcard(length) => {
+                let minimum_end = position + length;
+                if !(position..minimum_end).all(|i| self.wildcard_matches(i)) {
+                    return None;
+                }
+                let mut ends = std::iter::successors(Some(minimum_end), |end| Some(end + 1).filter(|_| self.wildcard_matches(*end)));
+                return match self.config.wildcard_policy {
+                    WildcardPolicy::Lazy => ends.find_map(|end| self.match_here(token_index + 1, end)),
+                    WildcardPolicy::Greedy => ends.collect::<Vec<_>>().into_iter().rev().find_map(|end| self.match_here(token_index + 1, end)),
+                };
+            },
+        }
+    }
+}
+
+impl<'g> ParsedGlobString<'g> {
+    /// finds the leftmost occurrence of this pattern in a slice of characters, like
+    /// [`find`](Self::find) does in a string, and returns its range of indices. Each wildcard unit
+    /// is one element of the slice, so text that is already held as characters doesn't have to be
+    /// encoded as a string to be matched. The step budget of the pattern doesn't apply.
+    /// ```
+    /// use glob::ParsedGlobString;
+    /// let pattern = ParsedGlobString::try_from("gr??e").unwrap();
+    /// let text: Vec<char> = "viele Grüße".chars().collect();
+    /// assert_eq!(pattern.find_in_chars(&text), None);
+    /// let text: Vec<char> = "viele grüße".chars().collect();
+    /// assert_eq!(pattern.find_in_chars(&text), Some(6..11));
+    /// ```
+    pub fn find_in_chars(&self, haystack: &[char]) -> Option<Range<usize>> {
+        return self.find_in_chars_anchored(haystack, Anchor::None);
+    }
+
+    /// checks if this pattern matches a slice of characters with the options of this pattern,
+    /// including its anchoring, like [`is_match`](Self::is_match) does for a string.
+    pub fn is_match_chars(&self, haystack: &[char]) -> bool {
+        return self.find_in_chars_anchored(haystack, self.options().anchor).is_some();
+    }
+
+    fn find_in_chars_anchored(&self, haystack: &[char], anchor: Anchor) -> Option<Range<usize>> {
+        let matcher = CharMatcher {
+            tokens: self.tokens(),
+            haystack: haystack,
+            anchor_end: matches!(anchor, Anchor::End | Anchor::Both),
+            config: self.config(),
+            longest: Cell::new(None),
+        };
+        let last_start = if matches!(anchor, Anchor::Start | Anchor::Both) { 0 } else { haystack.len() };
+        for start in 0..=last_start {
+            if let Option::Some(end) = matcher.match_here(0, start) {
+                return Some(start..end);
+            }
+            if let Option::Some(end) = matcher.longest.take() {
+                return Some(start..end);
+            }
+        }
+        return None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Anchor, DefaultCaseFold, GlobBuilder, MatchOptions, ParsedGlobString, WildcardPolicy};
+
+    fn chars(string: &str) -> Vec<char> {
+        return string.chars().collect();
+    }
+
+    #[test]
+    fn test_find_in_chars_agrees_with_strings() {
+        let cases = [("*.rs", "main.rs"), ("a?c", "xxabcabc"), ("\\bid*", "uid id=1"), ("", "ä"), ("?*?", "ä"), ("x*", "abc")];
+        for (pattern, string) in cases {
+            let pattern = ParsedGlobString::try_from(pattern).unwrap();
+            let expected = pattern.find(string).map(|range| string[..range.start].chars().count()..string[..range.end].chars().count());
+            assert_eq!(pattern.find_in_chars(&chars(string)), expected, "{} in {}", pattern, string);
+        }
+    }
+
+    #[test]
+    fn test_is_match_chars_with_options() {
+        let options = MatchOptions { anchor: Anchor::Both, case_fold: Some(&DefaultCaseFold), path_separator: Some('/'), ..MatchOptions::default() };
+        let pattern = GlobBuilder::new("src/*.RS").options(&options).build().unwrap();
+        assert!(pattern.is_match_chars(&chars("SRC/lib.rs")));
+        assert!(!pattern.is_match_chars(&chars("src/bin/main.rs")));
+        assert!(!pattern.is_match_chars(&chars("src/lib.rs.orig")));
+    }
+
+    #[test]
+    fn test_find_in_chars_greedy_and_longest() {
+        let greedy = GlobBuilder::new("<*>").wildcard_policy(WildcardPolicy::Greedy).build().unwrap();
+        assert_eq!(greedy.find_in_chars(&chars("<a><b>")), Some(0..6));
+        let longest = GlobBuilder::new("a*").leftmost_longest(true).build().unwrap();
+        assert_eq!(longest.find_in_chars(&chars("xaaa")), Some(1..4));
+    }
+}
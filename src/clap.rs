@@ -0,0 +1,85 @@
+//! parsing command line arguments into compiled patterns with `clap`. Requires the `clap` feature.
+//!
+//! Arguments of type `ParsedGlobString<'static>` are parsed with [`PatternParser`] automatically,
+//! so a malformed pattern is reported by `clap` like any other invalid value, with the offending
+//! part of the pattern underlined:
+//! ```text
+//! error: invalid pattern '*.\x' for '<PATTERN>': unknown escape sequence `\x` at index 2
+//!
+//!   *.\x
+//!     ^^
+//! ```
+//! ```
+//! use clap::{Arg, ArgAction, Command, value_parser};
+//! use glob::ParsedGlobString;
+//! let command = Command::new("find")
+//!     .arg(Arg::new("include").long("include").action(ArgAction::Append).value_parser(value_parser!(ParsedGlobString<'static>)));
+//! let matches = command.try_get_matches_from(["find", "--include", "*.rs", "--include", "*.toml"]).unwrap();
+//! let patterns: Vec<&ParsedGlobString<'static>> = matches.get_many("include").unwrap().collect();
+//! assert!(patterns[1].matches_completely("Cargo.toml"));
+//! ```
+
+use std::ffi::OsStr;
+use ::clap::builder::{TypedValueParser, ValueParserFactory};
+use ::clap::error::ErrorKind;
+use ::clap::{Arg, Command, Error};
+use crate::ParsedGlobString;
+
+/// a `clap` value parser that parses an argument into a [`ParsedGlobString`] with the default
+/// options.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PatternParser;
+
+impl TypedValueParser for PatternParser {
+    type Value = ParsedGlobString<'static>;
+
+    fn parse_ref(&self, command: &Command, arg: Option<&Arg>, value: &OsStr) -> Result<Self::Value, Error> {
+        let Option::Some(pattern) = value.to_str() else {
+            return Err(command.clone().error(ErrorKind::InvalidUtf8, "invalid UTF-8 in a pattern"));
+        };
+        let error = match ParsedGlobString::try_from(pattern) {
+            Ok(parsed) => return Ok(parsed.into_owned()),
+            Err(error) => error,
+        };
+        let arg = arg.map_or_else(|| String::from("..."), |arg| arg.to_string());
+        let indent = pattern[..error.position()].chars().count();
+        let message = format!(
+            "invalid pattern '{}' for '{}': {}\n\n  {}\n  {}{}",
+            pattern, arg, error, pattern, " ".repeat(indent), "^".repeat(error.fragment().chars().count()),
+        );
+        return Err(command.clone().error(ErrorKind::ValueValidation, message));
+    }
+}
+
+impl ValueParserFactory for ParsedGlobString<'static> {
+    type Parser = PatternParser;
+
+    fn value_parser() -> Self::Parser {
+        return PatternParser;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::error::ErrorKind;
+    use clap::{value_parser, Arg, Command};
+    use crate::ParsedGlobString;
+
+    fn command() -> Command {
+        return Command::new("test").arg(Arg::new("pattern").value_name("PATTERN").required(true).value_parser(value_parser!(ParsedGlobString<'static>)));
+    }
+
+    #[test]
+    fn test_parse_pattern() {
+        let matches = command().try_get_matches_from(["test", "src/*.rs"]).unwrap();
+        assert!(matches.get_one::<ParsedGlobString<'static>>("pattern").unwrap().matches_completely("src/lib.rs"));
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let error = command().try_get_matches_from(["test", "ä*\\x"]).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::ValueValidation);
+        let message = error.to_string();
+        assert!(message.contains("invalid pattern 'ä*\\x' for '<PATTERN>': unknown escape sequence `\\x` at index 3\n\n  ä*\\x\n    ^^\n"), "{}", message);
+    }
+}
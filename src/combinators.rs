@@ -0,0 +1,202 @@
+use crate::Matcher;
+
+/// matches if either of two matchers matches, see [`Matcher::or`].
+#[derive(Debug, Clone)]
+pub struct Or<A, B> {
+    first: A,
+    second: B,
+}
+
+/// matches if both of two matchers match, see [`Matcher::and`].
+#[derive(Debug, Clone)]
+pub struct And<A, B> {
+    first: A,
+    second: B,
+}
+
+/// matches if a matcher doesn't match, see [`Matcher::negate`].
+#[derive(Debug, Clone)]
+pub struct Not<M> {
+    matcher: M,
+}
+
+/// matches if any of several matchers matches, see [`any`].
+#[derive(Debug, Clone)]
+pub struct Any<M> {
+    matchers: Vec<M>,
+}
+
+/// matches if all of several matchers match, see [`all`].
+#[derive(Debug, Clone)]
+pub struct All<M> {
+    matchers: Vec<M>,
+}
+
+impl<A, B> Or<A, B> {
+    pub(crate) fn new(first: A, second: B) -> Self {
+        return Or { first: first, second: second };
+    }
+}
+
+impl<A, B> And<A, B> {
+    pub(crate) fn new(first: A, second: B) -> Self {
+        return And { first: first, second: second };
+    }
+}
+
+impl<M> Not<M> {
+    pub(crate) fn new(matcher: M) -> Self {
+        return Not { matcher: matcher };
+    }
+}
+
+impl<A: Matcher, B: Matcher> Matcher for Or<A, B> {
+    fn is_match(&self, haystack: &str) -> bool {
+        return self.first.is_match(haystack) || self.second.is_match(haystack);
+    }
+}
+
+impl<A: Matcher, B: Matcher> Matcher for And<A, B> {
+    fn is_match(&self, haystack: &str) -> bool {
+        return self.first.is_match(haystack) && self.second.is_match(haystack);
+    }
+}
+
+impl<M: Matcher> Matcher for Not<M> {
+    fn is_match(&self, haystack: &str) -> bool {
+        return !self.matcher.is_match(haystack);
+    }
+}
+
+impl<M: Matcher> Matcher for Any<M> {
+    fn is_match(&self, haystack: &str) -> bool {
+        return self.matchers.iter().any(|matcher| matcher.is_match(haystack));
+    }
+}
+
+impl<M: Matcher> Matcher for All<M> {
+    fn is_match(&self, haystack: &str) -> bool {
+        return self.matchers.iter().all(|matcher| matcher.is_match(haystack));
+    }
+}
+
+/// matches strings that an include matcher matches, unless an exclude matcher matches them too,
+/// the common allow/deny composition. The exclude matcher is only evaluated for strings the
+/// include matcher matches, so it is cheap to exclude a few strings with a large set.
+/// ```
+/// use glob::{DiffMatcher, GlobSet, Matcher, ParsedGlobString};
+/// let include = ParsedGlobString::try_from("*.log").unwrap();
+/// let exclude: GlobSet = ["debug*", "*.tmp.*"].into_iter().map(|pattern| ParsedGlobString::try_from(pattern).unwrap()).collect();
+/// let logs = DiffMatcher::new(include, exclude);
+/// assert!(logs.is_match("server.log"));
+/// assert!(!logs.is_match("debug.log"));
+/// assert!(!logs.is_match("server.txt"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct DiffMatcher<I, E> {
+    include: I,
+    exclude: E,
+}
+
+impl<I: Matcher, E: Matcher> DiffMatcher<I, E> {
+    /// creates a matcher for the strings `include` matches and `exclude` doesn't.
+    pub fn new(include: I, exclude: E) -> Self {
+        return DiffMatcher { include: include, exclude: exclude };
+    }
+
+    /// returns the include matcher.
+    pub fn include(&self) -> &I {
+        return &self.include;
+    }
+
+    /// returns the exclude matcher.
+    pub fn exclude(&self) -> &E {
+        return &self.exclude;
+    }
+
+    /// checks if the string is matched by the include matcher, but excluded by the exclude matcher.
+    pub fn is_excluded(&self, haystack: &str) -> bool {
+        return self.include.is_match(haystack) && self.exclude.is_match(haystack);
+    }
+}
+
+impl<I: Matcher, E: Matcher> Matcher for DiffMatcher<I, E> {
+    fn is_match(&self, haystack: &str) -> bool {
+        return self.include.is_match(haystack) && !self.exclude.is_match(haystack);
+    }
+}
+
+/// combines matchers into one that matches if any of them matches. Without matchers, it matches
+/// nothing.
+/// ```
+/// use glob::{any, Matcher, ParsedGlobString};
+/// let patterns = ["*.jpg", "*.png"].map(|pattern| ParsedGlobString::try_from(pattern).unwrap());
+/// let images = any(patterns);
+/// assert!(images.is_match("cat.png"));
+/// assert!(!images.is_match("cat.txt"));
+/// ```
+pub fn any<M: Matcher>(matchers: impl IntoIterator<Item = M>) -> Any<M> {
+    return Any { matchers: matchers.into_iter().collect() };
+}
+
+/// combines matchers into one that matches if all of them match. Without matchers, it matches
+/// everything.
+pub fn all<M: Matcher>(matchers: impl IntoIterator<Item = M>) -> All<M> {
+    return All { matchers: matchers.into_iter().collect() };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use crate::{Matcher, ParsedGlobString};
+    use super::{all, any, DiffMatcher};
+
+    fn pattern(pattern: &str) -> ParsedGlobString<'_> {
+        return ParsedGlobString::try_from(pattern).unwrap();
+    }
+
+    #[test]
+    fn test_binary_combinators() {
+        let source = pattern("*.rs").or(pattern("*.toml"));
+        assert!(source.is_match("Cargo.toml"));
+        assert!(!source.is_match("README.md"));
+        let tests = pattern("tests/*").and(pattern("*.rs"));
+        assert!(tests.is_match("tests/cli.rs"));
+        assert!(!tests.is_match("src/lib.rs"));
+        let production = source.and(tests.negate());
+        assert!(production.is_match("src/lib.rs"));
+        assert!(!production.is_match("tests/cli.rs"));
+    }
+
+    #[test]
+    fn test_any_and_all() {
+        assert!(!any(Vec::<ParsedGlobString>::new()).is_match(""));
+        assert!(all(Vec::<ParsedGlobString>::new()).is_match(""));
+        let boxed: Vec<Box<dyn Matcher>> = vec![Box::new(pattern("a*")), Box::new(pattern("*b").negate())];
+        let combined = all(boxed);
+        assert!(combined.is_match("ac"));
+        assert!(!combined.is_match("ab"));
+    }
+
+    /// counts how often it is asked to match.
+    struct Counting<'c>(&'c Cell<usize>);
+
+    impl<'c> Matcher for Counting<'c> {
+        fn is_match(&self, _haystack: &str) -> bool {
+            self.0.set(self.0.get() + 1);
+            return true;
+        }
+    }
+
+    #[test]
+    fn test_diff_matcher_skips_exclude_if_include_does_not_match() {
+        let calls = Cell::new(0);
+        let diff = DiffMatcher::new(pattern("*.rs"), Counting(&calls));
+        assert!(!diff.is_match("README.md"));
+        assert_eq!(calls.get(), 0);
+        assert!(!diff.is_match("lib.rs"));
+        assert!(diff.is_excluded("lib.rs"));
+        assert!(!diff.is_excluded("README.md"));
+        assert_eq!(calls.get(), 2);
+    }
+}
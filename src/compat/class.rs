@@ -0,0 +1,47 @@
+//! character classes like `[a-z]`, which patterns of this crate don't have, for the compatibility
+//! layers.
+
+use crate::{CaseFold, DefaultCaseFold};
+
+/// a character class: the characters it lists or, if it is negated, the characters it doesn't.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(super) struct Class {
+    pub(super) negated: bool,
+    pub(super) ranges: Vec<(char, char)>, // inclusive, a listed character is a range of one
+}
+
+impl Class {
+    /// checks if the class matches `c`. If `case_insensitive`, a letter also matches if the class
+    /// lists it in another case.
+    pub(super) fn matches(&self, c: char, case_insensitive: bool) -> bool {
+        let lists = |c: char| self.ranges.iter().any(|(low, high)| (*low..=*high).contains(&c));
+        let mut listed = lists(c);
+        if case_insensitive && !listed {
+            let mut uppercase = c.to_uppercase();
+            let uppercase = match (uppercase.next(), uppercase.next()) {
+                (Option::Some(upper), Option::None) => upper,
+                _ => c,
+            };
+            listed = lists(DefaultCaseFold.fold(c)) || lists(uppercase);
+        }
+        return listed != self.negated;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Class;
+
+    #[test]
+    fn test_matches() {
+        let lowercase = Class { negated: false, ranges: vec![('a', 'z'), ('_', '_')] };
+        assert!(lowercase.matches('q', false));
+        assert!(lowercase.matches('_', false));
+        assert!(!lowercase.matches('Q', false));
+        assert!(lowercase.matches('Q', true));
+        let uppercase = Class { negated: true, ranges: vec![('A', 'Z')] };
+        assert!(uppercase.matches('q', false));
+        assert!(!uppercase.matches('q', true));
+        assert!(uppercase.matches('/', true));
+    }
+}
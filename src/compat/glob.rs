@@ -0,0 +1,718 @@
+//! the API of the [`glob`](https://docs.rs/glob) crate from crates.io, implemented with this
+//! crate, so projects can switch by changing their imports from `glob::` to `glob::compat::glob::`.
+//!
+//! Patterns use the syntax of that crate: `?` and `*` are wildcards, `**` as a whole path
+//! component matches any number of directories, and `[...]` matches one of the characters it
+//! lists, like `[a-z_]`, or with `[!...]`, one that it doesn't list. `[c]` is how `*`, `?` and `[`
+//! are written literally. A backslash is an ordinary character. Case-insensitive matching folds
+//! all of Unicode, not only ASCII.
+//!
+//! Patterns of this crate have no character classes, so patterns with classes, and patterns with
+//! so many `**/` that they would expand to too many patterns of this crate, are matched by a
+//! simpler matcher that follows the positions a match can reach in the string.
+//! ```
+//! use glob::compat::glob::{MatchOptions, Pattern};
+//! let pattern = Pattern::new("src/**/*.rs").unwrap();
+//! assert!(pattern.matches("src/bin/glob.rs"));
+//! let options = MatchOptions { require_literal_separator: true, ..MatchOptions::new() };
+//! assert!(pattern.matches_with("src/lib.rs", options));
+//! assert!(!Pattern::new("src/*.rs").unwrap().matches_with("src/bin/glob.rs", options));
+//! ```
+
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use crate::{Anchor, CaseFold, DefaultCaseFold, GlobWalker, ParsedGlobString, PathPattern};
+use super::class::Class;
+use super::globset::{render, Piece};
+
+const ERROR_WILDCARDS: &str = "wildcards are either regular `*` or recursive `**`";
+const ERROR_RECURSIVE_WILDCARDS: &str = "recursive wildcards must form a single path component";
+const ERROR_INVALID_RANGE: &str = "invalid range pattern";
+/// the most patterns of this crate that a pattern is expanded to; patterns with more `**/` are
+/// matched by [`matches_pieces`].
+const MAX_FLAT_PATTERNS: usize = 16;
+
+/// returned if a pattern is malformed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternError {
+    /// the index of the character in the pattern where the problem was found.
+    pub pos: usize,
+    /// describes the problem.
+    pub msg: &'static str,
+}
+
+impl Display for PatternError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "Pattern syntax error near position {}: {}", self.pos, self.msg);
+    }
+}
+
+impl Error for PatternError {}
+
+/// options for matching patterns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MatchOptions {
+    /// whether letters only match letters of the same case. Defaults to `true`.
+    pub case_sensitive: bool,
+    /// whether `*` and `?` never match `/`, so only `**` matches several path components. Defaults
+    /// to `false`.
+    pub require_literal_separator: bool,
+    /// whether a `.` at the start of a path component can only be matched by a literal `.`, so
+    /// wildcards don't match hidden files. Defaults to `false`.
+    pub require_literal_leading_dot: bool,
+}
+
+impl MatchOptions {
+    /// returns the default options.
+    pub fn new() -> Self {
+        return MatchOptions { case_sensitive: true, require_literal_separator: false, require_literal_leading_dot: false };
+    }
+
+    /// returns the options of this crate that correspond to these options.
+    fn to_options(self) -> crate::MatchOptions {
+        let case_fold: Option<&'static dyn crate::CaseFold> = if self.case_sensitive { None } else { Some(&DefaultCaseFold) };
+        return crate::MatchOptions { anchor: Anchor::Both, case_fold: case_fold, ..crate::MatchOptions::default() };
+    }
+}
+
+impl Default for MatchOptions {
+    fn default() -> Self {
+        return MatchOptions::new();
+    }
+}
+
+/// returns patterns of this crate for matching whole strings, in which wildcards match `/`, that
+/// together match the same strings as the pieces, which have no classes. A globstar followed by
+/// `/` matches either nothing or anything ending with `/`. Returns `None` if there would be more
+/// than [`MAX_FLAT_PATTERNS`].
+fn flat_patterns(pieces: &[Piece]) -> Option<Vec<String>> {
+    let mut patterns = vec![String::new()];
+    let mut i = 0;
+    while i < pieces.len() {
+        if pieces[i] == Piece::Globstar && pieces.get(i + 1) == Some(&Piece::Literal('/')) {
+            if patterns.len() * 2 > MAX_FLAT_PATTERNS {
+                return None;
+            }
+            let skipped = patterns.clone();
+            patterns.iter_mut().for_each(|pattern| pattern.push_str("*/"));
+            patterns.extend(skipped);
+            i += 2;
+            continue;
+        }
+        let piece = if pieces[i] == Piece::Globstar { &Piece::AnyString } else { &pieces[i] };
+        patterns.iter_mut().for_each(|pattern| render(pattern, piece));
+        i += 1;
+    }
+    return Some(patterns);
+}
+
+/// returns pieces that match the same paths component-wise, like a [`PathPattern`], as the given
+/// pieces if wildcards don't match `/`. A trailing `/**` only matches paths below the directory,
+/// not the directory.
+fn path_pieces(pieces: &[Piece]) -> Vec<Piece> {
+    let mut path_pieces = Vec::with_capacity(pieces.len() + 2);
+    for (i, piece) in pieces.iter().enumerate() {
+        if *piece == Piece::Globstar && i > 0 && i == pieces.len() - 1 {
+            path_pieces.extend([Piece::AnyString, Piece::Literal('/')]);
+        }
+        path_pieces.push(piece.clone());
+    }
+    return path_pieces;
+}
+
+/// returns the ranges of the characters listed in a class, like the `glob` crate: a `-` between two
+/// characters forms a range, elsewhere it is listed.
+fn class_ranges(listed: &[char]) -> Vec<(char, char)> {
+    let mut ranges = Vec::new();
+    let mut i = 0;
+    while i < listed.len() {
+        if i + 2 < listed.len() && listed[i + 1] == '-' {
+            ranges.push((listed[i], listed[i + 2]));
+            i += 3;
+        } else {
+            ranges.push((listed[i], listed[i]));
+            i += 1;
+        }
+    }
+    return ranges;
+}
+
+/// checks if the pieces match the whole string with the given options, like the `glob` crate
+/// matches a pattern, by following the positions in the string that a match can reach after
+/// each piece. With `require_literal_separator`, the pieces are matched component-wise like a
+/// [`PathPattern`].
+fn matches_pieces(pieces: &[Piece], string: &str, options: MatchOptions) -> bool {
+    let chars: Vec<char> = string.chars().collect();
+    let component_wise = options.require_literal_separator;
+    let is_equal = |a: char, b: char| if options.case_sensitive { a == b } else { DefaultCaseFold.fold(a) == DefaultCaseFold.fold(b) };
+    // checks if the character at `j` may be matched by a wildcard or a class
+    let is_wildcard_match = |j: usize| {
+        let is_leading_dot = chars[j] == '.' && (j == 0 || chars[j - 1] == '/');
+        let is_explicit_only = (component_wise && chars[j] == '/') || (options.require_literal_leading_dot && is_leading_dot);
+        return !is_explicit_only;
+    };
+    // checks if the path component that starts at `j` may be matched by a globstar
+    let is_globstar_match = |j: usize| !options.require_literal_leading_dot || chars.get(j) != Some(&'.');
+    // returns where the path component that starts at `j` ends
+    let component_end = |j: usize| chars[j..].iter().position(|c| *c == '/').map_or(chars.len(), |offset| j + offset);
+    // `reached[j]` tells whether the pieces so far can match `chars[..j]`
+    let mut reached: Vec<bool> = (0..=chars.len()).map(|j| j == 0).collect();
+    let mut i = 0;
+    while i < pieces.len() {
+        let mut next = vec![false; chars.len() + 1];
+        let is_globstar_next = pieces.get(i + 1) == Some(&Piece::Globstar);
+        let is_separator_next = pieces.get(i + 1) == Some(&Piece::Literal('/'));
+        match &pieces[i] {
+            // a trailing `/**` matches any number of components below, or none
+            Piece::Literal('/') if component_wise && is_globstar_next && i + 2 == pieces.len() => {
+                for j in 0..=chars.len() {
+                    next[j] = next[j] || reached[j];
+                    if next[j] && j < chars.len() && chars[j] == '/' && is_globstar_match(j + 1) {
+                        next[component_end(j + 1)] = true;
+                    }
+                }
+                i += 1;
+            },
+            Piece::Literal(c) => {
+                for j in 0..chars.len() {
+                    next[j + 1] = reached[j] && is_equal(chars[j], *c);
+                }
+            },
+            Piece::AnyChar => {
+                for j in 0..chars.len() {
+                    next[j + 1] = reached[j] && is_wildcard_match(j);
+                }
+            },
+            Piece::Class(class) => {
+                for j in 0..chars.len() {
+                    next[j + 1] = reached[j] && is_wildcard_match(j) && class.matches(chars[j], !options.case_sensitive);
+                }
+            },
+            // `**` as the whole pattern matches any number of components
+            Piece::Globstar if component_wise && pieces.len() == 1 => {
+                let mut component_starts = (0..chars.len()).filter(|j| chars[*j] == '/').map(|j| j + 1);
+                next[chars.len()] = is_globstar_match(0) && component_starts.all(is_globstar_match);
+            },
+            // `**/` matches any number of components followed by `/`
+            Piece::Globstar if component_wise && is_separator_next => {
+                for j in 0..=chars.len() {
+                    next[j] = next[j] || reached[j];
+                    if next[j] && is_globstar_match(j) && component_end(j) < chars.len() {
+                        next[component_end(j) + 1] = true;
+                    }
+                }
+                i += 1;
+            },
+            // `**/` matches nothing or anything ending with `/`
+            Piece::Globstar if is_separator_next => {
+                let mut any_string = false;
+                for j in 0..chars.len() {
+                    any_string = reached[j] || (any_string && is_wildcard_match(j - 1));
+                    next[j] = next[j] || reached[j];
+                    next[j + 1] = any_string && chars[j] == '/';
+                }
+                next[chars.len()] = next[chars.len()] || reached[chars.len()];
+                i += 1;
+            },
+            Piece::AnyString | Piece::Globstar => {
+                for j in 0..=chars.len() {
+                    next[j] = reached[j] || (j > 0 && next[j - 1] && is_wildcard_match(j - 1));
+                }
+            },
+        }
+        reached = next;
+        i += 1;
+    }
+    return reached[chars.len()];
+}
+
+/// splits a pattern in the syntax of the `glob` crate into the pieces of a `globset` glob, which
+/// have the same meaning.
+fn translate(pattern: &str) -> Result<Vec<Piece>, PatternError> {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut translated = Vec::with_capacity(chars.len());
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '*' => {
+                let run = chars[i..].iter().take_while(|c| **c == '*').count();
+                if run > 2 {
+                    return Err(PatternError { pos: i + 2, msg: ERROR_WILDCARDS });
+                }
+                let is_component = (i == 0 || chars[i - 1] == '/') && chars.get(i + run).is_none_or(|c| *c == '/');
+                if run == 2 && !is_component {
+                    return Err(PatternError { pos: i, msg: ERROR_RECURSIVE_WILDCARDS });
+                }
+                translated.push(if run == 2 { Piece::Globstar } else { Piece::AnyString });
+                i += run;
+            },
+            '?' => {
+                translated.push(Piece::AnyChar);
+                i += 1;
+            },
+            '[' => {
+                // a `]` right after `[` or `[!` belongs to the class
+                let first = if chars.get(i + 1) == Some(&'!') { i + 2 } else { i + 1 };
+                let Option::Some(end) = chars.iter().skip(first + 1).position(|c| *c == ']').map(|offset| first + 1 + offset) else {
+                    return Err(PatternError { pos: i, msg: ERROR_INVALID_RANGE });
+                };
+                let negated = first != i + 1;
+                let listed = &chars[first..end];
+                // a class can't match `/` or a leading `.` if the options say so, unlike a literal
+                if !negated && listed.len() == 1 && !matches!(listed[0], '/' | '.') {
+                    translated.push(Piece::Literal(listed[0]));
+                } else {
+                    translated.push(Piece::Class(Class { negated: negated, ranges: class_ranges(listed) }));
+                }
+                i = end + 1;
+            },
+            c => {
+                translated.push(Piece::Literal(c));
+                i += 1;
+            },
+        }
+    }
+    return Ok(translated);
+}
+
+/// a compiled pattern.
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    original: String,
+    compiled: Compiled,
+}
+
+#[derive(Debug, Clone)]
+enum Compiled {
+    Patterns {
+        flat: Vec<ParsedGlobString<'static>>, // for matching with wildcards that match `/`
+        paths: [[PathPattern<'static>; 2]; 2], // for matching component-wise, by case sensitivity and literal leading dot
+    },
+    // for patterns that patterns of this crate can't express, matched with `matches_pieces`
+    Pieces {
+        flat: Vec<Piece>,
+        path: Vec<Piece>,
+    },
+}
+
+impl Pattern {
+    /// parses the pattern.
+    pub fn new(pattern: &str) -> Result<Self, PatternError> {
+        let pieces = translate(pattern)?;
+        let path_pieces = path_pieces(&pieces);
+        let flat = match flat_patterns(&pieces) {
+            Option::Some(flat) if !pieces.iter().any(|piece| matches!(piece, Piece::Class(_))) => flat,
+            _ => return Ok(Pattern { original: String::from(pattern), compiled: Compiled::Pieces { flat: pieces, path: path_pieces } }),
+        };
+        let flat = flat.iter()
+            .map(|flat| ParsedGlobString::try_from(flat.as_str()).expect("translated patterns are valid").into_owned())
+            .collect();
+        let mut path = String::new();
+        path_pieces.iter().for_each(|piece| render(&mut path, piece));
+        let compile = |case_sensitive: bool, require_literal_leading_dot: bool| -> PathPattern<'static> {
+            let options = MatchOptions { case_sensitive: case_sensitive, require_literal_separator: true, require_literal_leading_dot: require_literal_leading_dot };
+            return PathPattern::with_options(&path, &options.to_options())
+                .expect("translated patterns are valid")
+                .literal_leading_dot(require_literal_leading_dot)
+                .into_owned();
+        };
+        let paths = [[compile(false, false), compile(false, true)], [compile(true, false), compile(true, true)]];
+        return Ok(Pattern { original: String::from(pattern), compiled: Compiled::Patterns { flat: flat, paths: paths } });
+    }
+
+    /// escapes the characters with a special meaning in patterns, so the result matches `s`
+    /// literally.
+    pub fn escape(s: &str) -> String {
+        let mut escaped = String::with_capacity(s.len());
+        for c in s.chars() {
+            if matches!(c, '*' | '?' | '[' | ']') {
+                escaped.push('[');
+                escaped.push(c);
+                escaped.push(']');
+            } else {
+                escaped.push(c);
+            }
+        }
+        return escaped;
+    }
+
+    /// returns the pattern as it was written.
+    pub fn as_str(&self) -> &str {
+        return &self.original;
+    }
+
+    /// checks if the pattern matches the whole string, with the default options.
+    pub fn matches(&self, str: &str) -> bool {
+        return self.matches_with(str, MatchOptions::new());
+    }
+
+    /// checks if the pattern matches the whole path, with the default options. Paths that are not
+    /// valid Unicode never match.
+    pub fn matches_path(&self, path: &Path) -> bool {
+        return self.matches_path_with(path, MatchOptions::new());
+    }
+
+    /// checks if the pattern matches the whole string, with the given options.
+    ///
+    /// Like in the `glob` crate, `**/` matches nothing or anything that ends with `/`, and with
+    /// `require_literal_separator`, a trailing `/**` only matches paths below the directory.
+    /// ```
+    /// use glob::compat::glob::{MatchOptions, Pattern};
+    /// assert!(Pattern::new("src/**/*.rs").unwrap().matches("src/lib.rs"));
+    /// let options = MatchOptions { require_literal_separator: true, ..MatchOptions::new() };
+    /// assert!(!Pattern::new("src/**").unwrap().matches_with("src", options));
+    /// ```
+    pub fn matches_with(&self, str: &str, options: MatchOptions) -> bool {
+        let (flat, paths) = match &self.compiled {
+            Compiled::Pieces { path, .. } if options.require_literal_separator => return matches_pieces(path, str, options),
+            Compiled::Pieces { flat, .. } => return matches_pieces(flat, str, options),
+            Compiled::Patterns { flat, paths } => (flat, paths),
+        };
+        if options.require_literal_separator {
+            return paths[usize::from(options.case_sensitive)][usize::from(options.require_literal_leading_dot)].matches(str);
+        }
+        let bytes = str.as_bytes();
+        let is_leading_dot = |index: usize| bytes[index] == b'.' && (index == 0 || bytes[index - 1] == b'/');
+        return flat.iter().any(|flat| {
+            let Option::Some(captures) = flat.captures_with_config(str, Anchor::Both, &options.to_options().apply_to(*flat.config())) else {
+                return false;
+            };
+            return !options.require_literal_leading_dot || !captures.ranges().any(|mut range| range.any(is_leading_dot));
+        });
+    }
+
+    /// checks if the pattern matches the whole path, with the given options. Paths that are not
+    /// valid Unicode never match.
+    pub fn matches_path_with(&self, path: &Path, options: MatchOptions) -> bool {
+        return path.to_str().is_some_and(|path| self.matches_with(path, options));
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = PatternError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return Pattern::new(s);
+    }
+}
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "{}", self.original);
+    }
+}
+
+impl Default for Pattern {
+    fn default() -> Self {
+        return Pattern::new("").expect("the empty pattern is valid");
+    }
+}
+
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        return self.original == other.original;
+    }
+}
+
+impl Eq for Pattern {}
+
+impl PartialOrd for Pattern {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        return Some(self.cmp(other));
+    }
+}
+
+impl Ord for Pattern {
+    fn cmp(&self, other: &Self) -> Ordering {
+        return self.original.cmp(&other.original);
+    }
+}
+
+impl Hash for Pattern {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.original.hash(state);
+    }
+}
+
+/// an error while searching for paths, see [`glob`].
+#[derive(Debug)]
+pub struct GlobError {
+    path: PathBuf,
+    error: io::Error,
+}
+
+impl GlobError {
+    /// returns the path of the directory that was searched when the error occurred.
+    pub fn path(&self) -> &Path {
+        return &self.path;
+    }
+
+    /// returns the error.
+    pub fn error(&self) -> &io::Error {
+        return &self.error;
+    }
+
+    /// returns the error, consuming this error.
+    pub fn into_error(self) -> io::Error {
+        return self.error;
+    }
+}
+
+impl Display for GlobError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "attempting to read `{}` resulted in an error: {}", self.path.display(), self.error);
+    }
+}
+
+impl Error for GlobError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        return Some(&self.error);
+    }
+}
+
+/// the result of [`Paths`].
+pub type GlobResult = Result<PathBuf, GlobError>;
+
+/// an iterator over the paths that match a pattern, see [`glob`].
+pub struct Paths {
+    walker: Option<GlobWalker<'static, 'static>>,
+    root: PathBuf,
+    literal_path: Option<PathBuf>, // for patterns without wildcards
+    classes: Option<(Vec<Piece>, MatchOptions)>, // for the rest of a pattern with classes, which the walker matches like `?`
+}
+
+impl Paths {
+    /// checks if the path that the walker found matches the classes of the pattern, if it has any.
+    fn matches_classes(&self, path: &Path) -> bool {
+        let Option::Some((pieces, options)) = &self.classes else {
+            return true;
+        };
+        let Option::Some(relative) = path.strip_prefix(&self.root).ok().and_then(Path::to_str) else {
+            return false;
+        };
+        if cfg!(windows) {
+            return matches_pieces(pieces, &relative.replace('\\', "/"), *options);
+        }
+        return matches_pieces(pieces, relative, *options);
+    }
+}
+
+impl Iterator for Paths {
+    type Item = GlobResult;
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Option::Some(path) = self.literal_path.take() {
+            return Some(Ok(path));
+        }
+        loop {
+            let result = self.walker.as_mut()?.next()?;
+            if result.as_ref().is_ok_and(|path| !self.matches_classes(path)) {
+                continue;
+            }
+            return Some(result.map_err(|error| GlobError { path: self.root.clone(), error: error }));
+        }
+    }
+}
+
+/// returns an iterator over the paths that match the pattern, with the default options.
+/// ```no_run
+/// use glob::compat::glob::glob;
+/// for path in glob("/media/pictures/*.jpg").unwrap() {
+///     println!("{}", path.unwrap().display());
+/// }
+/// ```
+pub fn glob(pattern: &str) -> Result<Paths, PatternError> {
+    return glob_with(pattern, MatchOptions::new());
+}
+
+/// returns an iterator over the paths that match the pattern, with the given options.
+///
+/// The pattern is matched component by component, so wildcards and classes never match `/`. The
+/// leading components without wildcards name the directory that is searched; relative patterns
+/// are searched in the current directory and yield relative paths. Paths are yielded in
+/// alphabetical order, directories before their contents.
+pub fn glob_with(pattern: &str, options: MatchOptions) -> Result<Paths, PatternError> {
+    translate(pattern)?;
+    let components: Vec<&str> = pattern.split('/').collect();
+    let literal_count = components.iter().take_while(|component| !component.contains(['*', '?', '['])).count();
+    if literal_count == components.len() {
+        let path = PathBuf::from(pattern);
+        let exists = !pattern.is_empty() && path.exists();
+        return Ok(Paths { walker: None, root: path.clone(), literal_path: if exists { Some(path) } else { None }, classes: None });
+    }
+    let root = match components[..literal_count].join("/") {
+        root if root.is_empty() && literal_count > 0 => PathBuf::from("/"),
+        root => PathBuf::from(root),
+    };
+    let pieces = translate(&components[literal_count..].join("/")).expect("parts of valid patterns are valid");
+    let mut rest = String::new();
+    pieces.iter().for_each(|piece| render(&mut rest, piece));
+    let has_classes = pieces.iter().any(|piece| matches!(piece, Piece::Class(_)));
+    let classes = if has_classes { Some((pieces, MatchOptions { require_literal_separator: true, ..options })) } else { None };
+    let path_pattern = PathPattern::with_options(&rest, &options.to_options())
+        .expect("translated patterns are valid")
+        .literal_leading_dot(options.require_literal_leading_dot)
+        .into_owned();
+    let walker = if literal_count == 0 {
+        GlobWalker::with_owned_path_pattern(PathBuf::from("."), path_pattern, true)
+    } else {
+        GlobWalker::with_owned_path_pattern(root.clone(), path_pattern, false)
+    };
+    return Ok(Paths { walker: Some(walker), root: root, literal_path: None, classes: classes });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+    use crate::walk::tests::create_tree;
+    use super::{flat_patterns, glob_with, translate, Class, MatchOptions, Pattern, PatternError, Piece};
+
+    #[test]
+    fn test_translate() {
+        assert_eq!(flat_patterns(&translate("a\\b[*]c[[]?").unwrap()).unwrap(), vec!["a\\\\b\\*c[?"]);
+        assert_eq!(flat_patterns(&translate("**/x/**").unwrap()).unwrap(), vec!["*/x/*", "x/*"]);
+        assert_eq!(flat_patterns(&translate(&"**/x/".repeat(5)).unwrap()), None);
+        assert_eq!(translate("a**").unwrap_err(), PatternError { pos: 1, msg: super::ERROR_RECURSIVE_WILDCARDS });
+        assert_eq!(translate("***").unwrap_err().pos, 2);
+        assert_eq!(translate("a[b").unwrap_err(), PatternError { pos: 1, msg: super::ERROR_INVALID_RANGE });
+        assert_eq!(translate("[]]").unwrap(), vec![Piece::Literal(']')]);
+        assert_eq!(translate("[!]a-c-]").unwrap(), vec![Piece::Class(Class { negated: true, ranges: vec![(']', ']'), ('a', 'c'), ('-', '-')] })]);
+        assert_eq!(translate("[.]").unwrap(), vec![Piece::Class(Class { negated: false, ranges: vec![('.', '.')] })]);
+    }
+
+    #[test]
+    fn test_matches() {
+        let pattern = Pattern::new("*.rs").unwrap();
+        assert!(pattern.matches("src/lib.rs"));
+        assert!(!pattern.matches("lib.rs.orig"));
+        assert!(Pattern::new("[*]").unwrap().matches("*"));
+        assert!(Pattern::new("C:\\*").unwrap().matches("C:\\x"));
+        assert_eq!(Pattern::escape("a*b[c]"), "a[*]b[[]c[]]");
+        assert!(Pattern::new(&Pattern::escape("a*b[c]")).unwrap().matches("a*b[c]"));
+    }
+
+    #[test]
+    fn test_match_options() {
+        let insensitive = MatchOptions { case_sensitive: false, ..MatchOptions::new() };
+        assert!(Pattern::new("*.TXT").unwrap().matches_with("a.txt", insensitive));
+        let separator = MatchOptions { require_literal_separator: true, ..MatchOptions::new() };
+        assert!(!Pattern::new("a*b").unwrap().matches_with("a/b", separator));
+        assert!(Pattern::new("a/**/b").unwrap().matches_with("a/x/y/b", separator));
+        let dot = MatchOptions { require_literal_leading_dot: true, ..MatchOptions::new() };
+        assert!(!Pattern::new("*").unwrap().matches_with(".hidden", dot));
+        assert!(!Pattern::new("a*").unwrap().matches_with("a/.hidden", dot));
+        assert!(Pattern::new(".*").unwrap().matches_with(".hidden", dot));
+        assert!(Pattern::new("*").unwrap().matches_with("a.b", dot));
+    }
+
+    #[test]
+    fn test_character_classes() {
+        let pattern = Pattern::new("[a-c]*.[!o]").unwrap();
+        assert!(pattern.matches("build.c"));
+        assert!(!pattern.matches("build.o"));
+        assert!(!pattern.matches("Build.c"));
+        let insensitive = MatchOptions { case_sensitive: false, ..MatchOptions::new() };
+        assert!(pattern.matches_with("Build.c", insensitive));
+        assert!(Pattern::new("[]-]").unwrap().matches("-"));
+        let separator = MatchOptions { require_literal_separator: true, ..MatchOptions::new() };
+        assert!(Pattern::new("a[!b]c").unwrap().matches("a/c"));
+        assert!(!Pattern::new("a[!b]c").unwrap().matches_with("a/c", separator));
+        assert!(Pattern::new("src/**/[a-z]*.rs").unwrap().matches_with("src/bin/glob.rs", separator));
+        let dot = MatchOptions { require_literal_leading_dot: true, ..MatchOptions::new() };
+        assert!(Pattern::new("[.]a").unwrap().matches(".a"));
+        assert!(!Pattern::new("[.]a").unwrap().matches_with(".a", dot));
+        assert!(!Pattern::new("x/[!a]*").unwrap().matches_with("x/.hidden", dot));
+    }
+
+    #[test]
+    fn test_many_recursive_wildcards() {
+        let pattern = Pattern::new(&"**/x/".repeat(8)).unwrap();
+        assert!(pattern.matches(&"x/".repeat(8)));
+        assert!(pattern.matches(&"a/x/".repeat(8)));
+        assert!(!pattern.matches(&"x/".repeat(7)));
+        let separator = MatchOptions { require_literal_separator: true, ..MatchOptions::new() };
+        assert!(pattern.matches_with(&"a/b/x/".repeat(8), separator));
+        assert!(!pattern.matches_with(&"ax/".repeat(8), separator));
+    }
+
+    #[test]
+    fn test_recursive_wildcards() {
+        let pattern = Pattern::new("some/**/needle.txt").unwrap();
+        assert!(pattern.matches("some/needle.txt"));
+        assert!(pattern.matches("some/one/needle.txt"));
+        assert!(pattern.matches("some/one/two/needle.txt"));
+        assert!(!pattern.matches("some/other/notthis.txt"));
+        let pattern = Pattern::new("**").unwrap();
+        assert!(pattern.matches(""));
+        assert!(pattern.matches(".asdf"));
+        assert!(pattern.matches("/x/.asdf"));
+        let pattern = Pattern::new("**/test").unwrap();
+        assert!(pattern.matches("one/two/test"));
+        assert!(pattern.matches("test"));
+        let pattern = Pattern::new("/**/test").unwrap();
+        assert!(pattern.matches("/one/test"));
+        assert!(pattern.matches("/test"));
+        assert!(!pattern.matches("/notthis"));
+        let pattern = Pattern::new("**/.*").unwrap();
+        assert!(pattern.matches(".abc"));
+        assert!(pattern.matches("abc/.abc"));
+        assert!(!pattern.matches("ab.c"));
+        assert!(!pattern.matches("abc/ab.c"));
+        assert!(Pattern::new("**/*.rs").unwrap().matches("lib.rs"));
+        assert!(Pattern::new("src/**/*.rs").unwrap().matches("src/lib.rs"));
+    }
+
+    #[test]
+    fn test_require_literal_separator() {
+        let separator = MatchOptions { require_literal_separator: true, ..MatchOptions::new() };
+        assert!(Pattern::new("abc/def").unwrap().matches_with("abc/def", separator));
+        assert!(!Pattern::new("abc?def").unwrap().matches_with("abc/def", separator));
+        assert!(!Pattern::new("abc*def").unwrap().matches_with("abc/def", separator));
+        assert!(Pattern::new("abc*def").unwrap().matches_with("abc/def", MatchOptions::new()));
+        assert!(!Pattern::new("?/**").unwrap().matches_with("a", separator));
+        assert!(!Pattern::new("*/**").unwrap().matches_with("a", separator));
+        assert!(!Pattern::new("/**").unwrap().matches_with("", separator));
+        assert!(Pattern::new("*/**").unwrap().matches_with("a/b", separator));
+        let both = MatchOptions { require_literal_leading_dot: true, ..separator };
+        assert!(Pattern::new("*.*").unwrap().matches_with(".a", both));
+        assert!(!Pattern::new("*").unwrap().matches_with(".a", both));
+    }
+
+    #[test]
+    fn test_require_literal_leading_dot() {
+        let dot = MatchOptions { require_literal_leading_dot: true, ..MatchOptions::new() };
+        let matches = |pattern: &str, string: &str| -> (bool, bool) {
+            let pattern = Pattern::new(pattern).unwrap();
+            return (pattern.matches(string), pattern.matches_with(string, dot));
+        };
+        assert_eq!(matches("*.txt", ".hello.txt"), (true, false));
+        assert_eq!(matches(".*.*", ".hello.txt"), (true, true));
+        assert_eq!(matches("aaa/bbb/*", "aaa/bbb/.ccc"), (true, false));
+        assert_eq!(matches("aaa/bbb/*", "aaa/bbb/c.c.c."), (true, true));
+        assert_eq!(matches("aaa/bbb/.*", "aaa/bbb/.ccc"), (true, true));
+        assert_eq!(matches("aaa/?bbb", "aaa/.bbb"), (true, false));
+        assert_eq!(matches("**/*", ".bbb"), (true, false));
+    }
+
+    #[test]
+    fn test_glob_with() {
+        let root = create_tree("compat-glob", &["Cargo.toml", "src/lib.rs", "src/.hidden.rs", "src/bin/cli.rs"]);
+        let root_pattern = root.to_str().unwrap();
+        let paths = |pattern: &str, options: MatchOptions| -> Vec<PathBuf> {
+            return glob_with(&format!("{}/{}", root_pattern, pattern), options).unwrap().map(|path| path.unwrap()).collect();
+        };
+        assert_eq!(paths("src/*.rs", MatchOptions::new()), vec![root.join("src/.hidden.rs"), root.join("src/lib.rs")]);
+        let dot = MatchOptions { require_literal_leading_dot: true, ..MatchOptions::new() };
+        assert_eq!(paths("**/*.rs", dot), vec![root.join("src/bin/cli.rs"), root.join("src/lib.rs")]);
+        assert_eq!(paths("Cargo.toml", MatchOptions::new()), vec![root.join("Cargo.toml")]);
+        assert_eq!(paths("Cargo.lock", MatchOptions::new()), Vec::<PathBuf>::new());
+        assert_eq!(paths("[!C]*/[!l]*", MatchOptions::new()), vec![root.join("src/.hidden.rs"), root.join("src/bin")]);
+        assert_eq!(paths("[!C]*/[!l]*", dot), vec![root.join("src/bin")]);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+}
@@ -0,0 +1,858 @@
+//! the API of the [`globset`](https://docs.rs/globset) crate, implemented with this crate, so tools
+//! of the ripgrep ecosystem can use it as a backend by changing their imports.
+//!
+//! Patterns use the syntax of that crate: `?` and `*` are wildcards, `**` as a whole path
+//! component matches any number of directories and is `*` elsewhere, `{a,b}` matches one of the
+//! alternatives, which may be nested, `\` escapes the next character and `[a-z]` matches one of
+//! the characters a class lists, or with `[!a-z]` or `[^a-z]` one it doesn't. Like in globset,
+//! classes match `/` even with [`GlobBuilder::literal_separator`]; unlike in globset, they match a
+//! character rather than a byte. Case-insensitive matching folds all of Unicode.
+//!
+//! Globs are compiled to patterns of this crate, one per alternative. Globs with classes, or with
+//! too many alternatives, are matched directly instead, without expanding their alternates.
+//! ```
+//! use glob::compat::globset::{Glob, GlobSetBuilder};
+//! let mut builder = GlobSetBuilder::new();
+//! builder.add(Glob::new("*.rs").unwrap());
+//! builder.add(Glob::new("src/**/foo.rs").unwrap());
+//! builder.add(Glob::new("*.{md,txt}").unwrap());
+//! let set = builder.build().unwrap();
+//! assert_eq!(set.matches("src/bar/baz/foo.rs"), vec![0, 1]);
+//! assert!(set.is_match("README.md"));
+//! ```
+
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use crate::{Anchor, CaseFold, DefaultCaseFold, GlobBuilder as PatternBuilder, MatchOptions, ParsedGlobString, PathPattern};
+use super::class::Class;
+
+/// the kinds of errors in a glob.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// no longer returned: like in globset, `**` together with other characters in a path
+    /// component is two `*`.
+    InvalidRecursive,
+    /// a `[` is not closed by a `]`.
+    UnclosedClass,
+    /// no longer returned: like in globset, classes may list several characters and ranges.
+    UnsupportedClass,
+    /// a range in a character class ends before it starts, like `[z-a]`.
+    InvalidRange(char, char),
+    /// a `}` has no matching `{`.
+    UnopenedAlternates,
+    /// a `{` is not closed by a `}`.
+    UnclosedAlternates,
+    /// no longer returned: like in globset, alternates may be nested.
+    NestedAlternates,
+    /// the glob ends with a `\`.
+    DanglingEscape,
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let description = match self {
+            ErrorKind::InvalidRecursive => "invalid use of **; must be one path component",
+            ErrorKind::UnclosedClass => "unclosed character class; missing ']'",
+            ErrorKind::UnsupportedClass => "character classes with several characters are not supported",
+            ErrorKind::InvalidRange(start, end) => return write!(f, "invalid range; '{}' > '{}'", start, end),
+            ErrorKind::UnopenedAlternates => "unopened alternate group; missing '{' (maybe escape '}' with '[}]'?)",
+            ErrorKind::UnclosedAlternates => "unclosed alternate group; missing '}' (maybe escape '{' with '[{]'?)",
+            ErrorKind::NestedAlternates => "nested alternate groups are not allowed",
+            ErrorKind::DanglingEscape => "dangling '\\'",
+        };
+        return write!(f, "{}", description);
+    }
+}
+
+/// returned if a glob is malformed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    glob: Option<String>,
+    kind: ErrorKind,
+}
+
+impl Error {
+    /// returns the glob that caused the error, if known.
+    pub fn glob(&self) -> Option<&str> {
+        return self.glob.as_deref();
+    }
+
+    /// returns the kind of the error.
+    pub fn kind(&self) -> &ErrorKind {
+        return &self.kind;
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.glob {
+            Option::Some(glob) => write!(f, "error parsing glob '{}': {}", glob, self.kind),
+            Option::None => write!(f, "{}", self.kind),
+        }
+    }
+}
+
+impl StdError for Error {}
+
+/// a part of a glob without alternates.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(super) enum Piece {
+    Literal(char),
+    AnyChar,
+    AnyString,
+    Globstar,
+    Class(Class),
+}
+
+/// the most patterns of this crate a glob is compiled to. Globs that would need more, because the
+/// number of alternatives multiplies with each group of alternates, are matched directly.
+const MAX_PATTERNS: usize = 64;
+
+/// parses the class that starts at `chars[start]`, which is a `[`, the way globset does, and
+/// returns it with the index after its `]`. A `]` right after `[`, `[!` or `[^` is listed, a `-`
+/// between two characters forms a range, a `-` after a range extends it and a trailing `-` is
+/// listed.
+fn parse_class(chars: &[char], start: usize) -> Result<(Class, usize), ErrorKind> {
+    let mut i = start + 1;
+    let negated = matches!(chars.get(i), Some('!' | '^'));
+    if negated {
+        i += 1;
+    }
+    let mut ranges: Vec<(char, char)> = Vec::new();
+    let mut is_first = true;
+    let mut in_range = false;
+    loop {
+        let c = *chars.get(i).ok_or(ErrorKind::UnclosedClass)?;
+        i += 1;
+        if c == ']' && !is_first {
+            break;
+        }
+        if c == '-' && !is_first && !in_range {
+            in_range = true;
+        } else if in_range {
+            let range = ranges.last_mut().expect("a range follows a character");
+            if c < range.0 {
+                return Err(ErrorKind::InvalidRange(range.0, c));
+            }
+            range.1 = c;
+            in_range = false;
+        } else {
+            ranges.push((c, c));
+        }
+        is_first = false;
+    }
+    if in_range {
+        ranges.push(('-', '-'));
+    }
+    return Ok((Class { negated: negated, ranges: ranges }, i));
+}
+
+/// a token of a glob, like in globset. The recursive tokens include the `/` next to the `**`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Token {
+    Literal(char),
+    AnyChar,
+    AnyString,
+    RecursivePrefix, // `**/` at the start
+    RecursiveSuffix, // `/**` at the end
+    RecursiveZeroOrMore, // `/**/`
+    Class(Class),
+    Alternates(Vec<Vec<Token>>),
+}
+
+/// parses a glob into tokens the way globset does. `**` that doesn't form a whole path component
+/// is two `*`, and alternates may be nested.
+fn parse(chars: &[char], backslash_escape: bool) -> Result<Vec<Token>, ErrorKind> {
+    let mut branches: Vec<Vec<Token>> = vec![Vec::new()];
+    let mut alternates_stack: Vec<usize> = Vec::new(); // the index of the first branch of each open group
+    let mut i = 0;
+    while i < chars.len() {
+        let branch = branches.last_mut().expect("never empty");
+        match chars[i] {
+            '\\' if backslash_escape => {
+                let c = *chars.get(i + 1).ok_or(ErrorKind::DanglingEscape)?;
+                branch.push(Token::Literal(c));
+                i += 2;
+            },
+            '[' => {
+                let (class, end) = parse_class(chars, i)?;
+                match class.ranges.as_slice() {
+                    // a class of one character, like `[*]`, only escapes it
+                    [(low, high)] if low == high && !class.negated => branch.push(Token::Literal(*low)),
+                    _ => branch.push(Token::Class(class)),
+                }
+                i = end;
+            },
+            '{' => {
+                alternates_stack.push(branches.len());
+                branches.push(Vec::new());
+                i += 1;
+            },
+            '}' => {
+                let first = alternates_stack.pop().ok_or(ErrorKind::UnopenedAlternates)?;
+                let alternates = Token::Alternates(branches.drain(first..).collect());
+                branches.last_mut().expect("never empty").push(alternates);
+                i += 1;
+            },
+            ',' if !alternates_stack.is_empty() => {
+                branches.push(Vec::new());
+                i += 1;
+            },
+            '?' => {
+                branch.push(Token::AnyChar);
+                i += 1;
+            },
+            '*' if chars.get(i + 1) != Some(&'*') => {
+                branch.push(Token::AnyString);
+                i += 1;
+            },
+            '*' => {
+                let previous = if i == 0 { None } else { Some(chars[i - 1]) };
+                let next = chars.get(i + 2).copied();
+                i += 2;
+                if branch.is_empty() {
+                    if next.is_some_and(|c| c != '/') {
+                        branch.extend([Token::AnyString, Token::AnyString]);
+                    } else {
+                        branch.push(Token::RecursivePrefix);
+                        if next.is_some() {
+                            i += 1;
+                        }
+                    }
+                    continue;
+                }
+                if previous != Some('/') && (alternates_stack.is_empty() || !matches!(previous, Some(',' | '{'))) {
+                    branch.extend([Token::AnyString, Token::AnyString]);
+                    continue;
+                }
+                let is_suffix = match next {
+                    Option::None => true,
+                    Option::Some(',' | '}') if !alternates_stack.is_empty() => true,
+                    Option::Some('/') => {
+                        i += 1;
+                        false
+                    },
+                    Option::Some(_) => {
+                        branch.extend([Token::AnyString, Token::AnyString]);
+                        continue;
+                    },
+                };
+                let token = match branch.pop() {
+                    Option::Some(Token::RecursivePrefix) => Token::RecursivePrefix,
+                    Option::Some(Token::RecursiveSuffix) => Token::RecursiveSuffix,
+                    _ if is_suffix => Token::RecursiveSuffix,
+                    _ => Token::RecursiveZeroOrMore,
+                };
+                branch.push(token);
+            },
+            c => {
+                branch.push(Token::Literal(c));
+                i += 1;
+            },
+        }
+    }
+    if branches.len() > 1 {
+        return Err(ErrorKind::UnclosedAlternates);
+    }
+    return Ok(branches.pop().expect("never empty"));
+}
+
+/// expands the alternates in the tokens, e.g. `*.{md,txt}` to `*.md` and `*.txt`, or returns
+/// `None` if there would be more than [`MAX_PATTERNS`] alternatives. Alternatives that are empty
+/// are left out, unless `empty_alternates` is set.
+fn expand(tokens: &[Token], empty_alternates: bool) -> Option<Vec<Vec<Token>>> {
+    let mut expanded = vec![Vec::new()];
+    for token in tokens {
+        let suffixes = match token {
+            Token::Alternates(branches) => {
+                let mut alternatives: Vec<Vec<Token>> = Vec::new();
+                for branch in branches {
+                    let branch_alternatives = expand(branch, empty_alternates)?;
+                    if empty_alternates || branch_alternatives != [Vec::new()] {
+                        alternatives.extend(branch_alternatives);
+                    }
+                }
+                if alternatives.is_empty() { vec![Vec::new()] } else { alternatives }
+            },
+            token => vec![vec![token.clone()]],
+        };
+        if expanded.len() * suffixes.len() > MAX_PATTERNS {
+            return None;
+        }
+        let mut combined = Vec::with_capacity(expanded.len() * suffixes.len());
+        for prefix in &expanded {
+            for suffix in &suffixes {
+                combined.push([prefix.as_slice(), suffix.as_slice()].concat());
+            }
+        }
+        expanded = combined;
+    }
+    return Some(expanded);
+}
+
+/// returns the pieces of patterns of this crate that together match the same strings as the
+/// tokens, which have no alternates. With `literal_separator`, the pieces form path patterns,
+/// otherwise patterns for whole strings, in which wildcards match `/`. Returns `None` if the
+/// tokens have a class or would need more than [`MAX_PATTERNS`] patterns.
+fn translate(tokens: &[Token], literal_separator: bool) -> Option<Vec<Vec<Piece>>> {
+    use Piece::{AnyString, Globstar, Literal};
+    let mut translated = vec![Vec::new()];
+    for (i, token) in tokens.iter().enumerate() {
+        let at_component_start = i == 0 || tokens[i - 1] == Token::Literal('/');
+        let fragments = match (token, literal_separator) {
+            (Token::Literal(c), _) => vec![vec![Literal(*c)]],
+            (Token::AnyChar, _) => vec![vec![Piece::AnyChar]],
+            (Token::AnyString, _) => vec![vec![AnyString]],
+            (Token::RecursivePrefix, false) => vec![vec![], vec![AnyString, Literal('/')]],
+            (Token::RecursiveSuffix, false) => vec![vec![Literal('/'), AnyString]],
+            (Token::RecursiveZeroOrMore, false) => vec![vec![Literal('/')], vec![Literal('/'), AnyString, Literal('/')]],
+            // a globstar component matches any number of components, so the recursive tokens
+            // become globstars with wildcards for the parts of components next to them
+            (Token::RecursivePrefix, true) if at_component_start => vec![vec![Globstar, Literal('/')]],
+            (Token::RecursivePrefix, true) => vec![vec![], vec![AnyString, Literal('/'), Globstar, Literal('/')]],
+            (Token::RecursiveSuffix, true) if i == tokens.len() - 1 => vec![vec![Literal('/'), AnyString, Literal('/'), Globstar]],
+            (Token::RecursiveSuffix, true) => vec![
+                vec![Literal('/'), AnyString],
+                vec![Literal('/'), AnyString, Literal('/'), Globstar, Literal('/'), AnyString],
+            ],
+            (Token::RecursiveZeroOrMore, true) => vec![vec![Literal('/'), Globstar, Literal('/')]],
+            (Token::Class(_), _) => return None,
+            (Token::Alternates(_), _) => unreachable!("alternates are expanded before"),
+        };
+        if translated.len() * fragments.len() > MAX_PATTERNS {
+            return None;
+        }
+        let mut combined = Vec::with_capacity(translated.len() * fragments.len());
+        for prefix in &translated {
+            for fragment in &fragments {
+                let mut pieces = prefix.clone();
+                for piece in fragment {
+                    // consecutive `*` would form a globstar
+                    if !(*piece == AnyString && pieces.last() == Some(&AnyString)) {
+                        pieces.push(piece.clone());
+                    }
+                }
+                combined.push(pieces);
+            }
+        }
+        translated = combined;
+    }
+    return Some(translated);
+}
+
+/// checks if globset compiles the tokens to nothing when `empty_alternates` is not set, which it
+/// does if they are only alternates whose branches all compile to nothing.
+fn compiles_to_nothing(tokens: &[Token]) -> bool {
+    return tokens.iter().all(|token| match token {
+        Token::Alternates(branches) => branches.iter().all(|branch| compiles_to_nothing(branch)),
+        _ => false,
+    });
+}
+
+/// returns whether a match of the tokens can end at each position in `chars`, given whether a
+/// match can start there, by following the regular expression globset compiles the tokens to.
+/// Alternates are matched branch by branch rather than expanded, so this takes time linear in
+/// the size of the glob.
+fn advance(tokens: &[Token], chars: &[char], starts: Vec<bool>, glob: &Glob) -> Vec<bool> {
+    let is_equal = |a: char, b: char| if glob.case_insensitive { DefaultCaseFold.fold(a) == DefaultCaseFold.fold(b) } else { a == b };
+    // checks if the character at `j` may be matched by `?` or `*`
+    let is_wildcard_match = |j: usize| !glob.literal_separator || chars[j] != '/';
+    let mut reached = starts;
+    for token in tokens {
+        let mut next = vec![false; chars.len() + 1];
+        match token {
+            Token::Literal(c) => {
+                for j in 0..chars.len() {
+                    next[j + 1] = reached[j] && is_equal(chars[j], *c);
+                }
+            },
+            Token::AnyChar => {
+                for j in 0..chars.len() {
+                    next[j + 1] = reached[j] && is_wildcard_match(j);
+                }
+            },
+            Token::Class(class) => {
+                for j in 0..chars.len() {
+                    next[j + 1] = reached[j] && class.matches(chars[j], glob.case_insensitive);
+                }
+            },
+            Token::AnyString => {
+                for j in 0..=chars.len() {
+                    next[j] = reached[j] || (j > 0 && next[j - 1] && is_wildcard_match(j - 1));
+                }
+            },
+            // `(?:/?|.*/)`: nothing, or anything that ends with `/`
+            Token::RecursivePrefix => {
+                let mut any_string = false;
+                for j in 0..chars.len() {
+                    any_string = any_string || reached[j];
+                    next[j] = next[j] || reached[j];
+                    next[j + 1] = any_string && chars[j] == '/';
+                }
+                next[chars.len()] = next[chars.len()] || reached[chars.len()];
+            },
+            // `/.*`: `/` and anything after it
+            Token::RecursiveSuffix => {
+                let mut after_separator = false;
+                for j in 0..chars.len() {
+                    after_separator = after_separator || (reached[j] && chars[j] == '/');
+                    next[j + 1] = after_separator;
+                }
+            },
+            // `(?:/|/.*/)`: `/`, or anything between two `/`
+            Token::RecursiveZeroOrMore => {
+                let mut after_separator = false;
+                for j in 0..chars.len() {
+                    if chars[j] == '/' {
+                        next[j + 1] = reached[j] || after_separator;
+                        after_separator = after_separator || reached[j];
+                    }
+                }
+            },
+            Token::Alternates(branches) => {
+                // like in globset, branches that compile to nothing are left out, and so is a
+                // group of only such branches
+                let kept: Vec<&Vec<Token>> = branches.iter().filter(|branch| glob.empty_alternates || !compiles_to_nothing(branch)).collect();
+                if kept.is_empty() {
+                    next = reached.clone();
+                }
+                for branch in kept {
+                    let ends = advance(branch, chars, reached.clone(), glob);
+                    for j in 0..=chars.len() {
+                        next[j] = next[j] || ends[j];
+                    }
+                }
+            },
+        }
+        reached = next;
+    }
+    return reached;
+}
+
+/// appends a piece to a pattern of this crate. Patterns of this crate have no classes, so a class
+/// becomes `?`, which matches more.
+pub(super) fn render(pattern: &mut String, piece: &Piece) {
+    match piece {
+        Piece::Literal(c) => {
+            if matches!(c, '*' | '?' | '\\') {
+                pattern.push('\\');
+            }
+            pattern.push(*c);
+        },
+        Piece::AnyChar | Piece::Class(_) => pattern.push('?'),
+        Piece::AnyString => pattern.push('*'),
+        Piece::Globstar => pattern.push_str("**"),
+    }
+}
+
+/// a single glob.
+/// ```
+/// use glob::compat::globset::Glob;
+/// let matcher = Glob::new("*.rs").unwrap().compile_matcher();
+/// assert!(matcher.is_match("src/lib.rs"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Glob {
+    glob: String,
+    literal_separator: bool,
+    case_insensitive: bool,
+    backslash_escape: bool,
+    empty_alternates: bool,
+    tokens: Vec<Token>,
+}
+
+impl Glob {
+    /// parses a glob with the default options.
+    pub fn new(glob: &str) -> Result<Glob, Error> {
+        return GlobBuilder::new(glob).build();
+    }
+
+    /// returns the glob as it was written.
+    pub fn glob(&self) -> &str {
+        return &self.glob;
+    }
+
+    /// compiles the glob into a matcher.
+    pub fn compile_matcher(&self) -> GlobMatcher {
+        let mut options = MatchOptions { anchor: Anchor::Both, ..MatchOptions::default() };
+        if self.case_insensitive {
+            options.case_fold = Some(&DefaultCaseFold);
+        }
+        let patterns = match self.patterns() {
+            Option::Some(patterns) => patterns,
+            Option::None => return GlobMatcher { glob: self.clone(), alternatives: vec![Alternative::Tokens] },
+        };
+        let mut alternatives = Vec::new();
+        for pieces in patterns {
+            let mut pattern = String::new();
+            pieces.iter().for_each(|piece| render(&mut pattern, piece));
+            if self.literal_separator {
+                let compiled = PathPattern::with_options(&pattern, &options).expect("rendered patterns are valid").into_owned();
+                alternatives.push(Alternative::Path(compiled));
+            } else {
+                let compiled = PatternBuilder::new(&pattern).options(&options).build().expect("rendered patterns are valid");
+                alternatives.push(Alternative::Flat(compiled.into_owned()));
+            }
+        }
+        return GlobMatcher { glob: self.clone(), alternatives: alternatives };
+    }
+
+    /// returns the pieces of the patterns of this crate that the glob is compiled to, or `None` if
+    /// it has classes or would need more than [`MAX_PATTERNS`] patterns.
+    fn patterns(&self) -> Option<Vec<Vec<Piece>>> {
+        let mut patterns = Vec::new();
+        for tokens in expand(&self.tokens, self.empty_alternates)? {
+            patterns.extend(translate(&tokens, self.literal_separator)?);
+            if patterns.len() > MAX_PATTERNS {
+                return None;
+            }
+        }
+        return Some(patterns);
+    }
+
+    /// checks if the glob matches the whole string without compiling it.
+    fn matches_tokens(&self, string: &str) -> bool {
+        let chars: Vec<char> = string.chars().collect();
+        let starts = (0..=chars.len()).map(|j| j == 0).collect();
+        return advance(&self.tokens, &chars, starts, self)[chars.len()];
+    }
+}
+
+impl Display for Glob {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "{}", self.glob);
+    }
+}
+
+impl std::str::FromStr for Glob {
+    type Err = Error;
+    fn from_str(glob: &str) -> Result<Self, Self::Err> {
+        return Glob::new(glob);
+    }
+}
+
+/// configures how a [`Glob`] is parsed and matched.
+#[derive(Debug, Clone)]
+pub struct GlobBuilder<'a> {
+    glob: &'a str,
+    literal_separator: bool,
+    case_insensitive: bool,
+    backslash_escape: bool,
+    empty_alternates: bool,
+}
+
+impl<'a> GlobBuilder<'a> {
+    /// creates a builder with the default options.
+    pub fn new(glob: &'a str) -> Self {
+        return GlobBuilder { glob: glob, literal_separator: false, case_insensitive: false, backslash_escape: true, empty_alternates: false };
+    }
+
+    /// sets whether `*` and `?` never match `/`. Defaults to `false`.
+    pub fn literal_separator(&mut self, yes: bool) -> &mut Self {
+        self.literal_separator = yes;
+        return self;
+    }
+
+    /// sets whether letters match letters of any case. Defaults to `false`.
+    pub fn case_insensitive(&mut self, yes: bool) -> &mut Self {
+        self.case_insensitive = yes;
+        return self;
+    }
+
+    /// sets whether `\` escapes the next character. Otherwise, it is an ordinary character.
+    /// Defaults to `true`.
+    pub fn backslash_escape(&mut self, yes: bool) -> &mut Self {
+        self.backslash_escape = yes;
+        return self;
+    }
+
+    /// sets whether an empty alternative matches the empty string, so `{,a}` matches both `` and
+    /// `a`. Otherwise, empty alternatives are ignored. Defaults to `false`.
+    pub fn empty_alternates(&mut self, yes: bool) -> &mut Self {
+        self.empty_alternates = yes;
+        return self;
+    }
+
+    /// parses the glob.
+    pub fn build(&self) -> Result<Glob, Error> {
+        let error = |kind: ErrorKind| Error { glob: Some(String::from(self.glob)), kind: kind };
+        let chars: Vec<char> = self.glob.chars().collect();
+        let mut tokens = parse(&chars, self.backslash_escape).map_err(error)?;
+        if tokens == [Token::RecursivePrefix] {
+            // like in globset, a glob that is only `**` or `**/` matches everything, like `**/*`
+            tokens.push(Token::AnyString);
+        }
+        return Ok(Glob {
+            glob: String::from(self.glob),
+            literal_separator: self.literal_separator,
+            case_insensitive: self.case_insensitive,
+            backslash_escape: self.backslash_escape,
+            empty_alternates: self.empty_alternates,
+            tokens: tokens,
+        });
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Alternative {
+    Flat(ParsedGlobString<'static>),
+    Path(PathPattern<'static>),
+    Tokens, // the glob itself, for globs that aren't compiled to patterns of this crate
+}
+
+/// a compiled [`Glob`].
+#[derive(Debug, Clone)]
+pub struct GlobMatcher {
+    glob: Glob,
+    alternatives: Vec<Alternative>,
+}
+
+impl GlobMatcher {
+    /// returns the glob this matcher was compiled from.
+    pub fn glob(&self) -> &Glob {
+        return &self.glob;
+    }
+
+    /// checks if the glob matches the path.
+    pub fn is_match(&self, path: impl AsRef<Path>) -> bool {
+        return self.is_match_candidate(&Candidate::new(&path));
+    }
+
+    /// checks if the glob matches the candidate.
+    pub fn is_match_candidate(&self, candidate: &Candidate) -> bool {
+        return self.alternatives.iter().any(|alternative| match alternative {
+            Alternative::Flat(pattern) => pattern.matches_completely(&candidate.path),
+            Alternative::Path(pattern) => pattern.matches(&candidate.path),
+            Alternative::Tokens => self.glob.matches_tokens(&candidate.path),
+        });
+    }
+}
+
+/// a path prepared for matching against many globs.
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    path: String,
+}
+
+impl Candidate {
+    /// prepares the path. On Windows, `\` separators are replaced by `/`.
+    pub fn new(path: &impl AsRef<Path>) -> Candidate {
+        let path = path.as_ref().to_string_lossy();
+        if cfg!(windows) {
+            return Candidate { path: path.replace('\\', "/") };
+        }
+        return Candidate { path: path.into_owned() };
+    }
+}
+
+/// a set of globs that are matched together.
+#[derive(Debug, Clone, Default)]
+pub struct GlobSet {
+    matchers: Vec<GlobMatcher>,
+}
+
+impl GlobSet {
+    /// creates a set that matches nothing.
+    pub fn empty() -> GlobSet {
+        return GlobSet { matchers: Vec::new() };
+    }
+
+    /// returns the number of globs.
+    pub fn len(&self) -> usize {
+        return self.matchers.len();
+    }
+
+    /// checks if the set has no globs.
+    pub fn is_empty(&self) -> bool {
+        return self.matchers.is_empty();
+    }
+
+    /// checks if any glob matches the path.
+    pub fn is_match(&self, path: impl AsRef<Path>) -> bool {
+        return self.is_match_candidate(&Candidate::new(&path));
+    }
+
+    /// checks if any glob matches the candidate.
+    pub fn is_match_candidate(&self, candidate: &Candidate) -> bool {
+        return self.matchers.iter().any(|matcher| matcher.is_match_candidate(candidate));
+    }
+
+    /// returns the indices of the globs that match the path, in ascending order.
+    pub fn matches(&self, path: impl AsRef<Path>) -> Vec<usize> {
+        return self.matches_candidate(&Candidate::new(&path));
+    }
+
+    /// returns the indices of the globs that match the candidate, in ascending order.
+    pub fn matches_candidate(&self, candidate: &Candidate) -> Vec<usize> {
+        let mut matches = Vec::new();
+        self.matches_candidate_into(candidate, &mut matches);
+        return matches;
+    }
+
+    /// replaces the contents of `into` with the indices of the globs that match the path.
+    pub fn matches_into(&self, path: impl AsRef<Path>, into: &mut Vec<usize>) {
+        self.matches_candidate_into(&Candidate::new(&path), into);
+    }
+
+    /// replaces the contents of `into` with the indices of the globs that match the candidate.
+    pub fn matches_candidate_into(&self, candidate: &Candidate, into: &mut Vec<usize>) {
+        into.clear();
+        into.extend((0..self.matchers.len()).filter(|index| self.matchers[*index].is_match_candidate(candidate)));
+    }
+}
+
+/// collects globs for a [`GlobSet`].
+#[derive(Debug, Clone, Default)]
+pub struct GlobSetBuilder {
+    globs: Vec<Glob>,
+}
+
+impl GlobSetBuilder {
+    /// creates a builder without globs.
+    pub fn new() -> GlobSetBuilder {
+        return GlobSetBuilder { globs: Vec::new() };
+    }
+
+    /// adds a glob; its index in the set is the number of globs added before.
+    pub fn add(&mut self, glob: Glob) -> &mut GlobSetBuilder {
+        self.globs.push(glob);
+        return self;
+    }
+
+    /// compiles the globs into a set.
+    pub fn build(&self) -> Result<GlobSet, Error> {
+        return Ok(GlobSet { matchers: self.globs.iter().map(Glob::compile_matcher).collect() });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ErrorKind, Glob, GlobBuilder};
+
+    fn is_match(glob: &str, path: &str) -> bool {
+        return Glob::new(glob).unwrap().compile_matcher().is_match(path);
+    }
+
+    fn is_match_literal_separator(glob: &str, path: &str) -> bool {
+        return GlobBuilder::new(glob).literal_separator(true).build().unwrap().compile_matcher().is_match(path);
+    }
+
+    #[test]
+    fn test_errors() {
+        let kind = |glob: &str| Glob::new(glob).unwrap_err().kind().clone();
+        assert_eq!(kind("[a"), ErrorKind::UnclosedClass);
+        assert_eq!(kind("[z-a]"), ErrorKind::InvalidRange('z', 'a'));
+        assert_eq!(kind("a}"), ErrorKind::UnopenedAlternates);
+        assert_eq!(kind("{a"), ErrorKind::UnclosedAlternates);
+        assert_eq!(kind("{a,{b}"), ErrorKind::UnclosedAlternates);
+        assert_eq!(kind("{a,b}}"), ErrorKind::UnopenedAlternates);
+        assert_eq!(kind("a\\"), ErrorKind::DanglingEscape);
+        assert_eq!(Glob::new("a\\").unwrap_err().to_string(), "error parsing glob 'a\\': dangling '\\'");
+        assert_eq!(Glob::new("[z-a]").unwrap_err().to_string(), "error parsing glob '[z-a]': invalid range; 'z' > 'a'");
+    }
+
+    #[test]
+    fn test_character_classes() {
+        assert!(is_match("[a-c]x", "bx"));
+        assert!(!is_match("[a-c]x", "dx"));
+        assert!(is_match("[!a-c]x", "dx"));
+        assert!(!is_match("[^a-c]x", "ax"));
+        assert!(is_match("[]-]", "]"));
+        assert!(is_match("[]-]", "-"));
+        assert!(is_match("[a-c-e]", "d"));
+        assert!(is_match("[a-]", "-"));
+        assert!(is_match("[\\]", "\\"));
+        assert!(is_match_literal_separator("a[/]b", "a/b"));
+        assert!(is_match_literal_separator("a[!x]b", "a/b"));
+        assert!(!is_match_literal_separator("a?b", "a/b"));
+        assert!(is_match("{[a-c],x}.rs", "b.rs"));
+        assert!(is_match_literal_separator("**/[A-Z]*.md", "docs/README.md"));
+        assert!(!is_match_literal_separator("**/[A-Z]*.md", "docs/index.md"));
+        let glob = GlobBuilder::new("[a-c]").case_insensitive(true).build().unwrap();
+        assert!(glob.compile_matcher().is_match("B"));
+    }
+
+    #[test]
+    fn test_many_alternates() {
+        // the alternatives multiply with each group, so such globs are matched without expanding them
+        let glob = "{a,b}".repeat(40);
+        assert!(is_match(&glob, &"ab".repeat(20)));
+        assert!(!is_match(&glob, &"ab".repeat(19)));
+        let glob = "{**/,x}{a,b}".repeat(20);
+        assert!(is_match_literal_separator(&glob, &"x/a".repeat(20)));
+        assert!(!is_match_literal_separator(&glob, &"x/a/".repeat(20)));
+        let glob = GlobBuilder::new(&"{,a}".repeat(30)).empty_alternates(true).build().unwrap();
+        assert!(glob.compile_matcher().is_match("aaa"));
+    }
+
+    #[test]
+    fn test_wildcards_match_separators_by_default() {
+        assert!(is_match("*.rs", "src/lib.rs"));
+        assert!(is_match("**/foo", "foo"));
+        assert!(is_match("**/foo", "a/b/foo"));
+        assert!(is_match("a/**/b", "a/b"));
+        assert!(is_match("a/**/b", "a/x/y/b"));
+        assert!(is_match("a/**", "a/x/y"));
+        assert!(!is_match("a/**", "a"));
+        assert!(is_match("\\*[?]{x,y}", "*?y"));
+        assert!(!is_match("*.rs", "lib.rs.orig"));
+    }
+
+    #[test]
+    fn test_recursive_wildcards_within_components() {
+        assert!(is_match("a**", "abc"));
+        assert!(is_match("a**", "a/b"));
+        assert!(is_match("**A", "xA"));
+        assert!(!is_match("**A", "xa"));
+        assert!(is_match(".***", ".hidden"));
+        assert!(!is_match(".***", "hidden"));
+        assert!(is_match_literal_separator("a**", "abc"));
+        assert!(!is_match_literal_separator("a**", "a/b"));
+        assert!(!is_match_literal_separator("**A", "x/A"));
+    }
+
+    #[test]
+    fn test_trailing_recursive_wildcard() {
+        assert!(is_match("**/", "foo"));
+        assert!(is_match_literal_separator("**/", "a/b"));
+        assert!(is_match("a/**/", "a/"));
+        assert!(is_match("a/**/", "a/b/"));
+        assert!(!is_match("a/**/", "a/b"));
+        assert!(is_match_literal_separator("a/**/", "a/b/c/"));
+        assert!(!is_match_literal_separator("a/**/", "a"));
+    }
+
+    #[test]
+    fn test_alternates() {
+        assert!(is_match("{,a/}b", "a/b"));
+        assert!(!is_match("{,a/}b", "b"));
+        assert!(is_match("x{,}", "x"));
+        let empty_alternates = GlobBuilder::new("{,a/}b").empty_alternates(true).build().unwrap().compile_matcher();
+        assert!(empty_alternates.is_match("b"));
+        assert!(empty_alternates.is_match("a/b"));
+        assert!(is_match("{a,{b,c}d}", "cd"));
+        assert!(!is_match("{a,{b,c}d}", "c"));
+        assert!(is_match("a,b", "a,b"));
+        assert!(is_match_literal_separator("x/{**/,}y", "x/a/b/y"));
+        assert!(is_match_literal_separator("x{**/,}y", "xa/b/y"));
+        assert!(!is_match_literal_separator("x{**/,}y", "xa/b"));
+    }
+
+    #[test]
+    fn test_literal_separator() {
+        assert!(!is_match_literal_separator("*.rs", "src/lib.rs"));
+        assert!(is_match_literal_separator("**/*.rs", "src/lib.rs"));
+        assert!(is_match_literal_separator("src/**", "src/a/b"));
+        assert!(!is_match_literal_separator("src/**", "src"));
+    }
+
+    #[test]
+    fn test_options() {
+        let glob = GlobBuilder::new("*.RS").case_insensitive(true).build().unwrap();
+        assert!(glob.compile_matcher().is_match("lib.rs"));
+        let glob = GlobBuilder::new("a\\b").backslash_escape(false).build().unwrap();
+        assert!(glob.compile_matcher().is_match("a\\b"));
+    }
+}
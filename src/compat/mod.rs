@@ -0,0 +1,6 @@
+//! APIs of other glob crates, implemented with this crate, so projects using them can switch
+//! without rewriting their call sites.
+
+mod class;
+pub mod glob;
+pub mod globset;
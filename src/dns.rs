@@ -0,0 +1,174 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use crate::{GlobParseError, ParsedGlobString};
+
+/// a DNS name pattern with wildcards in the style of certificate names (RFC 6125, section 6.4.3).
+///
+/// Wildcards are only allowed in the leftmost label of the pattern and only match within the
+/// leftmost label of the host name, so `*.example.com` matches `www.example.com`, but neither
+/// `a.b.example.com` nor `example.com`. A wildcard never matches an empty label. Names are compared
+/// case-insensitively (ASCII only) and a trailing dot of an absolute name is ignored. A wildcard that
+/// is only part of a label, like in `w*.example.com`, does not match internationalized labels
+/// (`xn--…`), since their encoded form has nothing in common with what the user sees.
+/// ```
+/// use glob::DnsPattern;
+/// let pattern = DnsPattern::try_from("*.example.com").unwrap();
+/// assert!(pattern.matches("www.example.com"));
+/// assert!(pattern.matches("WWW.Example.COM."));
+/// assert!(!pattern.matches("a.b.example.com"));
+/// assert!(!pattern.matches("example.com"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct DnsPattern<'g> {
+    leftmost_label: ParsedGlobString<'static>, // lowercase
+    parent_domain: Option<&'g str>,
+    is_partial_wildcard: bool,
+}
+
+/// returned if creating a [`DnsPattern`] fails.
+#[derive(Debug, PartialEq, Eq)]
+pub enum DnsPatternError<'g> {
+    /// returned when the leftmost label is not a valid glob pattern.
+    Glob(GlobParseError<'g>),
+    /// returned when there is a wildcard outside of the leftmost label. Encapsulates the index of
+    /// the wildcard in the pattern string.
+    WildcardOutsideLeftmostLabel(usize), // index
+}
+
+impl<'g> Display for DnsPatternError<'g> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DnsPatternError::Glob(error) => write!(f, "{}", error),
+            DnsPatternError::WildcardOutsideLeftmostLabel(index) => write!(f, "wildcard outside of the leftmost label at index {}", index),
+        }
+    }
+}
+
+impl<'g> Error for DnsPatternError<'g> {}
+
+impl<'g> From<GlobParseError<'g>> for DnsPatternError<'g> {
+    fn from(error: GlobParseError<'g>) -> Self {
+        return DnsPatternError::Glob(error);
+    }
+}
+
+impl<'g> TryFrom<&'g str> for DnsPattern<'g> {
+    type Error = DnsPatternError<'g>;
+    /// parses the given DNS name pattern and returns the result or an error.
+    /// ```
+    /// # use glob::{DnsPattern, DnsPatternError};
+    /// let error = DnsPattern::try_from("www.*.com").unwrap_err();
+    /// assert_eq!(error, DnsPatternError::WildcardOutsideLeftmostLabel(4));
+    /// ```
+    fn try_from(string: &'g str) -> Result<Self, Self::Error> {
+        let name = string.strip_suffix('.').unwrap_or(string);
+        let (label, parent_domain) = split_leftmost_label(name);
+        if let Some(index) = parent_domain.and_then(|parent_domain| parent_domain.find(['*', '?'])) {
+            return Err(DnsPatternError::WildcardOutsideLeftmostLabel(label.len() + 1 + index));
+        }
+        ParsedGlobString::try_from(label)?;
+        let lowercase_label = label.to_ascii_lowercase();
+        let leftmost_label = ParsedGlobString::try_from(lowercase_label.as_str())
+            .expect("lowercasing keeps escape sequences valid")
+            .into_owned();
+        return Ok(DnsPattern {
+            leftmost_label: leftmost_label,
+            parent_domain: parent_domain,
+            is_partial_wildcard: label != "*" && label.contains(['*', '?']),
+        });
+    }
+}
+
+fn split_leftmost_label(name: &str) -> (&str, Option<&str>) {
+    return match name.split_once('.') {
+        Option::Some((label, parent_domain)) => (label, Option::Some(parent_domain)),
+        Option::None => (name, Option::None),
+    };
+}
+
+impl<'g> DnsPattern<'g> {
+    /// checks if the given host name matches this pattern.
+    /// ```
+    /// use glob::DnsPattern;
+    /// let pattern = DnsPattern::try_from("api-*.example.com").unwrap();
+    /// assert!(pattern.matches("api-eu.example.com"));
+    /// assert!(!pattern.matches("api-eu.internal.example.com"));
+    /// ```
+    pub fn matches(&self, hostname: &str) -> bool {
+        let name = hostname.strip_suffix('.').unwrap_or(hostname);
+        let (label, parent_domain) = split_leftmost_label(name);
+        if label.is_empty() {
+            return false;
+        }
+        let parent_domain_matches = match (self.parent_domain, parent_domain) {
+            (Option::Some(expected), Option::Some(actual)) => expected.eq_ignore_ascii_case(actual),
+            (Option::None, Option::None) => true,
+            _ => false,
+        };
+        if !parent_domain_matches {
+            return false;
+        }
+        let label = label.to_ascii_lowercase();
+        if self.is_partial_wildcard && label.starts_with("xn--") {
+            return false;
+        }
+        return self.leftmost_label.matches_completely(&label);
+    }
+}
+
+/// checks if the given host name matches the given DNS name pattern.
+///
+/// This is a utility function for creating a [`DnsPattern`] and calling [`matches`](DnsPattern::matches)
+/// on it.
+///
+/// Returns a [`DnsPatternError`] if parsing the pattern fails.
+pub fn dns_pattern_matches<'g>(pattern: &'g str, hostname: &str) -> Result<bool, DnsPatternError<'g>> {
+    return DnsPattern::try_from(pattern).map(|pattern| pattern.matches(hostname));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::GlobParseError;
+    use super::{dns_pattern_matches, DnsPattern, DnsPatternError};
+
+    fn matches(pattern: &str, hostname: &str) -> bool {
+        return dns_pattern_matches(pattern, hostname).unwrap();
+    }
+
+    #[test]
+    fn test_wildcard_matches_single_label() {
+        assert!(matches("*.example.com", "a.example.com"));
+        assert!(!matches("*.example.com", "a.b.example.com"));
+        assert!(!matches("*.example.com", "example.com"));
+        assert!(!matches("*.example.com", ".example.com"));
+        assert!(!matches("*.example.com", "a.example.org"));
+    }
+
+    #[test]
+    fn test_partial_wildcard() {
+        assert!(matches("w?w*.example.com", "www1.example.com"));
+        assert!(!matches("w?w*.example.com", "web.example.com"));
+        assert!(!matches("x*.example.com", "xn--bcher-kva.example.com"));
+        assert!(matches("*.example.com", "xn--bcher-kva.example.com"));
+    }
+
+    #[test]
+    fn test_case_and_trailing_dot_are_ignored() {
+        assert!(matches("*.Example.COM", "WWW.example.com."));
+        assert!(matches("MAIL-*.example.com.", "mail-1.EXAMPLE.com"));
+        assert!(matches("localhost", "LocalHost"));
+        assert!(!matches("localhost", "localhost.localdomain"));
+    }
+
+    #[test]
+    fn test_wildcard_outside_leftmost_label() {
+        assert_eq!(DnsPattern::try_from("a.*.com").unwrap_err(), DnsPatternError::WildcardOutsideLeftmostLabel(2));
+        assert_eq!(DnsPattern::try_from("*.example.co?").unwrap_err(), DnsPatternError::WildcardOutsideLeftmostLabel(12));
+        assert_eq!(DnsPatternError::WildcardOutsideLeftmostLabel(2).to_string(), "wildcard outside of the leftmost label at index 2");
+    }
+
+    #[test]
+    fn test_malformed_leftmost_label() {
+        assert_eq!(DnsPattern::try_from("a\\N.com").unwrap_err(), DnsPatternError::Glob(GlobParseError::UnknownEscapeSequence(1, "\\N")));
+    }
+}
@@ -0,0 +1,149 @@
+use glob_parser::parse_events;
+use crate::path::offset_error;
+use crate::{split_pattern, GlobParseError, OverrideMatch, PathPattern};
+
+/// the rules of a `.dockerignore` file, which decide which files of a build context are sent to
+/// the builder, with the semantics of Docker rather than of `.gitignore` files (see
+/// [`IgnoreRules`](crate::IgnoreRules)).
+///
+/// Each line is a [path pattern](PathPattern) with surrounding whitespace removed; empty lines and
+/// lines starting with `#` are skipped, as is a byte order mark at the start of the file. All
+/// rules are relative to the root of the context: `*.md` only matches files in the root, `**/*.md`
+/// matches them everywhere. A leading or trailing `/` and `.` components are ignored, and `..`
+/// removes the component before it, like Docker cleans the rules. A rule excludes the paths it
+/// matches and everything below them; a rule starting with `!` re-includes them, even below an
+/// excluded directory. If several rules match, the last one decides.
+///
+/// A `**` is a globstar only as a whole component, like in all path patterns of this crate.
+/// ```
+/// use glob::{DockerIgnore, OverrideMatch};
+/// let rules = DockerIgnore::parse("# build context\n*.md\n!README.md\n/target/\n**/*.tmp\n").unwrap();
+/// assert!(rules.is_ignored("CHANGELOG.md"));
+/// assert!(!rules.is_ignored("docs/guide.md"));
+/// assert_eq!(rules.matched("README.md"), OverrideMatch::Whitelist);
+/// assert!(rules.is_ignored("target/debug/app"));
+/// assert!(rules.is_ignored("src/cache/a.tmp"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DockerIgnore {
+    rules: Vec<DockerRule>,
+}
+
+#[derive(Debug, Clone)]
+struct DockerRule {
+    pattern: PathPattern<'static>,
+    exception: bool,
+}
+
+impl DockerIgnore {
+    /// parses the contents of a `.dockerignore` file. The indices in errors refer to `contents`.
+    /// Rules that are empty after cleaning, like `.`, are skipped.
+    pub fn parse(contents: &str) -> Result<Self, GlobParseError<'_>> {
+        let mut rules = Vec::new();
+        let mut line_start = 0;
+        for line in contents.split_inclusive('\n') {
+            let start = line_start;
+            line_start += line.len();
+            let line = if start == 0 { line.trim_start_matches('\u{feff}') } else { line };
+            // like Docker, only a `#` at the very beginning starts a comment
+            let trimmed = line.trim();
+            if line.starts_with('#') || trimmed.is_empty() {
+                continue;
+            }
+            let (exception, rule) = match trimmed.strip_prefix('!') {
+                Option::Some(rule) => (true, rule.trim()),
+                Option::None => (false, trimmed),
+            };
+            // the rule ends where the trimmed line ends
+            let offset = start + contents[start..line_start].trim_end().len() - rule.len();
+            let mut components: Vec<&str> = Vec::new();
+            for range in split_pattern(rule, '/') {
+                let component = &rule[range.clone()];
+                match component {
+                    "" | "." => continue,
+                    ".." if components.last().is_some_and(|last| *last != "..") => {
+                        components.pop();
+                        continue;
+                    },
+                    _ => {},
+                }
+                parse_events(component, |_| {}).map_err(|error| offset_error(error, offset + range.start))?;
+                components.push(component);
+            }
+            if components.is_empty() {
+                continue;
+            }
+            let pattern = PathPattern::try_from(components.join("/").as_str()).expect("the components were checked").into_owned();
+            rules.push(DockerRule { pattern: pattern.windows_paths(false), exception: exception });
+        }
+        return Ok(DockerIgnore { rules: rules });
+    }
+
+    /// returns the number of rules.
+    pub fn len(&self) -> usize {
+        return self.rules.len();
+    }
+
+    /// checks if there are no rules.
+    pub fn is_empty(&self) -> bool {
+        return self.rules.is_empty();
+    }
+
+    /// decides about a `/`-separated path relative to the root of the context. A rule matches the
+    /// path if it matches the path itself or one of its parent directories. Returns
+    /// [`OverrideMatch::None`] if no rule matches.
+    /// ```
+    /// use glob::{DockerIgnore, OverrideMatch};
+    /// let rules = DockerIgnore::parse("node_modules\n!node_modules/keep/*.js\n").unwrap();
+    /// assert_eq!(rules.matched("node_modules/left-pad/index.js"), OverrideMatch::Ignore);
+    /// assert_eq!(rules.matched("node_modules/keep/index.js"), OverrideMatch::Whitelist);
+    /// assert_eq!(rules.matched("src/node_modules"), OverrideMatch::None);
+    /// ```
+    pub fn matched(&self, path: &str) -> OverrideMatch {
+        let ancestors = path.match_indices('/').map(|(end, _)| &path[..end]);
+        for rule in self.rules.iter().rev() {
+            if rule.pattern.matches(path) || ancestors.clone().any(|ancestor| rule.pattern.matches(ancestor)) {
+                return if rule.exception { OverrideMatch::Whitelist } else { OverrideMatch::Ignore };
+            }
+        }
+        return OverrideMatch::None;
+    }
+
+    /// checks if the path is excluded from the context, see [`matched`](Self::matched).
+    pub fn is_ignored(&self, path: &str) -> bool {
+        return self.matched(path) == OverrideMatch::Ignore;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{GlobParseError, OverrideMatch};
+    use super::DockerIgnore;
+
+    #[test]
+    fn test_rules_are_cleaned() {
+        let rules = DockerIgnore::parse("  ./a/../b//c/  \n!  /d\n.\n/\n../e\n\t# not a comment\n").unwrap();
+        assert_eq!(rules.len(), 4);
+        assert!(rules.is_ignored("b/c"));
+        assert!(rules.is_ignored("b/c/x"));
+        assert!(!rules.is_ignored("a/b/c"));
+        assert_eq!(rules.matched("d"), OverrideMatch::Whitelist);
+        assert!(rules.is_ignored("../e"));
+        assert!(rules.is_ignored("# not a comment"));
+        assert!(DockerIgnore::parse("\u{feff}# comment\n\n   \n").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_last_match_wins() {
+        let rules = DockerIgnore::parse("**/*.log\n!logs\nlogs/debug.log\n").unwrap();
+        assert!(rules.is_ignored("a/b.log"));
+        assert_eq!(rules.matched("logs/app.log"), OverrideMatch::Whitelist);
+        assert!(rules.is_ignored("logs/debug.log"));
+        assert_eq!(rules.matched("src/main.rs"), OverrideMatch::None);
+    }
+
+    #[test]
+    fn test_error_indices_refer_to_the_contents() {
+        assert_eq!(DockerIgnore::parse("*.o\n ! ./a/\\x\n").unwrap_err(), GlobParseError::UnknownEscapeSequence(11, "\\x"));
+    }
+}
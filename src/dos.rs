@@ -0,0 +1,146 @@
+use crate::{CaseFold, DefaultCaseFold, Matcher};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DosToken {
+    Literal(char), // folded
+    AnyChar, // `?`
+    OptionalChar, // `?` at the end of the name or the extension, which may match nothing
+    AnyString, // `*`
+}
+
+fn fold(c: char) -> char {
+    return DefaultCaseFold.fold(c);
+}
+
+/// parses the name or the extension of a pattern.
+fn parse_part(part: &str) -> Vec<DosToken> {
+    let mut tokens: Vec<DosToken> = part.chars().map(|c| match c {
+        '?' => DosToken::AnyChar,
+        '*' => DosToken::AnyString,
+        c => DosToken::Literal(fold(c)),
+    }).collect();
+    for token in tokens.iter_mut().rev().take_while(|token| **token == DosToken::AnyChar) {
+        *token = DosToken::OptionalChar;
+    }
+    return tokens;
+}
+
+/// checks if the tokens match the folded characters as a whole.
+fn part_matches(tokens: &[DosToken], chars: &[char]) -> bool {
+    // `matched[j]` tells whether the tokens after the current one match `chars[j..]`
+    let mut matched: Vec<bool> = (0..=chars.len()).map(|j| j == chars.len()).collect();
+    for token in tokens.iter().rev() {
+        let mut current = vec![false; chars.len() + 1];
+        for j in (0..=chars.len()).rev() {
+            let next = j < chars.len() && matched[j + 1];
+            current[j] = match token {
+                DosToken::Literal(c) => j < chars.len() && chars[j] == *c && matched[j + 1],
+                DosToken::AnyChar => next,
+                DosToken::OptionalChar => next || matched[j],
+                DosToken::AnyString => matched[j] || (j < chars.len() && current[j + 1]),
+            };
+        }
+        matched = current;
+    }
+    return matched[0];
+}
+
+/// a file name pattern with the wildcard rules of `cmd.exe` and the file APIs of Windows, for tools
+/// that must select exactly the files a legacy batch script or DOS program would.
+///
+/// The rules follow the 8.3 heritage of these patterns rather than the syntax of
+/// [`ParsedGlobString`](crate::ParsedGlobString):
+///
+/// - Names are compared case-insensitively, and there are no escape sequences.
+/// - If the pattern contains a `.`, the part after its last `.` is matched against the extension
+///   of the name, the part after the last `.` of the name, and the part before it against the rest.
+///   A name without a `.` has an empty extension, so `*.*` matches every name, `report.*` matches
+///   `report`, and `*.` only matches names without an extension.
+/// - `*` matches any string and `?` any character, but a run of `?` at the end of the name or the
+///   extension part may also match fewer characters, so `file??` matches `file`, `file1` and
+///   `file12`, and `fil?.txt` matches `fil.txt`.
+///
+/// Matches that Windows only reports because of a generated short name, like `*.htm` matching
+/// `index.html`, are not reproduced.
+/// ```
+/// use glob::DosPattern;
+/// assert!(DosPattern::new("*.*").matches("Makefile"));
+/// assert!(DosPattern::new("*.TXT").matches("notes.txt"));
+/// assert!(DosPattern::new("*.").matches("README"));
+/// assert!(!DosPattern::new("*.").matches("README.md"));
+/// assert!(DosPattern::new("log??.txt").matches("log1.txt"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DosPattern {
+    name: Vec<DosToken>,
+    extension: Option<Vec<DosToken>>, // if the pattern contains a `.`
+}
+
+impl DosPattern {
+    /// parses the given pattern. Every string is a valid pattern.
+    pub fn new(pattern: &str) -> Self {
+        return match pattern.rsplit_once('.') {
+            Option::Some((name, extension)) => DosPattern { name: parse_part(name), extension: Some(parse_part(extension)) },
+            Option::None => DosPattern { name: parse_part(pattern), extension: None },
+        };
+    }
+
+    /// checks if this pattern matches the given file name.
+    pub fn matches(&self, file_name: &str) -> bool {
+        let Option::Some(extension_tokens) = &self.extension else {
+            return part_matches(&self.name, &file_name.chars().map(fold).collect::<Vec<_>>());
+        };
+        let (name, extension) = file_name.rsplit_once('.').unwrap_or((file_name, ""));
+        let name: Vec<char> = name.chars().map(fold).collect();
+        let extension: Vec<char> = extension.chars().map(fold).collect();
+        return part_matches(&self.name, &name) && part_matches(extension_tokens, &extension);
+    }
+}
+
+impl From<&str> for DosPattern {
+    fn from(pattern: &str) -> Self {
+        return DosPattern::new(pattern);
+    }
+}
+
+impl Matcher for DosPattern {
+    fn is_match(&self, haystack: &str) -> bool {
+        return self.matches(haystack);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DosPattern;
+
+    #[test]
+    fn test_dos_semantics() {
+        let cases = [
+            ("*", "a.b.c", true),
+            ("*.*", "noext", true),
+            ("*.*", "a.b.c", true),
+            ("*.txt", "a.b.txt", true),
+            ("*.txt", "txt", false),
+            ("*.", "noext", true),
+            ("*.", "a.b", false),
+            ("readme.*", "README", true),
+            ("readme.*", "readme.md", true),
+            ("readme.*", "readme2.md", false),
+            ("a??", "a", true),
+            ("a??", "abc", true),
+            ("a??", "abcd", false),
+            ("?b", "b", false),
+            ("fil?.txt", "fil.txt", true),
+            ("fil?.txt", "file.txt", true),
+            ("fil?.txt", "files.txt", false),
+            ("*.t?", "a.t", true),
+            ("Ä*.DOC", "äpfel.doc", true),
+            ("[a].txt", "[A].TXT", true),
+            ("", "", true),
+            ("", "a", false),
+        ];
+        for (pattern, name, expected) in cases {
+            assert_eq!(DosPattern::new(pattern).matches(name), expected, "{} against {}", pattern, name);
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use std::ops::Range;
+use glob_parser::{append_token, parse_glob_string_with_spans, Syntax, Token};
+use crate::matcher::MatchConfig;
+use crate::prefilter::LiteralCheck;
+use crate::{Anchor, OwnedGlobParseError, ParsedGlobString};
+
+/// a pattern string that is edited in place, like the contents of a pattern field in an
+/// interactive UI, and reparsed after every edit.
+///
+/// [`reparse_edit`](Self::reparse_edit) only parses the tokens that the edit touches and reuses the
+/// tokens on either side of it, so the cost of a keystroke doesn't grow with the length of the
+/// pattern. The result is always the same as parsing the edited string from scratch. While the
+/// pattern string is malformed, e.g. right after typing a `\`, the parse error is kept, and the
+/// next edit parses the whole string again.
+/// ```
+/// use glob::EditablePattern;
+/// let mut pattern = EditablePattern::new("*.txt");
+/// assert!(pattern.get().unwrap().matches_completely("notes.txt"));
+/// pattern.reparse_edit(2..5, "rs").unwrap();
+/// assert_eq!(pattern.as_str(), "*.rs");
+/// assert!(pattern.get().unwrap().matches_completely("lib.rs"));
+/// assert!(pattern.reparse_edit(4..4, "\\").is_err());
+/// assert!(pattern.reparse_edit(5..5, "?").unwrap().matches_completely("lib.rs?"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct EditablePattern {
+    pattern: String,
+    syntax: Syntax,
+    parsed: Result<ParsedGlobString<'static>, OwnedGlobParseError>,
+}
+
+/// parses `pattern` into tokens that don't borrow from it.
+fn parse_owned(pattern: &str, syntax: Syntax) -> Result<ParsedGlobString<'static>, OwnedGlobParseError> {
+    let (tokens, spans) = parse_glob_string_with_spans(pattern, syntax)?;
+    return Ok(new_pattern(tokens.into_iter().map(|token| token.into_owned()).collect(), spans));
+}
+
+fn new_pattern(tokens: Vec<Token<'static>>, spans: Vec<Range<usize>>) -> ParsedGlobString<'static> {
+    let literal_check = LiteralCheck::new(&tokens);
+    return ParsedGlobString { tokens: tokens, config: MatchConfig::default(), anchor: Anchor::None, literal_check: literal_check, spans: spans };
+}
+
+/// parses the part of the edited `pattern` between the last token before `edit` and the
+/// first token after it, and joins the result with those tokens. Returns `None` if that part
+/// doesn't parse on its own, which leaves the whole string to be parsed again.
+fn reparse_around(pattern: &str, syntax: Syntax, previous: &mut ParsedGlobString<'static>, edit: Range<usize>, replacement_length: usize) -> Option<ParsedGlobString<'static>> {
+    let spans = &previous.spans;
+    // the tokens before the edit end at the start of a token, so they parse the same way
+    let prefix = spans.iter().take_while(|span| span.end <= edit.start).count();
+    let suffix = prefix + spans[prefix..].iter().take_while(|span| span.start < edit.end).count();
+    let shift = |position: usize| position - edit.end + edit.start + replacement_length;
+    let start = if prefix == 0 { 0 } else { spans[prefix - 1].end };
+    let end = spans.get(suffix).map_or(pattern.len(), |span| shift(span.start));
+    let (region_tokens, region_spans) = parse_glob_string_with_spans(&pattern[start..end], syntax).ok()?;
+    let mut old_tokens = std::mem::take(&mut previous.tokens);
+    let suffix_tokens = old_tokens.split_off(suffix);
+    old_tokens.truncate(prefix);
+    let mut tokens = old_tokens;
+    let mut spans = previous.spans[..prefix].to_vec();
+    for (token, span) in region_tokens.into_iter().zip(region_spans) {
+        append_token(&mut tokens, &mut spans, token.into_owned(), start + span.start..start + span.end);
+    }
+    for (token, span) in suffix_tokens.into_iter().zip(&previous.spans[suffix..]) {
+        append_token(&mut tokens, &mut spans, token, shift(span.start)..shift(span.end));
+    }
+    return Some(new_pattern(tokens, spans));
+}
+
+impl EditablePattern {
+    /// parses the given pattern string with the syntax of
+    /// [`ParsedGlobString::try_from`](ParsedGlobString::try_from).
+    pub fn new(pattern: &str) -> Self {
+        return EditablePattern::with_syntax(pattern, Syntax::extended());
+    }
+
+    /// parses the given pattern string, accepting the given syntax.
+    pub fn with_syntax(pattern: &str, syntax: Syntax) -> Self {
+        return EditablePattern { pattern: String::from(pattern), syntax: syntax, parsed: parse_owned(pattern, syntax) };
+    }
+
+    /// returns the current pattern string.
+    pub fn as_str(&self) -> &str {
+        return &self.pattern;
+    }
+
+    /// returns the pattern parsed from the current pattern string, or the error that parsing it
+    /// produced.
+    pub fn get(&self) -> Result<&ParsedGlobString<'static>, &OwnedGlobParseError> {
+        return self.parsed.as_ref();
+    }
+
+    /// replaces the byte range `edit` of the pattern string with `replacement` and returns the
+    /// parsed result, like [`get`](Self::get) does.
+    ///
+    /// Panics if the range is out of bounds or doesn't start and end on character boundaries,
+    /// like [`String::replace_range`].
+    pub fn reparse_edit(&mut self, edit: Range<usize>, replacement: &str) -> Result<&ParsedGlobString<'static>, &OwnedGlobParseError> {
+        self.pattern.replace_range(edit.clone(), replacement);
+        let reparsed = match &mut self.parsed {
+            Ok(previous) => reparse_around(&self.pattern, self.syntax, previous, edit, replacement.len()),
+            Err(_) => Option::None,
+        };
+        self.parsed = match reparsed {
+            Option::Some(parsed) => Ok(parsed),
+            Option::None => parse_owned(&self.pattern, self.syntax),
+        };
+        return self.get();
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::single_range_in_vec_init)] // vectors of token spans
+mod tests {
+    use crate::{OwnedGlobParseError, ParsedGlobString};
+    use super::EditablePattern;
+
+    fn parse_from_scratch(pattern: &str) -> Result<(String, Vec<std::ops::Range<usize>>), OwnedGlobParseError> {
+        let parsed = ParsedGlobString::try_from(pattern)?;
+        return Ok((parsed.dump(), parsed.token_spans().unwrap().to_vec()));
+    }
+
+    #[test]
+    fn test_edits_agree_with_parsing_from_scratch() {
+        let patterns = ["", "abc", "a*b?c", "\\*x\\b?*", "ä\\\\*ö", "**??\\bw"];
+        let replacements = ["", "*", "?", "\\", "\\*", "x", "\\b", "b", "y*z"];
+        for pattern in patterns {
+            let boundaries: Vec<usize> = (0..=pattern.len()).filter(|i| pattern.is_char_boundary(*i)).collect();
+            for (i, start) in boundaries.iter().enumerate() {
+                for end in &boundaries[i..] {
+                    for replacement in replacements {
+                        let mut editable = EditablePattern::new(pattern);
+                        let mut edited = String::from(pattern);
+                        edited.replace_range(*start..*end, replacement);
+                        let result = editable.reparse_edit(*start..*end, replacement)
+                            .map(|parsed| (parsed.dump(), parsed.token_spans().unwrap().to_vec()))
+                            .map_err(|error| error.clone());
+                        assert_eq!(result, parse_from_scratch(&edited), "{:?} with {}..{} replaced by {:?}", pattern, start, end, replacement);
+                        assert_eq!(editable.as_str(), edited);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_recovers_from_errors() {
+        let mut pattern = EditablePattern::new("a\\");
+        assert_eq!(pattern.get().unwrap_err().position(), 1);
+        assert_eq!(pattern.reparse_edit(2..2, "*").unwrap().dump(), "0 literal \"a*\"\n");
+        assert!(pattern.reparse_edit(0..1, "\\q").is_err());
+        assert_eq!(pattern.reparse_edit(0..2, "").unwrap().token_spans(), Some(&[0..2][..]));
+    }
+}
@@ -0,0 +1,102 @@
+//! UniFFI bindings, so Kotlin and Swift apps apply exactly the same patterns as Rust code.
+//!
+//! Build the library as a `cdylib` or `staticlib` with the `uniffi` feature and generate the
+//! bindings with `uniffi-bindgen`, e.g.
+//! ```commandline
+//! cargo rustc --release --features uniffi --crate-type cdylib
+//! uniffi-bindgen generate --library target/release/libglob.so --language kotlin --out-dir out
+//! ```
+
+use std::sync::Arc;
+use crate::{GlobSet, ParsedGlobString};
+
+/// the error of parsing a pattern.
+#[derive(Debug, uniffi::Error)]
+pub enum GlobError {
+    /// the pattern is malformed; `message` describes the problem.
+    InvalidPattern { message: String },
+}
+
+impl std::fmt::Display for GlobError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GlobError::InvalidPattern { message } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for GlobError {}
+
+/// a byte range of a match in a string encoded as UTF-8.
+#[derive(Debug, uniffi::Record)]
+pub struct MatchRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+fn parse(pattern: &str) -> Result<ParsedGlobString<'static>, GlobError> {
+    return ParsedGlobString::try_from(pattern)
+        .map(ParsedGlobString::into_owned)
+        .map_err(|error| GlobError::InvalidPattern { message: error.to_string() });
+}
+
+/// a parsed pattern, matched with the default options.
+#[derive(Debug, uniffi::Object)]
+pub struct Pattern {
+    parsed: ParsedGlobString<'static>,
+}
+
+#[uniffi::export]
+impl Pattern {
+    /// parses the pattern.
+    #[uniffi::constructor]
+    pub fn new(pattern: &str) -> Result<Arc<Self>, GlobError> {
+        return Ok(Arc::new(Pattern { parsed: parse(pattern)? }));
+    }
+
+    /// checks if the pattern occurs anywhere in the string.
+    pub fn matches(&self, string: &str) -> bool {
+        return self.parsed.matches_partially(string);
+    }
+
+    /// checks if the pattern matches the string as a whole.
+    pub fn matches_completely(&self, string: &str) -> bool {
+        return self.parsed.matches_completely(string);
+    }
+
+    /// returns the byte range of the leftmost occurrence of the pattern in the string.
+    pub fn find(&self, string: &str) -> Option<MatchRange> {
+        return self.parsed.find(string).map(|range| MatchRange { start: range.start as u64, end: range.end as u64 });
+    }
+
+    /// returns the strings the pattern occurs in, in their original order.
+    pub fn filter(&self, strings: Vec<String>) -> Vec<String> {
+        return strings.into_iter().filter(|string| self.parsed.matches_partially(string)).collect();
+    }
+}
+
+/// a set of patterns, see [`GlobSet`].
+#[derive(Debug, uniffi::Object)]
+pub struct PatternSet {
+    set: GlobSet<'static>,
+}
+
+#[uniffi::export]
+impl PatternSet {
+    /// parses the patterns.
+    #[uniffi::constructor]
+    pub fn new(patterns: Vec<String>) -> Result<Arc<Self>, GlobError> {
+        let parsed: Result<GlobSet<'static>, GlobError> = patterns.iter().map(|pattern| parse(pattern)).collect();
+        return Ok(Arc::new(PatternSet { set: parsed? }));
+    }
+
+    /// checks if any pattern occurs in the string.
+    pub fn is_match(&self, string: &str) -> bool {
+        return self.set.is_match(string);
+    }
+
+    /// returns the indices of the patterns that occur in the string, in ascending order.
+    pub fn matches(&self, string: &str) -> Vec<u64> {
+        return self.set.matches(string).into_iter().map(|index| index as u64).collect();
+    }
+}
@@ -0,0 +1,149 @@
+use glob_parser::Token;
+use crate::matcher::{find_match_with_captures, is_word_boundary, MatchConfig};
+use crate::WildcardPolicy;
+
+/// a token split into the parts that consume at most one character of the string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Item {
+    Char(char),
+    AnyChar, // `?`
+    AnyString, // `*`
+    WordBoundary,
+}
+
+pub fn items(tokens: &[Token<'_>]) -> Vec<Item> {
+    let mut items = Vec::new();
+    for token in tokens {
+        match token {
+            Token::Literal(literal) => items.extend(literal.chars().map(Item::Char)),
+            Token::ExactLengthWildcard(length) => items.extend(std::iter::repeat_n(Item::AnyChar, *length)),
+            Token::MinLengthWildcard(length) => {
+                items.extend(std::iter::repeat_n(Item::AnyChar, *length));
+                items.push(Item::AnyString);
+            },
+            Token::WordBoundary => items.push(Item::WordBoundary),
+        }
+    }
+    return items;
+}
+
+/// returns the smallest number of characters that have to be inserted, deleted or substituted in
+/// the literals of the token sequence for it to match some part of `string`, or `None` if no
+/// number of edits makes it match, e.g. because `string` is too short for the `?` wildcards.
+///
+/// Wildcards never absorb edits: a `?` still matches exactly one character that the configuration
+/// allows, and the string is always matched character by character.
+pub fn fuzzy_distance(tokens: &[Token<'_>], string: &str, config: &MatchConfig) -> Option<usize> {
+    let items = items(tokens);
+    let fold = |c: char| config.case_fold.map_or(c, |fold| fold.fold(c));
+    // costs[i] is the fewest edits for matching the first i items against a part of the string
+    // that ends at the current position, the standard dynamic program for approximate matching
+    let mut costs = vec![usize::MAX; items.len() + 1];
+    let mut previous = costs.clone();
+    let mut best = usize::MAX;
+    let mut last = Option::None;
+    // one step per character boundary, including the end of the string
+    for current in string.char_indices().map(Some).chain([None]) {
+        let position = current.map_or(string.len(), |(position, _)| position);
+        costs[0] = 0; // a match may start anywhere
+        for (i, item) in items.iter().enumerate() {
+            let consumed = last.map_or(usize::MAX, |c: char| match item {
+                Item::Char(expected) => previous[i].saturating_add(usize::from(fold(c) != fold(*expected))),
+                Item::AnyChar if config.wildcard_matches(c) => previous[i],
+                Item::AnyString if config.wildcard_matches(c) => previous[i + 1],
+                _ => usize::MAX,
+            });
+            costs[i + 1] = match item {
+                Item::Char(_) => consumed.min(costs[i].saturating_add(1)).min(last.map_or(usize::MAX, |_| previous[i + 1].saturating_add(1))),
+                Item::AnyChar => consumed,
+                Item::AnyString => consumed.min(costs[i]),
+                Item::WordBoundary if is_word_boundary(string, position) => costs[i],
+                Item::WordBoundary => usize::MAX,
+            };
+        }
+        best = best.min(costs[items.len()]);
+        last = current.map(|(_, c)| c);
+        std::mem::swap(&mut costs, &mut previous);
+    }
+    return if best == usize::MAX { None } else { Some(best) };
+}
+
+/// returns how well the token sequence matches `string`, or `None` if it doesn't match.
+///
+/// The leftmost match with the shortest wildcards is scored: each character matched by a literal
+/// earns 16 points, each wildcard that matches nothing (so the literals around it are contiguous)
+/// earns 8 points, and a match that starts a word earns 8 points. Each character matched by a
+/// wildcard costs 2 points and each character before the match costs 1 point. The score doesn't
+/// drop below 0.
+pub fn relevance_score(tokens: &[Token<'_>], string: &str, config: &MatchConfig) -> Option<u32> {
+    let config = MatchConfig { wildcard_policy: WildcardPolicy::Lazy, leftmost_longest: false, ..*config };
+    let (range, wildcard_ranges) = find_match_with_captures(tokens, string, false, false, &config)?;
+    let char_count = |range: &std::ops::Range<usize>| string[range.clone()].chars().count() as i64;
+    let wildcard_chars: i64 = wildcard_ranges.iter().map(char_count).sum();
+    let literal_chars = char_count(&range) - wildcard_chars;
+    let contiguous = wildcard_ranges.iter().filter(|range| range.is_empty()).count() as i64;
+    let starts_word = !string[..range.start].chars().next_back().is_some_and(|c| c.is_alphanumeric() || c == '_');
+    let score = 16 * literal_chars + 8 * contiguous + if starts_word { 8 } else { 0 }
+        - 2 * wildcard_chars - char_count(&(0..range.start));
+    return Some(score.clamp(0, i64::from(u32::MAX)) as u32);
+}
+
+#[cfg(test)]
+mod tests {
+    use glob_parser::parse_glob_string;
+    use crate::matcher::MatchConfig;
+    use super::{fuzzy_distance, relevance_score};
+
+    fn distance(pattern: &str, string: &str) -> Option<usize> {
+        return fuzzy_distance(&parse_glob_string(pattern).unwrap(), string, &MatchConfig::default());
+    }
+
+    #[test]
+    fn test_exact_matches_need_no_edits() {
+        assert_eq!(distance("", ""), Some(0));
+        assert_eq!(distance("b?d", "abcde"), Some(0));
+        assert_eq!(distance("*.rs", "main.rs"), Some(0));
+    }
+
+    #[test]
+    fn test_edits_in_literals() {
+        assert_eq!(distance("config", "src/confg.rs"), Some(1)); // deletion
+        assert_eq!(distance("config", "src/connfig.rs"), Some(1)); // insertion
+        assert_eq!(distance("config", "src/konfig.rs"), Some(1)); // substitution
+        assert_eq!(distance("config", "src/cnofig.rs"), Some(2));
+        assert_eq!(distance("abc", ""), Some(3));
+    }
+
+    #[test]
+    fn test_wildcards_do_not_absorb_edits() {
+        assert_eq!(distance("a??", "a"), None);
+        assert_eq!(distance("x?z", "xz"), Some(1));
+        assert_eq!(distance("main*.rs", "mian_test.rs"), Some(2));
+    }
+
+    #[test]
+    fn test_word_boundaries() {
+        assert_eq!(distance("\\bcat\\b", "concat"), Some(3));
+        assert_eq!(distance("\\bcat\\b", "a cot"), Some(1));
+    }
+
+    fn score(pattern: &str, string: &str) -> Option<u32> {
+        return relevance_score(&parse_glob_string(pattern).unwrap(), string, &MatchConfig::default());
+    }
+
+    #[test]
+    fn test_relevance_score() {
+        assert_eq!(score("*foo*bar*", "baz"), None);
+        assert_eq!(score("foo*bar", "foobar"), Some(16 * 6 + 8 + 8));
+        assert_eq!(score("foo*bar", "a foo-bar"), Some(16 * 6 + 8 - 2 - 2));
+        assert_eq!(score("?", &"x".repeat(100)), Some(8 - 2));
+        assert_eq!(score("a", &("x".repeat(100) + "a")), Some(0));
+    }
+
+    #[test]
+    fn test_relevance_order() {
+        let mut candidates = vec!["src/lib/foo_bar.rs", "a/foo/more/bar.rs", "foobar.rs", "lib/foobar.rs"];
+        candidates.sort_by_key(|candidate| std::cmp::Reverse(score("foo*bar", candidate)));
+        assert_eq!(candidates, vec!["foobar.rs", "lib/foobar.rs", "src/lib/foo_bar.rs", "a/foo/more/bar.rs"]);
+    }
+}
@@ -0,0 +1,272 @@
+use std::ffi::{OsStr, OsString};
+use std::ops::Range;
+
+/// input that patterns can be matched against with [`find_in`](crate::ParsedGlobString::find_in)
+/// and [`is_match_in`](crate::ParsedGlobString::is_match_in), so strings, byte strings and OS
+/// strings share one matcher.
+///
+/// A haystack is a sequence of bytes in UTF-8 or a superset of it. Positions are byte offsets.
+/// Wildcards match one unit at a time: a character, or a single byte that isn't part of a valid
+/// UTF-8 sequence. Only [`as_bytes`](Self::as_bytes) has to be implemented; the other methods
+/// decode the bytes leniently and can be overridden if the haystack knows more about them, like
+/// `str` knows that it is valid UTF-8.
+pub trait Haystack {
+    /// returns the bytes of the haystack.
+    fn as_bytes(&self) -> &[u8];
+
+    /// returns the length of the haystack in bytes.
+    fn len(&self) -> usize {
+        return self.as_bytes().len();
+    }
+
+    /// checks if the haystack is empty.
+    fn is_empty(&self) -> bool {
+        return self.len() == 0;
+    }
+
+    /// returns the bytes in `range`.
+    fn subslice(&self, range: Range<usize>) -> &[u8] {
+        return &self.as_bytes()[range];
+    }
+
+    /// returns the unit starting at `position` and the offset after it, or `None` at the end of
+    /// the haystack. A byte that doesn't start a valid UTF-8 sequence is a unit of its own and is
+    /// returned as [`char::REPLACEMENT_CHARACTER`].
+    fn next_unit(&self, position: usize) -> Option<(char, usize)> {
+        return decode_next(self.as_bytes(), position);
+    }
+
+    /// returns the unit ending at `position` and the offset at which it starts, or `None` at the
+    /// start of the haystack.
+    fn previous_unit(&self, position: usize) -> Option<(char, usize)> {
+        let bytes = self.as_bytes();
+        let last = position.checked_sub(1)?;
+        // a sequence of several bytes ending at `position` takes precedence over its last byte
+        for start in position.saturating_sub(4)..last {
+            if let Option::Some((c, end)) = decode_next(bytes, start) {
+                if end == position {
+                    return Some((c, start));
+                }
+            }
+        }
+        return decode_next(bytes, last).map(|(c, _)| (c, last));
+    }
+
+    /// checks if a unit starts at `position`, or if it is the end of the haystack.
+    fn is_unit_boundary(&self, position: usize) -> bool {
+        let bytes = self.as_bytes();
+        if position > bytes.len() {
+            return false;
+        }
+        if position == bytes.len() || !is_continuation_byte(bytes[position]) {
+            return true;
+        }
+        // a continuation byte is a unit of its own unless a valid sequence before it includes it
+        return !(position.saturating_sub(3)..position)
+            .any(|start| !is_continuation_byte(bytes[start]) && decode_next(bytes, start).is_some_and(|(_, end)| end > position));
+    }
+
+    /// returns the offset of the first occurrence of `literal` at or after `from`.
+    fn find_literal(&self, from: usize, literal: &[u8]) -> Option<usize> {
+        let bytes = self.as_bytes().get(from..)?;
+        return find_bytes(bytes, literal).map(|i| from + i);
+    }
+}
+
+/// returns the offset of the first occurrence of `literal` in `bytes`. With the `simd` feature,
+/// the search uses the vectorized substring search of `memchr`.
+#[cfg(feature = "simd")]
+pub(crate) fn find_bytes(bytes: &[u8], literal: &[u8]) -> Option<usize> {
+    return memchr::memmem::find(bytes, literal);
+}
+
+/// returns the offset of the first occurrence of `literal` in `bytes`.
+#[cfg(not(feature = "simd"))]
+pub(crate) fn find_bytes(bytes: &[u8], literal: &[u8]) -> Option<usize> {
+    if literal.is_empty() {
+        return Some(0);
+    }
+    return bytes.windows(literal.len()).position(|window| window == literal);
+}
+
+/// returns the offset of the first byte in `bytes` that is one of `set`. With the `simd` feature,
+/// sets of up to three bytes are searched for with the vectorized search of `memchr`.
+pub(crate) fn find_any_byte(bytes: &[u8], set: &[u8]) -> Option<usize> {
+    #[cfg(feature = "simd")]
+    match set {
+        [a] => return memchr::memchr(*a, bytes),
+        [a, b] => return memchr::memchr2(*a, *b, bytes),
+        [a, b, c] => return memchr::memchr3(*a, *b, *c, bytes),
+        _ => {},
+    }
+    return bytes.iter().position(|byte| set.contains(byte));
+}
+
+fn is_continuation_byte(byte: u8) -> bool {
+    return byte & 0xC0 == 0x80;
+}
+
+/// decodes the UTF-8 sequence at `position`, or the byte there as U+FFFD if it doesn't start one.
+fn decode_next(bytes: &[u8], position: usize) -> Option<(char, usize)> {
+    let first = *bytes.get(position)?;
+    let width = match first {
+        0x00..=0x7F => 1,
+        0xC2..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF4 => 4,
+        _ => 0,
+    };
+    let decoded = bytes.get(position..position + width)
+        .and_then(|sequence| std::str::from_utf8(sequence).ok())
+        .and_then(|sequence| sequence.chars().next());
+    return match decoded {
+        Option::Some(c) => Some((c, position + width)),
+        Option::None => Some((char::REPLACEMENT_CHARACTER, position + 1)),
+    };
+}
+
+impl Haystack for str {
+    fn as_bytes(&self) -> &[u8] {
+        return str::as_bytes(self);
+    }
+
+    fn next_unit(&self, position: usize) -> Option<(char, usize)> {
+        let c = self.get(position..)?.chars().next()?;
+        return Some((c, position + c.len_utf8()));
+    }
+
+    fn previous_unit(&self, position: usize) -> Option<(char, usize)> {
+        let c = self.get(..position)?.chars().next_back()?;
+        return Some((c, position - c.len_utf8()));
+    }
+
+    fn is_unit_boundary(&self, position: usize) -> bool {
+        return self.is_char_boundary(position);
+    }
+
+    // with the `simd` feature, the byte search of the default implementation is faster
+    #[cfg(not(feature = "simd"))]
+    fn find_literal(&self, from: usize, literal: &[u8]) -> Option<usize> {
+        let literal = std::str::from_utf8(literal).ok()?;
+        return self.get(from..)?.find(literal).map(|i| from + i);
+    }
+}
+
+impl Haystack for [u8] {
+    fn as_bytes(&self) -> &[u8] {
+        return self;
+    }
+}
+
+/// matches the bytes returned by [`OsStr::as_encoded_bytes`], which are UTF-8 for the parts of
+/// the string that are valid Unicode on all platforms.
+impl Haystack for OsStr {
+    fn as_bytes(&self) -> &[u8] {
+        return self.as_encoded_bytes();
+    }
+}
+
+impl Haystack for String {
+    fn as_bytes(&self) -> &[u8] {
+        return String::as_bytes(self);
+    }
+
+    fn next_unit(&self, position: usize) -> Option<(char, usize)> {
+        return self.as_str().next_unit(position);
+    }
+
+    fn previous_unit(&self, position: usize) -> Option<(char, usize)> {
+        return self.as_str().previous_unit(position);
+    }
+
+    fn is_unit_boundary(&self, position: usize) -> bool {
+        return self.is_char_boundary(position);
+    }
+
+    fn find_literal(&self, from: usize, literal: &[u8]) -> Option<usize> {
+        return self.as_str().find_literal(from, literal);
+    }
+}
+
+impl Haystack for Vec<u8> {
+    fn as_bytes(&self) -> &[u8] {
+        return self;
+    }
+}
+
+impl Haystack for OsString {
+    fn as_bytes(&self) -> &[u8] {
+        return self.as_encoded_bytes();
+    }
+}
+
+/// checks if a word character can be found on exactly one side of the byte offset `position`,
+/// like [`is_word_boundary`](crate::matcher::is_word_boundary) does for strings.
+pub(crate) fn is_word_boundary_in<H: Haystack + ?Sized>(haystack: &H, position: usize) -> bool {
+    let is_word_character = |(c, _): (char, usize)| c.is_alphanumeric() || c == '_';
+    let before = haystack.previous_unit(position).is_some_and(is_word_character);
+    let after = haystack.next_unit(position).is_some_and(is_word_character);
+    return before != after;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsStr;
+    use crate::{DefaultCaseFold, GlobBuilder, MatchOptions, MatchUnit, ParsedGlobString};
+    use super::{find_any_byte, Haystack};
+
+    #[test]
+    fn test_units_of_bytes() {
+        let bytes: &[u8] = b"a\xc3\xa4\xff\x80z";
+        let units: Vec<_> = std::iter::successors(Some((' ', 0)), |(_, position)| bytes.next_unit(*position)).skip(1).collect();
+        assert_eq!(units, vec![('a', 1), ('ä', 3), ('\u{fffd}', 4), ('\u{fffd}', 5), ('z', 6)]);
+        assert_eq!(bytes.previous_unit(3), Some(('ä', 1)));
+        assert_eq!(bytes.previous_unit(5), Some(('\u{fffd}', 4)));
+        assert_eq!(bytes.previous_unit(0), None);
+        let boundaries: Vec<_> = (0..=7).filter(|position| bytes.is_unit_boundary(*position)).collect();
+        assert_eq!(boundaries, vec![0, 1, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_units_agree_with_str() {
+        let string = "größe €";
+        for position in 0..=string.len() {
+            assert_eq!(string.as_bytes().next_unit(position).filter(|_| string.is_char_boundary(position)), string.next_unit(position));
+            assert_eq!(string.as_bytes().previous_unit(position).filter(|_| string.is_char_boundary(position)), string.previous_unit(position));
+            assert_eq!(string.as_bytes().is_unit_boundary(position), string.is_unit_boundary(position));
+        }
+    }
+
+    #[test]
+    fn test_find_literal() {
+        assert_eq!(OsStr::new("a.tar.gz").find_literal(2, b".gz"), Some(5));
+        assert_eq!(b"abc"[..].find_literal(1, b"a"), None);
+        assert_eq!("abc".find_literal(3, b""), Some(3));
+        assert_eq!("abc".find_literal(4, b""), None);
+        assert_eq!("größe".find_literal(1, "ö".as_bytes()), Some(2));
+        assert_eq!(b"abcabc"[..].find_literal(1, b"abc"), Some(3));
+    }
+
+    #[test]
+    fn test_find_any_byte() {
+        assert_eq!(find_any_byte(b"src/lib.rs", b"."), Some(7));
+        assert_eq!(find_any_byte(b"src/lib.rs", b"./"), Some(3));
+        assert_eq!(find_any_byte(b"src/lib.rs", b"xyz"), None);
+        assert_eq!(find_any_byte(b"src/lib.rs", b"wxyzs"), Some(0));
+        assert_eq!(find_any_byte(b"", b"a"), None);
+    }
+
+    #[test]
+    fn test_match_bytes_and_os_strings() {
+        let pattern = ParsedGlobString::try_from("\\bcat?").unwrap();
+        assert_eq!(pattern.find_in(&b"\xffcat\xfe"[..]), Some(1..5));
+        assert_eq!(pattern.find_in(&b"\xffcat"[..]), None);
+        let options = MatchOptions { case_fold: Some(&DefaultCaseFold), ..MatchOptions::default() };
+        let pattern = GlobBuilder::new("CAT").options(&options).build().unwrap();
+        assert_eq!(pattern.find_in(OsStr::new("a cat")), Some(2..5));
+        let options = MatchOptions { unit: MatchUnit::Byte, ..MatchOptions::default() };
+        let pattern = GlobBuilder::new("x??x").options(&options).build().unwrap();
+        assert!(pattern.is_match_in(&b"x\xc3\xa9x"[..]));
+        assert!(!pattern.is_match_in(&b"x\xffx"[..]));
+    }
+}
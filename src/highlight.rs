@@ -0,0 +1,160 @@
+use std::ops::Range;
+use crate::Captures;
+
+/// a part of a match: either text matched by a literal part of the pattern or by a wildcard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Segment<'s> {
+    Literal(&'s str),
+    Wildcard(&'s str),
+}
+
+/// a haystack split into the parts before, inside and after a match, for rendering matches in
+/// grep-like user interfaces.
+/// ```
+/// use glob::{Highlight, ParsedGlobString, Segment};
+/// let pattern = ParsedGlobString::try_from("thesis-*.pdf").unwrap();
+/// let captures = pattern.captures("docs/thesis-final.pdf (old)").unwrap();
+/// let highlight = Highlight::from(&captures);
+/// assert_eq!(highlight.before(), "docs/");
+/// assert_eq!(highlight.inside(), "thesis-final.pdf");
+/// assert_eq!(highlight.after(), " (old)");
+/// assert_eq!(highlight.segments(), &[
+///     Segment::Literal("thesis-"),
+///     Segment::Wildcard("final"),
+///     Segment::Literal(".pdf"),
+/// ]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Highlight<'s> {
+    before: &'s str,
+    inside: &'s str,
+    after: &'s str,
+    segments: Vec<Segment<'s>>,
+}
+
+impl<'s> From<&Captures<'s>> for Highlight<'s> {
+    fn from(captures: &Captures<'s>) -> Self {
+        let haystack = captures.haystack();
+        let range = captures.range();
+        // the ranges of the segments and whether they were matched by wildcards; adjacent parts of
+        // the same kind form one segment, e.g. when a wildcard between two literals matches nothing
+        let mut parts: Vec<(bool, Range<usize>)> = Vec::new();
+        let mut push = |is_wildcard: bool, part: Range<usize>| {
+            if part.is_empty() {
+                return;
+            }
+            match parts.last_mut() {
+                Option::Some((last_is_wildcard, last)) if *last_is_wildcard == is_wildcard => last.end = part.end,
+                _ => parts.push((is_wildcard, part)),
+            }
+        };
+        let mut position = range.start;
+        for wildcard_range in captures.ranges() {
+            push(false, position..wildcard_range.start);
+            push(true, wildcard_range.clone());
+            position = wildcard_range.end;
+        }
+        push(false, position..range.end);
+        let segments = parts.into_iter()
+            .map(|(is_wildcard, part)| if is_wildcard { Segment::Wildcard(&haystack[part]) } else { Segment::Literal(&haystack[part]) })
+            .collect();
+        return Highlight {
+            before: &haystack[..range.start],
+            inside: &haystack[range.clone()],
+            after: &haystack[range.end..],
+            segments: segments,
+        };
+    }
+}
+
+impl<'s> Highlight<'s> {
+    /// returns the part of the haystack before the match.
+    pub fn before(&self) -> &'s str {
+        return self.before;
+    }
+
+    /// returns the matched part of the haystack.
+    pub fn inside(&self) -> &'s str {
+        return self.inside;
+    }
+
+    /// returns the part of the haystack after the match.
+    pub fn after(&self) -> &'s str {
+        return self.after;
+    }
+
+    /// returns the matched part of the haystack, split into the parts matched by literals and by
+    /// wildcards. Wildcards that matched nothing are left out.
+    pub fn segments(&self) -> &[Segment<'s>] {
+        return &self.segments;
+    }
+
+    /// renders the haystack for a terminal, with the literal parts of the match in bold red and the
+    /// parts matched by wildcards in red.
+    /// ```
+    /// # use glob::{Highlight, ParsedGlobString};
+    /// let pattern = ParsedGlobString::try_from("a?c").unwrap();
+    /// let highlight = Highlight::from(&pattern.captures("xabcx").unwrap());
+    /// assert_eq!(highlight.to_ansi(), "x\x1b[1;31ma\x1b[0m\x1b[31mb\x1b[0m\x1b[1;31mc\x1b[0mx");
+    /// ```
+    #[cfg(feature = "color")]
+    pub fn to_ansi(&self) -> String {
+        const LITERAL_STYLE: &str = "\x1b[1;31m";
+        const WILDCARD_STYLE: &str = "\x1b[31m";
+        const RESET: &str = "\x1b[0m";
+        let mut rendered = String::from(self.before);
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(text) => rendered.push_str(&format!("{}{}{}", LITERAL_STYLE, text, RESET)),
+                Segment::Wildcard(text) => rendered.push_str(&format!("{}{}{}", WILDCARD_STYLE, text, RESET)),
+            }
+        }
+        rendered.push_str(self.after);
+        return rendered;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Anchor, ParsedGlobString};
+    use super::{Highlight, Segment};
+
+    fn highlight<'s>(pattern: &str, haystack: &'s str) -> Highlight<'s> {
+        let pattern = ParsedGlobString::try_from(pattern).unwrap();
+        return Highlight::from(&pattern.captures(haystack).unwrap());
+    }
+
+    #[test]
+    fn test_literal_only() {
+        let highlight = highlight("lib", "src/lib.rs");
+        assert_eq!((highlight.before(), highlight.inside(), highlight.after()), ("src/", "lib", ".rs"));
+        assert_eq!(highlight.segments(), &[Segment::Literal("lib")]);
+    }
+
+    #[test]
+    fn test_wildcards_at_both_ends() {
+        let pattern = ParsedGlobString::try_from("?/*").unwrap();
+        let highlight = Highlight::from(&pattern.captures_anchored("a/bc", Anchor::Both).unwrap());
+        assert_eq!(highlight.segments(), &[Segment::Wildcard("a"), Segment::Literal("/"), Segment::Wildcard("bc")]);
+        assert_eq!((highlight.before(), highlight.after()), ("", ""));
+    }
+
+    #[test]
+    fn test_empty_wildcard_is_left_out() {
+        let highlight = highlight("a*b", "xaby");
+        assert_eq!(highlight.segments(), &[Segment::Literal("ab")]);
+    }
+
+    #[test]
+    fn test_adjacent_wildcards_form_one_segment() {
+        let highlight = highlight("a*?b", "xacdby");
+        assert_eq!(highlight.segments(), &[Segment::Literal("a"), Segment::Wildcard("cd"), Segment::Literal("b")]);
+    }
+
+    #[test]
+    fn test_empty_match() {
+        let highlight = highlight("", "abc");
+        assert_eq!((highlight.before(), highlight.inside(), highlight.after()), ("", "", "abc"));
+        assert!(highlight.segments().is_empty());
+    }
+}
@@ -0,0 +1,349 @@
+use std::borrow::Cow;
+use std::fs;
+use std::io;
+use std::path::Path;
+use crate::{GlobParseError, OverrideMatch, PathPattern};
+
+/// the rules of one `.gitignore`-style file, which apply to the directory containing the file.
+///
+/// Each line is a [path pattern](PathPattern); empty lines and lines starting with `#` are
+/// skipped. A rule starting with `!` re-includes paths excluded by an earlier rule, a rule ending
+/// with `/` only matches directories. A rule with a `/` at the beginning or in the middle is
+/// anchored: it matches paths relative to the directory of the file. Other rules match the name of
+/// an entry at any depth below that directory. If several rules match, the last one decides.
+/// ```
+/// use glob::{IgnoreRules, OverrideMatch};
+/// let rules = IgnoreRules::parse("# build output\n/target/\n*.log\n!keep.log\n").unwrap();
+/// assert_eq!(rules.matched("target", true), OverrideMatch::Ignore);
+/// assert_eq!(rules.matched("src/target", true), OverrideMatch::None);
+/// assert_eq!(rules.matched("logs/debug.log", false), OverrideMatch::Ignore);
+/// assert_eq!(rules.matched("logs/keep.log", false), OverrideMatch::Whitelist);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreRules {
+    rules: Vec<IgnoreRule>,
+}
+
+#[derive(Debug, Clone)]
+struct IgnoreRule {
+    pattern: PathPattern<'static>,
+    negated: bool,
+    anchored: bool,
+    only_directories: bool,
+}
+
+/// returns the end of `line` without its trailing spaces, except for spaces escaped with `\`.
+fn trim_trailing_spaces(line: &str) -> usize {
+    let mut end = line.len();
+    while line[..end].ends_with(' ') {
+        let backslashes = line[..end - 1].bytes().rev().take_while(|byte| *byte == b'\\').count();
+        if backslashes % 2 == 1 {
+            break;
+        }
+        end -= 1;
+    }
+    return end;
+}
+
+/// replaces each `\ ` in `pattern` with a space, which the pattern syntax has no escape for.
+/// Returns the result and the positions in it of the spaces whose `\` was removed.
+fn unescape_spaces(pattern: &str) -> (Cow<'_, str>, Vec<usize>) {
+    if !pattern.contains("\\ ") {
+        return (Cow::Borrowed(pattern), Vec::new());
+    }
+    let mut unescaped = String::with_capacity(pattern.len());
+    let mut removed = Vec::new();
+    let mut chars = pattern.chars();
+    while let Option::Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Option::Some(' ') => {
+                removed.push(unescaped.len());
+                unescaped.push(' ');
+            },
+            Option::Some(escaped) => {
+                unescaped.push(c);
+                unescaped.push(escaped);
+            },
+            Option::None => unescaped.push(c),
+        }
+    }
+    return (Cow::Owned(unescaped), removed);
+}
+
+/// moves an error in a pattern with unescaped spaces (see [`unescape_spaces`]) that starts at
+/// `offset` in `contents` to its position in `contents`.
+fn locate_error<'c>(error: GlobParseError<'_>, contents: &'c str, offset: usize, removed: &[usize]) -> GlobParseError<'c> {
+    let locate = |index: usize| offset + index + removed.iter().filter(|position| **position <= index).count();
+    return match error {
+        GlobParseError::UnknownEscapeSequence(index, sequence) => {
+            let index = locate(index);
+            GlobParseError::UnknownEscapeSequence(index, &contents[index..index + sequence.len()])
+        },
+        GlobParseError::UnterminatedEscapeSequence(index) => GlobParseError::UnterminatedEscapeSequence(locate(index)),
+    };
+}
+
+impl IgnoreRules {
+    /// parses the contents of an ignore file. The indices in errors refer to `contents`.
+    ///
+    /// Like in `.gitignore` files, trailing spaces are removed unless they are escaped with `\`,
+    /// `\ ` stands for a space anywhere in a rule, and `\#` or `\!` at the beginning of a rule
+    /// stand for a literal `#` or `!`.
+    /// ```
+    /// use glob::{IgnoreRules, OverrideMatch};
+    /// let rules = IgnoreRules::parse("\\#notes.txt  \n\\!important\nsome\\ file\\ \n").unwrap();
+    /// assert_eq!(rules.matched("#notes.txt", false), OverrideMatch::Ignore);
+    /// assert_eq!(rules.matched("!important", false), OverrideMatch::Ignore);
+    /// assert_eq!(rules.matched("some file ", false), OverrideMatch::Ignore);
+    /// assert_eq!(rules.matched("some file", false), OverrideMatch::None);
+    /// ```
+    pub fn parse(contents: &str) -> Result<Self, GlobParseError<'_>> {
+        let mut rules = Vec::new();
+        let mut line_start = 0;
+        for line in contents.split_inclusive('\n') {
+            let start = line_start;
+            line_start += line.len();
+            let line = line.trim_end_matches(['\n', '\r']);
+            let line = &line[..trim_trailing_spaces(line)];
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (negated, mut pattern) = match line.strip_prefix('!') {
+                Option::Some(pattern) => (true, pattern),
+                Option::None => (false, line),
+            };
+            if pattern.starts_with("\\#") || pattern.starts_with("\\!") {
+                pattern = &pattern[1..];
+            }
+            let mut offset = start + line.len() - pattern.len();
+            let only_directories = pattern.ends_with('/') && !pattern.ends_with("\\/");
+            if only_directories {
+                pattern = &pattern[..pattern.len() - 1];
+            }
+            let anchored = pattern.contains('/');
+            if let Option::Some(relative) = pattern.strip_prefix('/') {
+                pattern = relative;
+                offset += 1;
+            }
+            let (pattern, removed) = unescape_spaces(pattern);
+            let parsed = PathPattern::try_from(pattern.as_ref()).map_err(|error| locate_error(error, contents, offset, &removed))?;
+            rules.push(IgnoreRule { pattern: parsed.into_owned(), negated: negated, anchored: anchored, only_directories: only_directories });
+        }
+        return Ok(IgnoreRules { rules: rules });
+    }
+
+    /// reads and parses the ignore file at `path`. Malformed rules are reported as errors of the
+    /// kind [`io::ErrorKind::InvalidData`] that name the file.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)?;
+        return IgnoreRules::parse(&contents).map_err(|error| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), error))
+        });
+    }
+
+    /// adds the rules of `other` after the rules of `self`, so they take precedence, e.g. to merge
+    /// several ignore files of one directory.
+    /// ```
+    /// use glob::{IgnoreRules, OverrideMatch};
+    /// let mut rules = IgnoreRules::parse("*.log\n").unwrap();
+    /// rules.append(IgnoreRules::parse("!debug.log\n").unwrap());
+    /// assert_eq!(rules.matched("debug.log", false), OverrideMatch::Whitelist);
+    /// ```
+    pub fn append(&mut self, other: IgnoreRules) {
+        self.rules.extend(other.rules);
+    }
+
+    /// returns the number of rules.
+    pub fn len(&self) -> usize {
+        return self.rules.len();
+    }
+
+    /// checks if there are no rules.
+    pub fn is_empty(&self) -> bool {
+        return self.rules.is_empty();
+    }
+
+    /// decides about a `/`-separated path relative to the directory of the ignore file. `is_dir`
+    /// tells if the path is a directory. Returns [`OverrideMatch::None`] if no rule matches.
+    ///
+    /// Like with `.gitignore` files, a path below an excluded directory is excluded even if a `!`
+    /// rule matches it, because the directory isn't entered to find it.
+    /// ```
+    /// use glob::{IgnoreRules, OverrideMatch};
+    /// let rules = IgnoreRules::parse("build/\n!build/keep.txt\n").unwrap();
+    /// assert_eq!(rules.matched("build/keep.txt", false), OverrideMatch::Ignore);
+    /// ```
+    pub fn matched(&self, path: &str, is_dir: bool) -> OverrideMatch {
+        let excluded_ancestor = path.match_indices('/').any(|(end, _)| self.matched_by_rule(&path[..end], true) == OverrideMatch::Ignore);
+        if excluded_ancestor {
+            return OverrideMatch::Ignore;
+        }
+        return self.matched_by_rule(path, is_dir);
+    }
+
+    /// returns the decision of the last rule that matches the path itself.
+    fn matched_by_rule(&self, path: &str, is_dir: bool) -> OverrideMatch {
+        let file_name = path.rsplit('/').next().unwrap_or(path);
+        for rule in self.rules.iter().rev() {
+            if rule.only_directories && !is_dir {
+                continue;
+            }
+            let matches = if rule.anchored {
+                rule.pattern.matches(path)
+            } else {
+                rule.pattern.matches(file_name)
+            };
+            if matches {
+                return if rule.negated { OverrideMatch::Whitelist } else { OverrideMatch::Ignore };
+            }
+        }
+        return OverrideMatch::None;
+    }
+}
+
+/// the ignore files of a directory and its ancestors, with the rules of deeper directories taking
+/// precedence over the rules of shallower ones, like nested `.gitignore` files.
+///
+/// [`GlobWalker::ignore_files`](crate::GlobWalker::ignore_files) maintains such a stack while it
+/// traverses the directories.
+/// ```
+/// use glob::{IgnoreRules, IgnoreStack};
+/// let mut stack = IgnoreStack::new();
+/// stack.push("", IgnoreRules::parse("*.log\n").unwrap());
+/// stack.push("logs", IgnoreRules::parse("!audit.log\n").unwrap());
+/// assert!(stack.is_ignored("debug.log", false));
+/// assert!(stack.is_ignored("logs/debug.log", false));
+/// assert!(!stack.is_ignored("logs/audit.log", false));
+/// assert!(stack.is_ignored("audit.log", false));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreStack {
+    levels: Vec<(String, IgnoreRules)>, // directories relative to the root, from shallow to deep
+}
+
+/// checks if `path` is `directory` or below it.
+fn is_within(path: &str, directory: &str) -> bool {
+    if directory.is_empty() {
+        return true;
+    }
+    return path.strip_prefix(directory).is_some_and(|rest| rest.is_empty() || rest.starts_with('/'));
+}
+
+impl IgnoreStack {
+    /// creates a stack without any rules.
+    pub fn new() -> Self {
+        return IgnoreStack { levels: Vec::new() };
+    }
+
+    /// adds the rules of the ignore file in `directory` (relative to the root, `""` for the root
+    /// itself). The rules of directories that don't contain `directory` are removed first, so a
+    /// depth-first traversal can push the rules of each directory it enters.
+    pub fn push(&mut self, directory: &str, rules: IgnoreRules) {
+        self.leave(directory);
+        self.levels.push((String::from(directory), rules));
+    }
+
+    /// removes the rules of directories that don't contain `directory`.
+    pub fn leave(&mut self, directory: &str) {
+        while self.levels.last().is_some_and(|(level, _)| !is_within(directory, level)) {
+            self.levels.pop();
+        }
+    }
+
+    /// decides about a `/`-separated path relative to the root. The rules of the deepest directory
+    /// containing the path that has a matching rule decide.
+    pub fn matched(&self, path: &str, is_dir: bool) -> OverrideMatch {
+        for (directory, rules) in self.levels.iter().rev() {
+            if directory.as_str() == path || !is_within(path, directory) {
+                continue;
+            }
+            let relative = if directory.is_empty() { path } else { &path[directory.len() + 1..] };
+            match rules.matched(relative, is_dir) {
+                OverrideMatch::None => continue,
+                decision => return decision,
+            }
+        }
+        return OverrideMatch::None;
+    }
+
+    /// checks if the path is excluded, see [`matched`](Self::matched).
+    pub fn is_ignored(&self, path: &str, is_dir: bool) -> bool {
+        return self.matched(path, is_dir) == OverrideMatch::Ignore;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{GlobParseError, OverrideMatch};
+    use super::{IgnoreRules, IgnoreStack};
+
+    #[test]
+    fn test_parse_skips_comments_and_empty_lines() {
+        let rules = IgnoreRules::parse("# comment\n\n*.o\r\n\n").unwrap();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules.matched("a/b.o", false), OverrideMatch::Ignore);
+        assert!(IgnoreRules::parse("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_anchoring() {
+        let rules = IgnoreRules::parse("doc/*.html\n/TODO\nbuild/\n").unwrap();
+        assert_eq!(rules.matched("doc/index.html", false), OverrideMatch::Ignore);
+        assert_eq!(rules.matched("src/doc/index.html", false), OverrideMatch::None);
+        assert_eq!(rules.matched("TODO", false), OverrideMatch::Ignore);
+        assert_eq!(rules.matched("src/TODO", false), OverrideMatch::None);
+        assert_eq!(rules.matched("src/build", true), OverrideMatch::Ignore);
+        assert_eq!(rules.matched("src/build", false), OverrideMatch::None);
+    }
+
+    #[test]
+    fn test_error_indices_refer_to_the_contents() {
+        assert_eq!(IgnoreRules::parse("*.o\n!/a\\x\n").unwrap_err(), GlobParseError::UnknownEscapeSequence(7, "\\x"));
+    }
+
+    #[test]
+    fn test_gitignore_escapes() {
+        let rules = IgnoreRules::parse("a.txt \t \nb.txt\\  \n\\\\ \n!\\!c\n").unwrap();
+        assert_eq!(rules.len(), 4);
+        assert_eq!(rules.matched("a.txt \t", false), OverrideMatch::Ignore);
+        assert_eq!(rules.matched("b.txt ", false), OverrideMatch::Ignore);
+        assert_eq!(rules.matched("b.txt", false), OverrideMatch::None);
+        assert_eq!(rules.matched("\\", false), OverrideMatch::Ignore);
+        assert_eq!(rules.matched("!c", false), OverrideMatch::Whitelist);
+        assert!(IgnoreRules::parse("   \n").unwrap().is_empty());
+        // the indices of errors after an escaped space refer to the contents
+        assert_eq!(IgnoreRules::parse("*\n/a\\ b\\x\n").unwrap_err(), GlobParseError::UnknownEscapeSequence(7, "\\x"));
+    }
+
+    #[test]
+    fn test_excluded_directories_hide_their_contents() {
+        let rules = IgnoreRules::parse("/logs\n!*.log\ntmp/\n!tmp/keep\n").unwrap();
+        assert_eq!(rules.matched("logs", true), OverrideMatch::Ignore);
+        assert_eq!(rules.matched("logs/a.log", false), OverrideMatch::Ignore);
+        assert_eq!(rules.matched("src/a.log", false), OverrideMatch::Whitelist);
+        assert_eq!(rules.matched("src/tmp/keep", false), OverrideMatch::Ignore);
+        assert_eq!(rules.matched("tmp/keep", false), OverrideMatch::Ignore);
+        // a file named like an excluded directory isn't one
+        assert_eq!(rules.matched("tmp", false), OverrideMatch::None);
+    }
+
+    #[test]
+    fn test_deeper_files_take_precedence() {
+        let mut stack = IgnoreStack::new();
+        stack.push("", IgnoreRules::parse("!*.gen.rs\n/*.rs\n").unwrap());
+        stack.push("src", IgnoreRules::parse("*.rs\n!lib.rs\n").unwrap());
+        assert!(stack.is_ignored("main.rs", false));
+        assert!(!stack.is_ignored("src/lib.rs", false));
+        assert!(stack.is_ignored("src/util.rs", false));
+        assert_eq!(stack.matched("src/bin/cli.gen.rs", false), OverrideMatch::Ignore);
+        assert_eq!(stack.matched("tests/a.gen.rs", false), OverrideMatch::Whitelist);
+        // leaving `src` removes its rules
+        stack.push("tests", IgnoreRules::default());
+        assert!(!stack.is_ignored("src/util.rs", false));
+    }
+}
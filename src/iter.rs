@@ -0,0 +1,133 @@
+use std::iter::FusedIterator;
+use std::ops::Range;
+use crate::haystack::find_any_byte;
+use crate::matcher::{find_last_match_with_captures, find_match_with_captures_from, next_search_position, previous_search_limit};
+use crate::prefilter::starting_bytes;
+use crate::ParsedGlobString;
+
+/// iterates over the byte ranges of the non-overlapping occurrences of a pattern in a string, see
+/// [`ParsedGlobString::find_iter`].
+#[derive(Debug, Clone)]
+pub struct FindIter<'p, 'g, 's> {
+    pattern: &'p ParsedGlobString<'g>,
+    string: &'s str,
+    position: Option<usize>, // where to look for the next occurrence
+    starting_bytes: Option<Vec<u8>>, // positions with other bytes are skipped
+}
+
+impl<'p, 'g, 's> FindIter<'p, 'g, 's> {
+    pub(crate) fn new(pattern: &'p ParsedGlobString<'g>, string: &'s str) -> Self {
+        let starting_bytes = starting_bytes(pattern.tokens(), pattern.config());
+        return FindIter { pattern: pattern, string: string, position: Some(0), starting_bytes: starting_bytes };
+    }
+
+    /// returns the first position at or after `from` at which an occurrence can start.
+    fn candidate_start(&self, from: usize) -> Option<usize> {
+        let Option::Some(bytes) = &self.starting_bytes else {
+            return Some(from);
+        };
+        // a starting byte is the first byte of a character, so the position is a character boundary
+        return find_any_byte(&self.string.as_bytes()[from..], bytes).map(|offset| from + offset);
+    }
+}
+
+impl<'p, 'g, 's> Iterator for FindIter<'p, 'g, 's> {
+    type Item = Range<usize>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let from = self.position.and_then(|from| self.candidate_start(from));
+        let Option::Some(from) = from else {
+            self.position = None;
+            return None;
+        };
+        let found = find_match_with_captures_from(self.pattern.tokens(), self.string, from, false, false, self.pattern.config());
+        return match found {
+            Option::None => {
+                self.position = None;
+                None
+            },
+            Option::Some((range, _)) => {
+                self.position = next_search_position(self.string, &range);
+                Some(range)
+            },
+        };
+    }
+}
+
+impl<'p, 'g, 's> FusedIterator for FindIter<'p, 'g, 's> {}
+
+/// iterates over the byte ranges of non-overlapping occurrences of a pattern in a string, from
+/// right to left, see [`ParsedGlobString::rfind_iter`].
+#[derive(Debug, Clone)]
+pub struct RFindIter<'p, 'g, 's> {
+    pattern: &'p ParsedGlobString<'g>,
+    string: &'s str,
+    limit: Option<usize>, // where the next occurrence has to end at the latest
+}
+
+impl<'p, 'g, 's> RFindIter<'p, 'g, 's> {
+    pub(crate) fn new(pattern: &'p ParsedGlobString<'g>, string: &'s str) -> Self {
+        return RFindIter { pattern: pattern, string: string, limit: Some(string.len()) };
+    }
+}
+
+impl<'p, 'g, 's> Iterator for RFindIter<'p, 'g, 's> {
+    type Item = Range<usize>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let limit = self.limit?;
+        let found = find_last_match_with_captures(self.pattern.tokens(), self.string, limit, false, self.pattern.config());
+        return match found {
+            Option::None => {
+                self.limit = None;
+                None
+            },
+            Option::Some((range, _)) => {
+                self.limit = previous_search_limit(self.string, &range);
+                Some(range)
+            },
+        };
+    }
+}
+
+impl<'p, 'g, 's> FusedIterator for RFindIter<'p, 'g, 's> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{GlobBuilder, ParsedGlobString};
+
+    #[test]
+    fn test_find_iter() {
+        let pattern = ParsedGlobString::try_from("#?*;").unwrap();
+        assert_eq!(pattern.find_iter("#a; #bc; #;").collect::<Vec<_>>(), vec![0..3, 4..8]);
+        assert_eq!(pattern.find_iter("none").next(), None);
+    }
+
+    #[test]
+    fn test_find_iter_skips_to_starting_bytes() {
+        let pattern = ParsedGlobString::try_from("\\bé?").unwrap();
+        assert_eq!(pattern.find_iter("aé éx, éé").collect::<Vec<_>>(), vec![4..7, 9..13]);
+        assert_eq!(pattern.find_iter("no match").next(), None);
+    }
+
+    #[test]
+    fn test_find_iter_empty_matches() {
+        let pattern = ParsedGlobString::try_from("").unwrap();
+        assert_eq!(pattern.find_iter("aä").collect::<Vec<_>>(), vec![0..0, 1..1, 3..3]);
+    }
+
+    #[test]
+    fn test_rfind_iter() {
+        let pattern = ParsedGlobString::try_from("#?*;").unwrap();
+        assert_eq!(pattern.rfind_iter("#a; #bc; #;").collect::<Vec<_>>(), vec![4..8, 0..3]);
+        let empty = ParsedGlobString::try_from("").unwrap();
+        assert_eq!(empty.rfind_iter("aä").collect::<Vec<_>>(), vec![3..3, 1..1, 0..0]);
+        let word = ParsedGlobString::try_from("\\b?").unwrap();
+        assert_eq!(word.rfind_iter("ab cd").collect::<Vec<_>>(), vec![3..4, 2..3, 0..1]);
+    }
+
+    #[test]
+    fn test_matched_slices() {
+        let pattern = GlobBuilder::new("<*>").wildcards_match_newline(false).build().unwrap();
+        let text = "<b>bold</b>\n<\n>";
+        assert_eq!(pattern.matched_slices(text).collect::<Vec<_>>(), vec!["<b>", "</b>"]);
+    }
+}
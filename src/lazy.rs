@@ -0,0 +1,86 @@
+use std::ops::Deref;
+use std::sync::OnceLock;
+use crate::{GlobParseError, ParsedGlobString};
+
+/// a pattern that is parsed on first use, so it can be stored in a `static`.
+///
+/// Parsing happens once, even if several threads use the pattern at the same time. Dereferencing a
+/// malformed pattern panics every time, with the same message; use [`try_get`](Self::try_get) to
+/// handle the error instead.
+/// ```
+/// use glob::LazyPattern;
+/// static RUST_FILES: LazyPattern = LazyPattern::new("*.rs");
+/// assert!(RUST_FILES.matches_completely("src/lib.rs"));
+/// ```
+#[derive(Debug)]
+pub struct LazyPattern {
+    pattern: &'static str,
+    parsed: OnceLock<Result<ParsedGlobString<'static>, GlobParseError<'static>>>,
+}
+
+impl LazyPattern {
+    /// creates a pattern that will parse the given pattern string on first use.
+    pub const fn new(pattern: &'static str) -> Self {
+        return LazyPattern { pattern: pattern, parsed: OnceLock::new() };
+    }
+
+    /// returns the pattern string.
+    pub fn as_str(&self) -> &'static str {
+        return self.pattern;
+    }
+
+    /// parses the pattern string if that hasn't happened yet and returns the result.
+    /// ```
+    /// use glob::{GlobParseError, LazyPattern};
+    /// static BROKEN: LazyPattern = LazyPattern::new("*.rs\\");
+    /// assert_eq!(BROKEN.try_get().unwrap_err(), &GlobParseError::UnterminatedEscapeSequence(4));
+    /// ```
+    pub fn try_get(&self) -> Result<&ParsedGlobString<'static>, &GlobParseError<'static>> {
+        return self.parsed.get_or_init(|| ParsedGlobString::try_from(self.pattern)).as_ref();
+    }
+}
+
+impl Deref for LazyPattern {
+    type Target = ParsedGlobString<'static>;
+    /// returns the parsed pattern, parsing it if that hasn't happened yet.
+    ///
+    /// Panics if the pattern string is malformed.
+    fn deref(&self) -> &Self::Target {
+        return match self.try_get() {
+            Ok(parsed) => parsed,
+            Err(error) => panic!("invalid pattern {:?}: {}", self.pattern, error),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic;
+    use std::thread;
+    use super::LazyPattern;
+
+    static LOG_FILES: LazyPattern = LazyPattern::new("*.log");
+    static BROKEN: LazyPattern = LazyPattern::new("\\x");
+
+    #[test]
+    fn test_static_pattern() {
+        assert_eq!(LOG_FILES.as_str(), "*.log");
+        assert!(LOG_FILES.matches_completely("debug.log"));
+        assert!(std::ptr::eq(LOG_FILES.try_get().unwrap(), &*LOG_FILES));
+    }
+
+    #[test]
+    fn test_used_from_several_threads() {
+        let threads: Vec<_> = (0..4).map(|_| thread::spawn(|| LOG_FILES.matches_partially("a.log.1"))).collect();
+        assert!(threads.into_iter().all(|thread| thread.join().unwrap()));
+    }
+
+    #[test]
+    fn test_broken_pattern_fails_consistently() {
+        for _ in 0..2 {
+            assert!(BROKEN.try_get().is_err());
+            let message = *panic::catch_unwind(|| BROKEN.matches_partially("x")).unwrap_err().downcast::<String>().unwrap();
+            assert_eq!(message, "invalid pattern \"\\\\x\": unknown escape sequence `\\x` at index 0");
+        }
+    }
+}
@@ -23,7 +23,26 @@
 //! handle the error case properly), whereas [`pattern.matches_partially`](ParsedGlobString::matches_partially) returns a plain and simple [`bool`].
 //!
 //! Both the [`ParsedGlobString`] and the [`GlobParseError`] currently borrow string slices from the
-//! pattern string, so they have a lifetime that is limited by the pattern string's lifetime.
+//! pattern string, so they have a lifetime that is limited by the pattern string's lifetime. Use
+//! [`into_owned`](ParsedGlobString::into_owned) to get a `ParsedGlobString<'static>` that can be
+//! stored independently of the pattern string.
+//!
+//! If the same patterns are used over and over again, e.g. once per request, the global pattern
+//! cache saves parsing them each time:
+//! ```
+//! use glob::pattern_matches_cached;
+//! assert_eq!(pattern_matches_cached("*.yaml", "path/to/foo.yaml"), Ok(true));
+//! ```
+//! See [`cached_pattern`] for details.
+//!
+//! With the `macros` feature enabled, the `glob!` macro validates constant patterns at compile time
+//! instead, so there is no runtime `unwrap()` to worry about:
+//! ```ignore
+//! use glob::glob;
+//! assert!(glob!("*.y?ml").matches_partially("path/to/foo.yaml"));
+//! ```
+//! The `glob_match!` macro builds on it to pick the first of several patterns that matches, like a
+//! `match` statement.
 //!
 //! # Pattern Syntax
 //!
@@ -40,7 +59,7 @@
 //! Note that in this example, we had to write `\\` because the rust compiler performs another level
 //! of unescaping.
 //!
-//! Apart from `*` and `?`, the only other escape character is the backslash itself: `\`. An escaped
+//! Apart from `*` and `?`, the backslash itself can be escaped: `\\`. An escaped
 //! backslash character in the pattern matches only a backslash character in the search string:
 //! ```
 //! # use glob::pattern_matches_partially;
@@ -48,15 +67,190 @@
 //! assert!(!pattern_matches_partially("\\\\", "Linux/Unix path separator: /").unwrap());
 //! ```
 //!
+//! The escape sequence `\b` matches a word boundary: the empty string between a word character
+//! (an alphanumeric character or `_`) and a non-word character, or the start or end of the string.
+//! This restricts matches to whole words:
+//! ```
+//! # use glob::pattern_matches_partially;
+//! assert!(pattern_matches_partially("\\bcat\\b", "a cat, a dog").unwrap());
+//! assert!(!pattern_matches_partially("\\bcat\\b", "concatenate").unwrap());
+//! ```
+//!
 //! Using a backslash for escaping any other character or using a backslash at the end of the
 //! pattern string results in a [`GlobParseError`].
 
 
-mod glob_parser;
-mod multislice;
+mod lru;
+mod cache;
+mod matcher;
+mod captures;
+mod highlight;
+mod walk;
+mod route;
+mod dns;
+mod builder;
+mod iter;
+mod case_fold;
+mod registry;
+mod cached_matcher;
+mod lazy;
+mod options;
+mod prefilter;
+mod fuzzy;
+mod prefix;
+mod set;
+mod subsumption;
+mod set_diff;
+mod optimize;
+mod lines;
+mod stream;
+mod path;
+mod overrides;
+mod ignore;
+mod dockerignore;
+mod provenance;
+mod report;
+pub mod compat;
+mod combinators;
+mod ranking;
+mod watch;
+mod bytes;
+mod chars;
+mod haystack;
+mod binary;
+mod search;
+mod progress;
+mod cancel;
+mod posix;
+mod dos;
+mod powershell;
+mod editable;
+mod synthesis;
+mod template;
+#[cfg(feature = "macros")]
+mod macros;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "clap")]
+pub mod clap;
+#[cfg(feature = "mmap")]
+mod mmap;
+#[cfg(feature = "test-util")]
+#[doc(hidden)]
+pub mod test_util;
+#[cfg(feature = "uniffi")]
+mod ffi;
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!();
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, Ordering};
 use glob_parser::*;
-use glob_parser::Token::*;
-pub use glob_parser::GlobParseError;
+use matcher::*;
+use prefilter::{starting_bytes, LiteralCheck, Prefilter};
+use fuzzy::{fuzzy_distance, relevance_score};
+use prefix::could_be_completed;
+use subsumption::{find_difference, Automaton};
+use optimize::optimize_tokens;
+use glob_parser::multislice::MultiSlice;
+pub use glob_parser::{ErrorCode, GlobParseError, OwnedGlobParseError, parse_events, parse_events_with, parse_partial, ParseEvent, PartialParse, PartialToken, split_pattern, Syntax, tokenize, tokenize_with, Tokenize, WildcardKind};
+#[cfg(feature = "macros")]
+pub use glob_macros::glob;
+pub use captures::Captures;
+pub use highlight::{Highlight, Segment};
+pub use walk::{GlobWalker, MatchedEntry, MatchedEntries, WalkBuilder, glob_in};
+pub use route::{RouteSet, RouteMatch};
+pub use dns::{DnsPattern, DnsPatternError, dns_pattern_matches};
+pub use builder::GlobBuilder;
+pub use iter::{FindIter, RFindIter};
+pub use matcher::{BudgetExceeded, Cache};
+pub use case_fold::{CaseFold, DefaultCaseFold, TurkishCaseFold};
+pub use registry::{PatternRegistry, PatternHandle};
+pub use cached_matcher::CachedMatcher;
+pub use lazy::LazyPattern;
+pub use options::{MatchOptions, MatchUnit};
+pub use set::{GlobSet, SetMatch};
+pub use set_diff::SetDiff;
+pub use lines::{filter_lines, context_lines, ContextLine, ContextLines, LineKind};
+pub use stream::{StreamMatcher, StreamMatch, CheckpointError};
+pub use path::PathPattern;
+pub use overrides::{Overrides, OverrideMatch};
+pub use ignore::{IgnoreRules, IgnoreStack};
+pub use dockerignore::DockerIgnore;
+pub use provenance::{Provenance, PatternListError, InvalidPattern, InvalidPatterns};
+pub use report::MatchReport;
+#[cfg(feature = "serde")]
+pub use report::JsonLinesWriter;
+pub use combinators::{any, all, Or, And, Not, Any, All, DiffMatcher};
+pub use ranking::Aggregation;
+pub use watch::WatchedPatternSet;
+#[cfg(feature = "notify")]
+pub use watch::{watch_glob, GlobWatch};
+#[cfg(feature = "mmap")]
+pub use mmap::{scan_file, scan_file_with};
+pub use haystack::Haystack;
+pub use search::{search, Search, SearchMatch};
+pub use progress::WalkStats;
+pub use cancel::Cancelled;
+pub use posix::{PosixOptions, PosixPattern, PosixPatternError};
+pub use dos::DosPattern;
+pub use powershell::{PowerShellPattern, PowerShellPatternError};
+pub use editable::EditablePattern;
+pub use synthesis::generalize;
+pub use template::{PatternTemplate, TemplateError};
+pub use binary::{is_binary, scan_bytes, BinaryDetection, FileScan, BINARY_DETECTION_LIMIT};
+pub use bytes::{BytePattern, BytePatternError, ByteValidation, LATIN1_CASE_FOLD};
+pub use cache::{cached_pattern, pattern_matches_cached, set_pattern_cache_capacity, clear_pattern_cache, DEFAULT_PATTERN_CACHE_CAPACITY};
+
+/// selects where a pattern has to match in a string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Anchor {
+    /// the pattern may occur anywhere in the string.
+    None,
+    /// the string must start with the pattern.
+    Start,
+    /// the string must end with the pattern.
+    End,
+    /// the pattern must match the string as a whole.
+    Both,
+}
+
+/// selects which alignment of the wildcards is reported if a pattern can match in several ways.
+///
+/// Matches always start as far left as possible. Among the matches starting there, the wildcards
+/// are assigned from left to right, each taking as few (lazy) or as many (greedy) characters as
+/// possible while the rest of the pattern still matches. This decides where an unanchored match
+/// ends (unless the longest match is requested with [`GlobBuilder::leftmost_longest`]) and which
+/// parts of the string the [`Captures`] report. Whether a pattern matches at all does not depend on
+/// the policy.
+/// ```
+/// use glob::{GlobBuilder, WildcardPolicy};
+/// let lazy = GlobBuilder::new("*-*").build().unwrap();
+/// let greedy = GlobBuilder::new("*-*").wildcard_policy(WildcardPolicy::Greedy).build().unwrap();
+/// assert_eq!(lazy.find("2024-01-31"), Some(0..5));
+/// assert_eq!(greedy.find("2024-01-31"), Some(0..10));
+/// assert_eq!(greedy.captures("2024-01-31").unwrap().get(0), Some("2024-01"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum WildcardPolicy {
+    /// wildcards match as few characters as possible (the default).
+    #[default]
+    Lazy,
+    /// wildcards match as many characters as possible.
+    Greedy,
+}
+
+impl Anchor {
+    fn is_anchored_at_start(self) -> bool {
+        return self == Anchor::Start || self == Anchor::Both;
+    }
+
+    fn is_anchored_at_end(self) -> bool {
+        return self == Anchor::End || self == Anchor::Both;
+    }
+}
 
 /// Represents the result of parsing a glob pattern.
 ///
@@ -65,9 +259,14 @@ pub use glob_parser::GlobParseError;
 ///
 /// This structure is intentionally left opaque. Use the [`try_from`](Self::try_from) method to create an instance of
 /// this type and the [`matches_partially`](Self::matches_partially) method to perform pattern matching.
-#[derive(Debug)]
+/// Use a [`GlobBuilder`] to create patterns with non-default options.
+#[derive(Debug, Clone)]
 pub struct ParsedGlobString<'g> {
     tokens: Vec<Token<'g>>,
+    config: MatchConfig,
+    anchor: Anchor, // used by `is_match`
+    literal_check: LiteralCheck,
+    spans: Vec<Range<usize>>, // one for each token, or none if the tokens were transformed
 }
 
 impl<'g> TryFrom<&'g str> for ParsedGlobString<'g> {
@@ -79,7 +278,10 @@ impl<'g> TryFrom<&'g str> for ParsedGlobString<'g> {
     /// # assert!(pattern.is_ok());
     /// ```
     fn try_from(string: &'g str) -> Result<Self, Self::Error> {
-        return parse_glob_string(string).map(|tokens| ParsedGlobString { tokens: tokens });
+        return parse_glob_string_with_spans(string, Syntax::extended()).map(|(tokens, spans)| {
+            let literal_check = LiteralCheck::new(&tokens);
+            ParsedGlobString { tokens: tokens, config: MatchConfig::default(), anchor: Anchor::None, literal_check: literal_check, spans: spans }
+        });
     }
 }
 
@@ -91,60 +293,738 @@ impl<'g> ParsedGlobString<'g> {
     /// assert!(pattern.matches_partially("My Documents/thesis/thesis-final-2.pdf"));
     /// ```
     pub fn matches_partially(&self, string : &str) -> bool {
+        if !self.can_use_fast_path() {
+            return self.find(string).is_some();
+        }
         return token_sequence_matches_partially(self.tokens.as_slice(), string);
     }
-    // FIXME: implement matches_at_start
-    // FIXME: maybe implement matches_completely and matches_at_end
-}
 
-/// checks if the given pattern occurs anywhere in the given string.
-///
-/// This is a utility function for creating a [`ParsedGlobString`] and calling [`matches_partially`](ParsedGlobString::matches_partially)
-/// on it.
-///
-/// Returns a [`GlobParseError`] if parsing the pattern fails.
-pub fn pattern_matches_partially<'g>(pattern: &'g str, string : &str) -> Result<bool, GlobParseError<'g>> {
-    ParsedGlobString::try_from(pattern).map(|pgs| pgs.matches_partially(string))
-}
+    /// finds the leftmost occurrence of this pattern in the given string and returns its byte range.
+    /// If the occurrence can end at different positions, the [`WildcardPolicy`] decides which one
+    /// is returned.
+    /// ```
+    /// use glob::ParsedGlobString;
+    /// let pattern = ParsedGlobString::try_from("thesis-*.pdf").unwrap();
+    /// let string = "My Documents/thesis/thesis-final-2.pdf";
+    /// assert_eq!(pattern.find(string), Some(20..38));
+    /// assert_eq!(pattern.find("thesis.txt"), None);
+    /// ```
+    pub fn find(&self, string: &str) -> Option<Range<usize>> {
+        return find_match(self.tokens.as_slice(), string, &self.config);
+    }
+
+    /// returns the smallest byte offset at which an occurrence of this pattern in the given string
+    /// ends, or `None` if it doesn't occur, with the options of this pattern, including its
+    /// anchoring, like [`is_match`](Self::is_match). A stream processor can act on the input up to
+    /// that offset as soon as it is known that there is a match, without determining where
+    /// the match starts or how far [`find`](Self::find) would extend it.
+    /// ```
+    /// use glob::{GlobBuilder, WildcardPolicy};
+    /// let pattern = GlobBuilder::new("ERROR*;").wildcard_policy(WildcardPolicy::Greedy).build().unwrap();
+    /// let log = "INFO start; ERROR disk; ERROR net;";
+    /// assert_eq!(pattern.shortest_match(log), Some(23));
+    /// assert_eq!(pattern.find(log), Some(12..34));
+    /// assert_eq!(pattern.shortest_match("ERROR disk"), None);
+    /// ```
+    pub fn shortest_match(&self, string: &str) -> Option<usize> {
+        if self.literal_check.rules_out(self.tokens.as_slice(), string, self.anchor, &self.config) {
+            return None;
+        }
+        return shortest_match_end(self.tokens.as_slice(), string, self.anchor.is_anchored_at_start(), self.anchor.is_anchored_at_end(), &self.config);
+    }
+
+    /// checks if this pattern occurs within the byte range `range` of the given string, like
+    /// [`matches_partially`](Self::matches_partially) checks it for `&string[range]`, but without
+    /// slicing: a range that is out of bounds or doesn't start and end at character boundaries
+    /// contains no occurrence instead of causing a panic. Word boundaries at the ends of the range
+    /// are checked against the characters around it, so a range can start or end in the middle of
+    /// a word.
+    /// ```
+    /// use glob::ParsedGlobString;
+    /// let pattern = ParsedGlobString::try_from("\\bid=?*").unwrap();
+    /// let text = "uid=1\nid=2";
+    /// assert!(!pattern.matches_partially_in(text, 0..5));
+    /// assert!(pattern.matches_partially_in(text, 6..10));
+    /// assert!(!pattern.matches_partially_in(text, 7..10));
+    /// assert!(!pattern.matches_partially_in("größe", 0..3));
+    /// ```
+    pub fn matches_partially_in(&self, string: &str, range: Range<usize>) -> bool {
+        return self.captures_in_range(string, range).is_some();
+    }
+
+    /// finds the leftmost occurrence of this pattern within the byte range `range` of the given
+    /// string, see [`matches_partially_in`](Self::matches_partially_in), and returns its byte
+    /// range in the whole string.
+    /// ```
+    /// use glob::ParsedGlobString;
+    /// let pattern = ParsedGlobString::try_from("v?.?").unwrap();
+    /// assert_eq!(pattern.find_in_range("v1.0 v2.1", 1..9), Some(5..9));
+    /// assert_eq!(pattern.find_in_range("v1.0 v2.1", 5..8), None);
+    /// ```
+    pub fn find_in_range(&self, string: &str, range: Range<usize>) -> Option<Range<usize>> {
+        return self.captures_in_range(string, range).map(|captures| captures.range());
+    }
+
+    /// like [`find_in_range`](Self::find_in_range), but returns the parts of the string matched
+    /// by the wildcards, too.
+    pub fn captures_in_range<'s>(&self, string: &'s str, range: Range<usize>) -> Option<Captures<'s>> {
+        if range.start > range.end || !string.is_char_boundary(range.start) || !string.is_char_boundary(range.end) {
+            return None;
+        }
+        let (range, wildcard_ranges) = find_match_in_range(self.tokens.as_slice(), string, range, &self.config)?;
+        return Some(Captures::new(string, range, wildcard_ranges));
+    }
+
+    /// finds the occurrence of this pattern in the given string that starts last and returns its
+    /// byte range. The search starts at the end of the string, so the last occurrence is found
+    /// without looking at the earlier ones. Of the occurrences starting at the same position, the
+    /// [`WildcardPolicy`] decides which one is found, like with [`find`](Self::find).
+    /// ```
+    /// use glob::ParsedGlobString;
+    /// let timestamp = ParsedGlobString::try_from("??:??:??").unwrap();
+    /// let line = "started 09:14:02, retried 09:15:40, failed 09:17:03";
+    /// assert_eq!(timestamp.rfind(line), Some(43..51));
+    /// assert_eq!(timestamp.find(line), Some(8..16));
+    /// ```
+    pub fn rfind(&self, string: &str) -> Option<Range<usize>> {
+        return find_last_match_with_captures(self.tokens.as_slice(), string, string.len(), false, &self.config).map(|(range, _)| range);
+    }
+
+    /// finds the leftmost occurrence of this pattern in the given string like [`find`](Self::find),
+    /// but gives up after `max_steps` steps of the matcher. Use this for patterns or strings from
+    /// untrusted sources. It overrides a step budget set with [`GlobBuilder::step_budget`].
+    /// ```
+    /// use glob::ParsedGlobString;
+    /// let pattern = ParsedGlobString::try_from("*a*a*a*a*b").unwrap();
+    /// let haystack = "a".repeat(1000);
+    /// assert!(pattern.match_with_budget(&haystack, 100_000).is_err());
+    /// assert_eq!(pattern.match_with_budget("aaaab", 100_000), Ok(Some(0..5)));
+    /// ```
+    pub fn match_with_budget(&self, string: &str, max_steps: u64) -> Result<Option<Range<usize>>, BudgetExceeded> {
+        let found = find_match_with_budget(self.tokens.as_slice(), string, 0, false, false, &self.config, Some(max_steps))?;
+        return Ok(found.map(|(range, _)| range));
+    }
+
+    /// finds the leftmost occurrence of this pattern in the given string, like [`find`](Self::find),
+    /// and returns the parts of the string matched by the wildcards as well.
+    /// ```
+    /// use glob::ParsedGlobString;
+    /// let pattern = ParsedGlobString::try_from("thesis-*.pdf").unwrap();
+    /// let captures = pattern.captures("My Documents/thesis/thesis-final-2.pdf").unwrap();
+    /// assert_eq!(captures.get(0), Some("final-2"));
+    /// ```
+    pub fn captures<'s>(&self, string: &'s str) -> Option<Captures<'s>> {
+        return self.captures_anchored(string, Anchor::None);
+    }
+
+    /// like [`captures`](Self::captures), but only finds matches that are anchored as requested.
+    /// ```
+    /// use glob::{Anchor, ParsedGlobString};
+    /// let pattern = ParsedGlobString::try_from("*.rs").unwrap();
+    /// assert_eq!(pattern.captures_anchored("src/lib.rs", Anchor::Both).unwrap().get(0), Some("src/lib"));
+    /// assert!(pattern.captures_anchored("src/lib.rs.orig", Anchor::Both).is_none());
+    /// ```
+    pub fn captures_anchored<'s>(&self, string: &'s str, anchor: Anchor) -> Option<Captures<'s>> {
+        return self.captures_with_config(string, anchor, &self.config);
+    }
+
+    pub(crate) fn captures_with_config<'s>(&self, string: &'s str, anchor: Anchor, config: &MatchConfig) -> Option<Captures<'s>> {
+        if self.literal_check.rules_out(self.tokens.as_slice(), string, anchor, config) {
+            return None;
+        }
+        let (range, wildcard_ranges) = find_match_with_captures(self.tokens.as_slice(), string, anchor.is_anchored_at_start(), anchor.is_anchored_at_end(), config)?;
+        return Some(Captures::new(string, range, wildcard_ranges));
+    }
+
+    /// iterates over the byte ranges of the non-overlapping occurrences of this pattern in the
+    /// given string, from left to right. After an occurrence, the search continues at its end, or
+    /// one character further if it is empty.
+    /// ```
+    /// use glob::ParsedGlobString;
+    /// let pattern = ParsedGlobString::try_from("v?.?").unwrap();
+    /// let ranges: Vec<_> = pattern.find_iter("v1.0, v1.1 and v2.0").collect();
+    /// assert_eq!(ranges, vec![0..4, 6..10, 15..19]);
+    /// ```
+    pub fn find_iter<'p, 's>(&'p self, string: &'s str) -> FindIter<'p, 'g, 's> {
+        return FindIter::new(self, string);
+    }
+
+    /// iterates over the byte ranges of non-overlapping occurrences of this pattern in the given
+    /// string, from right to left. Each occurrence is the one that starts last of those that end
+    /// before the previous one starts, see [`rfind`](Self::rfind), so the occurrences may differ
+    /// from the ones [`find_iter`](Self::find_iter) finds if they could overlap.
+    /// ```
+    /// use glob::ParsedGlobString;
+    /// let pattern = ParsedGlobString::try_from("v?.?").unwrap();
+    /// let ranges: Vec<_> = pattern.rfind_iter("v1.0, v1.1 and v2.0").collect();
+    /// assert_eq!(ranges, vec![15..19, 6..10, 0..4]);
+    /// ```
+    pub fn rfind_iter<'p, 's>(&'p self, string: &'s str) -> RFindIter<'p, 'g, 's> {
+        return RFindIter::new(self, string);
+    }
+
+    /// like [`find_iter`](Self::find_iter), but yields the matched parts of the string instead of
+    /// their byte ranges.
+    /// ```
+    /// use glob::ParsedGlobString;
+    /// let pattern = ParsedGlobString::try_from("v?.?").unwrap();
+    /// let versions: Vec<_> = pattern.matched_slices("v1.0, v1.1 and v2.0").collect();
+    /// assert_eq!(versions, vec!["v1.0", "v1.1", "v2.0"]);
+    /// ```
+    pub fn matched_slices<'p, 's>(&'p self, string: &'s str) -> impl Iterator<Item = &'s str> + 'p
+    where 's: 'p {
+        return self.find_iter(string).map(move |range| &string[range]);
+    }
+
+    /// checks if the simpler matcher that only answers whether there is a match can be used.
+    fn can_use_fast_path(&self) -> bool {
+        return self.config.allows_fast_path() && supports_fast_path(self.tokens.as_slice());
+    }
+
+    pub(crate) fn tokens(&self) -> &[Token<'g>] {
+        return self.tokens.as_slice();
+    }
+
+    pub(crate) fn config(&self) -> &MatchConfig {
+        return &self.config;
+    }
+
+    /// checks if this pattern occurs anywhere in the given string with at most `max_edits` characters
+    /// inserted, deleted or substituted in its literal parts, e.g. for a file finder that forgives
+    /// typos. Wildcards match as usual and never absorb edits.
+    /// ```
+    /// use glob::ParsedGlobString;
+    /// let pattern = ParsedGlobString::try_from("*/config.*").unwrap();
+    /// assert!(pattern.matches_fuzzy("src/confg.rs", 1));
+    /// assert!(!pattern.matches_fuzzy("src/cnofig.rs", 1));
+    /// assert!(pattern.matches_fuzzy("src/cnofig.rs", 2));
+    /// ```
+    /// Fuzzy matching always steps through the string character by character and ignores the
+    /// [`WildcardPolicy`] and step budget of the pattern; the other options apply.
+    pub fn matches_fuzzy(&self, string: &str, max_edits: usize) -> bool {
+        return fuzzy_distance(self.tokens.as_slice(), string, &self.config).is_some_and(|edits| edits <= max_edits);
+    }
+
+    /// returns a relevance score if this pattern occurs in the given string, so candidates can be
+    /// sorted by how well they match instead of just being filtered. Matches that start earlier,
+    /// that need fewer characters for the wildcards and whose literals are contiguous score higher.
+    /// ```
+    /// use glob::ParsedGlobString;
+    /// let pattern = ParsedGlobString::try_from("main*rs").unwrap();
+    /// let mut files = vec!["src/bin/main_helpers.rs", "src/main.rs", "main.rs", "README.md"];
+    /// files.retain(|file| pattern.score(file).is_some());
+    /// files.sort_by_key(|file| std::cmp::Reverse(pattern.score(file)));
+    /// assert_eq!(files, vec!["main.rs", "src/main.rs", "src/bin/main_helpers.rs"]);
+    /// ```
+    /// The score is only meant for comparing the results of one pattern; how exactly it is computed
+    /// may change between versions.
+    pub fn score(&self, string: &str) -> Option<u32> {
+        return relevance_score(self.tokens.as_slice(), string, &self.config);
+    }
+
+    /// checks if some string that starts with `partial` matches this pattern completely, i.e. if
+    /// typing more characters could still lead to a match. Use it to prune candidates in an
+    /// autocomplete filter, or to stop scanning a line as soon as it can't match anymore.
+    /// ```
+    /// use glob::ParsedGlobString;
+    /// let pattern = ParsedGlobString::try_from("src/*.rs").unwrap();
+    /// assert!(pattern.could_match_with_more_input("src/ma"));
+    /// assert!(pattern.could_match_with_more_input("src/main.rs"));
+    /// assert!(!pattern.could_match_with_more_input("tests/"));
+    /// ```
+    /// Like [`matches_fuzzy`](Self::matches_fuzzy), this steps through `partial` character by
+    /// character regardless of the [`MatchUnit`].
+    pub fn could_match_with_more_input(&self, partial: &str) -> bool {
+        return could_be_completed(self.tokens.as_slice(), partial, &self.config);
+    }
+
+    /// checks if this pattern matches every string that `other` matches, so `other` is redundant
+    /// next to this pattern. Both patterns are interpreted like [`is_match`](Self::is_match) does,
+    /// including their anchoring and options.
+    /// ```
+    /// use glob::ParsedGlobString;
+    /// let rust_files = ParsedGlobString::try_from("*.rs").unwrap();
+    /// let main_files = ParsedGlobString::try_from("main*.rs").unwrap();
+    /// assert!(rust_files.subsumes(&main_files));
+    /// assert!(!main_files.subsumes(&rust_files));
+    /// ```
+    pub fn subsumes(&self, other: &ParsedGlobString<'_>) -> bool {
+        return find_difference(&[Automaton::new(other)], &[Automaton::new(self)]).is_none();
+    }
+
+    /// removes the parts of this pattern that can't change whether it matches a string with the
+    /// anchoring of its options, e.g. a leading `*` if the match may start anywhere anyway.
+    /// Matching gets faster and the [displayed](Display) pattern gets simpler.
+    /// ```
+    /// use glob::ParsedGlobString;
+    /// let pattern = ParsedGlobString::try_from("*ERROR?*").unwrap().optimize();
+    /// assert_eq!(pattern.to_string(), "ERROR?");
+    /// assert!(pattern.is_match("[ERROR] disk full"));
+    /// ```
+    /// The optimized pattern matches the same strings as this one with [`is_match`](Self::is_match),
+    /// but [`find`](Self::find) and [`captures`](Self::captures) report different ranges, since the
+    /// removed wildcards are no longer part of the match.
+    pub fn optimize(self) -> Self {
+        let tokens = optimize_tokens(self.tokens, self.anchor);
+        let literal_check = LiteralCheck::new(&tokens);
+        return ParsedGlobString { tokens: tokens, config: self.config, anchor: self.anchor, literal_check: literal_check, spans: Vec::new() };
+    }
+
+    /// returns the bytes that an occurrence of this pattern can start with, in ascending order, or
+    /// `None` if it can start with any byte or be empty. [`find_iter`](Self::find_iter) uses them
+    /// to skip the positions where no occurrence can start, and other code can do the same, e.g.
+    /// to find candidate lines in a large buffer with a fast byte search.
+    /// ```
+    /// use glob::{GlobBuilder, ParsedGlobString};
+    /// assert_eq!(ParsedGlobString::try_from("#*;").unwrap().starting_bytes(), Some(vec![b'#']));
+    /// assert_eq!(ParsedGlobString::try_from("*;").unwrap().starting_bytes(), None);
+    /// assert_eq!(GlobBuilder::new("#*;").case_insensitive(true).build().unwrap().starting_bytes(), None);
+    /// ```
+    /// Only a leading literal limits the first byte, and only if the pattern isn't matched with
+    /// case folding.
+    pub fn starting_bytes(&self) -> Option<Vec<u8>> {
+        return starting_bytes(self.tokens.as_slice(), &self.config);
+    }
+
+    /// returns a description of the compiled pattern, one line per token with its index, its kind
+    /// and its argument, for debugging and snapshot tests. Unlike the `Debug` output, this format
+    /// doesn't change with the internal representation of patterns:
+    /// ```
+    /// use glob::ParsedGlobString;
+    /// let pattern = ParsedGlobString::try_from("*\\bmain?.rs").unwrap();
+    /// assert_eq!(pattern.dump(), "\
+    /// 0 min-length-wildcard 0
+    /// 1 word-boundary
+    /// 2 literal \"main\"
+    /// 3 exact-length-wildcard 1
+    /// 4 literal \".rs\"
+    /// ");
+    /// ```
+    /// Literals are quoted and escaped like Rust string literals. Consecutive wildcards are merged,
+    /// so `*?*` is a single `min-length-wildcard 1`.
+    pub fn dump(&self) -> String {
+        let mut dump = String::new();
+        for (index, token) in self.tokens.iter().enumerate() {
+            let line = match token {
+                Token::ExactLengthWildcard(length) => format!("{} exact-length-wildcard {}\n", index, length),
+                Token::MinLengthWildcard(length) => format!("{} min-length-wildcard {}\n", index, length),
+                Token::Literal(literal) => format!("{} literal {:?}\n", index, literal.chars().collect::<String>()),
+                Token::WordBoundary => format!("{} word-boundary\n", index),
+            };
+            dump.push_str(&line);
+        }
+        return dump;
+    }
+
+    /// returns the byte range in the pattern string of each token, in the order of the lines of
+    /// [`dump`](Self::dump), so editors can highlight the parts of a pattern or underline them.
+    /// Escape sequences belong to the literal they are part of, and merged wildcards share one
+    /// range:
+    /// ```
+    /// use glob::ParsedGlobString;
+    /// let pattern = ParsedGlobString::try_from("*\\bmain?*.rs").unwrap();
+    /// assert_eq!(pattern.token_spans(), Some(&[0..1, 1..3, 3..7, 7..9, 9..12][..]));
+    /// ```
+    /// Returns `None` if the tokens no longer correspond to the pattern string, e.g. after
+    /// [`optimize`](Self::optimize) or for a pattern built with [`GlobBuilder::repeat`].
+    pub fn token_spans(&self) -> Option<&[Range<usize>]> {
+        if self.spans.len() != self.tokens.len() {
+            return None;
+        }
+        return Some(&self.spans);
+    }
+
+    /// counts the non-overlapping occurrences of this pattern in the given string, i.e. the items
+    /// [`find_iter`](Self::find_iter) would yield.
+    /// ```
+    /// use glob::ParsedGlobString;
+    /// let pattern = ParsedGlobString::try_from("a?a").unwrap();
+    /// assert_eq!(pattern.count("ananas and bananas"), 2);
+    /// ```
+    pub fn count(&self, string: &str) -> usize {
+        return self.count_at_most(string, usize::MAX);
+    }
+
+    /// like [`count`](Self::count), but stops searching once `limit` occurrences are found, so
+    /// checking for at least `limit` occurrences doesn't scan the rest of the string.
+    /// ```
+    /// use glob::ParsedGlobString;
+    /// let pattern = ParsedGlobString::try_from("ERROR").unwrap();
+    /// let log = "ERROR 1\nINFO 2\nERROR 3\nERROR 4";
+    /// assert_eq!(pattern.count_at_most(log, 2), 2);
+    /// assert!(pattern.count_at_most(log, 5) < 5);
+    /// ```
+    pub fn count_at_most(&self, string: &str, limit: usize) -> usize {
+        return self.find_iter(string).take(limit).count();
+    }
 
-fn token_sequence_matches_at_start<'g>(token_sequence: &[Token<'g>], string: &str) -> bool {
-    match token_sequence.split_first() {
-        Option::None => true,
-        Option::Some((token, rest)) => match token {
-            ExactLengthWildcard(length) => {
-                string.len() >= *length && token_sequence_matches_at_start(rest, &string[*length..])
-            },
-            Literal(literal) => {
-                literal.matches_string_start(string) && token_sequence_matches_at_start(rest, &string[literal.get_combined_length()..])
-            },
-            MinLengthWildcard(length) => {
-                // FIXME: try matching from the back
-                string.len() >= *length && token_sequence_matches_partially(rest, &string[*length..])
+    /// copies all borrowed parts of the pattern, so the result is no longer limited by the
+    /// lifetime of the pattern string.
+    /// ```
+    /// # use glob::ParsedGlobString;
+    /// let pattern = {
+    ///     let pattern_string = String::from("*.rs");
+    ///     ParsedGlobString::try_from(pattern_string.as_str()).unwrap().into_owned()
+    /// };
+    /// assert!(pattern.matches_partially("src/lib.rs"));
+    /// ```
+    pub fn into_owned(self) -> ParsedGlobString<'static> {
+        return ParsedGlobString { tokens: self.tokens.into_iter().map(Token::into_owned).collect(), config: self.config, anchor: self.anchor, literal_check: self.literal_check, spans: self.spans };
+    }
+
+    /// appends `literal` to the end of this pattern, so it matches the characters of `literal`
+    /// exactly, including `*`, `?` and `\`. Query builders can grow a pattern one segment at a
+    /// time without escaping the segments and parsing the joined pattern string again. A literal
+    /// right after another one is merged with it, like the parser merges them.
+    /// ```
+    /// use glob::{ParsedGlobString, WildcardKind};
+    /// let mut pattern = ParsedGlobString::try_from("src/").unwrap();
+    /// pattern.push_wildcard(WildcardKind::Star);
+    /// pattern.push_literal("_test?.rs");
+    /// assert_eq!(pattern.to_string(), "src/*_test\\?.rs");
+    /// assert!(pattern.matches_completely("src/parser_test?.rs"));
+    /// assert!(!pattern.matches_completely("src/parser_test1.rs"));
+    /// ```
+    /// The tokens no longer correspond to the pattern string afterwards, so
+    /// [`token_spans`](Self::token_spans) returns `None`.
+    pub fn push_literal(&mut self, literal: &str) {
+        if literal.is_empty() {
+            return;
+        }
+        self.push_token(Token::Literal(MultiSlice::from(literal).into_owned()));
+    }
+
+    /// appends a wildcard to the end of this pattern, like [`push_literal`](Self::push_literal)
+    /// appends a literal. Wildcards right after other wildcards are merged with them, so pushing
+    /// `?` and then `*` gives the pattern `?*`, which matches at least one character.
+    pub fn push_wildcard(&mut self, kind: WildcardKind) {
+        self.push_token(match kind {
+            WildcardKind::Star => Token::MinLengthWildcard(0),
+            WildcardKind::QuestionMark => Token::ExactLengthWildcard(1),
+        });
+    }
+
+    fn push_token(&mut self, token: Token<'static>) {
+        append_token(&mut self.tokens, &mut self.spans, token, 0..0);
+        self.spans.clear();
+        self.literal_check = LiteralCheck::new(&self.tokens);
+    }
+
+    /// parses the given `string` like [`try_from`](Self::try_from), but into `arena`, see
+    /// [`GlobBuilder::build_in`]. Requires the `arena` feature.
+    /// ```
+    /// use bumpalo::Bump;
+    /// use glob::ParsedGlobString;
+    /// let arena = Bump::new();
+    /// let pattern = ParsedGlobString::parse_in(&arena, &String::from("*.rs")).unwrap();
+    /// assert!(pattern.matches_partially("src/lib.rs"));
+    /// ```
+    #[cfg(feature = "arena")]
+    pub fn parse_in(arena: &'g bumpalo::Bump, string: &str) -> Result<Self, GlobParseError<'g>> {
+        return GlobBuilder::new(string).build_in(arena);
+    }
+
+    /// returns an estimate of the number of bytes this pattern occupies in memory, including the
+    /// heap allocations it owns, so caches can limit how much memory their compiled patterns take.
+    /// A pattern that borrows from its pattern string doesn't count that string.
+    /// ```
+    /// use glob::ParsedGlobString;
+    /// let small = ParsedGlobString::try_from("*.rs").unwrap();
+    /// let large = ParsedGlobString::try_from("src/*/tests/*_test.rs").unwrap();
+    /// assert!(large.memory_usage() > small.memory_usage());
+    /// ```
+    pub fn memory_usage(&self) -> usize {
+        let tokens = self.tokens.iter().map(|token| match token {
+            Token::Literal(literal) => literal.heap_size(),
+            _ => 0,
+        });
+        return std::mem::size_of::<Self>()
+            + self.tokens.capacity() * std::mem::size_of::<Token<'g>>()
+            + tokens.sum::<usize>()
+            + self.spans.capacity() * std::mem::size_of::<Range<usize>>();
+    }
+
+    /// checks if this pattern matches the given string, anchored as requested. This selects one of
+    /// [`matches_partially`](Self::matches_partially), [`matches_at_start`](Self::matches_at_start),
+    /// [`matches_at_end`](Self::matches_at_end) and [`matches_completely`](Self::matches_completely),
+    /// e.g. depending on a command line flag.
+    /// ```
+    /// use glob::{Anchor, ParsedGlobString};
+    /// let pattern = ParsedGlobString::try_from("*.log").unwrap();
+    /// let anchor = if std::env::args().any(|arg| arg == "--whole") { Anchor::Both } else { Anchor::None };
+    /// assert!(pattern.matches_anchored("app.log", anchor));
+    /// assert!(!pattern.matches_anchored("app.log.1", Anchor::End));
+    /// ```
+    pub fn matches_anchored(&self, string: &str, anchor: Anchor) -> bool {
+        return match anchor {
+            Anchor::None => self.matches_partially(string),
+            Anchor::Start => self.matches_at_start(string),
+            Anchor::End => self.matches_at_end(string),
+            Anchor::Both => self.matches_completely(string),
+        };
+    }
+
+    /// checks if this pattern matches the given string with the given options, which replace the
+    /// options of this pattern for this call.
+    ///
+    /// The simpler methods like [`matches_partially`](Self::matches_partially) use the options of
+    /// the pattern with a fixed [`Anchor`].
+    /// ```
+    /// use glob::{Anchor, MatchOptions, ParsedGlobString};
+    /// let pattern = ParsedGlobString::try_from("*.md").unwrap();
+    /// let options = MatchOptions { anchor: Anchor::End, ..MatchOptions::default() };
+    /// assert!(pattern.match_with("README.md", &options));
+    /// assert!(!pattern.match_with("README.md.orig", &options));
+    /// ```
+    pub fn match_with(&self, string: &str, options: &MatchOptions) -> bool {
+        return self.captures_with_config(string, options.anchor, &options.apply_to(self.config)).is_some();
+    }
+
+    /// checks if this pattern matches the given string with the options of this pattern, including
+    /// its anchoring (see [`GlobBuilder::options`]). Patterns that were not built with options
+    /// match anywhere in the string, like with [`matches_partially`](Self::matches_partially).
+    pub fn is_match(&self, string: &str) -> bool {
+        return self.match_with(string, &self.options());
+    }
+
+    /// checks if this pattern matches the given string like [`is_match`](Self::is_match) does,
+    /// but uses the buffers of `cache` instead of allocating new ones, for matching many strings
+    /// in a hot loop.
+    pub fn matches_with_cache(&self, string: &str, cache: &mut Cache) -> bool {
+        return self.captures_with_cache(string, cache).is_some();
+    }
+
+    /// finds the match of this pattern in the given string with the options of this pattern,
+    /// including its anchoring, like [`matches_with_cache`](Self::matches_with_cache), and returns
+    /// its byte range. The ranges of the parts matched by the wildcards, which
+    /// [`captures`](Self::captures) would return, are left in `cache` until the next match:
+    /// ```
+    /// use glob::{Cache, ParsedGlobString};
+    /// let pattern = ParsedGlobString::try_from("v?.*").unwrap();
+    /// let mut cache = Cache::new();
+    /// assert_eq!(pattern.captures_with_cache("see v2.10", &mut cache), Some(4..7));
+    /// assert_eq!(cache.capture_ranges(), &[5..6, 7..7]);
+    /// assert_eq!(pattern.captures_with_cache("no version", &mut cache), None);
+    /// ```
+    pub fn captures_with_cache(&self, string: &str, cache: &mut Cache) -> Option<Range<usize>> {
+        if self.literal_check.rules_out(self.tokens.as_slice(), string, self.anchor, &self.config) {
+            return None;
+        }
+        let anchor_start = self.anchor.is_anchored_at_start();
+        let anchor_end = self.anchor.is_anchored_at_end();
+        return find_match_with_cache(self.tokens.as_slice(), string, 0, anchor_start, anchor_end, &self.config, self.config.step_budget, cache).unwrap_or(None);
+    }
+
+    /// finds the leftmost occurrence of this pattern in a string, byte string or OS string, like
+    /// [`find`](Self::find) does in a string, and returns its byte range. Bytes that aren't part of
+    /// valid UTF-8 are matched by wildcards one byte at a time, but never by literals.
+    /// ```
+    /// use std::ffi::OsStr;
+    /// use glob::ParsedGlobString;
+    /// let pattern = ParsedGlobString::try_from("*.rs").unwrap();
+    /// assert_eq!(pattern.find_in(&b"lib\xff.rs"[..]), Some(0..7));
+    /// assert_eq!(pattern.find_in(OsStr::new("src/main.rs")), Some(0..11));
+    /// assert_eq!(pattern.find_in("main.rs"), pattern.find("main.rs"));
+    /// ```
+    pub fn find_in<H: Haystack + ?Sized>(&self, haystack: &H) -> Option<Range<usize>> {
+        return find_match(self.tokens.as_slice(), haystack, &self.config);
+    }
+
+    /// checks if this pattern matches a string, byte string or OS string with the options of this
+    /// pattern, including its anchoring, like [`is_match`](Self::is_match) does for a string.
+    /// ```
+    /// use std::path::Path;
+    /// use glob::{Anchor, GlobBuilder, MatchOptions};
+    /// let options = MatchOptions { anchor: Anchor::Both, path_separator: Some('/'), ..MatchOptions::default() };
+    /// let pattern = GlobBuilder::new("src/*.rs").options(&options).build().unwrap();
+    /// assert!(pattern.is_match_in(Path::new("src/lib.rs").as_os_str()));
+    /// assert!(!pattern.is_match_in(Path::new("src/bin/main.rs").as_os_str()));
+    /// ```
+    pub fn is_match_in<H: Haystack + ?Sized>(&self, haystack: &H) -> bool {
+        let tokens = self.tokens.as_slice();
+        if self.literal_check.rules_out(tokens, haystack, self.anchor, &self.config) {
+            return false;
+        }
+        return find_match_with_captures(tokens, haystack, self.anchor.is_anchored_at_start(), self.anchor.is_anchored_at_end(), &self.config).is_some();
+    }
+
+    /// checks if this pattern matches any of the given strings, like [`is_match`](Self::is_match),
+    /// and stops at the first one that matches. What all matching strings have in common, like their
+    /// minimum length and the bytes they must contain, is worked out once, so strings that can't
+    /// match are skipped quickly.
+    /// ```
+    /// use glob::ParsedGlobString;
+    /// let pattern = ParsedGlobString::try_from("*.lock").unwrap();
+    /// assert!(pattern.matches_any(["Cargo.toml", "Cargo.lock", "src/main.rs"]));
+    /// assert!(!pattern.matches_any(vec!["README.md"]));
+    /// ```
+    pub fn matches_any<'s>(&self, haystacks: impl IntoIterator<Item = &'s str>) -> bool {
+        let prefilter = Prefilter::new(self.tokens.as_slice(), &self.config);
+        let options = self.options();
+        return haystacks.into_iter().any(|haystack| !prefilter.rules_out(haystack) && self.match_with(haystack, &options));
+    }
+
+    /// checks if this pattern matches any of the given strings, like
+    /// [`matches_any`](Self::matches_any), but stops with [`Cancelled`] once `cancel` is set. The
+    /// flag is checked before each string, so a UI can abort matching a large batch promptly.
+    /// ```
+    /// use std::sync::atomic::AtomicBool;
+    /// use glob::{Cancelled, ParsedGlobString};
+    /// let pattern = ParsedGlobString::try_from("*.lock").unwrap();
+    /// assert_eq!(pattern.matches_any_cancellable(["Cargo.lock"], &AtomicBool::new(false)), Ok(true));
+    /// assert_eq!(pattern.matches_any_cancellable(["Cargo.lock"], &AtomicBool::new(true)), Err(Cancelled));
+    /// ```
+    pub fn matches_any_cancellable<'s>(&self, haystacks: impl IntoIterator<Item = &'s str>, cancel: &AtomicBool) -> Result<bool, Cancelled> {
+        let prefilter = Prefilter::new(self.tokens.as_slice(), &self.config);
+        let options = self.options();
+        for haystack in haystacks {
+            if cancel.load(Ordering::Relaxed) {
+                return Err(Cancelled);
             }
+            if !prefilter.rules_out(haystack) && self.match_with(haystack, &options) {
+                return Ok(true);
+            }
+        }
+        return Ok(false);
+    }
+
+    /// returns the options of this pattern.
+    pub fn options(&self) -> MatchOptions {
+        return MatchOptions::from_config(&self.config, self.anchor);
+    }
+
+    /// checks if this pattern matches the given string as a whole.
+    /// ```
+    /// use glob::ParsedGlobString;
+    /// let pattern = ParsedGlobString::try_from("*.pdf").unwrap();
+    /// assert!(pattern.matches_completely("thesis/thesis-final-2.pdf"));
+    /// assert!(!pattern.matches_completely("thesis/thesis-final-2.pdf.bak"));
+    /// ```
+    pub fn matches_completely(&self, string: &str) -> bool {
+        if self.literal_check.rules_out(self.tokens.as_slice(), string, Anchor::Both, &self.config) {
+            return false;
         }
+        return token_sequence_matches_completely(self.tokens.as_slice(), string, &self.config);
+    }
+
+    /// checks if the given string starts with this pattern.
+    /// ```
+    /// use glob::ParsedGlobString;
+    /// let pattern = ParsedGlobString::try_from("ERROR*:").unwrap();
+    /// assert!(pattern.matches_at_start("ERROR [disk]: full"));
+    /// assert!(!pattern.matches_at_start("[disk] ERROR: full"));
+    /// ```
+    pub fn matches_at_start(&self, string: &str) -> bool {
+        if !self.can_use_fast_path() {
+            return self.captures_anchored(string, Anchor::Start).is_some();
+        }
+        return token_sequence_matches_at_start(self.tokens.as_slice(), string);
+    }
+
+    /// checks if the given string ends with this pattern.
+    /// ```
+    /// use glob::ParsedGlobString;
+    /// let pattern = ParsedGlobString::try_from(".y?ml").unwrap();
+    /// assert!(pattern.matches_at_end("path/to/foo.yaml"));
+    /// assert!(!pattern.matches_at_end("path/to/foo.yaml.bak"));
+    /// ```
+    pub fn matches_at_end(&self, string: &str) -> bool {
+        if self.literal_check.rules_out(self.tokens.as_slice(), string, Anchor::End, &self.config) {
+            return false;
+        }
+        return token_sequence_matches_at_end(self.tokens.as_slice(), string, &self.config);
     }
 }
 
-fn token_sequence_matches_partially(tokens: &[Token], string : &str) -> bool {
-    match tokens.split_first() {
-        Option::None => true,
-        Option::Some((token, rest)) => match token {
-            MinLengthWildcard(length) | ExactLengthWildcard(length) => {
-                string.len() >= *length && token_sequence_matches_partially(rest, &string[*length..])
-            },
-            Literal(literal) => {
-                // FIXME: try matching from the end
-                for m in literal.find_all_occurences_in(string) {
-                    if token_sequence_matches_at_start(rest,&string[m + literal.get_combined_length()..]) {
-                        return true
+impl<'g> Display for ParsedGlobString<'g> {
+    /// writes the pattern in the syntax it was parsed from, as compiled: consecutive wildcards are
+    /// normalized to `?` followed by at most one `*`, and only `*`, `?` and `\` are escaped.
+    /// ```
+    /// use glob::ParsedGlobString;
+    /// let pattern = ParsedGlobString::try_from("*?**a\\*\\bc").unwrap();
+    /// assert_eq!(pattern.to_string(), "?*a\\*\\bc");
+    /// ```
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for token in &self.tokens {
+            match token {
+                Token::ExactLengthWildcard(length) => write!(f, "{}", "?".repeat(*length))?,
+                Token::MinLengthWildcard(length) => write!(f, "{}*", "?".repeat(*length))?,
+                Token::WordBoundary => write!(f, "\\b")?,
+                Token::Literal(literal) => {
+                    for c in literal.chars() {
+                        if matches!(c, '*' | '?' | '\\') {
+                            write!(f, "\\")?;
+                        }
+                        write!(f, "{}", c)?;
                     }
-                }
-                return false
+                },
             }
         }
+        return Ok(());
+    }
+}
+
+/// anything that decides whether a string matches, like a [`ParsedGlobString`] or a [`GlobSet`].
+///
+/// Functions like [`filter_lines`] take a matcher, so they work with single patterns and pattern
+/// sets alike. Matchers can be combined with [`or`](Self::or), [`and`](Self::and),
+/// [`negate`](Self::negate), [`any`] and [`all`]:
+/// ```
+/// use glob::{Matcher, ParsedGlobString};
+/// let pattern = |pattern| ParsedGlobString::try_from(pattern).unwrap();
+/// let sources = pattern("*.rs").or(pattern("*.toml")).and(pattern("target/*").negate());
+/// assert!(sources.is_match("src/lib.rs"));
+/// assert!(!sources.is_match("target/build.rs"));
+/// ```
+pub trait Matcher {
+    /// checks if the given string matches.
+    fn is_match(&self, haystack: &str) -> bool;
+
+    /// returns a matcher that matches if this matcher or `other` matches. `other` is only
+    /// evaluated if this matcher doesn't match.
+    fn or<M: Matcher>(self, other: M) -> Or<Self, M> where Self: Sized {
+        return Or::new(self, other);
+    }
+
+    /// returns a matcher that matches if this matcher and `other` match. `other` is only
+    /// evaluated if this matcher matches.
+    fn and<M: Matcher>(self, other: M) -> And<Self, M> where Self: Sized {
+        return And::new(self, other);
+    }
+
+    /// returns a matcher that matches if this matcher doesn't.
+    fn negate(self) -> Not<Self> where Self: Sized {
+        return Not::new(self);
     }
 }
 
+impl<'g> Matcher for ParsedGlobString<'g> {
+    fn is_match(&self, haystack: &str) -> bool {
+        return ParsedGlobString::is_match(self, haystack);
+    }
+}
+
+impl<M: Matcher + ?Sized> Matcher for &M {
+    fn is_match(&self, haystack: &str) -> bool {
+        return (**self).is_match(haystack);
+    }
+}
+
+impl<M: Matcher + ?Sized> Matcher for Box<M> {
+    fn is_match(&self, haystack: &str) -> bool {
+        return (**self).is_match(haystack);
+    }
+}
+
+/// checks if the given pattern occurs anywhere in the given string.
+///
+/// This is a utility function for creating a [`ParsedGlobString`] and calling [`matches_partially`](ParsedGlobString::matches_partially)
+/// on it.
+///
+/// Returns a [`GlobParseError`] if parsing the pattern fails.
+pub fn pattern_matches_partially<'g>(pattern: &'g str, string : &str) -> Result<bool, GlobParseError<'g>> {
+    ParsedGlobString::try_from(pattern).map(|pgs| pgs.matches_partially(string))
+}
 
 #[cfg(test)]
 mod test {
@@ -455,6 +1335,82 @@ mod test {
         test_matches_partially("thesis*", "path/to/thesis-final-3.pdf")
     }
 
+    #[test]
+    fn test_question_mark_matches_partially_on_non_ascii_character() {
+        test_matches_partially("gr??e", "viele grüße");
+        test_not_matches_partially("gr???e", "viele grüße");
+        test_matches_partially("*ß?", "Straße");
+    }
+
+    #[test]
+    fn test_find_returns_range_of_match() {
+        let pgs = ParsedGlobString::try_from("*.json").unwrap();
+        assert_eq!(pgs.find("path/to/foo.json"), Some(0..16));
+        assert_eq!(pgs.find("path/to/foo.yaml"), None);
+    }
+
+    #[test]
+    fn test_matches_completely() {
+        let pgs = ParsedGlobString::try_from("*.y?ml").unwrap();
+        assert!(pgs.matches_completely("deployment.yaml"));
+        assert!(pgs.matches_completely(".yaml"));
+        assert!(!pgs.matches_completely("deployment.yaml.bak"));
+        assert!(!pgs.matches_completely("old/deployment.yml"));
+    }
+
+    #[test]
+    fn test_matches_completely_needs_backtracking() {
+        let pgs = ParsedGlobString::try_from("*a").unwrap();
+        assert!(pgs.matches_completely("banana"));
+        assert!(!pgs.matches_completely("bananas"));
+        assert!(ParsedGlobString::try_from("").unwrap().matches_completely(""));
+        assert!(!ParsedGlobString::try_from("").unwrap().matches_completely("a"));
+    }
+
+    #[test]
+    fn test_matches_at_start() {
+        let pgs = ParsedGlobString::try_from("ab?").unwrap();
+        assert!(pgs.matches_at_start("abc"));
+        assert!(pgs.matches_at_start("abcd"));
+        assert!(!pgs.matches_at_start("xabc"));
+        assert!(!pgs.matches_at_start("ab"));
+    }
+
+    #[test]
+    fn test_matches_at_end() {
+        let pgs = ParsedGlobString::try_from("?bc").unwrap();
+        assert!(pgs.matches_at_end("abc"));
+        assert!(pgs.matches_at_end("xabc"));
+        assert!(!pgs.matches_at_end("abcd"));
+        assert!(!pgs.matches_at_end("bc"));
+        assert!(ParsedGlobString::try_from("*").unwrap().matches_at_end(""));
+    }
+
+    #[test]
+    fn test_literal_with_non_ascii_character_matches_partially() {
+        test_not_matches_partially("é*x", "éb");
+        test_not_matches_partially("é*x", "abé");
+        test_not_matches_partially("AA*bé", "abé");
+        test_matches_partially("AA*bé", "AA-bé");
+        test_matches_partially("é*x", "ééx");
+    }
+
+    #[test]
+    fn test_literal_with_non_ascii_character_matches_at_end() {
+        let pgs = ParsedGlobString::try_from("é*x").unwrap();
+        assert!(!pgs.matches_at_end("éb"));
+        assert!(!pgs.matches_at_end("abé"));
+        assert!(pgs.matches_at_end("aééx"));
+        assert!(!ParsedGlobString::try_from("AA*bé").unwrap().matches_at_end("abé"));
+    }
+
+    #[test]
+    fn test_parsed_glob_string_is_unwind_safe() {
+        fn assert_unwind_safe<T: std::panic::UnwindSafe + std::panic::RefUnwindSafe>() {}
+        assert_unwind_safe::<ParsedGlobString<'static>>();
+        assert_unwind_safe::<&ParsedGlobString<'static>>();
+    }
+
     #[test]
     fn test_unknown_escape_sequence_creates_globparseerror() {
         let parsed = ParsedGlobString::try_from("\\n");
@@ -470,4 +1426,158 @@ mod test {
         assert_eq!(parsed.unwrap_err(), GlobParseError::UnterminatedEscapeSequence(s.len() - 1));
     }
 
-}
\ No newline at end of file
+
+    #[test]
+    fn test_count_non_overlapping() {
+        let pattern = ParsedGlobString::try_from("aa").unwrap();
+        assert_eq!(pattern.count("aaaaa"), 2);
+        assert_eq!(pattern.count(""), 0);
+    }
+
+    #[test]
+    fn test_count_empty_matches() {
+        let pattern = ParsedGlobString::try_from("*").unwrap();
+        assert_eq!(pattern.count("äbc"), 4);
+        assert_eq!(pattern.count(""), 1);
+    }
+
+    #[test]
+    fn test_count_at_most() {
+        let pattern = ParsedGlobString::try_from("?").unwrap();
+        assert_eq!(pattern.count_at_most("abcdef", 3), 3);
+        assert_eq!(pattern.count_at_most("ab", 3), 2);
+        assert_eq!(pattern.count_at_most("ab", 0), 0);
+    }
+
+    #[test]
+    fn test_word_boundary_in_all_match_modes() {
+        let pattern = ParsedGlobString::try_from("\\bto\\b").unwrap();
+        assert!(pattern.matches_partially("path to file"));
+        assert!(!pattern.matches_partially("path/tofile"));
+        assert!(pattern.matches_at_start("to do"));
+        assert!(!pattern.matches_at_start("todo"));
+        assert!(pattern.matches_at_end("back to"));
+        assert!(pattern.matches_completely("to"));
+    }
+
+    #[test]
+    fn test_dump() {
+        assert_eq!(ParsedGlobString::try_from("").unwrap().dump(), "");
+        let pattern = ParsedGlobString::try_from("a\\*b\\\\*?*\"").unwrap();
+        assert_eq!(pattern.dump(), "0 literal \"a*b\\\\\"\n1 min-length-wildcard 1\n2 literal \"\\\"\"\n");
+    }
+
+    #[test]
+    fn test_matches_with_cache() {
+        use crate::{Cache, GlobBuilder, WildcardPolicy};
+        let mut cache = Cache::new();
+        let patterns = [
+            ParsedGlobString::try_from("a*b?c").unwrap(),
+            GlobBuilder::new("*x*").wildcard_policy(WildcardPolicy::Greedy).build().unwrap(),
+            GlobBuilder::new("a*").leftmost_longest(true).build().unwrap(),
+        ];
+        for pattern in &patterns {
+            for string in ["", "abxc", "xaxbxcx", "a-b-c", "ab", "xx"] {
+                let captures = pattern.captures(string);
+                assert_eq!(pattern.captures_with_cache(string, &mut cache), captures.as_ref().map(|captures| captures.range()), "{} in {}", pattern, string);
+                if let Option::Some(captures) = captures {
+                    let ranges: Vec<_> = (0..captures.len()).map(|index| captures.get_range(index).unwrap()).collect();
+                    assert_eq!(cache.capture_ranges(), ranges.as_slice());
+                }
+                assert_eq!(pattern.matches_with_cache(string, &mut cache), pattern.is_match(string));
+            }
+        }
+    }
+
+    #[test]
+    fn test_matching_in_range_agrees_with_slicing() {
+        let patterns = ["", "a", "*b", "a?*", "?", "b*a"];
+        let string = "abäba";
+        for pattern in patterns {
+            let parsed = ParsedGlobString::try_from(pattern).unwrap();
+            for start in 0..=string.len() + 1 {
+                for end in 0..=string.len() + 1 {
+                    let expected = string.get(start..end).and_then(|slice| parsed.find(slice)).map(|found| found.start + start..found.end + start);
+                    assert_eq!(parsed.find_in_range(string, start..end), expected, "{} in {}..{}", pattern, start, end);
+                }
+            }
+        }
+        let captures = ParsedGlobString::try_from("b*a").unwrap().captures_in_range(string, 1..6).unwrap();
+        assert_eq!(captures.get_range(0), Some(2..5));
+    }
+
+    #[test]
+    fn test_token_spans() {
+        assert_eq!(ParsedGlobString::try_from("").unwrap().token_spans(), Some(&[][..]));
+        let pattern = ParsedGlobString::try_from("ab\\*c*?\\bx").unwrap();
+        assert_eq!(pattern.token_spans(), Some(&[0..5, 5..7, 7..9, 9..10][..]));
+        let pattern = ParsedGlobString::try_from("€?").unwrap();
+        assert_eq!(pattern.token_spans(), Some(&[0..3, 3..4][..]));
+        assert_eq!(ParsedGlobString::try_from("*a*").unwrap().optimize().token_spans(), None);
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        for pattern in ["", "abc", "a\\?b\\\\c\\*", "??*x?", "\\bword\\b", "*"] {
+            assert_eq!(ParsedGlobString::try_from(pattern).unwrap().to_string(), pattern);
+        }
+    }
+
+    #[test]
+    fn test_optimized_patterns_match_the_same_strings() {
+        use crate::{Anchor, GlobBuilder, MatchOptions};
+        let strings = ["", "a", "ab", "xaby", "b a", "aXb", "main.rs", "main.rs.orig"];
+        for pattern in ["*a*", "?*a?*", "*\\ba*b", "**?", "a*b", "*.rs"] {
+            for anchor in [Anchor::None, Anchor::Start, Anchor::End, Anchor::Both] {
+                let options = MatchOptions { anchor: anchor, ..MatchOptions::default() };
+                let original = GlobBuilder::new(pattern).options(&options).build().unwrap();
+                let optimized = original.clone().optimize();
+                for string in strings {
+                    assert_eq!(optimized.is_match(string), original.is_match(string), "{:?} {:?} {:?}", pattern, anchor, string);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_matches_anchored() {
+        use crate::Anchor;
+        let pattern = ParsedGlobString::try_from("a?c").unwrap();
+        let expected = [(Anchor::None, [true, true, true, true]), (Anchor::Start, [true, true, false, false]),
+            (Anchor::End, [true, false, true, false]), (Anchor::Both, [true, false, false, false])];
+        for (anchor, results) in expected {
+            for (string, result) in ["abc", "abc-", "-abc", "-abc-"].into_iter().zip(results) {
+                assert_eq!(pattern.matches_anchored(string, anchor), result, "{:?} {:?}", anchor, string);
+            }
+        }
+    }
+
+    #[test]
+    fn test_memory_usage() {
+        let short = ParsedGlobString::try_from("*.rs").unwrap();
+        let long = ParsedGlobString::try_from("src/*/mod.rs").unwrap();
+        assert!(short.memory_usage() >= std::mem::size_of::<ParsedGlobString<'_>>());
+        assert!(long.memory_usage() > short.memory_usage());
+        let literal = "x".repeat(1000);
+        let borrowed = ParsedGlobString::try_from(literal.as_str()).unwrap();
+        assert!(borrowed.memory_usage() < 1000);
+        assert!(borrowed.into_owned().memory_usage() >= 1000);
+    }
+
+    #[test]
+    fn test_push_segments() {
+        use crate::WildcardKind;
+        let mut pattern = ParsedGlobString::try_from("a?").unwrap();
+        pattern.push_wildcard(WildcardKind::Star);
+        pattern.push_literal("");
+        pattern.push_literal("b\\");
+        pattern.push_literal("c");
+        assert_eq!(pattern.dump(), ParsedGlobString::try_from("a?*b\\\\c").unwrap().dump());
+        assert_eq!(pattern.token_spans(), None);
+        assert!(pattern.matches_completely("axyb\\c"));
+        let mut empty = ParsedGlobString::try_from("").unwrap();
+        empty.push_literal("*.rs");
+        assert!(empty.matches_completely("*.rs"));
+        assert!(empty.matches_partially("x*.rs") && !empty.matches_partially("a.rs"));
+    }
+}
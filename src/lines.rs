@@ -0,0 +1,199 @@
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::io::{self, BufRead, BufWriter, Write};
+use crate::Matcher;
+
+/// copies the lines of `reader` that `matcher` matches to `writer`, or, if `invert` is set, the
+/// lines that it doesn't match. Returns the number of lines written.
+///
+/// Lines are matched without their line break (`\n` or `\r\n`) and written unchanged, including
+/// the line break. Lines that aren't valid UTF-8 are matched with replacement characters in place
+/// of the invalid sequences. The output is buffered and flushed before returning.
+/// ```
+/// use glob::{filter_lines, GlobSet, ParsedGlobString};
+/// let set: GlobSet = ["ERROR*", "WARN*disk*"].into_iter().map(|pattern| ParsedGlobString::try_from(pattern).unwrap()).collect();
+/// let log = "INFO start\nERROR: cpu\nWARN: disk full\nINFO stop\n";
+/// let mut output = Vec::new();
+/// assert_eq!(filter_lines(log.as_bytes(), &mut output, &set, false).unwrap(), 2);
+/// assert_eq!(output, b"ERROR: cpu\nWARN: disk full\n");
+/// ```
+pub fn filter_lines(mut reader: impl BufRead, writer: impl Write, matcher: &impl Matcher, invert: bool) -> io::Result<u64> {
+    let mut writer = BufWriter::new(writer);
+    let mut line = Vec::new();
+    let mut written_lines = 0;
+    while reader.read_until(b'\n', &mut line)? > 0 {
+        let content = line.strip_suffix(b"\n").unwrap_or(&line);
+        let content = content.strip_suffix(b"\r").unwrap_or(content);
+        if matcher.is_match(&String::from_utf8_lossy(content)) != invert {
+            writer.write_all(&line)?;
+            written_lines += 1;
+        }
+        line.clear();
+    }
+    writer.flush()?;
+    return Ok(written_lines);
+}
+
+/// whether a line yielded by [`context_lines`] matched, or surrounds a line that matched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LineKind {
+    /// the matcher matched the line.
+    Match,
+    /// the line is one of the lines before or after a match that were requested as context.
+    Context,
+}
+
+/// a line yielded by [`context_lines`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextLine {
+    line_number: u64,
+    kind: LineKind,
+    line: Vec<u8>,
+}
+
+impl ContextLine {
+    /// returns the number of the line, starting at 1.
+    pub fn line_number(&self) -> u64 {
+        return self.line_number;
+    }
+
+    /// returns whether the line matched or is context.
+    pub fn kind(&self) -> LineKind {
+        return self.kind;
+    }
+
+    /// returns the line without its line break.
+    pub fn line(&self) -> &[u8] {
+        return &self.line;
+    }
+
+    /// returns the line without its line break as text, with replacement characters in place of
+    /// invalid UTF-8 sequences.
+    pub fn text(&self) -> Cow<'_, str> {
+        return String::from_utf8_lossy(&self.line);
+    }
+}
+
+/// the iterator returned by [`context_lines`].
+#[derive(Debug)]
+pub struct ContextLines<'m, R, M: ?Sized> {
+    reader: R,
+    matcher: &'m M,
+    before: usize,
+    after: usize,
+    line_number: u64, // of the last line read
+    preceding: VecDeque<ContextLine>, // up to `before` lines after the last yielded one
+    ready: VecDeque<ContextLine>, // lines to yield before reading on
+    remaining_after: usize, // lines after the last match still to yield as context
+}
+
+/// iterates over the lines of `reader` that `matcher` matches, together with up to `before` lines
+/// before and `after` lines after each of them as context, like `grep -B` and `-A`. Lines are
+/// matched like by [`filter_lines`], and each line is yielded once, in order, even if it is
+/// context of several matches; a gap in the line numbers separates groups of lines.
+/// ```
+/// use glob::{context_lines, LineKind, ParsedGlobString};
+/// let pattern = ParsedGlobString::try_from("ERROR*").unwrap();
+/// let log = "start\nload config\nERROR: no config\nretry\nload defaults\nready\n";
+/// let lines: Vec<_> = context_lines(log.as_bytes(), &pattern, 1, 1).map(Result::unwrap).collect();
+/// let rendered: Vec<_> = lines.iter().map(|line| (line.line_number(), line.kind(), line.text())).collect();
+/// assert_eq!(rendered, vec![
+///     (2, LineKind::Context, "load config".into()),
+///     (3, LineKind::Match, "ERROR: no config".into()),
+///     (4, LineKind::Context, "retry".into()),
+/// ]);
+/// ```
+pub fn context_lines<R: BufRead, M: Matcher + ?Sized>(reader: R, matcher: &M, before: usize, after: usize) -> ContextLines<'_, R, M> {
+    return ContextLines {
+        reader: reader,
+        matcher: matcher,
+        before: before,
+        after: after,
+        line_number: 0,
+        preceding: VecDeque::new(),
+        ready: VecDeque::new(),
+        remaining_after: 0,
+    };
+}
+
+impl<'m, R: BufRead, M: Matcher + ?Sized> Iterator for ContextLines<'m, R, M> {
+    type Item = io::Result<ContextLine>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut line = Vec::new();
+        while self.ready.is_empty() {
+            line.clear();
+            match self.reader.read_until(b'\n', &mut line) {
+                Err(error) => return Some(Err(error)),
+                Ok(0) => return None,
+                Ok(_) => {},
+            }
+            self.line_number += 1;
+            let content = line.strip_suffix(b"\n").unwrap_or(&line);
+            let content = content.strip_suffix(b"\r").unwrap_or(content);
+            let is_match = self.matcher.is_match(&String::from_utf8_lossy(content));
+            let kind = if is_match { LineKind::Match } else { LineKind::Context };
+            let context_line = ContextLine { line_number: self.line_number, kind: kind, line: content.to_vec() };
+            if is_match {
+                self.ready.extend(self.preceding.drain(..));
+                self.ready.push_back(context_line);
+                self.remaining_after = self.after;
+            } else if self.remaining_after > 0 {
+                self.remaining_after -= 1;
+                self.ready.push_back(context_line);
+            } else if self.before > 0 {
+                if self.preceding.len() == self.before {
+                    self.preceding.pop_front();
+                }
+                self.preceding.push_back(context_line);
+            }
+        }
+        return self.ready.pop_front().map(Ok);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ParsedGlobString;
+    use super::{context_lines, filter_lines, LineKind};
+
+    fn filter(pattern: &str, input: &[u8], invert: bool) -> (u64, Vec<u8>) {
+        let pattern = ParsedGlobString::try_from(pattern).unwrap();
+        let mut output = Vec::new();
+        let written_lines = filter_lines(input, &mut output, &pattern, invert).unwrap();
+        return (written_lines, output);
+    }
+
+    #[test]
+    fn test_lines_are_written_unchanged() {
+        assert_eq!(filter("*.rs", b"lib.rs\r\nlib.py\nmain.rs", false), (2, b"lib.rs\r\nmain.rs".to_vec()));
+        assert_eq!(filter("*", b"", false), (0, Vec::new()));
+    }
+
+    #[test]
+    fn test_invert() {
+        assert_eq!(filter("DEBUG", b"DEBUG a\nINFO b\n", true), (1, b"INFO b\n".to_vec()));
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        assert_eq!(filter("a?b", b"a\xffb\nab\n", false), (1, b"a\xffb\n".to_vec()));
+    }
+
+    fn context(pattern: &str, input: &[u8], before: usize, after: usize) -> Vec<(u64, LineKind)> {
+        let pattern = ParsedGlobString::try_from(pattern).unwrap();
+        return context_lines(input, &pattern, before, after).map(|line| line.unwrap()).map(|line| (line.line_number(), line.kind())).collect();
+    }
+
+    #[test]
+    fn test_context_lines() {
+        use LineKind::{Context, Match};
+        let input = b"a\nERR1\nb\nc\nd\nERR2\nERR3\ne\r\nf";
+        assert_eq!(context("ERR*", input, 1, 1), vec![(1, Context), (2, Match), (3, Context), (5, Context), (6, Match), (7, Match), (8, Context)]);
+        assert_eq!(context("ERR*", input, 0, 0), vec![(2, Match), (6, Match), (7, Match)]);
+        assert_eq!(context("ERR*", input, 5, 0), vec![(1, Context), (2, Match), (3, Context), (4, Context), (5, Context), (6, Match), (7, Match)]);
+        assert_eq!(context("f", input, 0, 3), vec![(9, Match)]);
+        let pattern = ParsedGlobString::try_from("e").unwrap();
+        let lines: Vec<_> = context_lines(&input[..], &pattern, 0, 1).map(|line| line.unwrap()).collect();
+        assert_eq!((lines[0].line(), lines[1].line()), (&b"e"[..], &b"f"[..]));
+    }
+}
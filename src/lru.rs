@@ -0,0 +1,151 @@
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+/// a bounded map that evicts the least recently used entry once it is full.
+///
+/// Every access stamps the entry with a fresh tick; the ticks are mirrored in a `BTreeMap`, so
+/// finding the least recently used entry is a lookup of the smallest key.
+#[derive(Debug)]
+pub struct LruCache<K, V> {
+    capacity: usize,
+    entries: HashMap<K, (V, u64)>, // value, tick of the last access
+    recency: BTreeMap<u64, K>,
+    next_tick: u64,
+}
+
+impl<K: Eq + Hash + Clone, V> LruCache<K, V> {
+
+    pub fn new(capacity: usize) -> Self {
+        return LruCache {
+            capacity: capacity,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            next_tick: 0,
+        };
+    }
+
+    fn tick(&mut self) -> u64 {
+        let tick = self.next_tick;
+        self.next_tick += 1;
+        return tick;
+    }
+
+    /// returns the value for `key` and marks it as the most recently used entry.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where K: Borrow<Q>, Q: Eq + Hash + ?Sized {
+        let tick = self.tick();
+        let (stored_key, entry) = match self.entries.get_key_value(key) {
+            Option::None => return None,
+            Option::Some((stored_key, (_, old_tick))) => (stored_key.clone(), *old_tick),
+        };
+        self.recency.remove(&entry);
+        self.recency.insert(tick, stored_key);
+        let entry = self.entries.get_mut(key).expect("entry was found above");
+        entry.1 = tick;
+        return Some(&entry.0);
+    }
+
+    /// inserts `value` under `key`, evicting the least recently used entry if the cache is full.
+    pub fn insert(&mut self, key: K, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+        let tick = self.tick();
+        if let Some((_, old_tick)) = self.entries.remove(&key) {
+            self.recency.remove(&old_tick);
+        } else if self.entries.len() >= self.capacity {
+            self.evict_least_recently_used();
+        }
+        self.recency.insert(tick, key.clone());
+        self.entries.insert(key, (value, tick));
+    }
+
+    fn evict_least_recently_used(&mut self) {
+        if let Some((_, key)) = self.recency.pop_first() {
+            self.entries.remove(&key);
+        }
+    }
+
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        return self.entries.len();
+    }
+
+    /// changes the capacity, evicting the least recently used entries if necessary.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.entries.len() > capacity {
+            self.evict_least_recently_used();
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LruCache;
+
+    #[test]
+    fn test_get_after_insert() {
+        let mut cache = LruCache::new(2);
+        cache.insert("a", 1);
+        assert_eq!(cache.get("a"), Some(&1));
+        assert_eq!(cache.get("b"), None);
+    }
+
+    #[test]
+    fn test_evicts_least_recently_inserted() {
+        let mut cache = LruCache::new(2);
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        cache.insert("c", 3);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get("a"), None);
+        assert_eq!(cache.get("b"), Some(&2));
+        assert_eq!(cache.get("c"), Some(&3));
+    }
+
+    #[test]
+    fn test_get_refreshes_entry() {
+        let mut cache = LruCache::new(2);
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        cache.get("a");
+        cache.insert("c", 3);
+        assert_eq!(cache.get("a"), Some(&1));
+        assert_eq!(cache.get("b"), None);
+    }
+
+    #[test]
+    fn test_reinsert_replaces_value() {
+        let mut cache = LruCache::new(2);
+        cache.insert("a", 1);
+        cache.insert("a", 2);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get("a"), Some(&2));
+    }
+
+    #[test]
+    fn test_zero_capacity_stores_nothing() {
+        let mut cache = LruCache::new(0);
+        cache.insert("a", 1);
+        assert_eq!(cache.len(), 0);
+        assert_eq!(cache.get("a"), None);
+    }
+
+    #[test]
+    fn test_shrinking_capacity_evicts() {
+        let mut cache = LruCache::new(3);
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        cache.insert("c", 3);
+        cache.set_capacity(1);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get("c"), Some(&3));
+    }
+}
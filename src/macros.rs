@@ -0,0 +1,30 @@
+/// matches a string against several patterns in order, like a `match` statement, and evaluates the
+/// arm of the first pattern that matches the string completely, or the `_` arm if none does.
+///
+/// Each pattern is validated at compile time and parsed once into a hidden static, see
+/// [`glob!`](crate::glob). Several patterns can share an arm, separated by `|`. Requires the
+/// `macros` feature.
+/// ```
+/// use glob::glob_match;
+/// fn language(path: &str) -> &'static str {
+///     return glob_match!(path => {
+///         "*.rs" => "Rust",
+///         "*.toml" | "*.y?ml" => "configuration",
+///         _ => "unknown",
+///     });
+/// }
+/// assert_eq!(language("src/lib.rs"), "Rust");
+/// assert_eq!(language("ci.yaml"), "configuration");
+/// assert_eq!(language("lib.rs.orig"), "unknown");
+/// ```
+/// ```compile_fail
+/// use glob::glob_match;
+/// let kind = glob_match!("notes.txt" => { "*.\\x" => 1, _ => 0 }); // unknown escape sequence
+/// ```
+#[macro_export]
+macro_rules! glob_match {
+    ($haystack:expr => { $($($pattern:literal)|+ => $arm:expr,)* _ => $default:expr $(,)? }) => {{
+        let haystack: &str = ::core::convert::AsRef::<str>::as_ref(&$haystack);
+        $(if $($crate::glob!($pattern).matches_completely(haystack))||+ { $arm } else)* { $default }
+    }};
+}
@@ -0,0 +1,822 @@
+use std::cell::{Cell, RefCell};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+use glob_parser::Token;
+use glob_parser::Token::*;
+use crate::haystack::{is_word_boundary_in, Haystack};
+use glob_parser::multislice::MultiSlice;
+use crate::{CaseFold, MatchUnit, WildcardPolicy};
+
+/// returns the rest of `string` after skipping `count` characters, or `None` if `string` is too short.
+pub fn skip_chars(string: &str, count: usize) -> Option<&str> {
+    if count == 0 {
+        return Some(string);
+    }
+    return match string.char_indices().nth(count - 1) {
+        Option::None => None,
+        Option::Some((index, c)) => Some(&string[index + c.len_utf8()..]),
+    };
+}
+
+/// checks if `token_sequence` can be matched by the functions below, which don't support all
+/// tokens: they match the rest of the string without looking back at the characters before it.
+pub fn supports_fast_path(token_sequence: &[Token<'_>]) -> bool {
+    return !token_sequence.contains(&WordBoundary);
+}
+
+/// checks if a word character can be found on exactly one side of the byte offset `position`.
+/// Word characters are alphanumeric characters and `_`.
+pub fn is_word_boundary(string: &str, position: usize) -> bool {
+    let is_word_character = |c: char| c.is_alphanumeric() || c == '_';
+    let before = string[..position].chars().next_back().is_some_and(is_word_character);
+    let after = string[position..].chars().next().is_some_and(is_word_character);
+    return before != after;
+}
+
+pub fn token_sequence_matches_at_start(token_sequence: &[Token<'_>], string: &str) -> bool {
+    match token_sequence.split_first() {
+        Option::None => true,
+        Option::Some((token, rest)) => match token {
+            ExactLengthWildcard(length) => {
+                skip_chars(string, *length).is_some_and(|string| token_sequence_matches_at_start(rest, string))
+            },
+            Literal(literal) => {
+                literal.matches_string_start(string) && token_sequence_matches_at_start(rest, &string[literal.get_combined_length()..])
+            },
+            MinLengthWildcard(length) => {
+                // FIXME: try matching from the back
+                skip_chars(string, *length).is_some_and(|string| token_sequence_matches_partially(rest, string))
+            },
+            WordBoundary => unreachable!("word boundaries need the backtracking matcher"),
+        }
+    }
+}
+
+pub fn token_sequence_matches_partially(tokens: &[Token], string : &str) -> bool {
+    match tokens.split_first() {
+        Option::None => true,
+        Option::Some((token, rest)) => match token {
+            MinLengthWildcard(length) | ExactLengthWildcard(length) => {
+                skip_chars(string, *length).is_some_and(|string| token_sequence_matches_partially(rest, string))
+            },
+            Literal(literal) => {
+                // FIXME: try matching from the end
+                for m in literal.find_all_occurences_in(string) {
+                    if token_sequence_matches_at_start(rest,&string[m + literal.get_combined_length()..]) {
+                        return true
+                    }
+                }
+                return false
+            },
+            WordBoundary => unreachable!("word boundaries need the backtracking matcher"),
+        }
+    }
+}
+
+/// settings that change how a token sequence is matched.
+#[derive(Debug, Clone, Copy)]
+pub struct MatchConfig {
+    /// a character that wildcards never match, like `/` for paths.
+    pub separator: Option<char>,
+    /// whether wildcards match `\n`.
+    pub wildcards_match_newline: bool,
+    /// which alignment of the wildcards is preferred if there are several.
+    pub wildcard_policy: WildcardPolicy,
+    /// whether the longest of the leftmost matches is preferred over the wildcard policy.
+    pub leftmost_longest: bool,
+    /// the maximum number of steps a search may take, see [`BudgetExceeded`].
+    pub step_budget: Option<u64>,
+    /// how literals are folded for case-insensitive matching, or `None` for matching them exactly.
+    pub case_fold: Option<&'static dyn CaseFold>,
+    /// what a `?` matches.
+    pub unit: MatchUnit,
+}
+
+impl Default for MatchConfig {
+    fn default() -> Self {
+        return MatchConfig { separator: None, wildcards_match_newline: true, wildcard_policy: WildcardPolicy::Lazy, leftmost_longest: false, step_budget: None, case_fold: None, unit: MatchUnit::Char };
+    }
+}
+
+impl MatchConfig {
+    /// checks if the functions that only answer whether there is a match, which match wildcards
+    /// against all characters and literals exactly, give the right answer.
+    pub fn allows_fast_path(&self) -> bool {
+        return self.separator.is_none() && self.wildcards_match_newline && self.step_budget.is_none() && self.case_fold.is_none()
+            && self.unit == MatchUnit::Char;
+    }
+
+    pub fn wildcard_matches(&self, c: char) -> bool {
+        return Some(c) != self.separator && (self.wildcards_match_newline || c != '\n');
+    }
+}
+
+/// the byte range of a match and the byte ranges matched by its wildcard tokens.
+pub type MatchRanges = (Range<usize>, Vec<Range<usize>>);
+
+/// returned if a search was aborted, because it took more steps than allowed.
+///
+/// Backtracking over the wildcards can take time polynomial in the length of the string, with the
+/// number of wildcards as exponent. A step budget bounds the work done for patterns and strings
+/// from untrusted sources.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BudgetExceeded {
+    steps: u64,
+}
+
+impl BudgetExceeded {
+    /// returns the number of steps that were allowed.
+    pub fn steps(&self) -> u64 {
+        return self.steps;
+    }
+}
+
+impl Display for BudgetExceeded {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "matching took more than {} steps", self.steps)
+    }
+}
+
+impl Error for BudgetExceeded {}
+
+/// buffers for matching patterns, which can be created once and reused for many matches to avoid
+/// allocating them for each one, see
+/// [`matches_with_cache`](crate::ParsedGlobString::matches_with_cache). A cache can be used with
+/// any pattern, but only by one match at a time.
+/// ```
+/// use glob::{Cache, ParsedGlobString};
+/// let pattern = ParsedGlobString::try_from("*-*.log").unwrap();
+/// let mut cache = Cache::new();
+/// let names = ["app-1.log", "app.log", "db-2.log"];
+/// let count = names.iter().filter(|name| pattern.matches_with_cache(name, &mut cache)).count();
+/// assert_eq!(count, 2);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Cache {
+    captures: Vec<Range<usize>>, // of the current alignment, and of the match found
+    longest_captures: Vec<Range<usize>>, // of the longest match so far
+    ends: Vec<usize>, // a stack of the ends that greedy wildcards still have to try
+    folded: Vec<char>, // the literals of the tokens, case folded, one after the other
+    folded_ends: Vec<usize>, // for each token, the end of its folded literal in `folded`
+}
+
+impl Cache {
+    /// creates an empty cache. Its buffers grow as needed.
+    pub fn new() -> Self {
+        return Cache::default();
+    }
+
+    /// returns the ranges of the wildcard tokens of the last successful match, see
+    /// [`captures_with_cache`](crate::ParsedGlobString::captures_with_cache).
+    pub fn capture_ranges(&self) -> &[Range<usize>] {
+        return &self.captures;
+    }
+}
+
+/// matches a token sequence against a haystack by trying all alignments of the wildcards,
+/// recording the range each wildcard token matched.
+struct Backtracker<'a, 'g, H: Haystack + ?Sized> {
+    tokens: &'a [Token<'g>],
+    string: &'a H,
+    limit: usize, // matches end at or before this offset; word boundaries look beyond it
+    anchor_end: bool,
+    config: &'a MatchConfig,
+    longest: Cell<Option<usize>>, // end of the longest match so far, whose captures are in the cache
+    cache: RefCell<&'a mut Cache>,
+    step_budget: Option<u64>,
+    steps: Cell<u64>,
+}
+
+impl<'a, 'g, H: Haystack + ?Sized> Backtracker<'a, 'g, H> {
+    fn new(tokens: &'a [Token<'g>], string: &'a H, limit: usize, anchor_end: bool, config: &'a MatchConfig, step_budget: Option<u64>, cache: &'a mut Cache) -> Self {
+        // the literals are folded once here instead of at each position they are tried at
+        if let Option::Some(fold) = config.case_fold {
+            cache.folded.clear();
+            cache.folded_ends.clear();
+            for token in tokens {
+                if let Literal(literal) = token {
+                    cache.folded.extend(literal.chars().map(|c| fold.fold(c)));
+                }
+                cache.folded_ends.push(cache.folded.len());
+            }
+        }
+        return Backtracker {
+            tokens: tokens,
+            string: string,
+            limit: limit,
+            anchor_end: anchor_end,
+            config: config,
+            longest: Cell::new(None),
+            cache: RefCell::new(cache),
+            step_budget: step_budget,
+            steps: Cell::new(0),
+        };
+    }
+
+    /// tries to match the tokens at each of the `starts` in turn and returns the range of the
+    /// first match. The ranges of its wildcard tokens are left in the captures of the cache.
+    fn search(&self, starts: impl Iterator<Item = usize>) -> Result<Option<Range<usize>>, BudgetExceeded> {
+        let mut captures = std::mem::take(&mut self.cache.borrow_mut().captures);
+        let result = self.search_with(starts, &mut captures);
+        self.cache.borrow_mut().captures = captures;
+        return result;
+    }
+
+    fn search_with(&self, starts: impl Iterator<Item = usize>, captures: &mut Vec<Range<usize>>) -> Result<Option<Range<usize>>, BudgetExceeded> {
+        self.cache.borrow_mut().ends.clear();
+        for start in starts {
+            captures.clear();
+            if let Some(end) = self.match_here(0, start, captures) {
+                return Ok(Some(start..end));
+            }
+            if self.is_budget_exceeded() {
+                return Err(BudgetExceeded { steps: self.step_budget.unwrap_or(0) });
+            }
+            if let Some(end) = self.longest.take() {
+                captures.clear();
+                captures.extend_from_slice(&self.cache.borrow().longest_captures);
+                return Ok(Some(start..end));
+            }
+        }
+        return Ok(None);
+    }
+
+    /// returns the offset after the unit (character or byte) at `position`, if a wildcard may
+    /// match it. In byte mode, only ASCII bytes can be a separator or a newline.
+    fn next_unit_end(&self, position: usize) -> Option<usize> {
+        return match self.config.unit {
+            MatchUnit::Char => {
+                let (_, end) = self.string.next_unit(position).filter(|(c, end)| *end <= self.limit && self.config.wildcard_matches(*c))?;
+                Some(end)
+            },
+            MatchUnit::Byte => {
+                let byte = *self.string.as_bytes()[..self.limit].get(position)?;
+                if byte.is_ascii() && !self.config.wildcard_matches(char::from(byte)) {
+                    None
+                } else {
+                    Some(position + 1)
+                }
+            },
+        };
+    }
+
+    /// returns the offset after `count` units starting at `position`, if all of them may be
+    /// matched by a wildcard.
+    fn skip_wildcard_chars(&self, position: usize, count: usize) -> Option<usize> {
+        let mut end = position;
+        for _ in 0..count {
+            end = self.next_unit_end(end)?;
+        }
+        return Some(end);
+    }
+
+    fn is_budget_exceeded(&self) -> bool {
+        return self.step_budget.is_some_and(|budget| self.steps.get() > budget);
+    }
+
+    /// returns the offset after `literal`, the token at `token_index`, if it matches at `position`,
+    /// with case folding if configured. Folded literals are compared with the folded characters of
+    /// the string, and ASCII characters are lowercased directly if the folding allows it.
+    fn match_literal(&self, token_index: usize, literal: &MultiSlice, position: usize) -> Option<usize> {
+        if !self.string.is_unit_boundary(position) {
+            return None; // a literal never starts with a UTF-8 continuation byte
+        }
+        let fold = match self.config.case_fold {
+            Option::None => {
+                return if literal.matches_bytes_start(self.string.subslice(position..self.limit)) {
+                    Some(position + literal.get_combined_length())
+                } else {
+                    None
+                };
+            },
+            Option::Some(fold) => fold,
+        };
+        let cache = self.cache.borrow();
+        let start = if token_index == 0 { 0 } else { cache.folded_ends[token_index - 1] };
+        let bytes = &self.string.as_bytes()[..self.limit];
+        let ascii_fast_path = fold.folds_ascii_to_lowercase();
+        let mut end = position;
+        for expected in &cache.folded[start..cache.folded_ends[token_index]] {
+            if let (true, Option::Some(byte)) = (ascii_fast_path, bytes.get(end).filter(|byte| byte.is_ascii())) {
+                if char::from(byte.to_ascii_lowercase()) != *expected {
+                    return None;
+                }
+                end += 1;
+                continue;
+            }
+            (_, end) = self.string.next_unit(end).filter(|(c, next)| *next <= self.limit && fold.fold(*c) == *expected)?;
+        }
+        return Some(end);
+    }
+
+    /// handles a match of all tokens that ends at `position`.
+    fn match_end(&self, position: usize, captures: &[Range<usize>]) -> Option<usize> {
+        if (self.anchor_end && position != self.limit) || !self.string.is_unit_boundary(position) {
+            return None;
+        }
+        if self.config.leftmost_longest && position != self.limit {
+            if self.longest.get().is_none_or(|end| position > end) {
+                self.longest.set(Some(position));
+                let longest_captures = &mut self.cache.borrow_mut().longest_captures;
+                longest_captures.clear();
+                longest_captures.extend_from_slice(captures);
+            }
+            return None;
+        }
+        return Some(position);
+    }
+
+    /// tries to match the tokens starting at `token_index` at byte offset `position`. On success,
+    /// returns the offset at which the match ends and leaves the range of each wildcard token in
+    /// `captures`.
+    ///
+    /// Wildcards of variable length are tried with as few characters as possible first, or with as
+    /// many as possible for [`WildcardPolicy::Greedy`].
+    ///
+    /// If the longest match is requested, matches that don't end at the end of the string are only
+    /// recorded in `longest` and the search continues.
+    fn match_here(&self, token_index: usize, position: usize, captures: &mut Vec<Range<usize>>) -> Option<usize> {
+        self.steps.set(self.steps.get() + 1);
+        if self.is_budget_exceeded() {
+            return None;
+        }
+        let token = match self.tokens.get(token_index) {
+            Option::None => return self.match_end(position, captures),
+            Option::Some(token) => token,
+        };
+        match token {
+            Literal(literal) => {
+                let end = self.match_literal(token_index, literal, position)?;
+                self.match_here(token_index + 1, end, captures)
+            },
+            ExactLengthWildcard(length) => {
+                let end = self.skip_wildcard_chars(position, *length)?;
+                captures.push(position..end);
+                let result = self.match_here(token_index + 1, end, captures);
+                if result.is_none() {
+                    captures.pop();
+                }
+                result
+            },
+            WordBoundary => {
+                if self.string.is_unit_boundary(position) && is_word_boundary_in(self.string, position) {
+                    self.match_here(token_index + 1, position, captures)
+                } else {
+                    None
+                }
+            },
+            MinLengthWildcard(length) => {
+                let minimum_end = self.skip_wildcard_chars(position, *length)?;
+                let mut ends = std::iter::successors(Some(minimum_end), |end| self.next_unit_end(*end));
+                let mut try_end = |end: usize| {
+                    captures.push(position..end);
+                    let result = self.match_here(token_index + 1, end, captures);
+                    if result.is_none() {
+                        captures.pop();
+                    }
+                    result
+                };
+                match self.config.wildcard_policy {
+                    WildcardPolicy::Lazy => ends.find_map(try_end),
+                    WildcardPolicy::Greedy => {
+                        // the ends are pushed onto the shared stack, and the tokens after this one
+                        // push theirs above them and remove them again before returning
+                        let base = self.cache.borrow().ends.len();
+                        self.cache.borrow_mut().ends.extend(ends);
+                        let top = self.cache.borrow().ends.len();
+                        let result = (base..top).rev().find_map(|i| {
+                            let end = self.cache.borrow().ends[i];
+                            return try_end(end);
+                        });
+                        self.cache.borrow_mut().ends.truncate(base);
+                        result
+                    },
+                }
+            }
+        }
+    }
+}
+
+/// finds the leftmost match of the token sequence in `string`, which starts at the beginning of
+/// `string` if `anchor_start` is set and ends at its end if `anchor_end` is set. Of the matches
+/// starting there, the first one found or the longest one is chosen, depending on `config`. Returns
+/// the range of the match and the ranges of the wildcard tokens.
+pub fn find_match_with_captures<H: Haystack + ?Sized>(tokens: &[Token], string: &H, anchor_start: bool, anchor_end: bool, config: &MatchConfig) -> Option<MatchRanges> {
+    return find_match_with_captures_from(tokens, string, 0, anchor_start, anchor_end, config);
+}
+
+/// like [`find_match_with_captures`], but ignores matches starting before the byte offset `from`.
+/// If `anchor_start` is set, the match must start at `from`.
+/// Exceeding the step budget of `config` counts as no match.
+pub fn find_match_with_captures_from<H: Haystack + ?Sized>(tokens: &[Token], string: &H, from: usize, anchor_start: bool, anchor_end: bool, config: &MatchConfig) -> Option<MatchRanges> {
+    return find_match_with_budget(tokens, string, from, anchor_start, anchor_end, config, config.step_budget).unwrap_or(None);
+}
+
+/// like [`find_match_with_captures_from`], but gives up after `step_budget` steps, where a step is
+/// an attempt to match a token at some position.
+pub fn find_match_with_budget<H: Haystack + ?Sized>(tokens: &[Token], string: &H, from: usize, anchor_start: bool, anchor_end: bool, config: &MatchConfig, step_budget: Option<u64>) -> Result<Option<MatchRanges>, BudgetExceeded> {
+    let mut cache = Cache::new();
+    let range = find_match_with_cache(tokens, string, from, anchor_start, anchor_end, config, step_budget, &mut cache)?;
+    return Ok(range.map(|range| (range, cache.captures)));
+}
+
+/// like [`find_match_with_budget`], but uses the buffers of `cache` and leaves the ranges of the
+/// wildcard tokens in it instead of returning them.
+#[allow(clippy::too_many_arguments)]
+pub fn find_match_with_cache<H: Haystack + ?Sized>(tokens: &[Token], string: &H, from: usize, anchor_start: bool, anchor_end: bool, config: &MatchConfig, step_budget: Option<u64>, cache: &mut Cache) -> Result<Option<Range<usize>>, BudgetExceeded> {
+    // a match can only start where a leading literal occurs, which is found by a substring search.
+    // With a step budget, trying a start counts as a step, so all of them are tried.
+    let leading_literal = match tokens.first() {
+        Option::Some(Literal(literal)) if !anchor_start && config.case_fold.is_none() && step_budget.is_none() => Some(literal.joined()),
+        _ => None,
+    };
+    let next_candidate = |position: usize| match &leading_literal {
+        Option::None => Some(position),
+        Option::Some(literal) => string.find_literal(position, literal.as_bytes()),
+    };
+    let backtracker = Backtracker::new(tokens, string, string.len(), anchor_end, config, step_budget, cache);
+    let first_start = Some(from).filter(|from| *from <= string.len()).and_then(next_candidate);
+    let candidate_starts = std::iter::successors(first_start, |start| string.next_unit(*start).and_then(|(_, end)| next_candidate(end)));
+    return backtracker.search(candidate_starts.take(if anchor_start { 1 } else { usize::MAX }));
+}
+
+/// finds the leftmost match of the token sequence that lies within the byte range `range` of
+/// `string`, like [`find_match_with_captures`] finds it in all of `string`. Word boundaries at the
+/// ends of `range` are checked against the characters outside of it.
+/// Exceeding the step budget of `config` counts as no match.
+pub fn find_match_in_range<H: Haystack + ?Sized>(tokens: &[Token], string: &H, range: Range<usize>, config: &MatchConfig) -> Option<MatchRanges> {
+    let mut cache = Cache::new();
+    let backtracker = Backtracker::new(tokens, string, range.end, false, config, config.step_budget, &mut cache);
+    let candidate_starts = std::iter::successors(Some(range.start), |start| string.next_unit(*start).map(|(_, end)| end)).take_while(|start| *start <= range.end);
+    let range = backtracker.search(candidate_starts).unwrap_or(None)?;
+    return Some((range, cache.captures));
+}
+
+/// finds the match of the token sequence that starts last among those that end at or before the
+/// byte offset `limit` of `string`. Of the matches starting there, the first one found or the
+/// longest one is chosen, like by [`find_match_with_captures`]. Word boundaries at `limit` are
+/// checked against the character after it.
+/// Exceeding the step budget of `config` counts as no match.
+pub fn find_last_match_with_captures<H: Haystack + ?Sized>(tokens: &[Token], string: &H, limit: usize, anchor_end: bool, config: &MatchConfig) -> Option<MatchRanges> {
+    let mut cache = Cache::new();
+    let backtracker = Backtracker::new(tokens, string, limit, anchor_end, config, config.step_budget, &mut cache);
+    let candidate_starts = std::iter::successors(Some(limit), |start| string.previous_unit(*start).map(|(_, start)| start));
+    let range = backtracker.search(candidate_starts).unwrap_or(None)?;
+    return Some((range, cache.captures));
+}
+
+/// returns the smallest byte offset at which a match of the token sequence in `string` ends, which
+/// starts at the beginning of `string` if `anchor_start` is set and ends at its end if `anchor_end`
+/// is set.
+///
+/// Instead of backtracking, this computes the set of offsets at which each prefix of the token
+/// sequence can end, one token after the other, so it takes time linear in the length of `string`
+/// for each token.
+pub fn shortest_match_end<H: Haystack + ?Sized>(tokens: &[Token], string: &H, anchor_start: bool, anchor_end: bool, config: &MatchConfig) -> Option<usize> {
+    let mut cache = Cache::new();
+    let backtracker = Backtracker::new(tokens, string, string.len(), anchor_end, config, None, &mut cache);
+    // `reachable[position]` tells whether the tokens so far can match a part of the string ending there
+    let mut reachable = vec![false; string.len() + 1];
+    let starts = std::iter::successors(Some(0), |start| string.next_unit(*start).map(|(_, end)| end));
+    for start in starts.take(if anchor_start { 1 } else { usize::MAX }) {
+        reachable[start] = true;
+    }
+    for (index, token) in tokens.iter().enumerate() {
+        let mut next = vec![false; string.len() + 1];
+        let positions = (0..=string.len()).filter(|position| reachable[*position]);
+        match token {
+            Literal(literal) => positions.filter_map(|position| backtracker.match_literal(index, literal, position)).for_each(|end| next[end] = true),
+            ExactLengthWildcard(length) => positions.filter_map(|position| backtracker.skip_wildcard_chars(position, *length)).for_each(|end| next[end] = true),
+            WordBoundary => positions.filter(|position| string.is_unit_boundary(*position) && is_word_boundary_in(string, *position)).for_each(|position| next[position] = true),
+            MinLengthWildcard(length) => {
+                positions.filter_map(|position| backtracker.skip_wildcard_chars(position, *length)).for_each(|end| next[end] = true);
+                // every offset after one that is reachable is too, as long as the wildcard may match the units in between
+                for position in 0..string.len() {
+                    if let (true, Option::Some(end)) = (next[position], backtracker.next_unit_end(position)) {
+                        next[end] = true;
+                    }
+                }
+            },
+        }
+        reachable = next;
+    }
+    if anchor_end {
+        return Some(string.len()).filter(|end| reachable[*end]);
+    }
+    return (0..=string.len()).find(|end| reachable[*end] && string.is_unit_boundary(*end));
+}
+
+/// returns the offset at which to look for the next of several non-overlapping matches after
+/// the match `range`, or `None` if there can be no further match. After an empty match, the next
+/// match has to start at the next character.
+pub fn next_search_position<H: Haystack + ?Sized>(string: &H, range: &Range<usize>) -> Option<usize> {
+    if !range.is_empty() {
+        return Some(range.end);
+    }
+    return string.next_unit(range.end).map(|(_, end)| end);
+}
+
+/// returns the offset before which to look for the previous of several non-overlapping matches
+/// before the match `range`, when searching from right to left, or `None` if there can be no
+/// further match. After an empty match, the previous match has to end at the previous character.
+pub fn previous_search_limit(string: &str, range: &Range<usize>) -> Option<usize> {
+    if !range.is_empty() {
+        return Some(range.start);
+    }
+    return string[..range.start].chars().next_back().map(|c| range.start - c.len_utf8());
+}
+
+/// finds the leftmost match of the token sequence in `string` and returns its byte range.
+pub fn find_match<H: Haystack + ?Sized>(tokens: &[Token], string: &H, config: &MatchConfig) -> Option<Range<usize>> {
+    return find_match_with_captures(tokens, string, false, false, config).map(|(range, _)| range);
+}
+
+/// checks if the token sequence matches all of `string`.
+pub fn token_sequence_matches_completely(tokens: &[Token], string: &str, config: &MatchConfig) -> bool {
+    return find_match_with_captures(tokens, string, true, true, config).is_some();
+}
+
+/// checks if the token sequence matches some suffix of `string`.
+pub fn token_sequence_matches_at_end(tokens: &[Token], string: &str, config: &MatchConfig) -> bool {
+    return find_match_with_captures(tokens, string, false, true, config).is_some();
+}
+
+#[cfg(test)]
+#[allow(clippy::single_range_in_vec_init)] // vectors of capture ranges
+mod tests {
+    use super::{find_match, find_match_with_budget, is_word_boundary, find_match_with_captures, find_match_with_captures_from, find_last_match_with_captures, next_search_position, shortest_match_end, skip_chars, BudgetExceeded, MatchConfig, token_sequence_matches_at_start, token_sequence_matches_partially};
+    use crate::{DefaultCaseFold, MatchUnit, TurkishCaseFold, WildcardPolicy};
+    use glob_parser::parse_glob_string;
+
+    fn matches_at_start(pattern: &str, string: &str) -> bool {
+        return token_sequence_matches_at_start(&parse_glob_string(pattern).unwrap(), string);
+    }
+
+    fn matches_partially(pattern: &str, string: &str) -> bool {
+        return token_sequence_matches_partially(&parse_glob_string(pattern).unwrap(), string);
+    }
+
+    fn find(pattern: &str, string: &str) -> Option<std::ops::Range<usize>> {
+        return find_match(&parse_glob_string(pattern).unwrap(), string, &MatchConfig::default());
+    }
+
+    #[test]
+    fn test_skip_chars() {
+        assert_eq!(skip_chars("abc", 0), Some("abc"));
+        assert_eq!(skip_chars("abc", 2), Some("c"));
+        assert_eq!(skip_chars("abc", 3), Some(""));
+        assert_eq!(skip_chars("abc", 4), None);
+        assert_eq!(skip_chars("äöü", 1), Some("öü"));
+    }
+
+    #[test]
+    fn test_matches_at_start() {
+        assert!(matches_at_start("ab", "abc"));
+        assert!(matches_at_start("?b*", "abc"));
+        assert!(!matches_at_start("bc", "abc"));
+        assert!(!matches_at_start("??", "a"));
+    }
+
+    #[test]
+    fn test_matches_at_start_after_min_length_wildcard() {
+        assert!(matches_at_start("*c", "abcd"));
+        assert!(!matches_at_start("?*a", "a"));
+    }
+
+    #[test]
+    fn test_matches_partially() {
+        assert!(matches_partially("bc", "abcd"));
+        assert!(matches_partially("b?d", "abcd"));
+        assert!(!matches_partially("b?c", "abcd"));
+        assert!(matches_partially("", ""));
+    }
+
+    #[test]
+    fn test_question_mark_consumes_one_character() {
+        assert!(matches_at_start("?b", "äb"));
+        assert!(!matches_at_start("??b", "äb"));
+        assert!(matches_partially("*?x", "éx"));
+    }
+
+    #[test]
+    fn test_find_literal() {
+        assert_eq!(find("bc", "abcd"), Some(1..3));
+        assert_eq!(find("bd", "abcd"), None);
+    }
+
+    #[test]
+    fn test_find_from_occurrences_of_leading_literal() {
+        assert_eq!(find("ab*c", "xabxab-c"), Some(1..8));
+        assert_eq!(find("a\\*b", "a*a*b"), Some(2..5));
+        assert_eq!(find("ä?\\b", "ääx äy"), Some(2..5));
+        let tokens = parse_glob_string("ab?").unwrap();
+        assert_eq!(find_match_with_captures_from(&tokens, "abxab", 1, false, false, &MatchConfig::default()), None);
+        assert_eq!(find_match_with_captures_from(&tokens, "abxabc", 1, false, false, &MatchConfig::default()), Some((3..6, vec![5..6])));
+        assert_eq!(find_match_with_captures_from(&tokens, "xabc", 1, true, true, &MatchConfig::default()), Some((1..4, vec![3..4])));
+    }
+
+    #[test]
+    fn test_find_empty_pattern() {
+        assert_eq!(find("", "abc"), Some(0..0));
+        assert_eq!(find("", ""), Some(0..0));
+    }
+
+    #[test]
+    fn test_find_leftmost_match() {
+        assert_eq!(find("a?", "xxabab"), Some(2..4));
+    }
+
+    #[test]
+    fn test_find_wildcards_between_literals() {
+        assert_eq!(find("a*b", "xxa--b--b"), Some(2..6));
+        assert_eq!(find("[*,*]", "x = [1, 2];"), Some(4..10));
+    }
+
+    #[test]
+    fn test_captures_of_wildcards() {
+        let tokens = parse_glob_string("[*,?*]").unwrap();
+        assert_eq!(find_match_with_captures(&tokens, "x = [1, 23];", false, false, &MatchConfig::default()), Some((4..11, vec![5..6, 7..10])));
+    }
+
+    #[test]
+    fn test_captures_are_reset_when_backtracking() {
+        let tokens = parse_glob_string("a?b").unwrap();
+        assert_eq!(find_match_with_captures(&tokens, "axcazb", false, false, &MatchConfig::default()), Some((3..6, vec![4..5])));
+    }
+
+    #[test]
+    fn test_anchored_captures() {
+        let tokens = parse_glob_string("*.rs").unwrap();
+        assert_eq!(find_match_with_captures(&tokens, "a.rs.rs", false, false, &MatchConfig::default()), Some((0..4, vec![0..1])));
+        assert_eq!(find_match_with_captures(&tokens, "a.rs.rs", true, true, &MatchConfig::default()), Some((0..7, vec![0..4])));
+        assert_eq!(find_match_with_captures(&tokens, "a.rs.txt", true, true, &MatchConfig::default()), None);
+        let tokens = parse_glob_string("b").unwrap();
+        assert_eq!(find_match_with_captures(&tokens, "ab", true, false, &MatchConfig::default()), None);
+    }
+
+    #[test]
+    fn test_wildcards_dont_match_separator() {
+        let config = MatchConfig { separator: Some('/'), ..MatchConfig::default() };
+        let tokens = parse_glob_string("/api/*/users/?").unwrap();
+        assert_eq!(find_match_with_captures(&tokens, "/api/v1/users/7", true, true, &config), Some((0..15, vec![5..7, 14..15])));
+        assert_eq!(find_match_with_captures(&tokens, "/api/v1/beta/users/7", true, true, &config), None);
+        assert_eq!(find_match_with_captures(&tokens, "/api/v1/users//", true, true, &config), None);
+        let tokens = parse_glob_string("a*").unwrap();
+        assert_eq!(find_match_with_captures(&tokens, "xab/c", false, true, &config), None);
+        assert_eq!(find_match_with_captures(&tokens, "xa/ab", false, true, &config), Some((3..5, vec![4..5])));
+    }
+
+    #[test]
+    fn test_find_question_mark_matches_non_ascii_character() {
+        assert_eq!(find("gr??e", "Grüße, große Grüße"), Some(9..15));
+    }
+
+    #[test]
+    fn test_wildcards_dont_match_newline() {
+        let config = MatchConfig { wildcards_match_newline: false, ..MatchConfig::default() };
+        let tokens = parse_glob_string("a*b").unwrap();
+        assert_eq!(find_match_with_captures(&tokens, "a\nb ab", false, false, &config), Some((4..6, vec![5..5])));
+        assert_eq!(find_match(&tokens, "a\nb ab", &MatchConfig::default()), Some(0..3));
+        let tokens = parse_glob_string("a?b").unwrap();
+        assert_eq!(find_match(&tokens, "a\nb", &config), None);
+    }
+
+    #[test]
+    fn test_greedy_wildcards() {
+        let config = MatchConfig { wildcard_policy: WildcardPolicy::Greedy, ..MatchConfig::default() };
+        let tokens = parse_glob_string("a*b").unwrap();
+        assert_eq!(find_match_with_captures(&tokens, "xaxbxb", false, false, &config), Some((1..6, vec![2..5])));
+        let tokens = parse_glob_string("*-*").unwrap();
+        assert_eq!(find_match_with_captures(&tokens, "a-b-c", true, true, &config), Some((0..5, vec![0..3, 4..5])));
+        assert_eq!(find_match_with_captures(&tokens, "a-b-c", true, true, &MatchConfig::default()), Some((0..5, vec![0..1, 2..5])));
+    }
+
+    #[test]
+    fn test_leftmost_longest() {
+        let longest = MatchConfig { leftmost_longest: true, ..MatchConfig::default() };
+        let tokens = parse_glob_string("a*b").unwrap();
+        assert_eq!(find_match_with_captures(&tokens, "xabab-", false, false, &longest), Some((1..5, vec![2..4])));
+        assert_eq!(find_match_with_captures(&tokens, "xabab", false, false, &longest), Some((1..5, vec![2..4])));
+        assert_eq!(find_match_with_captures(&tokens, "xa-", false, false, &longest), None);
+        let tokens = parse_glob_string("?-?*").unwrap();
+        assert_eq!(find_match_with_captures(&tokens, "a-b-c", false, false, &longest), Some((0..5, vec![0..1, 2..5])));
+        let greedy_longest = MatchConfig { wildcard_policy: WildcardPolicy::Greedy, ..longest };
+        let tokens = parse_glob_string("*-*").unwrap();
+        assert_eq!(find_match_with_captures(&tokens, "a-b-c", false, false, &greedy_longest), Some((0..5, vec![0..3, 4..5])));
+    }
+
+    #[test]
+    fn test_find_from() {
+        let tokens = parse_glob_string("a?").unwrap();
+        let config = MatchConfig::default();
+        assert_eq!(find_match_with_captures_from(&tokens, "abac", 1, false, false, &config), Some((2..4, vec![3..4])));
+        assert_eq!(find_match_with_captures_from(&tokens, "abac", 1, true, false, &config), None);
+        assert_eq!(find_match_with_captures_from(&tokens, "abac", 4, false, false, &config), None);
+    }
+
+    #[test]
+    fn test_next_search_position() {
+        assert_eq!(next_search_position("abc", &(0..2)), Some(2));
+        assert_eq!(next_search_position("äbc", &(0..0)), Some(2));
+        assert_eq!(next_search_position("abc", &(3..3)), None);
+    }
+
+    #[test]
+    fn test_step_budget() {
+        let tokens = parse_glob_string("*a*a*a*b").unwrap();
+        let string = "a".repeat(100);
+        let config = MatchConfig::default();
+        assert_eq!(find_match_with_budget(&tokens, &string, 0, false, false, &config, Some(10_000)), Err(BudgetExceeded { steps: 10_000 }));
+        assert_eq!(find_match_with_budget(&tokens, "aaab", 0, false, false, &config, Some(10_000)), Ok(Some((0..4, vec![0..0, 1..1, 2..2, 3..3]))));
+        let tokens = parse_glob_string("abc").unwrap();
+        assert_eq!(find_match_with_budget(&tokens, "xxabc", 0, false, false, &config, Some(3)), Err(BudgetExceeded { steps: 3 }));
+        assert_eq!(find_match_with_budget(&tokens, "xxabc", 0, false, false, &config, Some(4)), Ok(Some((2..5, vec![]))));
+    }
+
+    #[test]
+    fn test_exceeding_step_budget_of_config_is_no_match() {
+        let tokens = parse_glob_string("*a*a*a*b").unwrap();
+        let config = MatchConfig { step_budget: Some(1000), ..MatchConfig::default() };
+        assert_eq!(find_match_with_captures(&tokens, &"a".repeat(100), false, false, &config), None);
+    }
+
+    #[test]
+    fn test_is_word_boundary() {
+        assert!(is_word_boundary("foo bar", 0));
+        assert!(is_word_boundary("foo bar", 3));
+        assert!(!is_word_boundary("foo bar", 2));
+        assert!(!is_word_boundary("a  b", 2));
+        assert!(is_word_boundary("größe", 7));
+        assert!(!is_word_boundary("größe", 4));
+        assert!(!is_word_boundary("snake_case", 5));
+        assert!(!is_word_boundary("", 0));
+    }
+
+    #[test]
+    fn test_find_word_boundary() {
+        assert_eq!(find("\\bcat\\b", "concat cat category"), Some(7..10));
+        assert_eq!(find("\\b*ing\\b", "a string thing"), Some(0..8));
+        assert_eq!(find("\\bcat\\b", "concatenate"), None);
+        assert_eq!(find("\\b", "  "), None);
+    }
+
+    #[test]
+    fn test_case_folded_literals() {
+        let config = MatchConfig { case_fold: Some(&TurkishCaseFold), ..MatchConfig::default() };
+        let tokens = parse_glob_string("İSTANBUL*").unwrap();
+        assert_eq!(find_match_with_captures(&tokens, "-> istanbul.txt", false, false, &config), Some((3..11, vec![11..11])));
+        assert_eq!(find_match_with_captures(&tokens, "ıstanbul", false, false, &config), None);
+        let tokens = parse_glob_string("DIŞ").unwrap();
+        assert_eq!(find_match_with_captures(&tokens, "dış", true, true, &config), Some((0..5, vec![])));
+    }
+
+    #[test]
+    fn test_ascii_fast_path_of_folded_literals() {
+        let config = MatchConfig { case_fold: Some(&DefaultCaseFold), ..MatchConfig::default() };
+        // the Kelvin sign folds to an ASCII `k`, and `ẞ` to the non-ASCII `ß`
+        let cases = [("*ok*", "BOOK", Some(0..4)), ("*ok", "bo\u{212A}", Some(0..5)), ("\u{212A}*", "xK", Some(1..2)), ("STRA\u{1E9E}E", "straße", Some(0..7)), ("a*B", "AxAb", Some(0..4))];
+        for (pattern, string, expected) in cases {
+            let tokens = parse_glob_string(pattern).unwrap();
+            assert_eq!(find_match_with_captures(&tokens, string, false, false, &config).map(|(range, _)| range), expected, "{} in {}", pattern, string);
+            assert_eq!(shortest_match_end(&tokens, string, false, false, &config), expected.map(|range| range.end), "{} in {}", pattern, string);
+        }
+    }
+
+    #[test]
+    fn test_byte_unit() {
+        let config = MatchConfig { unit: MatchUnit::Byte, separator: Some('/'), ..MatchConfig::default() };
+        let tokens = parse_glob_string("x??x").unwrap();
+        assert_eq!(find_match_with_captures(&tokens, "xéx", false, false, &config), Some((0..4, vec![1..3])));
+        assert_eq!(find_match_with_captures(&tokens, "x/ax", false, false, &config), None);
+        let tokens = parse_glob_string("x?").unwrap();
+        assert_eq!(find_match_with_captures(&tokens, "xé", true, true, &config), None);
+        assert_eq!(find_match_with_captures(&tokens, "xé", false, false, &config), None);
+        let tokens = parse_glob_string("?*").unwrap();
+        assert_eq!(find_match_with_captures(&tokens, "éa", true, false, &config), Some((0..2, vec![0..2])));
+    }
+
+    #[test]
+    fn test_shortest_match_end() {
+        let configs = [
+            MatchConfig::default(),
+            MatchConfig { separator: Some('/'), ..MatchConfig::default() },
+            MatchConfig { unit: MatchUnit::Byte, ..MatchConfig::default() },
+        ];
+        let patterns = ["", "a", "*b", "?*/", "\\bab*", "a?c*c", "é*", "b\\b"];
+        let strings = ["", "abcbc", "xab/ab c", "é/ab", "aéc/c", "ab ab"];
+        for config in &configs {
+            for pattern in patterns {
+                let tokens = parse_glob_string(pattern).unwrap();
+                for string in strings {
+                    let expected = (0..=string.len()).find(|end| find_last_match_with_captures(&tokens, string, *end, true, config).is_some());
+                    assert_eq!(shortest_match_end(&tokens, string, false, false, config), expected, "{} in {} with {:?}", pattern, string, config);
+                }
+            }
+        }
+        let tokens = parse_glob_string("a*").unwrap();
+        assert_eq!(shortest_match_end(&tokens, "xaa", true, false, &MatchConfig::default()), None);
+        assert_eq!(shortest_match_end(&tokens, "aaa", true, false, &MatchConfig::default()), Some(1));
+        assert_eq!(shortest_match_end(&tokens, "xaa", false, true, &MatchConfig::default()), Some(3));
+    }
+}
@@ -0,0 +1,77 @@
+use std::fs::File;
+use std::io;
+use std::ops::Range;
+use std::path::Path;
+use memmap2::Mmap;
+use crate::binary::find_all_in;
+use crate::{scan_bytes, BinaryDetection, FileScan, ParsedGlobString};
+
+/// returns the byte ranges of the non-overlapping occurrences of `pattern` in the file at `path`,
+/// like [`find_iter`](ParsedGlobString::find_iter) does for a string. Requires the `mmap` feature.
+///
+/// The file is memory-mapped instead of read, so searching a large log file needs neither a copy
+/// of it in memory nor a pass to validate it as UTF-8. Bytes that aren't valid UTF-8 are matched
+/// like [`find_in`](ParsedGlobString::find_in) matches them. The file must not be truncated while
+/// it is scanned; on most platforms, that ends the process with a bus error.
+/// ```no_run
+/// use glob::{scan_file, ParsedGlobString};
+/// let pattern = ParsedGlobString::try_from("ERROR*timeout").unwrap();
+/// for range in scan_file("/var/log/app.log", &pattern).unwrap() {
+///     println!("{:?}", range);
+/// }
+/// ```
+pub fn scan_file(path: impl AsRef<Path>, pattern: &ParsedGlobString<'_>) -> io::Result<Vec<Range<usize>>> {
+    let map = map_file(path.as_ref())?;
+    return Ok(find_all_in(&map, pattern));
+}
+
+/// searches the file at `path` for `pattern` like [`scan_file`] does, and handles binary files as
+/// `binary` selects, see [`scan_bytes`]. Requires the `mmap` feature.
+/// ```no_run
+/// use glob::{scan_file_with, BinaryDetection, FileScan, ParsedGlobString};
+/// let pattern = ParsedGlobString::try_from("password").unwrap();
+/// for path in ["notes.txt", "backup.tar"] {
+///     match scan_file_with(path, &pattern, BinaryDetection::Report).unwrap() {
+///         FileScan::Matches(ranges) => println!("{}: {} matches", path, ranges.len()),
+///         FileScan::BinaryMatches => println!("binary file {} matches", path),
+///         FileScan::SkippedBinary => {},
+///     }
+/// }
+/// ```
+pub fn scan_file_with(path: impl AsRef<Path>, pattern: &ParsedGlobString<'_>, binary: BinaryDetection) -> io::Result<FileScan> {
+    let map = map_file(path.as_ref())?;
+    return Ok(scan_bytes(&map, pattern, binary));
+}
+
+fn map_file(path: &Path) -> io::Result<Mmap> {
+    let file = File::open(path)?;
+    // SAFETY: the map is only read while it exists, and the documentation asks callers not to
+    // truncate the file meanwhile; concurrent appends don't affect the mapped range
+    return unsafe { Mmap::map(&file) };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use crate::walk::tests::create_tree;
+    use crate::ParsedGlobString;
+    use crate::{BinaryDetection, FileScan};
+    use super::{scan_file, scan_file_with};
+
+    #[test]
+    fn test_scan_file() {
+        let root = create_tree("mmap-scan", &[]);
+        fs::create_dir_all(&root).unwrap();
+        let path = root.join("app.log");
+        fs::write(&path, b"INFO ok\nERROR \xff disk\nERROR net\n").unwrap();
+        let pattern = ParsedGlobString::try_from("ERROR ?").unwrap();
+        assert_eq!(scan_file(&path, &pattern).unwrap(), vec![8..15, 21..28]);
+        fs::write(&path, b"").unwrap();
+        assert_eq!(scan_file(&path, &ParsedGlobString::try_from("").unwrap()).unwrap(), vec![0..0]);
+        assert!(scan_file(root.join("missing.log"), &pattern).is_err());
+        fs::write(&path, b"ERROR \0 core dumped").unwrap();
+        assert_eq!(scan_file_with(&path, &pattern, BinaryDetection::Report).unwrap(), FileScan::BinaryMatches);
+        assert_eq!(scan_file_with(&path, &pattern, BinaryDetection::Skip).unwrap(), FileScan::SkippedBinary);
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,86 @@
+use glob_parser::Token;
+use crate::Anchor;
+
+/// appends `token` to `tokens`, merging it with the last token if both are wildcards or both are
+/// word boundaries.
+fn push_merged<'g>(tokens: &mut Vec<Token<'g>>, token: Token<'g>) {
+    let merged = match (tokens.last(), &token) {
+        (Option::Some(Token::ExactLengthWildcard(a)), Token::ExactLengthWildcard(b)) => Token::ExactLengthWildcard(a + b),
+        (Option::Some(Token::ExactLengthWildcard(a) | Token::MinLengthWildcard(a)), Token::ExactLengthWildcard(b) | Token::MinLengthWildcard(b)) => Token::MinLengthWildcard(a + b),
+        (Option::Some(Token::WordBoundary), Token::WordBoundary) => Token::WordBoundary,
+        _ => {
+            tokens.push(token);
+            return;
+        },
+    };
+    *tokens.last_mut().expect("only reached if there is a last token") = merged;
+}
+
+/// returns `count` copies of `tokens` one after the other, with the tokens where the copies meet
+/// merged like consecutive tokens of a parsed pattern.
+pub fn repeat_tokens<'g>(tokens: &[Token<'g>], count: usize) -> Vec<Token<'g>> {
+    let mut repeated = Vec::new();
+    for _ in 0..count {
+        for token in tokens {
+            match (repeated.last_mut(), token) {
+                (Option::Some(Token::Literal(last)), Token::Literal(literal)) => last.append(literal.clone()),
+                _ => push_merged(&mut repeated, token.clone()),
+            }
+        }
+    }
+    return repeated;
+}
+
+/// removes the parts of `tokens` that can't change whether the tokens match a string with the
+/// given anchoring:
+/// - empty literals,
+/// - repeated word boundaries,
+/// - a `*` at an end of the pattern that isn't anchored, because the match may end (or start)
+///   anywhere anyway. `?` before such a `*` still requires a character.
+pub fn optimize_tokens(tokens: Vec<Token<'_>>, anchor: Anchor) -> Vec<Token<'_>> {
+    let mut optimized = Vec::with_capacity(tokens.len());
+    for token in tokens {
+        match token {
+            Token::Literal(literal) if literal.get_combined_length() == 0 => {},
+            token => push_merged(&mut optimized, token),
+        }
+    }
+    if !matches!(anchor, Anchor::Start | Anchor::Both) {
+        if let Option::Some(Token::MinLengthWildcard(length)) = optimized.first() {
+            optimized[0] = Token::ExactLengthWildcard(*length);
+        }
+    }
+    if !matches!(anchor, Anchor::End | Anchor::Both) {
+        if let Option::Some(Token::MinLengthWildcard(length)) = optimized.last() {
+            let last = optimized.len() - 1;
+            optimized[last] = Token::ExactLengthWildcard(*length);
+        }
+    }
+    optimized.retain(|token| *token != Token::ExactLengthWildcard(0));
+    return optimized;
+}
+
+#[cfg(test)]
+mod tests {
+    use glob_parser::parse_glob_string;
+    use glob_parser::Token::*;
+    use glob_parser::multislice::MultiSlice;
+    use crate::Anchor;
+    use super::optimize_tokens;
+
+    #[test]
+    fn test_unanchored_ends() {
+        assert_eq!(optimize_tokens(parse_glob_string("*a*").unwrap(), Anchor::None), vec![Literal(MultiSlice::from("a"))]);
+        assert_eq!(optimize_tokens(parse_glob_string("?*a??*").unwrap(), Anchor::None), vec![ExactLengthWildcard(1), Literal(MultiSlice::from("a")), ExactLengthWildcard(2)]);
+        assert_eq!(optimize_tokens(parse_glob_string("*a*").unwrap(), Anchor::Start), vec![MinLengthWildcard(0), Literal(MultiSlice::from("a"))]);
+        assert_eq!(optimize_tokens(parse_glob_string("*a*").unwrap(), Anchor::End), vec![Literal(MultiSlice::from("a")), MinLengthWildcard(0)]);
+        assert_eq!(optimize_tokens(parse_glob_string("*").unwrap(), Anchor::Both), vec![MinLengthWildcard(0)]);
+        assert_eq!(optimize_tokens(parse_glob_string("*").unwrap(), Anchor::None), vec![]);
+    }
+
+    #[test]
+    fn test_redundant_tokens() {
+        let tokens = vec![ExactLengthWildcard(1), Literal(MultiSlice::new()), MinLengthWildcard(0), WordBoundary, WordBoundary, Literal(MultiSlice::from("a"))];
+        assert_eq!(optimize_tokens(tokens, Anchor::Both), vec![MinLengthWildcard(1), WordBoundary, Literal(MultiSlice::from("a"))]);
+    }
+}
@@ -0,0 +1,116 @@
+use crate::matcher::MatchConfig;
+use crate::{Anchor, CaseFold};
+
+/// selects what a `?` wildcard matches and in which steps `*` advances.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MatchUnit {
+    /// a wildcard unit is a Unicode scalar value (`char`), so `?` matches `é` (the default).
+    #[default]
+    Char,
+    /// a wildcard unit is a byte of the UTF-8 encoding, so `??` matches `é`. Matches still start and
+    /// end on character boundaries, and only ASCII characters can be separators.
+    Byte,
+}
+
+/// all options that influence whether a pattern matches a string, in one place.
+///
+/// Pass them to [`ParsedGlobString::match_with`](crate::ParsedGlobString::match_with) to decide
+/// per call how to match, or to [`GlobBuilder::options`](crate::GlobBuilder::options) to make them
+/// the options of a pattern. Fields that are not mentioned keep their defaults:
+/// ```
+/// use glob::{Anchor, MatchOptions, ParsedGlobString};
+/// let pattern = ParsedGlobString::try_from("src/*.rs").unwrap();
+/// let options = MatchOptions { anchor: Anchor::Both, path_separator: Some('/'), ..MatchOptions::default() };
+/// assert!(pattern.match_with("src/lib.rs", &options));
+/// assert!(!pattern.match_with("src/bin/glob.rs", &options));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct MatchOptions {
+    /// where the pattern has to match. Defaults to [`Anchor::None`].
+    pub anchor: Anchor,
+    /// how literals are folded for case-insensitive matching, or `None` (the default) for
+    /// case-sensitive matching.
+    pub case_fold: Option<&'static dyn CaseFold>,
+    /// a character that wildcards never match, like `/` for matching paths component-wise.
+    /// Defaults to `None`.
+    pub path_separator: Option<char>,
+    /// whether wildcards match line breaks (`\n`). Defaults to `true`.
+    pub wildcards_match_newline: bool,
+    /// what a `?` matches. Defaults to [`MatchUnit::Char`].
+    pub unit: MatchUnit,
+}
+
+impl Default for MatchOptions {
+    fn default() -> Self {
+        return MatchOptions {
+            anchor: Anchor::None,
+            case_fold: None,
+            path_separator: None,
+            wildcards_match_newline: true,
+            unit: MatchUnit::Char,
+        };
+    }
+}
+
+impl MatchOptions {
+    /// returns `config` with the settings that these options cover replaced.
+    pub(crate) fn apply_to(&self, config: MatchConfig) -> MatchConfig {
+        return MatchConfig {
+            case_fold: self.case_fold,
+            separator: self.path_separator,
+            wildcards_match_newline: self.wildcards_match_newline,
+            unit: self.unit,
+            ..config
+        };
+    }
+
+    /// collects the settings of `config` that these options cover.
+    pub(crate) fn from_config(config: &MatchConfig, anchor: Anchor) -> Self {
+        return MatchOptions {
+            anchor: anchor,
+            case_fold: config.case_fold,
+            path_separator: config.separator,
+            wildcards_match_newline: config.wildcards_match_newline,
+            unit: config.unit,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Anchor, DefaultCaseFold, GlobBuilder, MatchOptions, MatchUnit, ParsedGlobString};
+
+    #[test]
+    fn test_default_options_match_partially() {
+        let pattern = ParsedGlobString::try_from("b?").unwrap();
+        assert!(pattern.match_with("abc", &MatchOptions::default()));
+        assert!(!pattern.match_with("ab", &MatchOptions::default()));
+    }
+
+    #[test]
+    fn test_all_options() {
+        let pattern = ParsedGlobString::try_from("DIR/??.TXT").unwrap();
+        let options = MatchOptions {
+            anchor: Anchor::Both,
+            case_fold: Some(&DefaultCaseFold),
+            path_separator: Some('/'),
+            wildcards_match_newline: false,
+            unit: MatchUnit::Byte,
+        };
+        assert!(pattern.match_with("dir/é.txt", &options));
+        assert!(!pattern.match_with("dir/ab.txt.bak", &options));
+        assert!(!pattern.match_with("dir/\n\n.txt", &options));
+        assert!(!pattern.match_with("dir///.txt", &options));
+    }
+
+    #[test]
+    fn test_builder_options() {
+        let options = MatchOptions { anchor: Anchor::Start, case_fold: Some(&DefaultCaseFold), ..MatchOptions::default() };
+        let pattern = GlobBuilder::new("error:").options(&options).build().unwrap();
+        assert!(pattern.is_match("ERROR: disk full"));
+        assert!(!pattern.is_match("[ERROR: disk full]"));
+        assert!(pattern.matches_partially("[ERROR: disk full]"));
+        assert_eq!(pattern.options().anchor, Anchor::Start);
+        assert!(pattern.options().case_fold.is_some());
+    }
+}
@@ -0,0 +1,179 @@
+use crate::path::offset_error;
+use crate::{GlobParseError, PathPattern};
+
+/// the decision of [`Overrides`] or of ignore rules (see [`IgnoreRules`](crate::IgnoreRules))
+/// about a path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum OverrideMatch {
+    /// no rule decides about the path.
+    None,
+    /// the path is excluded. For [`Overrides`], this is also the case if there are include rules
+    /// and none of them matches.
+    Ignore,
+    /// the path is explicitly included by a rule, e.g. a `!` rule in an ignore file.
+    Whitelist,
+}
+
+/// an ordered list of include and exclude rules for paths, like the `--glob` flags of ripgrep.
+///
+/// Each rule is a [path pattern](PathPattern); a rule starting with `!` excludes the paths it
+/// matches, any other rule includes them. If several rules match a path, the last one decides, so
+/// an include rule can re-include paths excluded by an earlier rule and vice versa. If there is at
+/// least one include rule, paths that no rule matches are excluded.
+///
+/// A rule without a `/` matches the file name of a path in any directory; other rules match the
+/// whole path, relative to the directory that is searched (a leading `/` is ignored). A rule ending
+/// with `/` only matches directories.
+/// ```
+/// use glob::{OverrideMatch, Overrides};
+/// let mut overrides = Overrides::new();
+/// overrides.add("*.js").unwrap();
+/// overrides.add("!*.min.js").unwrap();
+/// overrides.add("vendor/jquery.min.js").unwrap();
+/// assert_eq!(overrides.matched("README.md", false), OverrideMatch::Ignore);
+/// assert_eq!(overrides.matched("src/app.js", false), OverrideMatch::Whitelist);
+/// assert_eq!(overrides.matched("src/app.min.js", false), OverrideMatch::Ignore);
+/// assert_eq!(overrides.matched("vendor/jquery.min.js", false), OverrideMatch::Whitelist);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Overrides<'g> {
+    rules: Vec<Rule<'g>>,
+}
+
+#[derive(Debug, Clone)]
+struct Rule<'g> {
+    pattern: PathPattern<'g>,
+    exclude: bool,
+    whole_path: bool, // if the pattern is matched against the whole path, not the file name
+    only_directories: bool,
+}
+
+impl<'g> Overrides<'g> {
+    /// creates an empty list of rules, which doesn't decide about any path.
+    pub fn new() -> Self {
+        return Overrides { rules: Vec::new() };
+    }
+
+    /// appends a rule, which takes precedence over all rules added before. The indices in errors
+    /// refer to the whole rule, including a leading `!`.
+    pub fn add(&mut self, rule: &'g str) -> Result<(), GlobParseError<'g>> {
+        let (exclude, mut pattern) = match rule.strip_prefix('!') {
+            Option::Some(pattern) => (true, pattern),
+            Option::None => (false, rule),
+        };
+        let mut offset = rule.len() - pattern.len();
+        let only_directories = pattern.len() > 1 && pattern.ends_with('/') && !pattern.ends_with("\\/");
+        if only_directories {
+            pattern = &pattern[..pattern.len() - 1];
+        }
+        let whole_path = pattern.contains('/');
+        if let Option::Some(relative) = pattern.strip_prefix('/') {
+            pattern = relative;
+            offset += 1;
+        }
+        let parsed = PathPattern::try_from(pattern).map_err(|error| offset_error(error, offset))?;
+        self.rules.push(Rule { pattern: parsed, exclude: exclude, whole_path: whole_path, only_directories: only_directories });
+        return Ok(());
+    }
+
+    /// returns the number of rules.
+    pub fn len(&self) -> usize {
+        return self.rules.len();
+    }
+
+    /// checks if there are no rules.
+    pub fn is_empty(&self) -> bool {
+        return self.rules.is_empty();
+    }
+
+    /// decides about a `/`-separated relative path. `is_dir` tells if the path is a directory.
+    pub fn matched(&self, path: &str, is_dir: bool) -> OverrideMatch {
+        let decision = self.matched_by_rule(path, is_dir);
+        if decision == OverrideMatch::None && self.rules.iter().any(|rule| !rule.exclude) {
+            return OverrideMatch::Ignore;
+        }
+        return decision;
+    }
+
+    /// decides about a path like [`matched`](Self::matched), but only if a rule matches it, so
+    /// paths are not excluded just because no include rule matches them.
+    pub(crate) fn matched_by_rule(&self, path: &str, is_dir: bool) -> OverrideMatch {
+        let file_name = path.rsplit('/').next().unwrap_or(path);
+        for rule in self.rules.iter().rev() {
+            if rule.only_directories && !is_dir {
+                continue;
+            }
+            let matches = if rule.whole_path {
+                rule.pattern.matches(path)
+            } else {
+                rule.pattern.matches(file_name)
+            };
+            if matches {
+                return if rule.exclude { OverrideMatch::Ignore } else { OverrideMatch::Whitelist };
+            }
+        }
+        return OverrideMatch::None;
+    }
+
+    /// checks if the path is excluded, see [`matched`](Self::matched).
+    pub fn is_ignored(&self, path: &str, is_dir: bool) -> bool {
+        return self.matched(path, is_dir) == OverrideMatch::Ignore;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::GlobParseError;
+    use super::{OverrideMatch, Overrides};
+
+    fn overrides(rules: &[&'static str]) -> Overrides<'static> {
+        let mut overrides = Overrides::new();
+        for rule in rules {
+            overrides.add(rule).unwrap();
+        }
+        return overrides;
+    }
+
+    #[test]
+    fn test_no_rules() {
+        assert!(Overrides::new().is_empty());
+        assert_eq!(Overrides::new().matched("a.rs", false), OverrideMatch::None);
+    }
+
+    #[test]
+    fn test_include_rules_exclude_everything_else() {
+        let overrides = overrides(&["*.rs", "*.toml"]);
+        assert_eq!(overrides.len(), 2);
+        assert_eq!(overrides.matched("src/lib.rs", false), OverrideMatch::Whitelist);
+        assert_eq!(overrides.matched("Cargo.toml", false), OverrideMatch::Whitelist);
+        assert!(overrides.is_ignored("README.md", false));
+    }
+
+    #[test]
+    fn test_later_rules_win() {
+        let excluded_again = overrides(&["!test*", "test_main.rs", "!*_main.rs"]);
+        assert_eq!(excluded_again.matched("tests.rs", false), OverrideMatch::Ignore);
+        assert_eq!(excluded_again.matched("test_main.rs", false), OverrideMatch::Ignore);
+        let reincluded = overrides(&["!test*", "test_main.rs"]);
+        assert_eq!(reincluded.matched("test_main.rs", false), OverrideMatch::Whitelist);
+        assert_eq!(reincluded.matched("test_lib.rs", false), OverrideMatch::Ignore);
+        let only_excludes = overrides(&["!test*"]);
+        assert_eq!(only_excludes.matched("lib.rs", false), OverrideMatch::None);
+    }
+
+    #[test]
+    fn test_paths_and_directories() {
+        let overrides = overrides(&["!/target/", "!docs/**/*.html"]);
+        assert!(overrides.is_ignored("target", true));
+        assert!(!overrides.is_ignored("target", false));
+        assert!(!overrides.is_ignored("src/target", true));
+        assert!(overrides.is_ignored("docs/api/index.html", false));
+        assert!(!overrides.is_ignored("index.html", false));
+    }
+
+    #[test]
+    fn test_error_indices_refer_to_the_rule() {
+        assert_eq!(Overrides::new().add("!/a\\x").unwrap_err(), GlobParseError::UnknownEscapeSequence(3, "\\x"));
+    }
+}
@@ -0,0 +1,389 @@
+use std::collections::BTreeSet;
+use std::iter::zip;
+use crate::{split_pattern, GlobBuilder, GlobParseError, MatchOptions, ParsedGlobString};
+
+/// a pattern for `/`-separated paths that is matched component by component.
+///
+/// The pattern is split at each `/` into component patterns, which must each match a whole
+/// component of the path, so wildcards never match `/`. A component that is exactly `**` (a
+/// globstar) matches any number of path components, including none.
+/// ```
+/// use glob::PathPattern;
+/// let pattern = PathPattern::try_from("src/**/*.rs").unwrap();
+/// assert!(pattern.matches("src/lib.rs"));
+/// assert!(pattern.matches("src/bin/cli/main.rs"));
+/// assert!(!pattern.matches("tests/lib.rs"));
+/// assert!(!pattern.matches("src/lib.rs/backup"));
+/// ```
+///
+/// With [`windows_paths`](Self::windows_paths), the prefix of an absolute Windows path is one
+/// component, or a fixed group of components, that wildcards and globstars don't cross.
+#[derive(Debug, Clone)]
+pub struct PathPattern<'g> {
+    components: Vec<Component<'g>>,
+    prefix_length: usize, // the number of components that match the prefix of a Windows path
+    literal_leading_dot: bool,
+    windows_paths: bool,
+}
+
+#[derive(Debug, Clone)]
+enum Component<'g> {
+    Globstar,
+    // and the pattern for the rest of a hidden name, if a literal `.` can match its leading `.`
+    Pattern(ParsedGlobString<'g>, Option<Box<ParsedGlobString<'g>>>),
+}
+
+/// shifts the indices in `error` by `offset`, for errors in a part of a pattern string.
+pub(crate) fn offset_error(error: GlobParseError<'_>, offset: usize) -> GlobParseError<'_> {
+    return match error {
+        GlobParseError::UnknownEscapeSequence(index, sequence) => GlobParseError::UnknownEscapeSequence(index + offset, sequence),
+        GlobParseError::UnterminatedEscapeSequence(index) => GlobParseError::UnterminatedEscapeSequence(index + offset),
+    };
+}
+
+/// returns the prefix of a Windows path, like `C:` or `\\server\share`, spelled with `/` and
+/// with verbatim prefixes like `\\?\C:` reduced to what they stand for, and the rest of the
+/// path after the separator that follows the prefix. Both `/` and `\\` are separators.
+fn split_windows_prefix(path: &str) -> Option<(String, &str)> {
+    let is_separator = |c: char| c == '/' || c == '\\';
+    // returns the component at the start of `rest` and what follows the separator after it
+    fn component(rest: &str) -> (&str, &str) {
+        return match rest.find(['/', '\\']) {
+            Option::None => (rest, ""),
+            Option::Some(end) => (&rest[..end], &rest[end + 1..]),
+        };
+    }
+    let drive = |rest: &str| -> Option<usize> {
+        let bytes = rest.as_bytes();
+        let is_drive = bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && rest[2..].chars().next().is_none_or(is_separator);
+        return Some(2).filter(|_| is_drive);
+    };
+    if let Option::Some(length) = drive(path) {
+        return Some((String::from(&path[..length]), path.get(length + 1..).unwrap_or("")));
+    }
+    let mut chars = path.chars();
+    if !(chars.next().is_some_and(is_separator) && chars.next().is_some_and(is_separator)) {
+        return None;
+    }
+    let rest = &path[2..];
+    let (first, after_first) = component(rest);
+    if first != "?" {
+        let (share, rest) = component(after_first);
+        return Some((format!("//{}/{}", first, share), rest));
+    }
+    // a verbatim path, which is a drive, a UNC path or some other device
+    if let Option::Some(length) = drive(after_first) {
+        return Some((String::from(&after_first[..length]), after_first.get(length + 1..).unwrap_or("")));
+    }
+    let (device, after_device) = component(after_first);
+    if device.eq_ignore_ascii_case("UNC") {
+        let (server, rest) = component(after_device);
+        let (share, rest) = component(rest);
+        return Some((format!("//{}/{}", server, share), rest));
+    }
+    return Some((format!("//?/{}", device), after_device));
+}
+
+/// returns the number of components at the start of `components` that match the prefix of a
+/// Windows path: a pattern like `*:` for a drive, or four components for `//server/share`.
+fn prefix_length(pattern: &str, components: &[std::ops::Range<usize>]) -> usize {
+    if pattern.starts_with("//") && components.len() >= 4 {
+        return 4;
+    }
+    let first = &pattern[components[0].clone()];
+    if first.ends_with(':') && first != "**" {
+        return 1;
+    }
+    return 0;
+}
+
+impl<'g> TryFrom<&'g str> for PathPattern<'g> {
+    type Error = GlobParseError<'g>;
+    /// parses the given path pattern. The indices in errors refer to the whole pattern string.
+    /// ```
+    /// # use glob::{GlobParseError, PathPattern};
+    /// let error = PathPattern::try_from("src/\\x/*.rs").unwrap_err();
+    /// assert_eq!(error, GlobParseError::UnknownEscapeSequence(4, "\\x"));
+    /// ```
+    fn try_from(pattern: &'g str) -> Result<Self, Self::Error> {
+        return PathPattern::with_options(pattern, &MatchOptions::default());
+    }
+}
+
+impl<'g> PathPattern<'g> {
+    /// parses the given path pattern, matching the components with the given options, e.g. for
+    /// case-insensitive matching. The anchor and the path separator of the options are ignored.
+    /// ```
+    /// use glob::{DefaultCaseFold, MatchOptions, PathPattern};
+    /// let options = MatchOptions { case_fold: Some(&DefaultCaseFold), ..MatchOptions::default() };
+    /// let pattern = PathPattern::with_options("Docs/**/*.MD", &options).unwrap();
+    /// assert!(pattern.matches("docs/guide/intro.md"));
+    /// ```
+    pub fn with_options(pattern: &'g str, options: &MatchOptions) -> Result<Self, GlobParseError<'g>> {
+        let mut components = Vec::new();
+        let ranges = split_pattern(pattern, '/');
+        for range in ranges.iter().cloned() {
+            let component = &pattern[range.clone()];
+            if component == "**" {
+                components.push(Component::Globstar);
+                continue;
+            }
+            let parsed = GlobBuilder::new(component).options(options).build().map_err(|error| offset_error(error, range.start))?;
+            // leading `*` can match nothing, so the `.` after them can match the leading `.`
+            let stars = component.len() - component.trim_start_matches('*').len();
+            let after_dot = match component[stars..].strip_prefix('.') {
+                Option::None => None,
+                Option::Some(rest) => Some(Box::new(GlobBuilder::new(rest).options(options).build().map_err(|error| offset_error(error, range.start + stars + 1))?)),
+            };
+            components.push(Component::Pattern(parsed, after_dot));
+        }
+        let prefix_length = prefix_length(pattern, &ranges);
+        return Ok(PathPattern { components: components, prefix_length: prefix_length, literal_leading_dot: false, windows_paths: cfg!(windows) });
+    }
+
+    /// sets whether the leading `.` of a path component, like a hidden file, can only be matched
+    /// by a literal `.` in the component pattern, not by wildcards or a globstar. Leading `*` in
+    /// the component pattern can still match nothing, so `*.*` matches `.a`. Defaults to `false`.
+    /// ```
+    /// use glob::PathPattern;
+    /// let pattern = PathPattern::try_from("**/*.toml").unwrap().literal_leading_dot(true);
+    /// assert!(pattern.matches("crates/a/Cargo.toml"));
+    /// assert!(!pattern.matches(".cargo/config.toml"));
+    /// assert!(!pattern.matches(".rustfmt.toml"));
+    /// ```
+    pub fn literal_leading_dot(mut self, yes: bool) -> Self {
+        self.literal_leading_dot = yes;
+        return self;
+    }
+
+    /// sets whether paths are Windows paths, which are separated by `\\` as well as `/` and can
+    /// start with a prefix: a drive like `C:`, a UNC share like `\\server\share`, or a
+    /// verbatim prefix like `\\?\C:` or `\\?\UNC\server\share`, which is treated like
+    /// the drive or share it stands for. Defaults to `true` on Windows and `false` elsewhere.
+    ///
+    /// The prefix of a path is matched by the prefix of the pattern, written with `/`: a first
+    /// component ending with `:` for a drive, or `//server/share` for a share. Wildcards don't
+    /// match across the separators in a prefix, and a pattern without a prefix doesn't match paths
+    /// with one, nor the other way around.
+    /// ```
+    /// use glob::PathPattern;
+    /// let pattern = PathPattern::try_from("*:/Users/*").unwrap().windows_paths(true);
+    /// assert!(pattern.matches("C:\\Users\\alice"));
+    /// assert!(pattern.matches("\\\\?\\D:\\Users\\bob"));
+    /// assert!(!pattern.matches("\\\\server\\share\\Users\\carol"));
+    /// let pattern = PathPattern::try_from("//*/public/**").unwrap().windows_paths(true);
+    /// assert!(pattern.matches("\\\\fileserver\\public\\docs\\a.txt"));
+    /// assert!(!pattern.matches("//fileserver/private/docs/a.txt"));
+    /// let relative = PathPattern::try_from("**/*.txt").unwrap().windows_paths(true);
+    /// assert!(relative.matches("docs\\a.txt"));
+    /// assert!(!relative.matches("C:\\docs\\a.txt"));
+    /// ```
+    pub fn windows_paths(mut self, yes: bool) -> Self {
+        self.windows_paths = yes;
+        return self;
+    }
+
+    /// checks if the pattern only matches absolute Windows paths, because it starts with a prefix.
+    pub(crate) fn has_windows_prefix(&self) -> bool {
+        return self.windows_paths && self.prefix_length > 0;
+    }
+
+    /// adds the component patterns that can be skipped, because they are globstars.
+    fn close(&self, states: &mut BTreeSet<usize>) {
+        let mut index = 0;
+        while let Option::Some(state) = states.iter().copied().find(|state| *state >= index) {
+            if let Option::Some(Component::Globstar) = self.components.get(state) {
+                states.insert(state + 1);
+            }
+            index = state + 1;
+        }
+    }
+
+    /// returns the positions in the component patterns that can be reached after matching the
+    /// given path components, starting at the component pattern `start`.
+    fn states<'s>(&self, path_components: impl Iterator<Item = &'s str>, start: usize) -> BTreeSet<usize> {
+        let mut states = BTreeSet::from([start]);
+        self.close(&mut states);
+        for name in path_components {
+            let is_hidden = self.literal_leading_dot && name.starts_with('.');
+            let mut next = BTreeSet::new();
+            for state in states {
+                match self.components.get(state) {
+                    Option::Some(Component::Globstar) if !is_hidden => { next.insert(state); },
+                    Option::Some(Component::Pattern(pattern, after_dot)) => {
+                        let matches = if is_hidden {
+                            after_dot.as_ref().is_some_and(|after_dot| after_dot.matches_completely(&name[1..]))
+                        } else {
+                            pattern.matches_completely(name)
+                        };
+                        if matches {
+                            next.insert(state + 1);
+                        }
+                    },
+                    _ => {},
+                }
+            }
+            if next.is_empty() {
+                return next;
+            }
+            self.close(&mut next);
+            states = next;
+        }
+        return states;
+    }
+
+    /// returns the positions in the component patterns that can be reached after matching `path`.
+    fn states_of_path(&self, path: &str) -> BTreeSet<usize> {
+        if !self.windows_paths {
+            return self.states(path.split('/'), 0);
+        }
+        let (prefix, rest) = match split_windows_prefix(path) {
+            Option::None if self.prefix_length == 0 => return self.states(path.split(['/', '\\']), 0),
+            Option::Some((prefix, rest)) if self.prefix_length > 0 => (prefix, rest),
+            _ => return BTreeSet::new(),
+        };
+        let prefix_components: Vec<&str> = prefix.split('/').collect();
+        let prefix_matches = prefix_components.len() == self.prefix_length && zip(&prefix_components, &self.components).all(|(name, component)| match component {
+            Component::Pattern(pattern, _) => pattern.matches_completely(name),
+            Component::Globstar => false,
+        });
+        if !prefix_matches {
+            return BTreeSet::new();
+        }
+        let rest_components = rest.split(['/', '\\']).filter(|_| !rest.is_empty());
+        return self.states(rest_components, self.prefix_length);
+    }
+
+    /// checks if the pattern matches the given `/`-separated path.
+    pub fn matches(&self, path: &str) -> bool {
+        return self.states_of_path(path).contains(&self.components.len());
+    }
+
+    /// checks if the pattern could match a path below the given directory, i.e. a path that starts
+    /// with `directory` followed by `/`. A walker doesn't need to descend into directories for
+    /// which this returns `false`.
+    /// ```
+    /// use glob::PathPattern;
+    /// let pattern = PathPattern::try_from("src/*/mod.rs").unwrap();
+    /// assert!(pattern.could_match_below("src"));
+    /// assert!(pattern.could_match_below("src/parser"));
+    /// assert!(!pattern.could_match_below("src/parser/tests"));
+    /// assert!(!pattern.could_match_below("target"));
+    /// ```
+    pub fn could_match_below(&self, directory: &str) -> bool {
+        return self.states_of_path(directory).iter().any(|state| *state < self.components.len());
+    }
+
+    /// copies all borrowed parts of the pattern, so the result is no longer limited by the
+    /// lifetime of the pattern string.
+    pub fn into_owned(self) -> PathPattern<'static> {
+        let components = self.components.into_iter().map(|component| match component {
+            Component::Globstar => Component::Globstar,
+            Component::Pattern(pattern, after_dot) => Component::Pattern(pattern.into_owned(), after_dot.map(|after_dot| Box::new(after_dot.into_owned()))),
+        });
+        return PathPattern { components: components.collect(), prefix_length: self.prefix_length, literal_leading_dot: self.literal_leading_dot, windows_paths: self.windows_paths };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::split_pattern;
+    use super::{split_windows_prefix, PathPattern};
+
+    fn matches(pattern: &str, path: &str) -> bool {
+        return PathPattern::try_from(pattern).unwrap().matches(path);
+    }
+
+    #[test]
+    fn test_split_components() {
+        assert_eq!(split_pattern("", '/'), vec![0..0]);
+        assert_eq!(split_pattern("a/b\\/c/\\\\/d", '/'), vec![0..1, 2..6, 7..9, 10..11]);
+    }
+
+    #[test]
+    fn test_wildcards_stay_within_components() {
+        assert!(matches("*.rs", "lib.rs"));
+        assert!(!matches("*.rs", "src/lib.rs"));
+        assert!(matches("*/*.rs", "src/lib.rs"));
+        assert!(!matches("src?lib.rs", "src/lib.rs"));
+    }
+
+    #[test]
+    fn test_globstar() {
+        assert!(matches("**", ""));
+        assert!(matches("**", "a/b/c"));
+        assert!(matches("**/*.rs", "lib.rs"));
+        assert!(matches("a/**/b/**/c", "a/b/c"));
+        assert!(matches("a/**/b/**/c", "a/x/b/y/z/c"));
+        assert!(!matches("a/**/b", "a/x/c"));
+        assert!(!matches("a**/b", "ax/y/b"));
+    }
+
+    #[test]
+    fn test_split_windows_prefix() {
+        let prefix = |path| split_windows_prefix(path).map(|(prefix, rest)| (prefix, String::from(rest)));
+        assert_eq!(prefix("C:\\Users\\bob"), Some((String::from("C:"), String::from("Users\\bob"))));
+        assert_eq!(prefix("c:"), Some((String::from("c:"), String::new())));
+        assert_eq!(prefix("\\\\server\\share\\a"), Some((String::from("//server/share"), String::from("a"))));
+        assert_eq!(prefix("\\\\?\\D:\\a"), Some((String::from("D:"), String::from("a"))));
+        assert_eq!(prefix("\\\\?\\UNC\\server\\share"), Some((String::from("//server/share"), String::new())));
+        assert_eq!(prefix("\\\\?\\Volume{1}\\a"), Some((String::from("//?/Volume{1}"), String::from("a"))));
+        assert_eq!(prefix("C:relative"), None);
+        assert_eq!(prefix("ab:/c"), None);
+        assert_eq!(prefix("/usr/bin"), None);
+    }
+
+    #[test]
+    fn test_windows_prefixes() {
+        let matches = |pattern, path| PathPattern::try_from(pattern).unwrap().windows_paths(true).matches(path);
+        assert!(matches("*:/Users/*", "C:/Users/bob"));
+        assert!(matches("*:/Users/*", "\\\\?\\C:\\Users\\bob"));
+        assert!(!matches("*:/Users/*", "C:/Users/bob/a.txt"));
+        assert!(!matches("*:/Users/*", "//server/share/Users/bob"));
+        assert!(matches("//*/*/docs/*", "\\\\server\\share\\docs\\a.txt"));
+        assert!(matches("//server/share/**", "\\\\?\\UNC\\server\\share\\docs\\a.txt"));
+        // wildcards and globstars don't cross the separators in a prefix
+        assert!(!matches("//*/docs/*", "//server/share/docs/a.txt"));
+        assert!(!matches("**/Users/*", "C:/Users/bob"));
+        assert!(!matches("*/Users/*", "C:/Users/bob"));
+        assert!(!matches("C:/**", "Users/bob"));
+        assert!(matches("**/*.txt", "docs\\a.txt"));
+        let pattern = PathPattern::try_from("C:/Users/*/Documents/**").unwrap().windows_paths(true);
+        assert!(pattern.could_match_below("C:"));
+        assert!(pattern.could_match_below("C:\\Users"));
+        assert!(!pattern.could_match_below("D:\\Users"));
+        assert!(!pattern.could_match_below("\\\\server\\share"));
+        // without the option, a prefix is an ordinary component
+        assert!(PathPattern::try_from("*:/Users/*").unwrap().windows_paths(false).matches("C:/Users/bob"));
+        assert!(!PathPattern::try_from("**/a").unwrap().windows_paths(false).matches("C:\\a"));
+    }
+
+    #[test]
+    fn test_could_match_below() {
+        let pattern = PathPattern::try_from("src/**/tests/*.rs").unwrap();
+        assert!(pattern.could_match_below("src/a/b"));
+        assert!(!pattern.could_match_below("docs"));
+        assert!(!PathPattern::try_from("*.rs").unwrap().could_match_below("src"));
+    }
+
+    #[test]
+    fn test_literal_leading_dot() {
+        let hidden = |pattern: &str, path: &str| PathPattern::try_from(pattern).unwrap().literal_leading_dot(true).matches(path);
+        assert!(hidden(".*", ".git"));
+        assert!(hidden("*.*", ".a"));
+        assert!(hidden("**/*.*", "src/.a"));
+        assert!(!hidden("*", ".git"));
+        assert!(!hidden("?git", ".git"));
+        assert!(!hidden("*.rs", ".x.rs"));
+        assert!(!hidden("**/x", ".git/x"));
+    }
+
+    #[test]
+    fn test_into_owned() {
+        let pattern = {
+            let pattern_string = String::from("src/**");
+            PathPattern::try_from(pattern_string.as_str()).unwrap().into_owned()
+        };
+        assert!(pattern.matches("src/a/b.rs"));
+    }
+}
@@ -0,0 +1,467 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use crate::Matcher;
+
+/// options for matching a [`PosixPattern`], named after the flags of `fnmatch()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct PosixOptions {
+    /// whether the string is a pathname (`FNM_PATHNAME`): a `/` is only matched by a `/` in the
+    /// pattern, not by `*`, `?` or a bracket expression. Defaults to `false`.
+    pub pathname: bool,
+    /// whether a leading `.` is only matched by a `.` in the pattern (`FNM_PERIOD`), not by `*`,
+    /// `?` or a bracket expression. A `.` is leading at the start of the string and, if
+    /// [`pathname`](Self::pathname) is set, right after a `/`. Defaults to `false`.
+    pub period: bool,
+    /// whether a backslash is an ordinary character (`FNM_NOESCAPE`) instead of escaping the
+    /// character after it. Defaults to `false`.
+    pub no_escape: bool,
+}
+
+/// returned if creating a [`PosixPattern`] fails. Encapsulates the byte index at which the
+/// offending part starts in the pattern string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PosixPatternError {
+    /// returned when the pattern ends with a backslash that escapes nothing.
+    TrailingBackslash(usize), // index
+    /// returned when a bracket expression contains a character class like `[:letter:]` that
+    /// doesn't exist.
+    UnknownCharacterClass(usize), // index
+    /// returned when a bracket expression contains an equivalence class or a collating symbol of
+    /// several characters, like `[.space.]`, which are locale-dependent.
+    UnsupportedCollatingElement(usize), // index
+    /// returned when the end of a range in a bracket expression comes before its start, or is a
+    /// character class.
+    InvalidRange(usize), // index
+}
+
+impl Display for PosixPatternError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PosixPatternError::TrailingBackslash(index) => write!(f, "trailing backslash at index {}", index),
+            PosixPatternError::UnknownCharacterClass(index) => write!(f, "unknown character class at index {}", index),
+            PosixPatternError::UnsupportedCollatingElement(index) => write!(f, "unsupported collating element at index {}", index),
+            PosixPatternError::InvalidRange(index) => write!(f, "invalid range at index {}", index),
+        }
+    }
+}
+
+impl Error for PosixPatternError {}
+
+/// a character class of a bracket expression, in the POSIX locale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Alnum,
+    Alpha,
+    Blank,
+    Cntrl,
+    Digit,
+    Graph,
+    Lower,
+    Print,
+    Punct,
+    Space,
+    Upper,
+    Xdigit,
+}
+
+impl CharClass {
+    fn from_name(name: &str) -> Option<Self> {
+        return match name {
+            "alnum" => Some(CharClass::Alnum),
+            "alpha" => Some(CharClass::Alpha),
+            "blank" => Some(CharClass::Blank),
+            "cntrl" => Some(CharClass::Cntrl),
+            "digit" => Some(CharClass::Digit),
+            "graph" => Some(CharClass::Graph),
+            "lower" => Some(CharClass::Lower),
+            "print" => Some(CharClass::Print),
+            "punct" => Some(CharClass::Punct),
+            "space" => Some(CharClass::Space),
+            "upper" => Some(CharClass::Upper),
+            "xdigit" => Some(CharClass::Xdigit),
+            _ => None,
+        };
+    }
+
+    fn contains(self, c: char) -> bool {
+        return match self {
+            CharClass::Alnum => c.is_ascii_alphanumeric(),
+            CharClass::Alpha => c.is_ascii_alphabetic(),
+            CharClass::Blank => c == ' ' || c == '\t',
+            CharClass::Cntrl => c.is_ascii_control(),
+            CharClass::Digit => c.is_ascii_digit(),
+            CharClass::Graph => c.is_ascii_graphic(),
+            CharClass::Lower => c.is_ascii_lowercase(),
+            CharClass::Print => c.is_ascii_graphic() || c == ' ',
+            CharClass::Punct => c.is_ascii_punctuation(),
+            CharClass::Space => c.is_ascii_whitespace() || c == '\x0b',
+            CharClass::Upper => c.is_ascii_uppercase(),
+            CharClass::Xdigit => c.is_ascii_hexdigit(),
+        };
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum BracketItem {
+    Char(char),
+    Range(char, char), // inclusive
+    Class(CharClass),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Bracket {
+    negated: bool,
+    items: Vec<BracketItem>,
+}
+
+impl Bracket {
+    fn contains(&self, c: char) -> bool {
+        let listed = self.items.iter().any(|item| match item {
+            BracketItem::Char(expected) => c == *expected,
+            BracketItem::Range(low, high) => (*low..=*high).contains(&c),
+            BracketItem::Class(class) => class.contains(c),
+        });
+        return listed != self.negated;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PosixToken {
+    Literal(char),
+    AnyChar, // `?`
+    AnyString, // `*`
+    Bracket(Bracket),
+}
+
+/// what a bracket expression lists at one position, before ranges are recognized.
+enum Atom {
+    Char(char),
+    Class(CharClass),
+}
+
+/// parses the element of a bracket expression at `i` and returns it, or the error it contains,
+/// together with the index after it. Returns `None` if the pattern ends before the element does.
+fn parse_atom(chars: &[(usize, char)], i: usize, no_escape: bool) -> Option<(Result<Atom, PosixPatternError>, usize)> {
+    let (index, c) = *chars.get(i)?;
+    if c == '\\' && !no_escape {
+        let (_, escaped) = *chars.get(i + 1)?;
+        return Some((Ok(Atom::Char(escaped)), i + 2));
+    }
+    let delimiter = chars.get(i + 1).map(|(_, c)| *c).filter(|c| matches!(c, ':' | '=' | '.'));
+    let Option::Some(delimiter) = delimiter.filter(|_| c == '[') else {
+        return Some((Ok(Atom::Char(c)), i + 1));
+    };
+    // `[:`, `[=` or `[.` without the matching `:]`, `=]` or `.]` is an ordinary `[`
+    let Option::Some(close) = (i + 2..chars.len().saturating_sub(1)).find(|k| chars[*k].1 == delimiter && chars[k + 1].1 == ']') else {
+        return Some((Ok(Atom::Char(c)), i + 1));
+    };
+    let name: String = chars[i + 2..close].iter().map(|(_, c)| *c).collect();
+    let atom = if delimiter == ':' {
+        CharClass::from_name(&name).map(Atom::Class).ok_or(PosixPatternError::UnknownCharacterClass(index))
+    } else {
+        // in the POSIX locale, each character is a collating element and its own equivalence class
+        let mut name_chars = name.chars();
+        match (name_chars.next(), name_chars.next()) {
+            (Option::Some(c), Option::None) => Ok(Atom::Char(c)),
+            _ => Err(PosixPatternError::UnsupportedCollatingElement(index)),
+        }
+    };
+    return Some((atom, close + 2));
+}
+
+/// parses the bracket expression whose `[` is at `start` and returns it together with the index
+/// after its `]`. Returns `None` if the `[` doesn't start a bracket expression, because there is no
+/// `]` to end it, in which case it is an ordinary character.
+fn parse_bracket(chars: &[(usize, char)], start: usize, no_escape: bool) -> Option<Result<(Bracket, usize), PosixPatternError>> {
+    let mut i = start + 1;
+    let negated = chars.get(i).is_some_and(|(_, c)| *c == '!');
+    if negated {
+        i += 1;
+    }
+    let mut items = Vec::new();
+    // errors are only reported once the `]` is found, since they don't matter otherwise
+    let mut error = None;
+    loop {
+        let (index, c) = *chars.get(i)?;
+        // a `]` right after `[` or `[!` is listed instead of ending the expression
+        if c == ']' && i > start + 1 + negated as usize {
+            return Some(match error {
+                Option::Some(error) => Err(error),
+                Option::None => Ok((Bracket { negated: negated, items: items }, i + 1)),
+            });
+        }
+        let (atom, next) = parse_atom(chars, i, no_escape)?;
+        i = next;
+        let low = match atom {
+            Err(atom_error) => {
+                error.get_or_insert(atom_error);
+                continue;
+            },
+            Ok(Atom::Class(class)) => {
+                items.push(BracketItem::Class(class));
+                continue;
+            },
+            Ok(Atom::Char(low)) => low,
+        };
+        // a `-` before the `]` is listed instead of forming a range
+        let is_range = chars.get(i).is_some_and(|(_, c)| *c == '-') && chars.get(i + 1).is_some_and(|(_, c)| *c != ']');
+        if !is_range {
+            items.push(BracketItem::Char(low));
+            continue;
+        }
+        let (high, next) = parse_atom(chars, i + 1, no_escape)?;
+        i = next;
+        match high {
+            Ok(Atom::Char(high)) if low <= high => items.push(BracketItem::Range(low, high)),
+            Err(high_error) => _ = error.get_or_insert(high_error),
+            _ => _ = error.get_or_insert(PosixPatternError::InvalidRange(index)),
+        }
+    }
+}
+
+fn parse(pattern: &str, no_escape: bool) -> Result<Vec<PosixToken>, PosixPatternError> {
+    let chars: Vec<(usize, char)> = pattern.char_indices().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while let Option::Some(&(index, c)) = chars.get(i) {
+        i += 1;
+        match c {
+            '*' => tokens.push(PosixToken::AnyString),
+            '?' => tokens.push(PosixToken::AnyChar),
+            '\\' if !no_escape => {
+                let Option::Some(&(_, escaped)) = chars.get(i) else {
+                    return Err(PosixPatternError::TrailingBackslash(index));
+                };
+                tokens.push(PosixToken::Literal(escaped));
+                i += 1;
+            },
+            '[' => match parse_bracket(&chars, i - 1, no_escape) {
+                Option::None => tokens.push(PosixToken::Literal('[')),
+                Option::Some(bracket) => {
+                    let (bracket, next) = bracket?;
+                    tokens.push(PosixToken::Bracket(bracket));
+                    i = next;
+                },
+            },
+            c => tokens.push(PosixToken::Literal(c)),
+        }
+    }
+    return Ok(tokens);
+}
+
+/// a pattern in the pattern matching notation of POSIX (XCU 2.13, described in `glob(7)`), matched
+/// like `fnmatch()` matches it, for tools that must behave exactly like the shell or `find -name`.
+///
+/// Unlike [`ParsedGlobString`](crate::ParsedGlobString), which has its own syntax, patterns follow
+/// the standard precisely:
+///
+/// - `?` matches one character and `*` any string, including the empty one.
+/// - A bracket expression like `[a-z]`, `[[:digit:]_]` or `[!.]` matches one character that it
+///   lists, or with `!`, one that it doesn't list. A `]` right after `[` or `[!`, and a `-` at the
+///   start or the end, are listed rather than special. Character classes and ranges follow the
+///   POSIX locale; equivalence classes `[=c=]` and collating symbols `[.c.]` are supported for
+///   single characters. A `[` that isn't followed by a `]` to end the expression matches itself.
+/// - A backslash makes the next character, also in a bracket expression, match itself, unless
+///   [`no_escape`](PosixOptions::no_escape) is set.
+/// - With [`pathname`](PosixOptions::pathname) and [`period`](PosixOptions::period), `/` and
+///   leading `.` are only matched explicitly.
+///
+/// The pattern must match the whole string.
+/// ```
+/// use glob::{PosixOptions, PosixPattern};
+/// let pattern = PosixPattern::try_from("[!.]*.[ch]").unwrap();
+/// assert!(pattern.matches("main.c"));
+/// assert!(!pattern.matches(".hidden.h"));
+/// let options = PosixOptions { pathname: true, period: true, ..PosixOptions::default() };
+/// let pattern = PosixPattern::new("src/*", options).unwrap();
+/// assert!(pattern.matches("src/lib.rs"));
+/// assert!(!pattern.matches("src/bin/glob.rs"));
+/// assert!(!pattern.matches("src/.gitignore"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PosixPattern {
+    tokens: Vec<PosixToken>,
+    options: PosixOptions,
+}
+
+impl PosixPattern {
+    /// parses the given pattern for matching with the given options.
+    pub fn new(pattern: &str, options: PosixOptions) -> Result<Self, PosixPatternError> {
+        return Ok(PosixPattern { tokens: parse(pattern, options.no_escape)?, options: options });
+    }
+
+    /// returns the options of this pattern.
+    pub fn options(&self) -> PosixOptions {
+        return self.options;
+    }
+
+    /// checks if this pattern matches the given string as a whole.
+    pub fn matches(&self, string: &str) -> bool {
+        let chars: Vec<char> = string.chars().collect();
+        let pathname = self.options.pathname;
+        // checks if the character at `j` may be matched by a wildcard or a bracket expression
+        let is_implicitly_matchable = |j: usize| {
+            let is_leading = j == 0 || (pathname && chars[j - 1] == '/');
+            let is_explicit_only = (pathname && chars[j] == '/') || (self.options.period && chars[j] == '.' && is_leading);
+            return !is_explicit_only;
+        };
+        // `matched[j]` tells whether the tokens after the current one match `chars[j..]`
+        let mut matched: Vec<bool> = (0..=chars.len()).map(|j| j == chars.len()).collect();
+        for token in self.tokens.iter().rev() {
+            let mut current = vec![false; chars.len() + 1];
+            if *token == PosixToken::AnyString {
+                current[chars.len()] = matched[chars.len()];
+            }
+            for j in (0..chars.len()).rev() {
+                current[j] = match token {
+                    PosixToken::Literal(c) => chars[j] == *c && matched[j + 1],
+                    PosixToken::AnyChar => is_implicitly_matchable(j) && matched[j + 1],
+                    PosixToken::Bracket(bracket) => is_implicitly_matchable(j) && bracket.contains(chars[j]) && matched[j + 1],
+                    PosixToken::AnyString => matched[j] || (is_implicitly_matchable(j) && current[j + 1]),
+                };
+            }
+            matched = current;
+        }
+        return matched[0];
+    }
+}
+
+impl TryFrom<&str> for PosixPattern {
+    type Error = PosixPatternError;
+    /// parses the given pattern for matching with the default options.
+    fn try_from(pattern: &str) -> Result<Self, Self::Error> {
+        return PosixPattern::new(pattern, PosixOptions::default());
+    }
+}
+
+impl Matcher for PosixPattern {
+    fn is_match(&self, haystack: &str) -> bool {
+        return self.matches(haystack);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PosixOptions, PosixPattern, PosixPatternError};
+
+    const NONE: PosixOptions = PosixOptions { pathname: false, period: false, no_escape: false };
+    const PATHNAME: PosixOptions = PosixOptions { pathname: true, ..NONE };
+    const PERIOD: PosixOptions = PosixOptions { period: true, ..NONE };
+    const PATHNAME_PERIOD: PosixOptions = PosixOptions { pathname: true, period: true, ..NONE };
+    const NOESCAPE: PosixOptions = PosixOptions { no_escape: true, ..NONE };
+
+    /// conformance vectors: pattern, string, options, whether `fnmatch()` reports a match.
+    const VECTORS: &[(&str, &str, PosixOptions, bool)] = &[
+        // ordinary characters and wildcards
+        ("abc", "abc", NONE, true),
+        ("abc", "abd", NONE, false),
+        ("", "", NONE, true),
+        ("", "a", NONE, false),
+        ("*", "", NONE, true),
+        ("?", "", NONE, false),
+        ("a*", "a", NONE, true),
+        ("*a", "ba", NONE, true),
+        ("a*b*c", "axxbyyc", NONE, true),
+        ("a*b*c", "axxbyy", NONE, false),
+        ("a??d", "abcd", NONE, true),
+        ("a??d", "abd", NONE, false),
+        ("*x*x*", "xx", NONE, true),
+        ("ä?", "äö", NONE, true),
+        // bracket expressions
+        ("[abc]", "b", NONE, true),
+        ("[abc]", "d", NONE, false),
+        ("[!abc]", "d", NONE, true),
+        ("[!abc]", "a", NONE, false),
+        ("[a-c]", "b", NONE, true),
+        ("[a-c]", "d", NONE, false),
+        ("[]]", "]", NONE, true),
+        ("[]a]", "a", NONE, true),
+        ("[!]]", "]", NONE, false),
+        ("[!]]", "x", NONE, true),
+        ("[-a]", "-", NONE, true),
+        ("[a-]", "-", NONE, true),
+        ("[!-]", "-", NONE, false),
+        ("[]-a]", "_", NONE, true),
+        ("[]-a]", "b", NONE, false),
+        ("[^a]", "^", NONE, true),
+        ("[[:digit:]]", "7", NONE, true),
+        ("[[:digit:]]", "x", NONE, false),
+        ("[[:alpha:][:digit:]]", "x", NONE, true),
+        ("[![:space:]]", " ", NONE, false),
+        ("[[:upper:]]", "é", NONE, false),
+        ("[[:xdigit:]]", "F", NONE, true),
+        ("[[:punct:]]", "!", NONE, true),
+        ("[[:blank:]]", "\t", NONE, true),
+        ("[[.-.]]", "-", NONE, true),
+        ("[[=a=]]", "a", NONE, true),
+        ("[[.a.]-c]", "b", NONE, true),
+        ("[[:alpha:]", "[:alpha:", NONE, false),
+        ("[[:alpha:]", "a", NONE, false),
+        ("[a-c", "[a-c", NONE, true),
+        ("[", "[", NONE, true),
+        ("[!", "[!", NONE, true),
+        ("[]", "[]", NONE, true),
+        ("a[", "a[", NONE, true),
+        ("[*", "[x", NONE, true),
+        // backslashes
+        ("\\*", "*", NONE, true),
+        ("\\*", "a", NONE, false),
+        ("\\a", "a", NONE, true),
+        ("[\\]]", "]", NONE, true),
+        ("[\\!a]", "!", NONE, true),
+        ("[a\\-c]", "b", NONE, false),
+        ("[a\\-c]", "-", NONE, true),
+        ("\\*", "\\x", NOESCAPE, true),
+        ("\\*", "*", NOESCAPE, false),
+        ("\\*", "\\*", NOESCAPE, true),
+        ("\\", "\\", NOESCAPE, true),
+        ("[\\]]", "\\]", NOESCAPE, true),
+        ("[\\]]", "]", NOESCAPE, false),
+        // pathnames
+        ("*", "a/b", NONE, true),
+        ("*", "a/b", PATHNAME, false),
+        ("*/*", "a/b", PATHNAME, true),
+        ("a?b", "a/b", NONE, true),
+        ("a?b", "a/b", PATHNAME, false),
+        ("a[/]b", "a/b", PATHNAME, false),
+        ("a[!x]b", "a/b", PATHNAME, false),
+        ("a/*", "a/", PATHNAME, true),
+        ("*/b", "a/x/b", PATHNAME, false),
+        ("a\\/b", "a/b", PATHNAME, true),
+        // leading periods
+        ("*", ".profile", NONE, true),
+        ("*", ".profile", PERIOD, false),
+        (".*", ".profile", PERIOD, true),
+        ("?profile", ".profile", PERIOD, false),
+        ("[.]profile", ".profile", PERIOD, false),
+        ("[!a]profile", ".profile", PERIOD, false),
+        ("\\.profile", ".profile", PERIOD, true),
+        ("x*", "x.y", PERIOD, true),
+        ("a/*", "a/.b", PERIOD, true),
+        ("a/*", "a/.b", PATHNAME_PERIOD, false),
+        ("a/.*", "a/.b", PATHNAME_PERIOD, true),
+        ("*/.b", "a/.b", PATHNAME_PERIOD, true),
+        ("a*b", "a.b", PATHNAME_PERIOD, true),
+        ("*", "a/.b", PERIOD, true),
+    ];
+
+    #[test]
+    fn test_conformance_vectors() {
+        for (pattern, string, options, expected) in VECTORS {
+            let posix = PosixPattern::new(pattern, *options).unwrap();
+            assert_eq!(posix.matches(string), *expected, "{:?} against {:?} with {:?}", pattern, string, options);
+        }
+    }
+
+    #[test]
+    fn test_invalid_patterns() {
+        assert_eq!(PosixPattern::try_from("ab\\"), Err(PosixPatternError::TrailingBackslash(2)));
+        assert_eq!(PosixPattern::try_from("[[:alhpa:]]"), Err(PosixPatternError::UnknownCharacterClass(1)));
+        assert_eq!(PosixPattern::try_from("x[[.space.]]"), Err(PosixPatternError::UnsupportedCollatingElement(2)));
+        assert_eq!(PosixPattern::try_from("[z-a]"), Err(PosixPatternError::InvalidRange(1)));
+        assert_eq!(PosixPattern::try_from("[a-[:digit:]]"), Err(PosixPatternError::InvalidRange(1)));
+        // without a `]` to end it, there is no bracket expression to be invalid
+        assert!(PosixPattern::try_from("[z-a").unwrap().matches("[z-a"));
+        assert_eq!(PosixPatternError::InvalidRange(1).to_string(), "invalid range at index 1");
+    }
+}
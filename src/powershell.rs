@@ -0,0 +1,224 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use crate::{CaseFold, DefaultCaseFold, Matcher};
+
+/// returned if creating a [`PowerShellPattern`] fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PowerShellPatternError {
+    /// returned when a `[` isn't closed by a `]`. Encapsulates the byte index of the `[` in the
+    /// pattern string.
+    UnterminatedBracket(usize), // index
+}
+
+impl Display for PowerShellPatternError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PowerShellPatternError::UnterminatedBracket(index) => write!(f, "unterminated `[` at index {}", index),
+        }
+    }
+}
+
+impl Error for PowerShellPatternError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SetItem {
+    Char(char),
+    Range(char, char), // inclusive
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PowerShellToken {
+    Literal(char),
+    AnyChar, // `?`
+    AnyString, // `*`
+    Set(Vec<SetItem>), // `[...]`
+}
+
+/// parses the set whose `[` is at `start` and returns its items and the index after its `]`.
+fn parse_set(chars: &[(usize, char)], start: usize) -> Result<(Vec<SetItem>, usize), PowerShellPatternError> {
+    let mut items = Vec::new();
+    let mut i = start + 1;
+    // returns the character at `i`, unescaped, and the index after it
+    let next_char = |i: usize| -> Option<(char, usize)> {
+        return match chars.get(i)? {
+            (_, '`') => chars.get(i + 1).map(|(_, c)| (*c, i + 2)),
+            (_, c) => Some((*c, i + 1)),
+        };
+    };
+    loop {
+        match chars.get(i) {
+            Option::None => return Err(PowerShellPatternError::UnterminatedBracket(chars[start].0)),
+            Option::Some((_, ']')) => return Ok((items, i + 1)),
+            Option::Some(_) => {},
+        }
+        let (low, next) = next_char(i).ok_or(PowerShellPatternError::UnterminatedBracket(chars[start].0))?;
+        i = next;
+        let is_range = chars.get(i).is_some_and(|(_, c)| *c == '-') && chars.get(i + 1).is_some_and(|(_, c)| *c != ']');
+        if !is_range {
+            items.push(SetItem::Char(low));
+            continue;
+        }
+        let (high, next) = next_char(i + 1).ok_or(PowerShellPatternError::UnterminatedBracket(chars[start].0))?;
+        i = next;
+        items.push(SetItem::Range(low, high));
+    }
+}
+
+fn parse(pattern: &str) -> Result<Vec<PowerShellToken>, PowerShellPatternError> {
+    let chars: Vec<(usize, char)> = pattern.char_indices().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while let Option::Some(&(_, c)) = chars.get(i) {
+        i += 1;
+        match c {
+            '*' => tokens.push(PowerShellToken::AnyString),
+            '?' => tokens.push(PowerShellToken::AnyChar),
+            '[' => {
+                let (items, next) = parse_set(&chars, i - 1)?;
+                tokens.push(PowerShellToken::Set(items));
+                i = next;
+            },
+            // a backtick at the end of the pattern matches itself
+            '`' if i < chars.len() => {
+                tokens.push(PowerShellToken::Literal(chars[i].1));
+                i += 1;
+            },
+            c => tokens.push(PowerShellToken::Literal(c)),
+        }
+    }
+    return Ok(tokens);
+}
+
+/// a pattern with the wildcard syntax of PowerShell, matched like its `-like` and `-clike`
+/// operators match it, so automation tooling can evaluate the same expressions on every platform.
+///
+/// `*` matches any string, `?` one character, and `[abc]` or `[a-z]` one of the listed characters
+/// or one in the range. There is no negation. A backtick makes the next character, also in
+/// brackets, match itself, like `` `* `` or `` `[ ``. The pattern must match the whole string.
+/// ```
+/// use glob::PowerShellPattern;
+/// let pattern = PowerShellPattern::like("Get-[a-c]*").unwrap();
+/// assert!(pattern.matches("get-ChildItem"));
+/// assert!(!pattern.matches("Set-Content"));
+/// assert!(!PowerShellPattern::clike("Get-*").unwrap().matches("get-Item"));
+/// assert!(PowerShellPattern::like("`[draft`]*").unwrap().matches("[Draft] notes.txt"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PowerShellPattern {
+    tokens: Vec<PowerShellToken>,
+    case_sensitive: bool,
+}
+
+impl PowerShellPattern {
+    /// parses a pattern that is matched case-insensitively, like with `-like`.
+    pub fn like(pattern: &str) -> Result<Self, PowerShellPatternError> {
+        return Ok(PowerShellPattern { tokens: parse(pattern)?, case_sensitive: false });
+    }
+
+    /// parses a pattern that is matched case-sensitively, like with `-clike`.
+    pub fn clike(pattern: &str) -> Result<Self, PowerShellPatternError> {
+        return Ok(PowerShellPattern { tokens: parse(pattern)?, case_sensitive: true });
+    }
+
+    /// checks if the pattern matches `c` for the literal `expected`.
+    fn char_matches(&self, expected: char, c: char) -> bool {
+        return c == expected || (!self.case_sensitive && DefaultCaseFold.fold(c) == DefaultCaseFold.fold(expected));
+    }
+
+    fn set_contains(&self, items: &[SetItem], c: char) -> bool {
+        // a letter is in a range if one of its cases is, e.g. `B` in `[a-c]`
+        let candidates = if self.case_sensitive {
+            vec![c]
+        } else {
+            c.to_lowercase().chain(c.to_uppercase()).chain(std::iter::once(c)).collect()
+        };
+        return items.iter().any(|item| match item {
+            SetItem::Char(expected) => self.char_matches(*expected, c),
+            SetItem::Range(low, high) => candidates.iter().any(|c| (*low..=*high).contains(c)),
+        });
+    }
+
+    /// checks if this pattern matches the given string as a whole.
+    pub fn matches(&self, string: &str) -> bool {
+        let chars: Vec<char> = string.chars().collect();
+        // `matched[j]` tells whether the tokens after the current one match `chars[j..]`
+        let mut matched: Vec<bool> = (0..=chars.len()).map(|j| j == chars.len()).collect();
+        for token in self.tokens.iter().rev() {
+            let mut current = vec![false; chars.len() + 1];
+            if *token == PowerShellToken::AnyString {
+                current[chars.len()] = matched[chars.len()];
+            }
+            for j in (0..chars.len()).rev() {
+                current[j] = match token {
+                    PowerShellToken::Literal(expected) => self.char_matches(*expected, chars[j]) && matched[j + 1],
+                    PowerShellToken::AnyChar => matched[j + 1],
+                    PowerShellToken::Set(items) => self.set_contains(items, chars[j]) && matched[j + 1],
+                    PowerShellToken::AnyString => matched[j] || current[j + 1],
+                };
+            }
+            matched = current;
+        }
+        return matched[0];
+    }
+}
+
+impl TryFrom<&str> for PowerShellPattern {
+    type Error = PowerShellPatternError;
+    /// parses a pattern that is matched case-insensitively, like [`like`](PowerShellPattern::like).
+    fn try_from(pattern: &str) -> Result<Self, Self::Error> {
+        return PowerShellPattern::like(pattern);
+    }
+}
+
+impl Matcher for PowerShellPattern {
+    fn is_match(&self, haystack: &str) -> bool {
+        return self.matches(haystack);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PowerShellPattern, PowerShellPatternError};
+
+    #[test]
+    fn test_like() {
+        let cases = [
+            ("*", "", true),
+            ("a*", "ABC", true),
+            ("?", "", false),
+            ("a?c", "a-c", true),
+            ("[abc]x", "Bx", true),
+            ("[a-c]", "B", true),
+            ("[a-c]", "d", false),
+            ("[-a]", "-", true),
+            ("[a-]", "-", true),
+            ("[!a]", "!", true),
+            ("[!a]", "b", false),
+            ("[]", "", false),
+            ("[`]]", "]", true),
+            ("`*", "*", true),
+            ("`*", "x", false),
+            ("a`", "a`", true),
+            ("``", "`", true),
+            ("*.PS1", "profile.ps1", true),
+            ("straße", "STRASSE", false),
+        ];
+        for (pattern, string, expected) in cases {
+            assert_eq!(PowerShellPattern::like(pattern).unwrap().matches(string), expected, "{} -like {}", string, pattern);
+        }
+    }
+
+    #[test]
+    fn test_clike() {
+        assert!(PowerShellPattern::clike("a[b-d]").unwrap().matches("ac"));
+        assert!(!PowerShellPattern::clike("a[b-d]").unwrap().matches("aC"));
+        assert!(!PowerShellPattern::clike("A*").unwrap().matches("abc"));
+    }
+
+    #[test]
+    fn test_unterminated_bracket() {
+        assert_eq!(PowerShellPattern::like("ab[cd"), Err(PowerShellPatternError::UnterminatedBracket(2)));
+        assert_eq!(PowerShellPattern::like("[a`]"), Err(PowerShellPatternError::UnterminatedBracket(0)));
+        assert_eq!(PowerShellPattern::try_from("[a-").unwrap_err().to_string(), "unterminated `[` at index 0");
+    }
+}
@@ -0,0 +1,151 @@
+use glob_parser::Token;
+use crate::haystack::Haystack;
+use crate::matcher::MatchConfig;
+use crate::Anchor;
+
+/// the trailing literal of a pattern that doesn't start with a literal, which a string has to end
+/// with if the match is anchored at its end. Checking it rejects most strings that don't match in
+/// time independent of their length; a leading literal needs no such check, as the matcher only
+/// tries the occurrences of it as starts.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LiteralCheck {
+    literal_index: Option<usize>,
+}
+
+impl LiteralCheck {
+    /// finds the trailing literal of the given tokens, if it is worth checking.
+    pub(crate) fn new(tokens: &[Token<'_>]) -> Self {
+        let literal_index = match (tokens.first(), tokens.last()) {
+            (Option::Some(Token::Literal(_)), _) => None,
+            (_, Option::Some(Token::Literal(_))) => Some(tokens.len() - 1),
+            _ => None,
+        };
+        return LiteralCheck { literal_index: literal_index };
+    }
+
+    /// checks if the trailing literal rules out a match in `string`, so matching the tokens can be
+    /// skipped. Only matches anchored at the end of `string` can be ruled out.
+    pub(crate) fn rules_out<H: Haystack + ?Sized>(&self, tokens: &[Token<'_>], string: &H, anchor: Anchor, config: &MatchConfig) -> bool {
+        let index = match self.literal_index {
+            Option::Some(index) if config.case_fold.is_none() && matches!(anchor, Anchor::End | Anchor::Both) => index,
+            _ => return false,
+        };
+        let literal = match &tokens[index] {
+            Token::Literal(literal) => literal,
+            _ => unreachable!("the literal check always refers to a literal"),
+        };
+        let start = string.len().checked_sub(literal.get_combined_length());
+        return !start.is_some_and(|start| string.is_unit_boundary(start) && literal.matches_bytes_start(string.subslice(start..string.len())));
+    }
+}
+
+/// returns the bytes that a match of the tokens can start with, in ascending order, or `None` if
+/// that can be any byte or the tokens can match the empty string. Only a leading literal, possibly
+/// after word boundaries, limits the first byte, and only if literals are matched without case
+/// folding.
+pub(crate) fn starting_bytes(tokens: &[Token<'_>], config: &MatchConfig) -> Option<Vec<u8>> {
+    if config.case_fold.is_some() {
+        return None;
+    }
+    let first = tokens.iter().find(|token| **token != Token::WordBoundary)?;
+    return match first {
+        Token::Literal(literal) => literal.chars().next().map(|c| vec![c.encode_utf8(&mut [0; 4]).as_bytes()[0]]),
+        _ => None,
+    };
+}
+
+/// what every string a pattern matches has in common, computed once to reject many strings
+/// cheaply, see [`ParsedGlobString::matches_any`](crate::ParsedGlobString::matches_any).
+#[derive(Debug, Clone)]
+pub(crate) struct Prefilter {
+    min_length: usize, // in bytes
+    required_bytes: Vec<u8>, // the distinct first bytes of the literals
+}
+
+impl Prefilter {
+    pub(crate) fn new(tokens: &[Token<'_>], config: &MatchConfig) -> Self {
+        let mut min_length = 0;
+        let mut required_bytes = Vec::new();
+        for token in tokens {
+            match token {
+                Token::ExactLengthWildcard(length) | Token::MinLengthWildcard(length) => min_length += length,
+                Token::WordBoundary => {},
+                // folded literals may be matched by characters of different lengths
+                Token::Literal(_) if config.case_fold.is_some() => {},
+                Token::Literal(literal) => {
+                    min_length += literal.get_combined_length();
+                    if let Option::Some(c) = literal.chars().next() {
+                        let byte = c.encode_utf8(&mut [0; 4]).as_bytes()[0];
+                        if !required_bytes.contains(&byte) {
+                            required_bytes.push(byte);
+                        }
+                    }
+                },
+            }
+        }
+        return Prefilter { min_length: min_length, required_bytes: required_bytes };
+    }
+
+    /// checks if the pattern can't match anywhere in `string`.
+    pub(crate) fn rules_out(&self, string: &str) -> bool {
+        if string.len() < self.min_length {
+            return true;
+        }
+        if self.required_bytes.is_empty() {
+            return false;
+        }
+        let mut present = [false; 256];
+        for byte in string.bytes() {
+            present[byte as usize] = true;
+        }
+        return self.required_bytes.iter().any(|byte| !present[*byte as usize]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glob_parser::parse_glob_string;
+    use crate::matcher::MatchConfig;
+    use crate::Anchor;
+    use super::{starting_bytes, LiteralCheck, Prefilter};
+
+    fn rules_out(pattern: &str, string: &str, anchor: Anchor) -> bool {
+        let tokens = parse_glob_string(pattern).unwrap();
+        return LiteralCheck::new(&tokens).rules_out(&tokens, string, anchor, &MatchConfig::default());
+    }
+
+    #[test]
+    fn test_rules_out() {
+        assert!(rules_out("*.rs", "main.rs.orig", Anchor::End));
+        assert!(!rules_out("*.rs", "main.rs.orig", Anchor::None));
+        assert!(!rules_out("*.rs", "main.rs", Anchor::Both));
+        assert!(rules_out("*ä", "äx", Anchor::End));
+        assert!(!rules_out("*ä", "aä", Anchor::End));
+        assert!(!rules_out("*.rs", "main.c", Anchor::Start));
+        assert!(!rules_out("main.*", "lib.rs", Anchor::Both));
+        assert!(!rules_out("main.rs", "lib.rs", Anchor::Both));
+    }
+
+    #[test]
+    fn test_prefilter() {
+        let prefilter = |pattern| Prefilter::new(&parse_glob_string(pattern).unwrap(), &MatchConfig::default());
+        assert!(prefilter("??*.rs").rules_out("a.rs"));
+        assert!(!prefilter("??*.rs").rules_out("ab.rs"));
+        assert!(prefilter("*error*Disk*").rules_out("error: disk full"));
+        assert!(!prefilter("*error*Disk*").rules_out("Disk error"));
+        assert!(!prefilter("ä\\b*").rules_out("äx"));
+        assert!(!prefilter("").rules_out(""));
+    }
+
+    #[test]
+    fn test_starting_bytes() {
+        let config = MatchConfig::default();
+        assert_eq!(starting_bytes(&parse_glob_string("src/*").unwrap(), &config), Some(vec![b's']));
+        assert_eq!(starting_bytes(&parse_glob_string("\\b\\bäh").unwrap(), &config), Some(vec![0xC3]));
+        assert_eq!(starting_bytes(&parse_glob_string("?a").unwrap(), &config), None);
+        assert_eq!(starting_bytes(&parse_glob_string("\\b").unwrap(), &config), None);
+        assert_eq!(starting_bytes(&parse_glob_string("").unwrap(), &config), None);
+        let config = MatchConfig { case_fold: Some(&crate::DefaultCaseFold), ..MatchConfig::default() };
+        assert_eq!(starting_bytes(&parse_glob_string("src").unwrap(), &config), None);
+    }
+}
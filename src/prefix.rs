@@ -0,0 +1,142 @@
+use std::collections::BTreeSet;
+use crate::fuzzy::{items, Item};
+use glob_parser::Token;
+use crate::matcher::{is_word_boundary, MatchConfig};
+
+fn is_word_character(c: char) -> bool {
+    return c.is_alphanumeric() || c == '_';
+}
+
+/// adds the items that can be reached from `states` without consuming a character at the byte
+/// offset `position` of `partial`. Word boundaries at the end of `partial` depend on the characters
+/// that follow, so they are left to [`can_be_completed`].
+fn close(items: &[Item], states: &mut BTreeSet<usize>, partial: &str, position: usize) {
+    let mut pending: Vec<usize> = states.iter().copied().collect();
+    while let Option::Some(state) = pending.pop() {
+        let skippable = match items.get(state) {
+            Option::Some(Item::AnyString) => true,
+            Option::Some(Item::WordBoundary) => position < partial.len() && is_word_boundary(partial, position),
+            _ => false,
+        };
+        if skippable && states.insert(state + 1) {
+            pending.push(state + 1);
+        }
+    }
+}
+
+/// checks if the items from `state` on match some string that follows a character of the given
+/// class (word character or not).
+fn can_be_completed(items: &[Item], state: usize, previous_is_word: bool) -> bool {
+    // wildcards can always produce a word character and a non-word character, since the
+    // configuration only ever excludes the separator and the line break
+    let mut visited = BTreeSet::new();
+    // `must_differ` requires the next character to be of the other class after a word boundary
+    let mut pending = vec![(state, previous_is_word, false)];
+    while let Option::Some((state, previous_is_word, must_differ)) = pending.pop() {
+        if !visited.insert((state, previous_is_word, must_differ)) {
+            continue;
+        }
+        let allows = |is_word: bool| !must_differ || is_word != previous_is_word;
+        match items.get(state) {
+            Option::None => {
+                // the end of the string counts as a non-word character
+                if allows(false) {
+                    return true;
+                }
+            },
+            Option::Some(Item::Char(c)) => {
+                if allows(is_word_character(*c)) {
+                    pending.push((state + 1, is_word_character(*c), false));
+                }
+            },
+            Option::Some(Item::AnyChar) => {
+                for is_word in [true, false].into_iter().filter(|is_word| allows(*is_word)) {
+                    pending.push((state + 1, is_word, false));
+                }
+            },
+            Option::Some(Item::AnyString) => {
+                pending.push((state + 1, previous_is_word, must_differ));
+                for is_word in [true, false].into_iter().filter(|is_word| allows(*is_word)) {
+                    pending.push((state, is_word, false));
+                }
+            },
+            Option::Some(Item::WordBoundary) => pending.push((state + 1, previous_is_word, true)),
+        }
+    }
+    return false;
+}
+
+/// checks if `partial` is the start of some string that the token sequence matches completely.
+pub fn could_be_completed(tokens: &[Token<'_>], partial: &str, config: &MatchConfig) -> bool {
+    let items = items(tokens);
+    let fold = |c: char| config.case_fold.map_or(c, |fold| fold.fold(c));
+    let mut states = BTreeSet::from([0]);
+    close(&items, &mut states, partial, 0);
+    for (position, c) in partial.char_indices() {
+        let mut next = BTreeSet::new();
+        for state in states {
+            match items.get(state) {
+                Option::Some(Item::Char(expected)) if fold(*expected) == fold(c) => { next.insert(state + 1); },
+                Option::Some(Item::AnyChar) if config.wildcard_matches(c) => { next.insert(state + 1); },
+                Option::Some(Item::AnyString) if config.wildcard_matches(c) => { next.insert(state); },
+                _ => {},
+            }
+        }
+        if next.is_empty() {
+            return false;
+        }
+        close(&items, &mut next, partial, position + c.len_utf8());
+        states = next;
+    }
+    let previous_is_word = partial.chars().next_back().is_some_and(is_word_character);
+    return states.into_iter().any(|state| can_be_completed(&items, state, previous_is_word));
+}
+
+#[cfg(test)]
+mod tests {
+    use glob_parser::parse_glob_string;
+    use crate::matcher::MatchConfig;
+    use super::could_be_completed;
+
+    fn could_complete(pattern: &str, partial: &str) -> bool {
+        return could_be_completed(&parse_glob_string(pattern).unwrap(), partial, &MatchConfig::default());
+    }
+
+    #[test]
+    fn test_prefixes_of_matches() {
+        assert!(could_complete("src/*.rs", ""));
+        assert!(could_complete("src/*.rs", "sr"));
+        assert!(could_complete("src/*.rs", "src/main.r"));
+        assert!(could_complete("src/*.rs", "src/main.rs"));
+        assert!(could_complete("src/*.rs", "src/main.rs.rs"));
+        assert!(!could_complete("src/*.rs", "tests/"));
+        assert!(!could_complete("", "a"));
+    }
+
+    #[test]
+    fn test_question_marks_limit_the_length() {
+        assert!(could_complete("v?.?", "v1."));
+        assert!(!could_complete("v?.?", "v10"));
+        assert!(!could_complete("v?.?", "v1.0.1"));
+    }
+
+    #[test]
+    fn test_separator_is_respected() {
+        let config = MatchConfig { separator: Some('/'), ..MatchConfig::default() };
+        let tokens = parse_glob_string("*.rs").unwrap();
+        assert!(could_be_completed(&tokens, "main", &config));
+        assert!(!could_be_completed(&tokens, "src/main", &config));
+    }
+
+    #[test]
+    fn test_word_boundaries() {
+        assert!(could_complete("cat\\b*", "cat"));
+        assert!(!could_complete("cat\\b*", "cats"));
+        assert!(could_complete("*\\bcat", "the "));
+        assert!(could_complete("*\\bcat", "the"));
+        assert!(!could_complete("a\\bb", "a"));
+        assert!(could_complete("a\\b?", "a"));
+        assert!(could_complete("?\\b", "x"));
+        assert!(!could_complete("\\b?", " "));
+    }
+}
@@ -0,0 +1,59 @@
+use std::time::{Duration, Instant};
+
+/// counts of what a [`GlobWalker`](crate::GlobWalker) or a [`search`](crate::search) did so far,
+/// passed to their progress callbacks so command line tools can render progress for large trees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct WalkStats {
+    pub(crate) directories_visited: u64,
+    pub(crate) files_matched: u64,
+    pub(crate) bytes_scanned: u64,
+}
+
+impl WalkStats {
+    /// returns the number of directories read so far.
+    pub fn directories_visited(&self) -> u64 {
+        return self.directories_visited;
+    }
+
+    /// returns the number of entries a walker yielded so far, or the number of files in which a
+    /// search found a matching line.
+    pub fn files_matched(&self) -> u64 {
+        return self.files_matched;
+    }
+
+    /// returns the number of bytes of file contents a search read so far. Walkers don't read files.
+    pub fn bytes_scanned(&self) -> u64 {
+        return self.bytes_scanned;
+    }
+}
+
+/// calls a progress callback at most once per interval, and once more when the walk is done.
+pub(crate) struct ProgressReporter<'p> {
+    callback: Box<dyn FnMut(&WalkStats) + Send + 'p>,
+    interval: Duration,
+    last_report: Option<Instant>,
+    finished: bool,
+}
+
+impl<'p> ProgressReporter<'p> {
+    pub(crate) fn new(interval: Duration, callback: impl FnMut(&WalkStats) + Send + 'p) -> Self {
+        return ProgressReporter { callback: Box::new(callback), interval: interval, last_report: None, finished: false };
+    }
+
+    /// calls the callback if the interval passed since it was called last.
+    pub(crate) fn tick(&mut self, stats: &WalkStats) {
+        let now = Instant::now();
+        if self.last_report.is_none_or(|last| now.duration_since(last) >= self.interval) {
+            self.last_report = Some(now);
+            (self.callback)(stats);
+        }
+    }
+
+    /// calls the callback with the final counts, once.
+    pub(crate) fn finish(&mut self, stats: &WalkStats) {
+        if !self.finished {
+            self.finished = true;
+            (self.callback)(stats);
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use crate::{ErrorCode, GlobParseError};
+
+/// where a pattern came from: the name of its source, e.g. the path of a configuration file, the
+/// number of the line in that source, and the line as it was written.
+/// ```
+/// use glob::GlobSet;
+/// let set = GlobSet::from_lines("ignore.txt", "# temporary files\n*.tmp\n*~\n").unwrap();
+/// let provenance = set.provenance(1).unwrap();
+/// assert_eq!((provenance.source(), provenance.line_number(), provenance.text()), ("ignore.txt", 3, "*~"));
+/// assert_eq!(provenance.to_string(), "ignore.txt:3");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Provenance {
+    source: String,
+    line_number: usize,
+    text: String,
+}
+
+impl Provenance {
+    /// creates the provenance of a pattern written as `text` in line `line_number` (starting at 1)
+    /// of `source`.
+    pub fn new(source: impl Into<String>, line_number: usize, text: impl Into<String>) -> Self {
+        return Provenance { source: source.into(), line_number: line_number, text: text.into() };
+    }
+
+    /// returns the name of the source.
+    pub fn source(&self) -> &str {
+        return &self.source;
+    }
+
+    /// returns the number of the line, starting at 1.
+    pub fn line_number(&self) -> usize {
+        return self.line_number;
+    }
+
+    /// returns the line as it was written, without its line break.
+    pub fn text(&self) -> &str {
+        return &self.text;
+    }
+
+    /// returns an estimate of the number of bytes the provenance occupies in memory.
+    pub(crate) fn memory_usage(&self) -> usize {
+        return std::mem::size_of::<Self>() + self.source.capacity() + self.text.capacity();
+    }
+}
+
+impl Display for Provenance {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "{}:{}", self.source, self.line_number);
+    }
+}
+
+/// returned if a pattern of a list can't be parsed, see [`GlobSet::from_lines`](crate::GlobSet::from_lines).
+/// Tells where the pattern came from, in addition to the parse error, whose indices refer to the
+/// line.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PatternListError<'g> {
+    provenance: Provenance,
+    error: GlobParseError<'g>,
+}
+
+impl<'g> PatternListError<'g> {
+    pub(crate) fn new(provenance: Provenance, error: GlobParseError<'g>) -> Self {
+        return PatternListError { provenance: provenance, error: error };
+    }
+
+    /// returns where the invalid pattern came from.
+    pub fn provenance(&self) -> &Provenance {
+        return &self.provenance;
+    }
+
+    /// returns the error of the invalid pattern.
+    pub fn error(&self) -> &GlobParseError<'g> {
+        return &self.error;
+    }
+}
+
+impl<'g> Display for PatternListError<'g> {
+    /// formats the error like `patterns.txt:3: unknown escape sequence ...`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "{}: {}", self.provenance, self.error);
+    }
+}
+
+impl<'g> Error for PatternListError<'g> {}
+
+/// a pattern of a list that can't be parsed, see [`InvalidPatterns`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidPattern {
+    index: usize,
+    pattern: String,
+    code: ErrorCode,
+    message: String,
+}
+
+impl InvalidPattern {
+    pub(crate) fn new(index: usize, pattern: &str, error: &GlobParseError<'_>) -> Self {
+        return InvalidPattern { index: index, pattern: String::from(pattern), code: error.code(), message: error.to_string() };
+    }
+
+    /// returns the index of the pattern in the list.
+    pub fn index(&self) -> usize {
+        return self.index;
+    }
+
+    /// returns the pattern.
+    pub fn pattern(&self) -> &str {
+        return &self.pattern;
+    }
+
+    /// returns the kind of the error.
+    pub fn code(&self) -> ErrorCode {
+        return self.code;
+    }
+
+    /// returns the message of the error, whose indices refer to the pattern.
+    pub fn message(&self) -> &str {
+        return &self.message;
+    }
+}
+
+impl Display for InvalidPattern {
+    /// formats the error like ``pattern 2 `*.\x`: unknown escape sequence ...``.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "pattern {} `{}`: {}", self.index, self.pattern, self.message);
+    }
+}
+
+/// returned if patterns of a list can't be parsed, see
+/// [`GlobSet::from_strings`](crate::GlobSet::from_strings). Unlike [`PatternListError`], it lists
+/// every invalid pattern, so all of them can be fixed at once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidPatterns {
+    errors: Vec<InvalidPattern>, // at least one, ordered by index
+}
+
+impl InvalidPatterns {
+    pub(crate) fn new(errors: Vec<InvalidPattern>) -> Self {
+        return InvalidPatterns { errors: errors };
+    }
+
+    /// returns the invalid patterns, ordered by their index.
+    pub fn errors(&self) -> &[InvalidPattern] {
+        return &self.errors;
+    }
+}
+
+impl Display for InvalidPatterns {
+    /// formats the errors of all invalid patterns, separated by `; `.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, error) in self.errors.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{}", error)?;
+        }
+        return Ok(());
+    }
+}
+
+impl Error for InvalidPatterns {}
@@ -0,0 +1,91 @@
+use std::cmp::Ordering;
+use crate::{GlobSet, ParsedGlobString};
+
+/// how the weights of the patterns matching a string are combined into its score, see
+/// [`GlobSet::score`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Aggregation {
+    /// the sum of the weights, so strings matched by more patterns score higher (the default).
+    #[default]
+    Sum,
+    /// the largest weight, so only the most relevant pattern counts.
+    Max,
+}
+
+impl<'g> GlobSet<'g> {
+    /// adds a pattern with a weight for ranking, see [`score`](Self::score). Patterns added with
+    /// [`add`](Self::add) have the weight 1.
+    pub fn add_weighted(&mut self, pattern: ParsedGlobString<'g>, weight: f64) -> usize {
+        let index = self.add(pattern);
+        self.weights[index] = weight;
+        return index;
+    }
+
+    /// returns the weight of the pattern with the given index.
+    pub fn weight(&self, index: usize) -> Option<f64> {
+        return self.weights.get(index).copied();
+    }
+
+    /// changes the weight of the pattern with the given index.
+    ///
+    /// # Panics
+    /// Panics if there is no pattern with the given index.
+    pub fn set_weight(&mut self, index: usize, weight: f64) {
+        self.weights[index] = weight;
+    }
+
+    /// combines the weights of the patterns that match the string, or returns `None` if no pattern
+    /// matches.
+    pub fn score(&self, string: &str, aggregation: Aggregation) -> Option<f64> {
+        let mut weights = self.matches(string).into_iter().map(|index| self.weights[index]).peekable();
+        weights.peek()?;
+        return Some(match aggregation {
+            Aggregation::Sum => weights.sum(),
+            Aggregation::Max => weights.fold(f64::NEG_INFINITY, f64::max),
+        });
+    }
+
+    /// returns the candidates that any pattern matches together with their
+    /// [scores](Self::score), from the highest score to the lowest. Candidates with the same score
+    /// keep their order.
+    /// ```
+    /// use glob::{Aggregation, GlobSet, ParsedGlobString};
+    /// let mut set = GlobSet::new();
+    /// set.add_weighted(ParsedGlobString::try_from("*report*").unwrap(), 2.0);
+    /// set.add_weighted(ParsedGlobString::try_from("*.pdf").unwrap(), 1.0);
+    /// let ranked = set.rank(["notes.pdf", "report.txt", "image.png", "report.pdf"], Aggregation::Sum);
+    /// assert_eq!(ranked, vec![("report.pdf", 3.0), ("report.txt", 2.0), ("notes.pdf", 1.0)]);
+    /// ```
+    pub fn rank<'c>(&self, candidates: impl IntoIterator<Item = &'c str>, aggregation: Aggregation) -> Vec<(&'c str, f64)> {
+        let mut ranked: Vec<_> = candidates.into_iter()
+            .filter_map(|candidate| self.score(candidate, aggregation).map(|score| (candidate, score)))
+            .collect();
+        ranked.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+        return ranked;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{GlobSet, ParsedGlobString};
+    use super::Aggregation;
+
+    #[test]
+    fn test_score() {
+        let mut set = GlobSet::new();
+        set.add(ParsedGlobString::try_from("a*").unwrap());
+        set.add_weighted(ParsedGlobString::try_from("*z").unwrap(), 5.0);
+        assert_eq!(set.weight(0), Some(1.0));
+        assert_eq!(set.score("az", Aggregation::Sum), Some(6.0));
+        assert_eq!(set.score("az", Aggregation::Max), Some(5.0));
+        assert_eq!(set.score("b", Aggregation::Sum), None);
+        set.set_weight(0, 10.0);
+        assert_eq!(set.score("az", Aggregation::Max), Some(10.0));
+    }
+
+    #[test]
+    fn test_rank_keeps_order_of_ties() {
+        let set: GlobSet = ["*.rs"].into_iter().map(|pattern| ParsedGlobString::try_from(pattern).unwrap()).collect();
+        assert_eq!(set.rank(["b.rs", "a.md", "a.rs"], Aggregation::Max), vec![("b.rs", 1.0), ("a.rs", 1.0)]);
+    }
+}
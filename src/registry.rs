@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::sync::Arc;
+use crate::{GlobParseError, ParsedGlobString};
+
+/// interns pattern strings, so identical patterns are parsed and stored only once.
+///
+/// Unlike the global pattern cache (see [`cached_pattern`](crate::cached_pattern)), a registry
+/// never evicts patterns and is owned by the caller, e.g. by the loader of a configuration file
+/// with thousands of rules that repeat the same patterns.
+/// ```
+/// use glob::PatternRegistry;
+/// let mut registry = PatternRegistry::new();
+/// let rules = ["*.log", "*.tmp", "*.log"];
+/// let handles: Vec<_> = rules.iter().map(|rule| registry.intern(rule).unwrap()).collect();
+/// assert_eq!(registry.len(), 2);
+/// assert_eq!(handles[0], handles[2]);
+/// assert!(handles[2].matches_completely("debug.log"));
+/// ```
+#[derive(Debug, Default)]
+pub struct PatternRegistry {
+    patterns: HashMap<String, PatternHandle>,
+}
+
+/// a cheaply clonable reference to a pattern interned by a [`PatternRegistry`].
+///
+/// Dereferences to the [`ParsedGlobString`]. Handles compare equal (and hash equally) if they refer
+/// to the same interned pattern, which takes constant time.
+#[derive(Debug, Clone)]
+pub struct PatternHandle {
+    pattern: Arc<ParsedGlobString<'static>>,
+}
+
+impl PatternRegistry {
+    /// creates an empty registry.
+    pub fn new() -> Self {
+        return PatternRegistry { patterns: HashMap::new() };
+    }
+
+    /// returns a handle to the compiled form of `pattern`, parsing it only if the registry doesn't
+    /// know it yet. Patterns that fail to parse are not added.
+    pub fn intern<'g>(&mut self, pattern: &'g str) -> Result<PatternHandle, GlobParseError<'g>> {
+        if let Some(handle) = self.patterns.get(pattern) {
+            return Ok(handle.clone());
+        }
+        let handle = PatternHandle { pattern: Arc::new(ParsedGlobString::try_from(pattern)?.into_owned()) };
+        self.patterns.insert(String::from(pattern), handle.clone());
+        return Ok(handle);
+    }
+
+    /// returns the handle for `pattern` if it has been interned before.
+    pub fn get(&self, pattern: &str) -> Option<PatternHandle> {
+        return self.patterns.get(pattern).cloned();
+    }
+
+    /// returns the number of distinct patterns.
+    pub fn len(&self) -> usize {
+        return self.patterns.len();
+    }
+
+    /// checks if no pattern has been interned.
+    pub fn is_empty(&self) -> bool {
+        return self.patterns.is_empty();
+    }
+}
+
+impl Deref for PatternHandle {
+    type Target = ParsedGlobString<'static>;
+    fn deref(&self) -> &Self::Target {
+        return &self.pattern;
+    }
+}
+
+impl PartialEq for PatternHandle {
+    fn eq(&self, other: &Self) -> bool {
+        return Arc::ptr_eq(&self.pattern, &other.pattern);
+    }
+}
+
+impl Eq for PatternHandle {}
+
+impl Hash for PatternHandle {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Arc::as_ptr(&self.pattern).hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use crate::GlobParseError;
+    use super::PatternRegistry;
+
+    #[test]
+    fn test_identical_patterns_are_interned_once() {
+        let mut registry = PatternRegistry::new();
+        let first = registry.intern("src/*.rs").unwrap();
+        let second = registry.intern(&String::from("src/*.rs")).unwrap();
+        let other = registry.intern("src/*.r?").unwrap();
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+        assert_eq!(registry.len(), 2);
+        assert_eq!(HashSet::from([first.clone(), second, other]).len(), 2);
+        assert_eq!(registry.get("src/*.rs"), Some(first));
+        assert_eq!(registry.get("tests/*.rs"), None);
+    }
+
+    #[test]
+    fn test_malformed_patterns_are_not_interned() {
+        let mut registry = PatternRegistry::new();
+        assert_eq!(registry.intern("a\\").unwrap_err(), GlobParseError::UnterminatedEscapeSequence(1));
+        assert!(registry.is_empty());
+    }
+
+    #[test]
+    fn test_handles_outlive_registry() {
+        let handle = {
+            let mut registry = PatternRegistry::new();
+            registry.intern("*.md").unwrap()
+        };
+        assert!(handle.matches_completely("readme.md"));
+    }
+}
@@ -0,0 +1,218 @@
+#[cfg(feature = "serde")]
+use std::fmt::Write;
+use std::ops::Range;
+use crate::{GlobSet, OverrideMatch, ParsedGlobString};
+
+/// a record of matching a pattern against a string, e.g. for an audit log of filtering decisions.
+///
+/// The string and the pattern are identified by ids chosen by the caller, like a file path and the
+/// index of the pattern in a [`GlobSet`]. With the `serde` feature, reports implement `Serialize`.
+/// ```
+/// use glob::{MatchReport, OverrideMatch, ParsedGlobString};
+/// let pattern = ParsedGlobString::try_from("*.key").unwrap();
+/// let report = MatchReport::new("upload-17", 0, &pattern, "id_rsa.key");
+/// assert_eq!(report.range(), Some(0..10));
+/// assert_eq!(report.captures(), ["id_rsa"]);
+/// assert_eq!(report.decision(), OverrideMatch::Whitelist);
+/// let report = report.with_decision(OverrideMatch::Ignore);
+/// assert_eq!(report.decision(), OverrideMatch::Ignore);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct MatchReport {
+    haystack_id: String,
+    pattern_id: usize,
+    range: Option<Range<usize>>,
+    captures: Vec<String>,
+    decision: OverrideMatch,
+}
+
+impl MatchReport {
+    /// matches `pattern` against `haystack` with the options of the pattern, including its
+    /// anchoring, and records the result. The decision is [`OverrideMatch::Whitelist`] if the
+    /// pattern matches and [`OverrideMatch::None`] otherwise; use
+    /// [`with_decision`](Self::with_decision) to record a different one, e.g. for exclude rules.
+    pub fn new(haystack_id: impl Into<String>, pattern_id: usize, pattern: &ParsedGlobString<'_>, haystack: &str) -> Self {
+        let captures = pattern.captures_anchored(haystack, pattern.options().anchor);
+        return MatchReport {
+            haystack_id: haystack_id.into(),
+            pattern_id: pattern_id,
+            range: captures.as_ref().map(|captures| captures.range()),
+            captures: captures.iter().flat_map(|captures| captures.iter()).map(String::from).collect(),
+            decision: if captures.is_some() { OverrideMatch::Whitelist } else { OverrideMatch::None },
+        };
+    }
+
+    /// replaces the decision that is recorded.
+    pub fn with_decision(mut self, decision: OverrideMatch) -> Self {
+        self.decision = decision;
+        return self;
+    }
+
+    /// returns the id of the string.
+    pub fn haystack_id(&self) -> &str {
+        return &self.haystack_id;
+    }
+
+    /// returns the id of the pattern.
+    pub fn pattern_id(&self) -> usize {
+        return self.pattern_id;
+    }
+
+    /// returns the byte range of the leftmost match, or `None` if the pattern didn't match.
+    pub fn range(&self) -> Option<Range<usize>> {
+        return self.range.clone();
+    }
+
+    /// returns the parts of the string matched by the wildcards of the pattern, see
+    /// [`Captures`](crate::Captures). Empty if the pattern didn't match.
+    pub fn captures(&self) -> &[String] {
+        return &self.captures;
+    }
+
+    /// returns the decision that was made about the string.
+    pub fn decision(&self) -> OverrideMatch {
+        return self.decision;
+    }
+}
+
+impl<'g> GlobSet<'g> {
+    /// returns a [`MatchReport`] for each pattern that matches `haystack`, ordered by the index of
+    /// the pattern, which is used as the id of the pattern.
+    /// ```
+    /// use glob::{GlobSet, ParsedGlobString};
+    /// let set: GlobSet = ["*.tmp", "build/*"].into_iter().map(|pattern| ParsedGlobString::try_from(pattern).unwrap()).collect();
+    /// let reports = set.reports("request-1", "src/main.tmp");
+    /// assert_eq!(reports.len(), 1);
+    /// assert_eq!((reports[0].haystack_id(), reports[0].pattern_id()), ("request-1", 0));
+    /// ```
+    pub fn reports(&self, haystack_id: &str, haystack: &str) -> Vec<MatchReport> {
+        return self.patterns().enumerate()
+            .map(|(index, pattern)| MatchReport::new(haystack_id, index, pattern, haystack))
+            .filter(|report| report.range.is_some())
+            .collect();
+    }
+}
+
+/// appends `value` to `json` as a JSON string.
+#[cfg(feature = "serde")]
+fn push_json_string(json: &mut String, value: &str) {
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c < ' ' => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+/// writes [`MatchReport`]s to a [`Write`](std::io::Write) as JSON Lines, one JSON object per line,
+/// e.g. to stream the decisions of a filtering service into an audit log. Requires the `serde`
+/// feature.
+///
+/// Each object has the fields `haystack_id`, `pattern_id`, `range` (an object with `start` and
+/// `end`, or `null` if the pattern didn't match), `captures` and `decision`, like the `Serialize`
+/// implementation of reports produces them. Wrap unbuffered writers like files in a
+/// [`BufWriter`](std::io::BufWriter).
+/// ```
+/// use glob::{JsonLinesWriter, MatchReport, ParsedGlobString};
+/// let pattern = ParsedGlobString::try_from("*.key").unwrap();
+/// let mut writer = JsonLinesWriter::new(Vec::new());
+/// writer.write(&MatchReport::new("upload-17", 0, &pattern, "id_rsa.key")).unwrap();
+/// writer.write(&MatchReport::new("upload-18", 0, &pattern, "notes.txt")).unwrap();
+/// assert_eq!(String::from_utf8(writer.into_inner()).unwrap(), concat!(
+///     "{\"haystack_id\":\"upload-17\",\"pattern_id\":0,\"range\":{\"start\":0,\"end\":10},\"captures\":[\"id_rsa\"],\"decision\":\"Whitelist\"}\n",
+///     "{\"haystack_id\":\"upload-18\",\"pattern_id\":0,\"range\":null,\"captures\":[],\"decision\":\"None\"}\n",
+/// ));
+/// ```
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+pub struct JsonLinesWriter<W: std::io::Write> {
+    writer: W,
+}
+
+#[cfg(feature = "serde")]
+impl<W: std::io::Write> JsonLinesWriter<W> {
+    /// creates a writer that writes the lines to `writer`.
+    pub fn new(writer: W) -> Self {
+        return JsonLinesWriter { writer: writer };
+    }
+
+    /// writes one report as a line.
+    pub fn write(&mut self, report: &MatchReport) -> std::io::Result<()> {
+        let mut line = String::from("{\"haystack_id\":");
+        push_json_string(&mut line, &report.haystack_id);
+        write!(line, ",\"pattern_id\":{},\"range\":", report.pattern_id).unwrap();
+        match &report.range {
+            Option::Some(range) => write!(line, "{{\"start\":{},\"end\":{}}}", range.start, range.end).unwrap(),
+            Option::None => line.push_str("null"),
+        }
+        line.push_str(",\"captures\":[");
+        for (index, capture) in report.captures.iter().enumerate() {
+            if index > 0 {
+                line.push(',');
+            }
+            push_json_string(&mut line, capture);
+        }
+        writeln!(line, "],\"decision\":\"{:?}\"}}", report.decision).unwrap();
+        return self.writer.write_all(line.as_bytes());
+    }
+
+    /// writes a line for each of the reports, e.g. the result of [`GlobSet::reports`].
+    pub fn write_all<'r>(&mut self, reports: impl IntoIterator<Item = &'r MatchReport>) -> std::io::Result<()> {
+        for report in reports {
+            self.write(report)?;
+        }
+        return Ok(());
+    }
+
+    /// flushes the underlying writer.
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        return self.writer.flush();
+    }
+
+    /// returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        return self.writer;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Anchor, GlobBuilder, MatchOptions, OverrideMatch};
+    use super::MatchReport;
+
+    #[test]
+    fn test_report_of_a_mismatch() {
+        let options = MatchOptions { anchor: Anchor::Both, ..MatchOptions::default() };
+        let pattern = GlobBuilder::new("*.rs").options(&options).build().unwrap();
+        let report = MatchReport::new("file", 3, &pattern, "lib.rs.orig");
+        assert_eq!((report.haystack_id(), report.pattern_id()), ("file", 3));
+        assert_eq!(report.range(), None);
+        assert!(report.captures().is_empty());
+        assert_eq!(report.decision(), OverrideMatch::None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_json_lines() {
+        use crate::{GlobSet, JsonLinesWriter, ParsedGlobString};
+        let set: GlobSet = ["*.tmp", "*/\"*"].into_iter().map(|pattern| ParsedGlobString::try_from(pattern).unwrap()).collect();
+        let mut writer = JsonLinesWriter::new(Vec::new());
+        writer.write_all(&set.reports("line\nbreak", "a/\"b.tmp")).unwrap();
+        writer.write(&MatchReport::new("\u{1}\t\\", 1, set.patterns().nth(1).unwrap(), "").with_decision(OverrideMatch::Ignore)).unwrap();
+        let output = String::from_utf8(writer.into_inner()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines, [
+            "{\"haystack_id\":\"line\\nbreak\",\"pattern_id\":0,\"range\":{\"start\":0,\"end\":8},\"captures\":[\"a/\\\"b\"],\"decision\":\"Whitelist\"}",
+            "{\"haystack_id\":\"line\\nbreak\",\"pattern_id\":1,\"range\":{\"start\":0,\"end\":3},\"captures\":[\"a\",\"\"],\"decision\":\"Whitelist\"}",
+            "{\"haystack_id\":\"\\u0001\\t\\\\\",\"pattern_id\":1,\"range\":null,\"captures\":[],\"decision\":\"Ignore\"}",
+        ]);
+    }
+}
@@ -0,0 +1,173 @@
+use crate::matcher::MatchConfig;
+use crate::{Anchor, Captures, GlobParseError, ParsedGlobString};
+
+/// maps HTTP-route style patterns to values, e.g. request handlers.
+///
+/// A route matches a path if its pattern matches the path as a whole, where wildcards never match
+/// the separator `/`: `*` matches any part of a single path segment and `?` exactly one character
+/// within a segment. The parts of the path matched by the wildcards are returned as the route
+/// parameters. If several routes match, the one added first wins.
+/// ```
+/// use glob::RouteSet;
+/// let mut routes = RouteSet::new();
+/// routes.add("/api/*/users/*", "user").unwrap();
+/// routes.add("/api/*/users", "users").unwrap();
+/// let route = routes.route("/api/v2/users/42").unwrap();
+/// assert_eq!(*route.value(), "user");
+/// assert_eq!(route.params().collect::<Vec<_>>(), vec!["v2", "42"]);
+/// assert!(routes.route("/api/v2/users/42/avatar").is_none());
+/// ```
+#[derive(Debug, Clone)]
+pub struct RouteSet<T> {
+    routes: Vec<(ParsedGlobString<'static>, T)>,
+    config: MatchConfig,
+}
+
+/// a route that matched a path, see [`RouteSet::route`].
+#[derive(Debug)]
+pub struct RouteMatch<'r, 's, T> {
+    index: usize,
+    value: &'r T,
+    captures: Captures<'s>,
+}
+
+impl<T> RouteSet<T> {
+
+    /// creates an empty route set using `/` as separator.
+    pub fn new() -> Self {
+        return RouteSet::with_separator('/');
+    }
+
+    /// creates an empty route set, whose wildcards never match `separator`.
+    pub fn with_separator(separator: char) -> Self {
+        return RouteSet { routes: Vec::new(), config: MatchConfig { separator: Some(separator), ..MatchConfig::default() } };
+    }
+
+    /// adds a route. Returns an error if the pattern is malformed.
+    pub fn add<'g>(&mut self, pattern: &'g str, value: T) -> Result<(), GlobParseError<'g>> {
+        let parsed = ParsedGlobString::try_from(pattern)?.into_owned();
+        self.routes.push((parsed, value));
+        return Ok(());
+    }
+
+    /// returns the number of routes.
+    pub fn len(&self) -> usize {
+        return self.routes.len();
+    }
+
+    /// checks if there are no routes.
+    pub fn is_empty(&self) -> bool {
+        return self.routes.is_empty();
+    }
+
+    /// finds the first route matching `path`.
+    pub fn route<'s>(&self, path: &'s str) -> Option<RouteMatch<'_, 's, T>> {
+        for (index, (pattern, value)) in self.routes.iter().enumerate() {
+            if let Some(captures) = pattern.captures_with_config(path, Anchor::Both, &self.config) {
+                return Some(RouteMatch { index: index, value: value, captures: captures });
+            }
+        }
+        return None;
+    }
+}
+
+impl<T> Default for RouteSet<T> {
+    fn default() -> Self {
+        return RouteSet::new();
+    }
+}
+
+impl<'r, 's, T> RouteMatch<'r, 's, T> {
+    /// returns the position of the matching route, in the order the routes were added.
+    pub fn index(&self) -> usize {
+        return self.index;
+    }
+
+    /// returns the value of the matching route.
+    pub fn value(&self) -> &'r T {
+        return self.value;
+    }
+
+    /// returns the `index`-th route parameter, i.e. the part of the path matched by the
+    /// `index`-th wildcard token.
+    pub fn param(&self, index: usize) -> Option<&'s str> {
+        return self.captures.get(index);
+    }
+
+    /// iterates over the route parameters in pattern order.
+    pub fn params(&self) -> impl Iterator<Item = &'s str> + '_ {
+        return self.captures.iter();
+    }
+
+    /// returns the full match information, including the byte ranges of the parameters.
+    pub fn captures(&self) -> &Captures<'s> {
+        return &self.captures;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::GlobParseError;
+    use super::RouteSet;
+
+    fn routes() -> RouteSet<&'static str> {
+        let mut routes = RouteSet::new();
+        routes.add("/", "index").unwrap();
+        routes.add("/users/?*", "user").unwrap();
+        routes.add("/users/*/posts/*", "post").unwrap();
+        routes.add("/static/*.css", "stylesheet").unwrap();
+        return routes;
+    }
+
+    #[test]
+    fn test_route_is_anchored() {
+        let routes = routes();
+        assert_eq!(routes.route("/").map(|route| *route.value()), Some("index"));
+        assert!(routes.route("/index.html").is_none());
+    }
+
+    #[test]
+    fn test_route_params() {
+        let routes = routes();
+        let route = routes.route("/users/alice/posts/7").unwrap();
+        assert_eq!(*route.value(), "post");
+        assert_eq!(route.index(), 2);
+        assert_eq!(route.param(0), Some("alice"));
+        assert_eq!(route.param(1), Some("7"));
+        assert_eq!(route.param(2), None);
+        assert_eq!(route.captures().get_range(1), Some(19..20));
+    }
+
+    #[test]
+    fn test_wildcards_dont_cross_separator() {
+        let routes = routes();
+        assert_eq!(routes.route("/users/alice").map(|route| *route.value()), Some("user"));
+        assert!(routes.route("/users/").is_none());
+        assert!(routes.route("/users/alice/profile").is_none());
+        assert!(routes.route("/static/css/site.css").is_none());
+        assert_eq!(routes.route("/static/site.css").unwrap().param(0), Some("site"));
+    }
+
+    #[test]
+    fn test_first_matching_route_wins() {
+        let mut routes = RouteSet::new();
+        routes.add("/files/*", 1).unwrap();
+        routes.add("/files/readme", 2).unwrap();
+        assert_eq!(routes.route("/files/readme").map(|route| *route.value()), Some(1));
+    }
+
+    #[test]
+    fn test_custom_separator() {
+        let mut routes = RouteSet::with_separator('.');
+        routes.add("orders.*.created", ()).unwrap();
+        assert!(routes.route("orders.eu.created").is_some());
+        assert!(routes.route("orders.eu.de.created").is_none());
+    }
+
+    #[test]
+    fn test_add_malformed_route() {
+        let mut routes = RouteSet::new();
+        assert_eq!(routes.add("/\\n", ()), Err(GlobParseError::UnknownEscapeSequence(1, "\\n")));
+        assert!(routes.is_empty());
+    }
+}
@@ -0,0 +1,201 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::Duration;
+use crate::progress::ProgressReporter;
+use crate::{is_binary, GlobWalker, ParsedGlobString, PathPattern, WalkStats};
+
+/// a line found by [`search`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchMatch {
+    path: PathBuf,
+    line_number: u64,
+    line: String,
+}
+
+impl SearchMatch {
+    /// returns the path of the file, the root directory joined with its relative path.
+    pub fn path(&self) -> &Path {
+        return &self.path;
+    }
+
+    /// returns the number of the line in the file, starting at 1.
+    pub fn line_number(&self) -> u64 {
+        return self.line_number;
+    }
+
+    /// returns the line without its line break, with replacement characters in place of invalid
+    /// UTF-8 sequences.
+    pub fn line(&self) -> &str {
+        return &self.line;
+    }
+}
+
+/// the iterator returned by [`search`].
+pub struct Search<'p, 'g> {
+    walker: GlobWalker<'p, 'g>,
+    content_pattern: &'p ParsedGlobString<'g>,
+    found: VecDeque<SearchMatch>, // in the file read last
+    stats: WalkStats, // of the files searched, the walker counts the directories
+    progress: Option<ProgressReporter<'p>>,
+}
+
+/// searches the files below `root` whose relative paths match `path_pattern` for lines that
+/// `content_pattern` matches, and yields them in the order of the files and of the lines in each
+/// file, like `rg` does.
+///
+/// The files are found like [`GlobWalker::with_path_pattern`] finds them, so directories below
+/// which the path pattern can't match are not read. Only regular files are searched; binary files,
+/// see [`is_binary`], are skipped. Lines are matched like by [`filter_lines`](crate::filter_lines).
+/// Errors while reading a directory or a file are yielded, and the search continues afterwards.
+/// ```no_run
+/// use glob::{search, ParsedGlobString, PathPattern};
+/// let files = PathPattern::try_from("src/**/*.rs").unwrap();
+/// let todo = ParsedGlobString::try_from("TODO*").unwrap();
+/// for found in search(".", &files, &todo) {
+///     let found = found.unwrap();
+///     println!("{}:{}: {}", found.path().display(), found.line_number(), found.line());
+/// }
+/// ```
+pub fn search<'p, 'g>(root: impl Into<PathBuf>, path_pattern: &'p PathPattern<'g>, content_pattern: &'p ParsedGlobString<'g>) -> Search<'p, 'g> {
+    return Search {
+        walker: GlobWalker::with_path_pattern(root, path_pattern),
+        content_pattern: content_pattern,
+        found: VecDeque::new(),
+        stats: WalkStats::default(),
+        progress: None,
+    };
+}
+
+impl<'p, 'g> Search<'p, 'g> {
+    /// calls `callback` with the counts of the search so far while it runs, at most once per
+    /// `interval`, and once more when it is done, like [`GlobWalker::on_progress`].
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use glob::{search, ParsedGlobString, PathPattern};
+    /// let files = PathPattern::try_from("**/*.log").unwrap();
+    /// let errors = ParsedGlobString::try_from("ERROR").unwrap();
+    /// let found = search("/var/log", &files, &errors).on_progress(Duration::from_millis(100), |stats| {
+    ///     eprint!("\r{} bytes in {} directories", stats.bytes_scanned(), stats.directories_visited());
+    /// });
+    /// println!("{} errors", found.filter_map(Result::ok).count());
+    /// ```
+    pub fn on_progress(mut self, interval: Duration, callback: impl FnMut(&WalkStats) + Send + 'p) -> Self {
+        self.progress = Some(ProgressReporter::new(interval, callback));
+        return self;
+    }
+
+    /// makes the search stop once `cancel` is set, like [`GlobWalker::cancel_on`]. The flag is
+    /// checked before each file.
+    pub fn cancel_on(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.walker = self.walker.cancel_on(cancel);
+        return self;
+    }
+
+    /// returns the counts of the search so far.
+    pub fn stats(&self) -> WalkStats {
+        return WalkStats { directories_visited: self.walker.stats().directories_visited, ..self.stats };
+    }
+
+    fn search_file(&mut self, path: PathBuf) -> io::Result<()> {
+        let contents = fs::read(&path)?;
+        self.stats.bytes_scanned += contents.len() as u64;
+        if is_binary(&contents) {
+            return Ok(());
+        }
+        let lines = contents.strip_suffix(b"\n").unwrap_or(&contents).split(|byte| *byte == b'\n');
+        for (index, line) in lines.enumerate() {
+            let line = String::from_utf8_lossy(line.strip_suffix(b"\r").unwrap_or(line));
+            if self.content_pattern.is_match(&line) {
+                self.found.push_back(SearchMatch { path: path.clone(), line_number: index as u64 + 1, line: line.into_owned() });
+            }
+        }
+        if !self.found.is_empty() {
+            self.stats.files_matched += 1;
+        }
+        return Ok(());
+    }
+}
+
+impl<'p, 'g> Iterator for Search<'p, 'g> {
+    type Item = io::Result<SearchMatch>;
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.found.is_empty() {
+            let entry = match self.walker.next_entry() {
+                Option::None => {
+                    let stats = self.stats();
+                    if let Option::Some(progress) = &mut self.progress {
+                        progress.finish(&stats);
+                    }
+                    return None;
+                },
+                Option::Some(Err(error)) => return Some(Err(error)),
+                Option::Some(Ok(entry)) => entry,
+            };
+            if entry.file_type().is_file() {
+                let result = self.search_file(entry.into_path());
+                let stats = self.stats();
+                if let Option::Some(progress) = &mut self.progress {
+                    progress.tick(&stats);
+                }
+                if let Err(error) = result {
+                    return Some(Err(error));
+                }
+            }
+        }
+        return self.found.pop_front().map(Ok);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use crate::walk::tests::create_tree;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use crate::{ParsedGlobString, PathPattern, WalkStats};
+    use super::search;
+
+    #[test]
+    fn test_search() {
+        let root = create_tree("search", &["src/lib.rs", "src/bin/cli.rs", "README.md", "src/data.bin"]);
+        fs::write(root.join("src/lib.rs"), "// TODO: docs\nfn main() {}\r\n// TODO: tests\n").unwrap();
+        fs::write(root.join("src/bin/cli.rs"), "// todo\n// TODO\n").unwrap();
+        fs::write(root.join("README.md"), "TODO: readme\n").unwrap();
+        fs::write(root.join("src/data.bin"), "\0TODO").unwrap();
+        let files = PathPattern::try_from("src/**").unwrap();
+        let todo = ParsedGlobString::try_from("TODO").unwrap();
+        let found: Vec<_> = search(&root, &files, &todo)
+            .map(|found| found.unwrap())
+            .map(|found| (found.path().strip_prefix(&root).unwrap().to_string_lossy().replace('\\', "/"), found.line_number(), found.line().to_string()))
+            .collect();
+        assert_eq!(found, vec![
+            ("src/bin/cli.rs".to_string(), 2, "// TODO".to_string()),
+            ("src/lib.rs".to_string(), 1, "// TODO: docs".to_string()),
+            ("src/lib.rs".to_string(), 3, "// TODO: tests".to_string()),
+        ]);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_progress() {
+        let root = create_tree("search-progress", &["a/x.txt", "a/y.txt", "b/z.txt"]);
+        let reports = Arc::new(Mutex::new(Vec::<WalkStats>::new()));
+        let files = PathPattern::try_from("**").unwrap();
+        let content = ParsedGlobString::try_from("a/").unwrap();
+        let log = Arc::clone(&reports);
+        let mut found = search(&root, &files, &content).on_progress(Duration::from_secs(3600), move |stats| log.lock().unwrap().push(*stats));
+        assert!(found.next().is_some());
+        assert!(found.next().is_some());
+        assert!(found.next().is_none());
+        assert_eq!((found.stats().directories_visited(), found.stats().files_matched(), found.stats().bytes_scanned()), (3, 2, 21));
+        // the first report comes right away, the next one only when the search is done
+        let reports = reports.lock().unwrap();
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[1], found.stats());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
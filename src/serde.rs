@@ -0,0 +1,81 @@
+//! helpers to (de)serialize struct fields as compiled patterns, for use with
+//! `#[serde(with = "...")]`. Requires the `serde` feature.
+//!
+//! Patterns are read from strings and parsed with the default options; they are written in the
+//! syntax they were parsed from, see the [`Display`](std::fmt::Display) implementation of
+//! [`ParsedGlobString`]. An invalid pattern fails deserialization with an error that names the
+//! pattern and the problem, so deserializers that report the position of errors, or wrappers like
+//! `serde_path_to_error`, point at the offending field.
+//! ```
+//! # use glob::{GlobSet, ParsedGlobString};
+//! #[derive(serde::Deserialize, serde::Serialize)]
+//! struct UploadConfig {
+//!     #[serde(with = "glob::serde::pattern")]
+//!     name: ParsedGlobString<'static>,
+//!     #[serde(with = "glob::serde::pattern_set")]
+//!     deny: GlobSet<'static>,
+//! }
+//! ```
+
+use serde::de::Error;
+use serde::ser::SerializeSeq;
+use serde::{Deserialize, Deserializer, Serializer};
+use crate::{GlobSet, ParsedGlobString};
+
+fn parse<E: Error>(pattern: &str) -> Result<ParsedGlobString<'static>, E> {
+    return ParsedGlobString::try_from(pattern)
+        .map(ParsedGlobString::into_owned)
+        .map_err(|error| E::custom(format!("invalid pattern `{}`: {}", pattern, error)));
+}
+
+/// (de)serializes a [`ParsedGlobString`] as a string.
+/// ```
+/// use serde::de::{value, IntoDeserializer};
+/// let pattern = glob::serde::pattern::deserialize::<value::StrDeserializer<value::Error>>("*.rs".into_deserializer()).unwrap();
+/// assert!(pattern.matches_completely("lib.rs"));
+/// let error = glob::serde::pattern::deserialize::<value::StrDeserializer<value::Error>>("*.\\x".into_deserializer()).unwrap_err();
+/// assert_eq!(error.to_string(), "invalid pattern `*.\\x`: unknown escape sequence `\\x` at index 2");
+/// ```
+pub mod pattern {
+    use super::*;
+
+    /// deserializes a pattern from a string.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<ParsedGlobString<'static>, D::Error> {
+        let pattern = String::deserialize(deserializer)?;
+        return parse(&pattern);
+    }
+
+    /// serializes a pattern as a string.
+    pub fn serialize<S: Serializer>(pattern: &ParsedGlobString<'_>, serializer: S) -> Result<S::Ok, S::Error> {
+        return serializer.collect_str(pattern);
+    }
+}
+
+/// (de)serializes a [`GlobSet`] as a list of strings, in the order of the indices of the patterns.
+/// Errors list every invalid pattern with its index in the list.
+/// ```
+/// use serde::de::{value, IntoDeserializer};
+/// type Deserializer = value::SeqDeserializer<std::vec::IntoIter<&'static str>, value::Error>;
+/// let set = glob::serde::pattern_set::deserialize::<Deserializer>(vec!["*.exe", "*.bat"].into_deserializer()).unwrap();
+/// assert_eq!(set.matches("run.bat"), vec![1]);
+/// let error = glob::serde::pattern_set::deserialize::<Deserializer>(vec!["*.exe", "*.\\x"].into_deserializer()).unwrap_err();
+/// assert_eq!(error.to_string(), "pattern 1 `*.\\x`: unknown escape sequence `\\x` at index 2");
+/// ```
+pub mod pattern_set {
+    use super::*;
+
+    /// deserializes a set from a list of strings, see [`GlobSet::from_strings`].
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<GlobSet<'static>, D::Error> {
+        let patterns = Vec::<String>::deserialize(deserializer)?;
+        return GlobSet::from_strings(patterns).map_err(D::Error::custom);
+    }
+
+    /// serializes a set as a list of strings.
+    pub fn serialize<S: Serializer>(set: &GlobSet<'_>, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut sequence = serializer.serialize_seq(Some(set.len()))?;
+        for pattern in set.patterns() {
+            sequence.serialize_element(&pattern.to_string())?;
+        }
+        return sequence.end();
+    }
+}
@@ -0,0 +1,541 @@
+use std::collections::HashMap;
+use std::mem::size_of;
+use std::ops::Range;
+use std::sync::Arc;
+use glob_parser::Token;
+use crate::provenance::{InvalidPattern, InvalidPatterns, PatternListError};
+use crate::{Anchor, MatchOptions, Matcher, ParsedGlobString, Provenance};
+
+/// a set of patterns that are matched against a string together.
+///
+/// Each pattern is matched with its own options, including its anchoring (see
+/// [`ParsedGlobString::is_match`]), so patterns built with different options can be mixed.
+/// ```
+/// use glob::{Anchor, GlobBuilder, GlobSet, MatchOptions, ParsedGlobString};
+/// let mut set = GlobSet::new();
+/// set.add(ParsedGlobString::try_from("ERROR").unwrap());
+/// let whole_line = MatchOptions { anchor: Anchor::Both, ..MatchOptions::default() };
+/// set.add(GlobBuilder::new("WARN*disk*").options(&whole_line).build().unwrap());
+/// assert!(set.is_match("[ERROR] cpu"));
+/// assert_eq!(set.matches("WARN: disk full, ERROR follows"), vec![0, 1]);
+/// assert!(!set.is_match("[WARN] disk full"));
+/// ```
+///
+/// Patterns that must match at the start of the string and begin with a literal are bucketed by
+/// the first byte of that literal, so matching only tries the patterns whose bucket fits the first
+/// byte of the string. Likewise, patterns that must match at the end and end with a literal are
+/// bucketed by its last byte. Large sets of anchored patterns, like route tables or file extension
+/// rules, are matched much faster. [`add_anchored`](Self::add_anchored) mixes patterns with
+/// different anchoring in one set.
+///
+/// # Order of results
+///
+/// The index of a pattern is the number of patterns added before it, and all methods that report
+/// several patterns, like [`matches`](Self::matches), [`matches_into`](Self::matches_into) and
+/// [`match_ranges`](Self::match_ranges), report them in ascending order of their indices, i.e. in
+/// the order they were added. The bucketing above never changes that order, so rule engines can
+/// rely on it for the precedence of their rules without sorting the results.
+#[derive(Debug, Clone, Default)]
+pub struct GlobSet<'g> {
+    patterns: Vec<ParsedGlobString<'g>>,
+    provenances: Vec<Option<Arc<Provenance>>>, // one for each pattern
+    pub(crate) weights: Vec<f64>, // one for each pattern
+    dispatch: ByteDispatch,
+}
+
+/// the indices of the patterns of a [`GlobSet`], bucketed by the byte a string must start or end
+/// with for them to match.
+#[derive(Debug, Clone, Default)]
+struct ByteDispatch {
+    by_first_byte: HashMap<u8, Vec<usize>>,
+    by_last_byte: HashMap<u8, Vec<usize>>,
+    undispatched: Vec<usize>, // patterns that may match strings starting and ending with any byte
+}
+
+impl ByteDispatch {
+    fn add(&mut self, index: usize, pattern: &ParsedGlobString<'_>) {
+        if let Option::Some(byte) = required_first_byte(pattern) {
+            self.by_first_byte.entry(byte).or_default().push(index);
+        } else if let Option::Some(byte) = required_last_byte(pattern) {
+            self.by_last_byte.entry(byte).or_default().push(index);
+        } else {
+            self.undispatched.push(index);
+        }
+    }
+
+    /// returns an estimate of the number of bytes the buckets occupy on the heap.
+    fn memory_usage(&self) -> usize {
+        let buckets = |buckets: &HashMap<u8, Vec<usize>>| {
+            // the table stores a control byte for each entry besides the entry itself
+            let table = buckets.capacity() * (size_of::<(u8, Vec<usize>)>() + 1);
+            return table + buckets.values().map(|bucket| bucket.capacity() * size_of::<usize>()).sum::<usize>();
+        };
+        return buckets(&self.by_first_byte) + buckets(&self.by_last_byte) + self.undispatched.capacity() * size_of::<usize>();
+    }
+
+    /// returns the indices of the patterns that may match the string, in ascending order.
+    fn candidates(&self, string: &str) -> Vec<usize> {
+        let mut candidates = Vec::new();
+        self.candidates_into(string, &mut candidates);
+        return candidates;
+    }
+
+    /// replaces the contents of `candidates` with the indices of the patterns that may match the
+    /// string, in ascending order.
+    fn candidates_into(&self, string: &str, candidates: &mut Vec<usize>) {
+        let first = bucket(&self.by_first_byte, string.as_bytes().first());
+        let last = bucket(&self.by_last_byte, string.as_bytes().last());
+        // each pattern is in one of the lists, so they only have to be merged
+        candidates.clear();
+        candidates.reserve(first.len() + last.len() + self.undispatched.len());
+        candidates.extend_from_slice(first);
+        candidates.extend_from_slice(last);
+        candidates.extend_from_slice(&self.undispatched);
+        candidates.sort_unstable();
+    }
+}
+
+/// returns the indices in the bucket for `byte`, if there is a byte.
+fn bucket<'a>(buckets: &'a HashMap<u8, Vec<usize>>, byte: Option<&u8>) -> &'a [usize] {
+    return match byte.and_then(|byte| buckets.get(byte)) {
+        Option::Some(bucket) => bucket.as_slice(),
+        Option::None => &[],
+    };
+}
+
+/// returns the byte every string the pattern matches starts with, if the pattern is anchored at
+/// the start and begins with a literal that is matched exactly.
+fn required_first_byte(pattern: &ParsedGlobString<'_>) -> Option<u8> {
+    let anchor = pattern.options().anchor;
+    if !(anchor == Anchor::Start || anchor == Anchor::Both) || pattern.config().case_fold.is_some() {
+        return None;
+    }
+    match pattern.tokens().first() {
+        Option::Some(Token::Literal(literal)) => {
+            let first = literal.chars().next()?;
+            return Some(first.encode_utf8(&mut [0; 4]).as_bytes()[0]);
+        },
+        _ => return None,
+    }
+}
+
+/// returns the byte every string the pattern matches ends with, if the pattern is anchored at the
+/// end and ends with a literal that is matched exactly.
+fn required_last_byte(pattern: &ParsedGlobString<'_>) -> Option<u8> {
+    let anchor = pattern.options().anchor;
+    if !(anchor == Anchor::End || anchor == Anchor::Both) || pattern.config().case_fold.is_some() {
+        return None;
+    }
+    match pattern.tokens().last() {
+        Option::Some(Token::Literal(literal)) => {
+            let last = literal.chars().last()?;
+            return last.encode_utf8(&mut [0; 4]).as_bytes().last().copied();
+        },
+        _ => return None,
+    }
+}
+
+/// a pattern of a [`GlobSet`] that matched, and where it matched, see
+/// [`GlobSet::match_ranges`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetMatch {
+    index: usize,
+    range: Range<usize>,
+    provenance: Option<Arc<Provenance>>,
+}
+
+impl SetMatch {
+    /// returns the index of the pattern that matched.
+    pub fn index(&self) -> usize {
+        return self.index;
+    }
+
+    /// returns the byte range of the leftmost occurrence of the pattern in the string.
+    pub fn range(&self) -> Range<usize> {
+        return self.range.clone();
+    }
+
+    /// returns where the pattern that matched came from, if the set knows it.
+    pub fn provenance(&self) -> Option<&Provenance> {
+        return self.provenance.as_deref();
+    }
+}
+
+impl<'g> GlobSet<'g> {
+    /// creates an empty set.
+    pub fn new() -> Self {
+        return GlobSet { patterns: Vec::new(), provenances: Vec::new(), weights: Vec::new(), dispatch: ByteDispatch::default() };
+    }
+
+    /// creates a set from a list of patterns, one per line, e.g. the contents of a configuration
+    /// file. Empty lines and lines starting with `#` are skipped. Each pattern remembers its
+    /// [provenance](Self::provenance), with `source` as the name of the source, and errors tell
+    /// which line contains the invalid pattern.
+    /// ```
+    /// use glob::GlobSet;
+    /// let error = GlobSet::from_lines("deny.txt", "*.exe\n\n*.\\x\n").unwrap_err();
+    /// assert_eq!(error.to_string(), "deny.txt:3: unknown escape sequence `\\x` at index 2");
+    /// ```
+    pub fn from_lines(source: &str, contents: &'g str) -> Result<Self, PatternListError<'g>> {
+        let mut set = GlobSet::new();
+        for (index, line) in contents.lines().enumerate() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let provenance = Provenance::new(source, index + 1, line);
+            match ParsedGlobString::try_from(line) {
+                Ok(pattern) => { set.add_with_provenance(pattern, provenance); },
+                Err(error) => return Err(PatternListError::new(provenance, error)),
+            }
+        }
+        return Ok(set);
+    }
+
+    /// adds a pattern and returns its index, which identifies it in the results of
+    /// [`matches`](Self::matches). Indices are assigned in the order the patterns are added.
+    pub fn add(&mut self, pattern: ParsedGlobString<'g>) -> usize {
+        return self.push(pattern, None);
+    }
+
+    /// adds a pattern that is matched with the given options, including their anchoring,
+    /// replacing the options it was built with, see [`add`](Self::add). Rule files often mix
+    /// case-sensitive and case-insensitive rules; each pattern of the set keeps its own options, and
+    /// the bucketing by first and last byte skips patterns whose literals are case-folded.
+    /// ```
+    /// use glob::{Anchor, DefaultCaseFold, GlobSet, MatchOptions, ParsedGlobString};
+    /// let exact = MatchOptions { anchor: Anchor::Both, ..MatchOptions::default() };
+    /// let any_case = MatchOptions { case_fold: Some(&DefaultCaseFold), ..exact };
+    /// let mut set = GlobSet::new();
+    /// set.add_with_options(ParsedGlobString::try_from("Makefile").unwrap(), &exact);
+    /// set.add_with_options(ParsedGlobString::try_from("readme*").unwrap(), &any_case);
+    /// assert_eq!(set.matches("README.md"), vec![1]);
+    /// assert_eq!(set.matches("makefile"), vec![]);
+    /// ```
+    pub fn add_with_options(&mut self, mut pattern: ParsedGlobString<'g>, options: &MatchOptions) -> usize {
+        pattern.config = options.apply_to(pattern.config);
+        pattern.anchor = options.anchor;
+        return self.push(pattern, None);
+    }
+
+    /// adds a pattern that matches anchored as requested, replacing the anchoring of its options,
+    /// see [`add`](Self::add). This mixes rules for prefixes, suffixes and whole strings in one set.
+    /// ```
+    /// use glob::{Anchor, GlobSet, ParsedGlobString};
+    /// let mut set = GlobSet::new();
+    /// set.add_anchored(ParsedGlobString::try_from(".tar.gz").unwrap(), Anchor::End);
+    /// set.add_anchored(ParsedGlobString::try_from("tmp/").unwrap(), Anchor::Start);
+    /// set.add_anchored(ParsedGlobString::try_from("core").unwrap(), Anchor::Both);
+    /// assert_eq!(set.matches("tmp/backup.tar.gz"), vec![0, 1]);
+    /// assert_eq!(set.matches("src/tmp/core.rs"), vec![]);
+    /// assert_eq!(set.matches("core"), vec![2]);
+    /// ```
+    pub fn add_anchored(&mut self, mut pattern: ParsedGlobString<'g>, anchor: Anchor) -> usize {
+        pattern.anchor = anchor;
+        return self.push(pattern, None);
+    }
+
+    /// adds a pattern together with where it came from, see [`add`](Self::add).
+    pub fn add_with_provenance(&mut self, pattern: ParsedGlobString<'g>, provenance: Provenance) -> usize {
+        return self.push(pattern, Some(Arc::new(provenance)));
+    }
+
+    fn push(&mut self, pattern: ParsedGlobString<'g>, provenance: Option<Arc<Provenance>>) -> usize {
+        let index = self.patterns.len();
+        self.dispatch.add(index, &pattern);
+        self.patterns.push(pattern);
+        self.provenances.push(provenance);
+        self.weights.push(1.0);
+        return index;
+    }
+
+    /// converts the set into one that doesn't borrow the strings its patterns were parsed from.
+    pub fn into_owned(self) -> GlobSet<'static> {
+        return GlobSet {
+            patterns: self.patterns.into_iter().map(ParsedGlobString::into_owned).collect(),
+            provenances: self.provenances,
+            weights: self.weights,
+            dispatch: self.dispatch,
+        };
+    }
+
+    /// returns where the pattern with the given index came from, if it was added with a
+    /// provenance.
+    pub fn provenance(&self, index: usize) -> Option<&Provenance> {
+        return self.provenances.get(index)?.as_deref();
+    }
+
+    /// returns the number of patterns.
+    pub fn len(&self) -> usize {
+        return self.patterns.len();
+    }
+
+    /// checks if the set has no patterns.
+    pub fn is_empty(&self) -> bool {
+        return self.patterns.is_empty();
+    }
+
+    /// returns an estimate of the number of bytes this set occupies in memory, including its
+    /// patterns, see [`ParsedGlobString::memory_usage`]. Provenances shared by several patterns
+    /// are counted for each of them.
+    /// ```
+    /// use glob::{GlobSet, ParsedGlobString};
+    /// let mut set = GlobSet::new();
+    /// let empty = set.memory_usage();
+    /// set.add(ParsedGlobString::try_from("*.rs").unwrap());
+    /// assert!(set.memory_usage() > empty);
+    /// ```
+    pub fn memory_usage(&self) -> usize {
+        let patterns = self.patterns.iter().map(|pattern| pattern.memory_usage()).sum::<usize>()
+            + (self.patterns.capacity() - self.patterns.len()) * size_of::<ParsedGlobString<'g>>();
+        let provenances = self.provenances.iter().flatten().map(|provenance| provenance.memory_usage()).sum::<usize>()
+            + self.provenances.capacity() * size_of::<Option<Arc<Provenance>>>();
+        return size_of::<Self>() + patterns + provenances + self.weights.capacity() * size_of::<f64>() + self.dispatch.memory_usage();
+    }
+
+    /// returns the pattern with the given index.
+    pub fn get(&self, index: usize) -> Option<&ParsedGlobString<'g>> {
+        return self.patterns.get(index);
+    }
+
+    /// iterates over the patterns in the order of their indices.
+    pub fn patterns(&self) -> impl Iterator<Item = &ParsedGlobString<'g>> + '_ {
+        return self.patterns.iter();
+    }
+
+    /// checks if any pattern of the set matches the given string. Stops at the first pattern that
+    /// matches.
+    pub fn is_match(&self, string: &str) -> bool {
+        return self.dispatch.candidates(string).into_iter().any(|index| self.patterns[index].is_match(string));
+    }
+
+    /// returns the indices of all patterns that match the given string, in the order the patterns
+    /// were added, see [Order of results](Self#order-of-results).
+    pub fn matches(&self, string: &str) -> Vec<usize> {
+        let mut matches = Vec::new();
+        self.matches_into(string, &mut matches);
+        return matches;
+    }
+
+    /// replaces the contents of `matches` with the indices of all patterns that match the given
+    /// string, in the order the patterns were added, like [`matches`](Self::matches). Reusing the
+    /// vector saves allocating one for each string.
+    /// ```
+    /// use glob::GlobSet;
+    /// let rules = GlobSet::from_strings(["*.log", "/var/*", "*"]).unwrap();
+    /// let mut matches = Vec::new();
+    /// for path in ["/var/log/syslog.log", "/home/notes.txt"] {
+    ///     rules.matches_into(path, &mut matches);
+    ///     // the first rule that matches takes precedence
+    ///     println!("{} is handled by rule {}", path, matches[0]);
+    /// }
+    /// assert_eq!(matches, vec![2]);
+    /// ```
+    pub fn matches_into(&self, string: &str, matches: &mut Vec<usize>) {
+        self.dispatch.candidates_into(string, matches);
+        matches.retain(|index| self.patterns[*index].is_match(string));
+    }
+
+    /// returns each pattern that matches the given string together with the byte range of its
+    /// leftmost occurrence, ordered by the index of the pattern. This explains which pattern matched
+    /// which part of the string, e.g. in audit logs.
+    /// ```
+    /// use glob::{GlobSet, ParsedGlobString};
+    /// let set: GlobSet = ["*.tmp", "build/*", "*~"].into_iter().map(|pattern| ParsedGlobString::try_from(pattern).unwrap()).collect();
+    /// let matches = set.match_ranges("build/cache.tmp");
+    /// let explanation: Vec<_> = matches.iter().map(|m| (m.index(), m.range())).collect();
+    /// assert_eq!(explanation, vec![(0, 0..15), (1, 0..6)]);
+    /// ```
+    pub fn match_ranges(&self, string: &str) -> Vec<SetMatch> {
+        let mut matches = Vec::new();
+        for index in self.dispatch.candidates(string) {
+            let pattern = &self.patterns[index];
+            if let Some(captures) = pattern.captures_anchored(string, pattern.options().anchor) {
+                matches.push(SetMatch { index: index, range: captures.range(), provenance: self.provenances[index].clone() });
+            }
+        }
+        return matches;
+    }
+}
+
+impl GlobSet<'static> {
+    /// parses a list of patterns, e.g. an array from a TOML or YAML configuration, into a set. The
+    /// index of each pattern in the list is its index in the set. If patterns are invalid, the error
+    /// lists all of them, with their indices.
+    /// ```
+    /// use glob::GlobSet;
+    /// let set = GlobSet::from_strings(["*.exe", "*.bat"]).unwrap();
+    /// assert_eq!(set.matches("run.bat"), vec![1]);
+    /// let error = GlobSet::from_strings(vec![String::from("a\\"), String::from("*"), String::from("\\x")]).unwrap_err();
+    /// assert_eq!(error.errors().iter().map(|error| error.index()).collect::<Vec<_>>(), vec![0, 2]);
+    /// assert_eq!(error.to_string(), "pattern 0 `a\\`: unterminated escape sequence at index 1; pattern 2 `\\x`: unknown escape sequence `\\x` at index 0");
+    /// ```
+    pub fn from_strings(patterns: impl IntoIterator<Item = impl AsRef<str>>) -> Result<Self, InvalidPatterns> {
+        let mut set = GlobSet::new();
+        let mut errors = Vec::new();
+        for (index, pattern) in patterns.into_iter().enumerate() {
+            let pattern = pattern.as_ref();
+            match ParsedGlobString::try_from(pattern) {
+                Ok(parsed) => { set.add(parsed.into_owned()); },
+                Err(error) => errors.push(InvalidPattern::new(index, pattern, &error)),
+            }
+        }
+        if !errors.is_empty() {
+            return Err(InvalidPatterns::new(errors));
+        }
+        return Ok(set);
+    }
+}
+
+impl<'g> FromIterator<ParsedGlobString<'g>> for GlobSet<'g> {
+    fn from_iter<I: IntoIterator<Item = ParsedGlobString<'g>>>(patterns: I) -> Self {
+        let mut set = GlobSet::new();
+        for pattern in patterns {
+            set.add(pattern);
+        }
+        return set;
+    }
+}
+
+impl<'g> Matcher for GlobSet<'g> {
+    fn is_match(&self, haystack: &str) -> bool {
+        return GlobSet::is_match(self, haystack);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Anchor, DefaultCaseFold, GlobBuilder, GlobParseError, MatchOptions, ParsedGlobString};
+    use super::{GlobSet, SetMatch};
+
+    fn set(patterns: &[&'static str]) -> GlobSet<'static> {
+        return patterns.iter().map(|pattern| ParsedGlobString::try_from(*pattern).unwrap()).collect();
+    }
+
+    #[test]
+    fn test_empty_set_matches_nothing() {
+        let set = GlobSet::new();
+        assert!(set.is_empty());
+        assert!(!set.is_match(""));
+        assert_eq!(set.matches("anything"), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_matches() {
+        let set = set(&["*.rs", "src/*", "*.md"]);
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.matches("src/lib.rs"), vec![0, 1]);
+        assert_eq!(set.matches("README.md"), vec![2]);
+        assert!(!set.is_match("Cargo.toml"));
+        assert!(set.get(1).unwrap().matches_completely("src/main.rs"));
+        assert!(set.get(3).is_none());
+    }
+
+    #[test]
+    fn test_patterns_keep_their_options() {
+        let mut set = GlobSet::new();
+        assert_eq!(set.add(GlobBuilder::new("readme*").case_insensitive(true).build().unwrap()), 0);
+        assert_eq!(set.add(ParsedGlobString::try_from("LICENSE").unwrap()), 1);
+        assert_eq!(set.matches("README.md"), vec![0]);
+        assert_eq!(set.matches("license"), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_match_ranges() {
+        let set = set(&["\\bid=?*", "secret", "*.log"]);
+        let expected = vec![SetMatch { index: 0, range: 5..9, provenance: None }, SetMatch { index: 1, range: 20..26, provenance: None }];
+        assert_eq!(set.match_ranges("user id=42 uploaded secret.txt"), expected);
+        assert_eq!(set.match_ranges("nothing"), vec![]);
+    }
+
+    #[test]
+    fn test_provenance() {
+        let set = GlobSet::from_lines("rules.txt", "# secrets\r\n*.key\r\n\r\nid_*\r\n").unwrap();
+        assert_eq!(set.len(), 2);
+        let matches = set.match_ranges("home/id_rsa");
+        let provenance = matches[0].provenance().unwrap();
+        assert_eq!((matches[0].index(), provenance.line_number(), provenance.text()), (1, 4, "id_*"));
+        let mut set = set;
+        set.add(ParsedGlobString::try_from("*").unwrap());
+        assert_eq!(set.provenance(2), None);
+        assert_eq!(set.provenance(3), None);
+    }
+
+    #[test]
+    fn test_first_byte_dispatch() {
+        let anchored = MatchOptions { anchor: Anchor::Start, ..MatchOptions::default() };
+        let folded = MatchOptions { anchor: Anchor::Both, case_fold: Some(&DefaultCaseFold), ..MatchOptions::default() };
+        let mut set = GlobSet::new();
+        set.add(GlobBuilder::new("/api/*").options(&anchored).build().unwrap());
+        set.add(GlobBuilder::new("/static/*").options(&anchored).build().unwrap());
+        set.add(ParsedGlobString::try_from("*.json").unwrap());
+        set.add(GlobBuilder::new("*/health").options(&anchored).build().unwrap());
+        set.add(GlobBuilder::new("Ärger").options(&anchored).build().unwrap());
+        set.add(GlobBuilder::new("HEAD").options(&folded).build().unwrap());
+        assert_eq!(set.matches("/api/users.json"), vec![0, 2]);
+        assert_eq!(set.matches("/static/health"), vec![1, 3]);
+        assert_eq!(set.matches("api/users"), vec![]);
+        assert_eq!(set.matches("Ärgernis"), vec![4]);
+        assert_eq!(set.matches("head"), vec![5]);
+        assert_eq!(set.matches(""), vec![]);
+        assert!(!set.is_match("static/x"));
+    }
+
+    #[test]
+    fn test_per_pattern_anchoring() {
+        let mut set = GlobSet::new();
+        set.add_anchored(ParsedGlobString::try_from(".rs").unwrap(), Anchor::End);
+        set.add_anchored(ParsedGlobString::try_from("*.md").unwrap(), Anchor::Both);
+        set.add_anchored(ParsedGlobString::try_from("src/").unwrap(), Anchor::Start);
+        set.add_anchored(ParsedGlobString::try_from("test").unwrap(), Anchor::None);
+        set.add_anchored(GlobBuilder::new("*.TXT").case_insensitive(true).build().unwrap(), Anchor::End);
+        set.add_anchored(ParsedGlobString::try_from("Ä").unwrap(), Anchor::End);
+        assert_eq!(set.matches("src/test.rs"), vec![0, 2, 3]);
+        assert_eq!(set.matches("src/README.md"), vec![1, 2]);
+        assert_eq!(set.matches("docs/test.rs.md"), vec![1, 3]);
+        assert_eq!(set.matches("notes.txt"), vec![4]);
+        assert_eq!(set.matches("BÄ"), vec![5]);
+        assert_eq!(set.match_ranges("lib.rs")[0].range(), 3..6);
+    }
+
+    #[test]
+    fn test_matches_in_insertion_order() {
+        // dispatched by the last byte, by the first byte, and not at all
+        let mut set = GlobSet::new();
+        set.add_anchored(ParsedGlobString::try_from("*.c").unwrap(), Anchor::Both);
+        set.add_anchored(ParsedGlobString::try_from("src/").unwrap(), Anchor::Start);
+        set.add(ParsedGlobString::try_from("main").unwrap());
+        set.add_anchored(ParsedGlobString::try_from("s").unwrap(), Anchor::Start);
+        set.add_anchored(ParsedGlobString::try_from("*").unwrap(), Anchor::Both);
+        assert_eq!(set.matches("src/main.c"), vec![0, 1, 2, 3, 4]);
+        let mut matches = vec![7, 8, 9];
+        set.matches_into("src/lib.h", &mut matches);
+        assert_eq!(matches, vec![1, 3, 4]);
+        set.matches_into("main.c", &mut matches);
+        assert_eq!(matches, vec![0, 2, 4]);
+        assert_eq!(set.match_ranges("main.c").iter().map(|m| m.index()).collect::<Vec<_>>(), matches);
+    }
+
+    #[test]
+    fn test_mixed_case_sensitivity() {
+        let sensitive = MatchOptions { anchor: Anchor::Both, ..MatchOptions::default() };
+        let insensitive = MatchOptions { case_fold: Some(&DefaultCaseFold), ..sensitive };
+        let mut set = GlobSet::new();
+        set.add_with_options(ParsedGlobString::try_from("Dockerfile").unwrap(), &sensitive);
+        set.add_with_options(ParsedGlobString::try_from("dockerfile").unwrap(), &insensitive);
+        set.add_with_options(ParsedGlobString::try_from("*.YML").unwrap(), &insensitive);
+        set.add_with_options(ParsedGlobString::try_from("*.yml").unwrap(), &sensitive);
+        assert_eq!(set.matches("Dockerfile"), vec![0, 1]);
+        assert_eq!(set.matches("DOCKERFILE"), vec![1]);
+        assert_eq!(set.matches("ci.yml"), vec![2, 3]);
+        assert_eq!(set.matches("ci.Yml"), vec![2]);
+        assert!(set.get(1).unwrap().options().case_fold.is_some());
+        assert!(!set.is_match("docker"));
+    }
+
+    #[test]
+    fn test_provenance_of_errors() {
+        let error = GlobSet::from_lines("rules.txt", "ok\nbad\\").unwrap_err();
+        assert_eq!((error.provenance().line_number(), error.provenance().text()), (2, "bad\\"));
+        assert_eq!(*error.error(), GlobParseError::UnterminatedEscapeSequence(3));
+    }
+}
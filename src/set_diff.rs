@@ -0,0 +1,167 @@
+use crate::subsumption::{find_difference, Automaton};
+use crate::GlobSet;
+
+/// how a new version of a pattern set differs from an old one, see [`GlobSet::diff`].
+///
+/// Patterns are compared by the strings they match, not by how they are written, so `*?` and `?*`
+/// are the same pattern.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SetDiff {
+    only_in_old: Vec<usize>,
+    only_in_new: Vec<usize>,
+    broadened: Vec<(usize, usize)>,
+    narrowed: Vec<(usize, usize)>,
+    newly_matched: Vec<String>,
+    no_longer_matched: Vec<String>,
+}
+
+impl SetDiff {
+    /// returns the indices of the old patterns that match different strings than every new pattern.
+    pub fn only_in_old(&self) -> &[usize] {
+        return &self.only_in_old;
+    }
+
+    /// returns the indices of the new patterns that match different strings than every old pattern.
+    pub fn only_in_new(&self) -> &[usize] {
+        return &self.only_in_new;
+    }
+
+    /// returns the pairs of an old and a new pattern, where the new pattern matches all strings that
+    /// the old pattern matches, and more.
+    pub fn broadened(&self) -> &[(usize, usize)] {
+        return &self.broadened;
+    }
+
+    /// returns the pairs of an old and a new pattern, where the old pattern matches all strings that
+    /// the new pattern matches, and more.
+    pub fn narrowed(&self) -> &[(usize, usize)] {
+        return &self.narrowed;
+    }
+
+    /// returns examples of strings that the new set matches, but the old set doesn't: for each
+    /// pattern in [`only_in_new`](Self::only_in_new) the shortest such string it matches, if any.
+    pub fn newly_matched(&self) -> &[String] {
+        return &self.newly_matched;
+    }
+
+    /// returns examples of strings that the old set matches, but the new set doesn't: for each
+    /// pattern in [`only_in_old`](Self::only_in_old) the shortest such string it matches, if any.
+    pub fn no_longer_matched(&self) -> &[String] {
+        return &self.no_longer_matched;
+    }
+
+    /// checks if both sets match exactly the same strings.
+    pub fn is_equivalent(&self) -> bool {
+        return self.newly_matched.is_empty() && self.no_longer_matched.is_empty();
+    }
+}
+
+/// returns the indices of the `patterns` that are not equivalent to any of the `others`.
+fn unmatched(patterns: &[Automaton], others: &[Automaton]) -> Vec<usize> {
+    let is_equivalent = |a: &Automaton, b: &Automaton| {
+        find_difference(std::slice::from_ref(a), std::slice::from_ref(b)).is_none()
+            && find_difference(std::slice::from_ref(b), std::slice::from_ref(a)).is_none()
+    };
+    return (0..patterns.len()).filter(|index| !others.iter().any(|other| is_equivalent(&patterns[*index], other))).collect();
+}
+
+/// returns one example for each of the `patterns` with the given indices that the `others` don't
+/// match, without duplicates.
+fn examples(patterns: &[Automaton], indices: &[usize], others: &[Automaton]) -> Vec<String> {
+    let mut examples = Vec::new();
+    for index in indices {
+        if let Some(example) = find_difference(std::slice::from_ref(&patterns[*index]), others) {
+            if !examples.contains(&example) {
+                examples.push(example);
+            }
+        }
+    }
+    return examples;
+}
+
+impl<'g> GlobSet<'g> {
+    /// compares this set with a new version of it, e.g. the patterns of an old and a new
+    /// configuration file, and explains what changed.
+    ///
+    /// Each pattern is interpreted like [`is_match`](crate::ParsedGlobString::is_match) does,
+    /// including its anchoring and options. Patterns with [`MatchUnit::Byte`](crate::MatchUnit::Byte)
+    /// are compared as if they matched characters.
+    /// ```
+    /// use glob::{GlobSet, ParsedGlobString};
+    /// let set = |patterns: &[&'static str]| -> GlobSet<'static> {
+    ///     patterns.iter().map(|pattern| ParsedGlobString::try_from(*pattern).unwrap()).collect()
+    /// };
+    /// let old = set(&["*.log", "*.tmp", "cache?"]);
+    /// let new = set(&["*.tmp", "*.log", "cache*"]);
+    /// let diff = old.diff(&new);
+    /// assert_eq!((diff.only_in_old(), diff.only_in_new()), (&[2][..], &[2][..]));
+    /// assert_eq!(diff.broadened(), &[(2, 2)]);
+    /// assert_eq!(diff.newly_matched(), &["cache"]);
+    /// assert!(diff.no_longer_matched().is_empty());
+    /// ```
+    pub fn diff(&self, new: &GlobSet<'_>) -> SetDiff {
+        let old_automata: Vec<_> = self.patterns().map(Automaton::new).collect();
+        let new_automata: Vec<_> = new.patterns().map(Automaton::new).collect();
+        let only_in_old = unmatched(&old_automata, &new_automata);
+        let only_in_new = unmatched(&new_automata, &old_automata);
+        let includes = |including: &Automaton, included: &Automaton| {
+            find_difference(std::slice::from_ref(included), std::slice::from_ref(including)).is_none()
+        };
+        let mut diff = SetDiff::default();
+        for old_index in &only_in_old {
+            for new_index in &only_in_new {
+                let (old, new) = (&old_automata[*old_index], &new_automata[*new_index]);
+                if includes(new, old) {
+                    diff.broadened.push((*old_index, *new_index));
+                } else if includes(old, new) {
+                    diff.narrowed.push((*old_index, *new_index));
+                }
+            }
+        }
+        diff.newly_matched = examples(&new_automata, &only_in_new, &old_automata);
+        diff.no_longer_matched = examples(&old_automata, &only_in_old, &new_automata);
+        diff.only_in_old = only_in_old;
+        diff.only_in_new = only_in_new;
+        return diff;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{GlobSet, ParsedGlobString};
+
+    fn set(patterns: &[&'static str]) -> GlobSet<'static> {
+        return patterns.iter().map(|pattern| ParsedGlobString::try_from(*pattern).unwrap()).collect();
+    }
+
+    #[test]
+    fn test_equivalent_sets() {
+        let diff = set(&["a*?", "b"]).diff(&set(&["b", "a?*"]));
+        assert!(diff.only_in_old().is_empty() && diff.only_in_new().is_empty());
+        assert!(diff.is_equivalent());
+    }
+
+    #[test]
+    fn test_narrowed_pattern() {
+        let diff = set(&["*.rs"]).diff(&set(&["src*.rs"]));
+        assert_eq!(diff.narrowed(), &[(0, 0)]);
+        assert!(diff.broadened().is_empty());
+        assert_eq!(diff.no_longer_matched(), &[".rs"]);
+        assert!(diff.newly_matched().is_empty());
+    }
+
+    #[test]
+    fn test_redundant_pattern_changes_nothing() {
+        let diff = set(&["*.rs"]).diff(&set(&["*.rs", "main.rs"]));
+        assert_eq!(diff.only_in_new(), &[1]);
+        assert!(diff.is_equivalent());
+    }
+
+    #[test]
+    fn test_unrelated_patterns() {
+        let diff = set(&["*.md"]).diff(&set(&["*.txt"]));
+        assert!(diff.broadened().is_empty() && diff.narrowed().is_empty());
+        assert_eq!(diff.newly_matched(), &[".txt"]);
+        assert_eq!(diff.no_longer_matched(), &[".md"]);
+    }
+}
@@ -0,0 +1,393 @@
+use std::error::Error;
+use std::cmp::min;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+use crate::{Anchor, MatchUnit, ParsedGlobString, WildcardPolicy};
+
+/// the first bytes of every checkpoint, followed by the format version.
+const CHECKPOINT_MAGIC: &[u8; 4] = b"GLSM";
+const CHECKPOINT_VERSION: u8 = 1;
+/// the characters whose folding identifies a case folding in the fingerprint of a pattern.
+const CASE_FOLD_SAMPLE: &str = "AZaz09ÄäÖöÜüßẞIıİiΣσς\u{212a}"; // `\u{212a}` is the Kelvin sign
+
+/// matches a pattern against the lines of a stream that arrives in chunks, like the output of a
+/// process or a growing log file.
+///
+/// Lines are separated by `\n` and may be split across chunks arbitrarily; a trailing `\r` is not
+/// part of the line. Each line is matched with the options of the pattern, including its anchoring
+/// (see [`ParsedGlobString::is_match`]). The matcher keeps the beginning of the current line until
+/// its line break arrives, so a stream without line breaks grows it without bound unless it is
+/// capped with [`max_line_length`](Self::max_line_length).
+/// ```
+/// use glob::{ParsedGlobString, StreamMatcher};
+/// let pattern = ParsedGlobString::try_from("ERROR*").unwrap();
+/// let mut matcher = StreamMatcher::new(&pattern);
+/// assert!(matcher.feed(b"INFO start\nERR").is_empty());
+/// let matches = matcher.feed(b"OR: disk full\nINFO stop\n");
+/// assert_eq!(matches.len(), 1);
+/// assert_eq!((matches[0].line_number(), matches[0].line()), (2, "ERROR: disk full"));
+/// ```
+///
+/// The state of the matcher can be saved as a [`checkpoint`](Self::checkpoint) and restored with
+/// [`resume`](Self::resume), e.g. by a log shipper that is restarted, so scanning continues where it
+/// stopped without reading the input again.
+#[derive(Debug, Clone)]
+pub struct StreamMatcher<'p, 'g> {
+    pattern: &'p ParsedGlobString<'g>,
+    partial_line: Vec<u8>, // the bytes after the last line break
+    skipped: u64, // the bytes of the current line after `partial_line` that exceeded the maximum length
+    line_start: u64, // stream offset of `partial_line`
+    line_number: u64, // of `partial_line`, starting at 1
+    max_line_length: usize,
+}
+
+/// a line of the stream that the pattern matched, see [`StreamMatcher::feed`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamMatch {
+    line_number: u64,
+    offset: u64,
+    line: String,
+    range: Range<usize>,
+}
+
+/// returned if a checkpoint can't be restored, see [`StreamMatcher::resume`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckpointError {
+    /// returned when the bytes are not a checkpoint, e.g. because they were truncated.
+    Malformed,
+    /// returned when the checkpoint was written in a format that this version of the library
+    /// doesn't know. Encapsulates the version of the format.
+    UnsupportedVersion(u8),
+    /// returned when the checkpoint was taken for a different pattern or different options.
+    PatternMismatch,
+}
+
+impl Display for CheckpointError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CheckpointError::Malformed => write!(f, "malformed checkpoint"),
+            CheckpointError::UnsupportedVersion(version) => write!(f, "unsupported checkpoint version {}", version),
+            CheckpointError::PatternMismatch => write!(f, "checkpoint was taken for a different pattern"),
+        }
+    }
+}
+
+impl Error for CheckpointError {}
+
+/// the 64-bit FNV-1a hash function, which, unlike the hashers of the standard library, gives the
+/// same result in every process.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        return Fnv1a(0xcbf29ce484222325);
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x100000001b3);
+        }
+    }
+
+    fn write_char(&mut self, c: char) {
+        self.write(&u32::from(c).to_le_bytes());
+    }
+}
+
+/// returns a hash of the compiled pattern and its options. Each option is hashed explicitly, and a
+/// case folding by how it folds some characters, so the hash doesn't depend on `Debug` output.
+fn fingerprint(pattern: &ParsedGlobString<'_>) -> u64 {
+    let options = pattern.options();
+    let mut hash = Fnv1a::new();
+    let dump = pattern.dump();
+    hash.write(&(dump.len() as u64).to_le_bytes());
+    hash.write(dump.as_bytes());
+    hash.write(&[match options.anchor {
+        Anchor::None => 0,
+        Anchor::Start => 1,
+        Anchor::End => 2,
+        Anchor::Both => 3,
+    }]);
+    match options.case_fold {
+        Option::None => hash.write(&[0]),
+        Option::Some(case_fold) => {
+            hash.write(&[1, u8::from(case_fold.folds_ascii_to_lowercase())]);
+            CASE_FOLD_SAMPLE.chars().for_each(|c| hash.write_char(case_fold.fold(c)));
+        },
+    }
+    match options.path_separator {
+        Option::None => hash.write(&[0]),
+        Option::Some(separator) => {
+            hash.write(&[1]);
+            hash.write_char(separator);
+        },
+    }
+    hash.write(&[u8::from(options.wildcards_match_newline)]);
+    hash.write(&[match options.unit {
+        MatchUnit::Char => 0,
+        MatchUnit::Byte => 1,
+    }]);
+    // decide which range a match reports
+    let config = pattern.config();
+    hash.write(&[match config.wildcard_policy {
+        WildcardPolicy::Lazy => 0,
+        WildcardPolicy::Greedy => 1,
+    }]);
+    hash.write(&[u8::from(config.leftmost_longest)]);
+    return hash.0;
+}
+
+impl<'p, 'g> StreamMatcher<'p, 'g> {
+    /// creates a matcher for a stream that starts now.
+    pub fn new(pattern: &'p ParsedGlobString<'g>) -> Self {
+        return StreamMatcher { pattern: pattern, partial_line: Vec::new(), skipped: 0, line_start: 0, line_number: 1, max_line_length: usize::MAX };
+    }
+
+    /// limits how many bytes of a line are kept, and matched, to `length`. The rest of a longer
+    /// line is dropped as it arrives, so the matcher uses bounded memory even for a stream without
+    /// line breaks. Offsets and positions still count the dropped bytes. Unlimited by default.
+    /// ```
+    /// use glob::{ParsedGlobString, StreamMatcher};
+    /// let pattern = ParsedGlobString::try_from("ERROR*").unwrap();
+    /// let mut matcher = StreamMatcher::new(&pattern).max_line_length(8);
+    /// matcher.feed(b"ERROR: disk ");
+    /// let matches = matcher.feed(b"full\nERROR\n");
+    /// assert_eq!(matches[0].line(), "ERROR: d");
+    /// assert_eq!((matches[1].line(), matches[1].offset()), ("ERROR", 17));
+    /// ```
+    pub fn max_line_length(mut self, length: usize) -> Self {
+        self.max_line_length = length;
+        return self;
+    }
+
+    /// appends bytes of the current line to `partial_line`, up to the maximum length.
+    fn extend_line(&mut self, bytes: &[u8]) {
+        let kept = min(bytes.len(), self.max_line_length.saturating_sub(self.partial_line.len()));
+        self.partial_line.extend_from_slice(&bytes[..kept]);
+        self.skipped += (bytes.len() - kept) as u64;
+    }
+
+    /// consumes the next chunk of the stream and returns the lines that were completed by it and
+    /// that the pattern matches, in order.
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<StreamMatch> {
+        let mut matches = Vec::new();
+        let mut rest = chunk;
+        while let Option::Some(end) = rest.iter().position(|byte| *byte == b'\n') {
+            self.extend_line(&rest[..end]);
+            matches.extend(self.complete_line(true));
+            rest = &rest[end + 1..];
+        }
+        self.extend_line(rest);
+        return matches;
+    }
+
+    /// treats the bytes after the last line break as a complete line, since the stream has ended,
+    /// and returns it if the pattern matches it.
+    pub fn finish(&mut self) -> Option<StreamMatch> {
+        if self.partial_line.is_empty() && self.skipped == 0 {
+            return None;
+        }
+        return self.complete_line(false);
+    }
+
+    /// matches the line in `partial_line` and starts the next line.
+    fn complete_line(&mut self, has_line_break: bool) -> Option<StreamMatch> {
+        let content = match self.skipped {
+            0 => self.partial_line.strip_suffix(b"\r").unwrap_or(&self.partial_line),
+            _ => &self.partial_line, // the line break was dropped with the end of the line
+        };
+        let line = String::from_utf8_lossy(content).into_owned();
+        let found = self.pattern.captures_anchored(&line, self.pattern.options().anchor).map(|captures| captures.range());
+        let result = found.map(|range| StreamMatch {
+            line_number: self.line_number,
+            offset: self.line_start,
+            line: line,
+            range: range,
+        });
+        self.line_start += (self.partial_line.len() + usize::from(has_line_break)) as u64 + self.skipped;
+        self.line_number += 1;
+        self.partial_line.clear();
+        self.skipped = 0;
+        return result;
+    }
+
+    /// returns the number of bytes consumed so far.
+    pub fn position(&self) -> u64 {
+        return self.line_start + self.partial_line.len() as u64 + self.skipped;
+    }
+
+    /// saves the state of the matcher: the position in the stream, the current line number and the
+    /// beginning of the current line. The pattern itself is not saved; the checkpoint can only be
+    /// restored with the same pattern and options. The maximum line length isn't saved either.
+    pub fn checkpoint(&self) -> Vec<u8> {
+        let mut checkpoint = Vec::with_capacity(4 + 1 + 5 * 8 + self.partial_line.len());
+        checkpoint.extend_from_slice(CHECKPOINT_MAGIC);
+        checkpoint.push(CHECKPOINT_VERSION);
+        for number in [fingerprint(self.pattern), self.line_start, self.line_number, self.skipped, self.partial_line.len() as u64] {
+            checkpoint.extend_from_slice(&number.to_le_bytes());
+        }
+        checkpoint.extend_from_slice(&self.partial_line);
+        return checkpoint;
+    }
+
+    /// restores a matcher from a [`checkpoint`](Self::checkpoint), so the stream can be fed from
+    /// the [`position`](Self::position) it had then.
+    /// ```
+    /// use glob::{ParsedGlobString, StreamMatcher};
+    /// let pattern = ParsedGlobString::try_from("*disk*").unwrap();
+    /// let mut matcher = StreamMatcher::new(&pattern);
+    /// matcher.feed(b"INFO start\nERROR: di");
+    /// let checkpoint = matcher.checkpoint();
+    /// // ... the process restarts ...
+    /// let mut matcher = StreamMatcher::resume(&pattern, &checkpoint).unwrap();
+    /// assert_eq!(matcher.position(), 20);
+    /// assert_eq!(matcher.feed(b"sk full\n")[0].line(), "ERROR: disk full");
+    /// ```
+    pub fn resume(pattern: &'p ParsedGlobString<'g>, checkpoint: &[u8]) -> Result<Self, CheckpointError> {
+        let rest = checkpoint.strip_prefix(CHECKPOINT_MAGIC).ok_or(CheckpointError::Malformed)?;
+        let (version, rest) = rest.split_first().ok_or(CheckpointError::Malformed)?;
+        if *version != CHECKPOINT_VERSION {
+            return Err(CheckpointError::UnsupportedVersion(*version));
+        }
+        let mut numbers = [0; 5];
+        let mut rest = rest;
+        for number in numbers.iter_mut() {
+            let (bytes, tail) = rest.split_first_chunk::<8>().ok_or(CheckpointError::Malformed)?;
+            *number = u64::from_le_bytes(*bytes);
+            rest = tail;
+        }
+        let [pattern_fingerprint, line_start, line_number, skipped, partial_line_length] = numbers;
+        if pattern_fingerprint != fingerprint(pattern) {
+            return Err(CheckpointError::PatternMismatch);
+        }
+        if rest.len() as u64 != partial_line_length || line_number == 0 {
+            return Err(CheckpointError::Malformed);
+        }
+        return Ok(StreamMatcher {
+            pattern: pattern,
+            partial_line: rest.to_vec(),
+            skipped: skipped,
+            line_start: line_start,
+            line_number: line_number,
+            max_line_length: usize::MAX,
+        });
+    }
+}
+
+impl StreamMatch {
+    /// returns the number of the line, starting at 1.
+    pub fn line_number(&self) -> u64 {
+        return self.line_number;
+    }
+
+    /// returns the position of the first byte of the line in the stream.
+    pub fn offset(&self) -> u64 {
+        return self.offset;
+    }
+
+    /// returns the line without its line break, or its beginning if it was longer than the
+    /// [maximum length](StreamMatcher::max_line_length). Invalid UTF-8 sequences are replaced with
+    /// replacement characters.
+    pub fn line(&self) -> &str {
+        return &self.line;
+    }
+
+    /// returns the byte range of the match within [`line`](Self::line).
+    pub fn range(&self) -> Range<usize> {
+        return self.range.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use crate::{Anchor, DefaultCaseFold, GlobBuilder, MatchOptions, MatchUnit, ParsedGlobString, TurkishCaseFold, WildcardPolicy};
+    use super::{fingerprint, CheckpointError, StreamMatcher};
+
+    #[test]
+    fn test_lines_split_across_chunks() {
+        let pattern = ParsedGlobString::try_from("b?").unwrap();
+        let mut matcher = StreamMatcher::new(&pattern);
+        let mut matches = Vec::new();
+        for chunk in [&b"a\r\nb"[..], b"", b"x\r", b"\nyy\nab", b"c"] {
+            matches.extend(matcher.feed(chunk));
+        }
+        matches.extend(matcher.finish());
+        let summary: Vec<_> = matches.iter().map(|m| (m.line_number(), m.offset(), m.line(), m.range())).collect();
+        assert_eq!(summary, vec![(2, 3, "bx", 0..2), (4, 10, "abc", 1..3)]);
+        assert_eq!(matcher.position(), 13);
+        assert_eq!(matcher.finish(), None);
+    }
+
+    #[test]
+    fn test_anchoring_of_the_pattern_is_used() {
+        let options = MatchOptions { anchor: Anchor::Both, ..MatchOptions::default() };
+        let pattern = GlobBuilder::new("*.rs").options(&options).build().unwrap();
+        let mut matcher = StreamMatcher::new(&pattern);
+        let lines: Vec<_> = matcher.feed(b"lib.rs\nlib.rs.orig\n").into_iter().map(|m| m.line_number()).collect();
+        assert_eq!(lines, vec![1]);
+    }
+
+    #[test]
+    fn test_resume_continues_where_the_checkpoint_was_taken() {
+        let pattern = ParsedGlobString::try_from("*!").unwrap();
+        let mut matcher = StreamMatcher::new(&pattern);
+        matcher.feed(b"a!\nb\nc");
+        let mut resumed = StreamMatcher::resume(&pattern, &matcher.checkpoint()).unwrap();
+        assert_eq!(resumed.feed(b"!\n"), matcher.feed(b"!\n"));
+        assert_eq!(resumed.position(), 8);
+    }
+
+    #[test]
+    fn test_max_line_length() {
+        let pattern = ParsedGlobString::try_from("a*").unwrap();
+        let mut matcher = StreamMatcher::new(&pattern).max_line_length(3);
+        assert!(matcher.feed(b"abcd").is_empty());
+        assert!(matcher.feed(b"efg").is_empty());
+        assert_eq!(matcher.position(), 7);
+        let resumed = StreamMatcher::resume(&pattern, &matcher.checkpoint()).unwrap();
+        assert_eq!(resumed.position(), 7);
+        let matches = matcher.feed(b"\r\nab\r\n");
+        let summary: Vec<_> = matches.iter().map(|m| (m.line_number(), m.offset(), m.line())).collect();
+        assert_eq!(summary, vec![(1, 0, "abc"), (2, 9, "ab")]);
+        assert_eq!(matcher.feed(b"abcdef").len(), 0);
+        assert_eq!(matcher.finish().map(|m| m.offset()), Some(13));
+        assert_eq!(matcher.position(), 19);
+    }
+
+    #[test]
+    fn test_fingerprint_covers_options() {
+        let pattern = ParsedGlobString::try_from("*.rs").unwrap();
+        let options = [
+            MatchOptions { anchor: Anchor::Both, ..MatchOptions::default() },
+            MatchOptions { case_fold: Some(&DefaultCaseFold), ..MatchOptions::default() },
+            MatchOptions { case_fold: Some(&TurkishCaseFold), ..MatchOptions::default() },
+            MatchOptions { path_separator: Some('/'), ..MatchOptions::default() },
+            MatchOptions { wildcards_match_newline: false, ..MatchOptions::default() },
+            MatchOptions { unit: MatchUnit::Byte, ..MatchOptions::default() },
+        ];
+        let mut fingerprints = vec![fingerprint(&pattern)];
+        for options in &options {
+            fingerprints.push(fingerprint(&GlobBuilder::new("*.rs").options(options).build().unwrap()));
+        }
+        fingerprints.push(fingerprint(&GlobBuilder::new("*.rs").wildcard_policy(WildcardPolicy::Greedy).build().unwrap()));
+        fingerprints.push(fingerprint(&GlobBuilder::new("*.rs").leftmost_longest(true).build().unwrap()));
+        let distinct: HashSet<_> = fingerprints.iter().collect();
+        assert_eq!(distinct.len(), fingerprints.len());
+        assert_eq!(fingerprint(&pattern), fingerprint(&ParsedGlobString::try_from("*.rs").unwrap()));
+    }
+
+    #[test]
+    fn test_invalid_checkpoints() {
+        let pattern = ParsedGlobString::try_from("*!").unwrap();
+        let other_pattern = ParsedGlobString::try_from("*?").unwrap();
+        let mut matcher = StreamMatcher::new(&pattern);
+        matcher.feed(b"abc");
+        let checkpoint = matcher.checkpoint();
+        assert_eq!(StreamMatcher::resume(&other_pattern, &checkpoint).unwrap_err(), CheckpointError::PatternMismatch);
+        assert_eq!(StreamMatcher::resume(&pattern, &checkpoint[..checkpoint.len() - 1]).unwrap_err(), CheckpointError::Malformed);
+        assert_eq!(StreamMatcher::resume(&pattern, b"GLSM").unwrap_err(), CheckpointError::Malformed);
+        assert_eq!(StreamMatcher::resume(&pattern, b"GLSM\x07").unwrap_err(), CheckpointError::UnsupportedVersion(7));
+        assert_eq!(StreamMatcher::resume(&pattern, b"").unwrap_err(), CheckpointError::Malformed);
+    }
+}
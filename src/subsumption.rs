@@ -0,0 +1,201 @@
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use crate::fuzzy::{items, Item};
+use crate::matcher::MatchConfig;
+use crate::{Anchor, ParsedGlobString};
+
+/// a position in the items of a pattern, and the class (word character or not) that the next
+/// character must have because a word boundary was just passed.
+type State = (usize, Option<bool>);
+
+fn is_word_character(c: char) -> bool {
+    return c.is_alphanumeric() || c == '_';
+}
+
+/// the strings a pattern matches with [`is_match`](ParsedGlobString::is_match), as a
+/// nondeterministic automaton over the items of the pattern. Anchoring is turned into leading and
+/// trailing `*` items, so the automaton always matches whole strings.
+#[derive(Debug, Clone)]
+pub struct Automaton {
+    items: Vec<Item>,
+    config: MatchConfig,
+}
+
+impl Automaton {
+    pub fn new(pattern: &ParsedGlobString<'_>) -> Self {
+        let anchor = pattern.options().anchor;
+        let mut all_items = Vec::new();
+        if !matches!(anchor, Anchor::Start | Anchor::Both) {
+            all_items.push(Item::AnyString);
+        }
+        all_items.extend(items(pattern.tokens()));
+        if !matches!(anchor, Anchor::End | Anchor::Both) {
+            all_items.push(Item::AnyString);
+        }
+        return Automaton { items: all_items, config: *pattern.config() };
+    }
+
+    /// adds the states that can be reached without consuming a character after a character of the
+    /// given class.
+    fn close(&self, states: &mut BTreeSet<State>, previous_is_word: bool) {
+        let mut pending: Vec<State> = states.iter().copied().collect();
+        while let Option::Some((index, need)) = pending.pop() {
+            let next = match self.items.get(index) {
+                Option::Some(Item::AnyString) => (index + 1, need),
+                Option::Some(Item::WordBoundary) if need.is_none_or(|need| need != previous_is_word) => (index + 1, Some(!previous_is_word)),
+                _ => continue,
+            };
+            if states.insert(next) {
+                pending.push(next);
+            }
+        }
+    }
+
+    fn start(&self) -> BTreeSet<State> {
+        let mut states = BTreeSet::from([(0, None)]);
+        self.close(&mut states, false);
+        return states;
+    }
+
+    fn step(&self, states: &BTreeSet<State>, c: char) -> BTreeSet<State> {
+        let fold = |c: char| self.config.case_fold.map_or(c, |fold| fold.fold(c));
+        let mut next = BTreeSet::new();
+        for (index, need) in states {
+            if need.is_some_and(|need| need != is_word_character(c)) {
+                continue;
+            }
+            match self.items.get(*index) {
+                Option::Some(Item::Char(expected)) if fold(*expected) == fold(c) => { next.insert((index + 1, None)); },
+                Option::Some(Item::AnyChar) if self.config.wildcard_matches(c) => { next.insert((index + 1, None)); },
+                Option::Some(Item::AnyString) if self.config.wildcard_matches(c) => { next.insert((*index, None)); },
+                _ => {},
+            }
+        }
+        self.close(&mut next, is_word_character(c));
+        return next;
+    }
+
+    fn accepts(&self, states: &BTreeSet<State>) -> bool {
+        // the end of the string counts as a non-word character
+        return states.iter().any(|(index, need)| *index == self.items.len() && need.is_none_or(|need| !need));
+    }
+
+    /// adds the characters that this automaton treats differently from other characters.
+    fn collect_alphabet(&self, alphabet: &mut BTreeSet<char>) {
+        for item in &self.items {
+            if let Item::Char(c) = item {
+                alphabet.insert(*c);
+                if self.config.case_fold.is_some() {
+                    alphabet.extend(c.to_uppercase().chain(c.to_lowercase()));
+                }
+            }
+        }
+        alphabet.extend(self.config.separator);
+    }
+}
+
+/// returns a string that one of the `included` automata accepts, but none of the `including`
+/// automata, or `None` if every string accepted by one of the `included` automata is accepted by
+/// one of the `including` automata. The shortest such string is returned.
+pub fn find_difference(included: &[Automaton], including: &[Automaton]) -> Option<String> {
+    let all = || included.iter().chain(including);
+    let mut mentioned = BTreeSet::new();
+    all().for_each(|automaton| automaton.collect_alphabet(&mut mentioned));
+    // characters that no pattern mentions only differ in whether they are word characters and
+    // whether they are line breaks, so one of each kind stands for all of them
+    let other_word_character = ('a'..).find(|c| c.is_alphanumeric() && !mentioned.contains(c));
+    let other_character = [' ', '-', '#', '~'].into_iter().chain('\u{e000}'..).find(|c| !mentioned.contains(c));
+    let mut alphabet: Vec<char> = mentioned.iter().copied().filter(|c| *c != '\n').collect();
+    alphabet.extend(other_word_character.into_iter().chain(other_character).chain(['\n']));
+
+    type Key = (bool, Vec<BTreeSet<State>>);
+    let start: Key = (false, all().map(Automaton::start).collect());
+    let mut parents: HashMap<Key, Option<(Key, char)>> = HashMap::from([(start.clone(), None)]);
+    let mut pending = VecDeque::from([start]);
+    while let Option::Some(key) = pending.pop_front() {
+        let (included_states, including_states) = key.1.split_at(included.len());
+        let is_included = included.iter().zip(included_states).any(|(automaton, states)| automaton.accepts(states));
+        let is_including = including.iter().zip(including_states).any(|(automaton, states)| automaton.accepts(states));
+        if is_included && !is_including {
+            let mut witness = Vec::new();
+            let mut current = &key;
+            while let Option::Some(Option::Some((parent, c))) = parents.get(current) {
+                witness.push(*c);
+                current = parent;
+            }
+            return Some(witness.into_iter().rev().collect());
+        }
+        for c in &alphabet {
+            let states: Vec<_> = all().zip(&key.1).map(|(automaton, states)| automaton.step(states, *c)).collect();
+            if states[..included.len()].iter().all(BTreeSet::is_empty) {
+                continue; // no string with this prefix is accepted by an included automaton
+            }
+            let next = (is_word_character(*c), states);
+            if !parents.contains_key(&next) {
+                parents.insert(next.clone(), Some((key.clone(), *c)));
+                pending.push_back(next);
+            }
+        }
+    }
+    return None;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Anchor, GlobBuilder, MatchOptions};
+    use super::{find_difference, Automaton};
+
+    fn automaton(pattern: &str) -> Automaton {
+        let options = MatchOptions { anchor: Anchor::Both, ..MatchOptions::default() };
+        return Automaton::new(&GlobBuilder::new(pattern).options(&options).build().unwrap());
+    }
+
+    fn difference(included: &[&str], including: &[&str]) -> Option<String> {
+        let included: Vec<_> = included.iter().map(|pattern| automaton(pattern)).collect();
+        let including: Vec<_> = including.iter().map(|pattern| automaton(pattern)).collect();
+        return find_difference(&included, &including);
+    }
+
+    #[test]
+    fn test_inclusion() {
+        assert_eq!(difference(&["*.rs"], &["*"]), None);
+        assert_eq!(difference(&["src/*.rs"], &["*.rs"]), None);
+        assert_eq!(difference(&["a?c"], &["a*"]), None);
+        assert_eq!(difference(&["??*"], &["*??"]), None);
+        assert_eq!(difference(&["*.rs", "*.md"], &["*.md", "*.rs", "*.txt"]), None);
+    }
+
+    #[test]
+    fn test_shortest_witness() {
+        assert_eq!(difference(&["*"], &["*.rs"]), Some(String::new()));
+        assert_eq!(difference(&["*.rs"], &["src/*"]), Some(String::from(".rs")));
+        assert_eq!(difference(&["a*"], &["a?c"]), Some(String::from("a")));
+        assert_eq!(difference(&["*.rs", "*.md"], &["*.rs"]), Some(String::from(".md")));
+    }
+
+    #[test]
+    fn test_unanchored_patterns() {
+        let partial = Automaton::new(&crate::ParsedGlobString::try_from("rs").unwrap());
+        assert_eq!(find_difference(&[automaton("*.rs")], std::slice::from_ref(&partial)), None);
+        assert_eq!(find_difference(&[partial], &[automaton("*.rs")]), Some(String::from("rs")));
+    }
+
+    #[test]
+    fn test_options() {
+        let separated = Automaton::new(&GlobBuilder::new("*.rs").options(&MatchOptions {
+            anchor: Anchor::Both,
+            path_separator: Some('/'),
+            ..MatchOptions::default()
+        }).build().unwrap());
+        assert_eq!(find_difference(&[automaton("*.rs")], std::slice::from_ref(&separated)), Some(String::from("/.rs")));
+        assert_eq!(find_difference(&[separated], &[automaton("*.rs")]), None);
+        let insensitive = Automaton::new(&GlobBuilder::new("*.RS").case_insensitive(true).build().unwrap());
+        assert_eq!(find_difference(&[automaton("*.rs")], &[insensitive]), None);
+    }
+
+    #[test]
+    fn test_word_boundaries() {
+        assert_eq!(difference(&["a\\b*"], &["a"]), Some(String::from("a ")));
+        assert_eq!(difference(&["a\\b?"], &["a?"]), None);
+        assert_eq!(difference(&["a?"], &["a\\b?"]), Some(String::from("aa")));
+    }
+}
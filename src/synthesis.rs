@@ -0,0 +1,124 @@
+use glob_parser::Token;
+use crate::matcher::MatchConfig;
+use glob_parser::multislice::MultiSlice;
+use crate::prefilter::LiteralCheck;
+use crate::{Anchor, ParsedGlobString};
+
+/// returns the longest common subsequence of `a` and `b`.
+fn longest_common_subsequence(a: &[char], b: &[char]) -> Vec<char> {
+    // `lengths[i][j]` is the length of the longest common subsequence of `a[i..]` and `b[j..]`
+    let mut lengths = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lengths[i][j] = if a[i] == b[j] { lengths[i + 1][j + 1] + 1 } else { lengths[i + 1][j].max(lengths[i][j + 1]) };
+        }
+    }
+    let mut common = Vec::with_capacity(lengths[0][0]);
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            common.push(a[i]);
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    return common;
+}
+
+/// returns the number of characters of `sample` before each character of `common`, and after the
+/// last one, when `common` is embedded into `sample` as far left as possible.
+fn gap_lengths(common: &[char], sample: &[char]) -> Vec<usize> {
+    let mut gaps = Vec::with_capacity(common.len() + 1);
+    let mut position = 0;
+    for c in common {
+        let offset = sample[position..].iter().position(|s| s == c).expect("`common` is a subsequence of `sample`");
+        gaps.push(offset);
+        position += offset + 1;
+    }
+    gaps.push(sample.len() - position);
+    return gaps;
+}
+
+/// proposes a pattern that matches each of the given sample strings as a whole, e.g. to create a
+/// rule from the files a user selected.
+///
+/// The characters that all samples have in common, in the same order, become literals, and the
+/// parts between them become wildcards: `?` where all samples have the same number of characters,
+/// and `*`, after as many `?` as the shortest of them has, where they differ. Returns `None` if
+/// there are no samples.
+/// ```
+/// use glob::generalize;
+/// let pattern = generalize(["report-2023.csv", "report-2024.csv"]).unwrap();
+/// assert_eq!(pattern.to_string(), "report-202?.csv");
+/// let pattern = generalize(["IMG_1.jpg", "IMG_20.jpg", "IMG_300.jpg"]).unwrap();
+/// assert_eq!(pattern.to_string(), "IMG_?*.jpg");
+/// assert!(pattern.matches_completely("IMG_4000.jpg"));
+/// ```
+/// Since the common characters are chosen from left to right, the result doesn't always have the
+/// fewest wildcards possible, but it always matches all samples.
+pub fn generalize<'s>(samples: impl IntoIterator<Item = &'s str>) -> Option<ParsedGlobString<'static>> {
+    let samples: Vec<Vec<char>> = samples.into_iter().map(|sample| sample.chars().collect()).collect();
+    let mut common = samples.first()?.clone();
+    for sample in &samples[1..] {
+        common = longest_common_subsequence(&common, sample);
+    }
+    let gaps: Vec<Vec<usize>> = samples.iter().map(|sample| gap_lengths(&common, sample)).collect();
+    let mut tokens: Vec<Token<'static>> = Vec::new();
+    let mut literal = String::new();
+    for index in 0..=common.len() {
+        let shortest = gaps.iter().map(|gaps| gaps[index]).min().unwrap_or(0);
+        let longest = gaps.iter().map(|gaps| gaps[index]).max().unwrap_or(0);
+        if longest > 0 {
+            if !literal.is_empty() {
+                tokens.push(Token::Literal(MultiSlice::from(literal.as_str()).into_owned()));
+                literal.clear();
+            }
+            tokens.push(if shortest == longest { Token::ExactLengthWildcard(shortest) } else { Token::MinLengthWildcard(shortest) });
+        }
+        if let Option::Some(c) = common.get(index) {
+            literal.push(*c);
+        }
+    }
+    if !literal.is_empty() {
+        tokens.push(Token::Literal(MultiSlice::from(literal.as_str()).into_owned()));
+    }
+    let literal_check = LiteralCheck::new(&tokens);
+    return Some(ParsedGlobString { tokens: tokens, config: MatchConfig::default(), anchor: Anchor::None, literal_check: literal_check, spans: Vec::new() });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::generalize;
+
+    #[test]
+    fn test_generalize() {
+        let cases: [(&[&str], &str); 7] = [
+            (&["main.rs"], "main.rs"),
+            (&["a*b?", "a*b?"], "a\\*b\\?"),
+            (&["a.txt", "bb.txt"], "?*.txt"),
+            (&["x", ""], "*"),
+            (&["", ""], ""),
+            (&["abc", "xyz"], "???"),
+            (&["läuft.log", "lauft.log"], "l?uft.log"),
+        ];
+        for (samples, expected) in cases {
+            assert_eq!(generalize(samples.iter().copied()).unwrap().to_string(), expected, "{:?}", samples);
+        }
+        assert!(generalize([]).is_none());
+    }
+
+    #[test]
+    fn test_generalized_pattern_matches_all_samples() {
+        let samples = ["src/lib.rs", "src/bin/glob.rs", "tests/glob_macro.rs", "a.t.txt", "a.txt", "*?\\"];
+        for count in 1..=samples.len() {
+            let pattern = generalize(samples[..count].iter().copied()).unwrap();
+            for sample in &samples[..count] {
+                assert!(pattern.matches_completely(sample), "{} against {}", pattern, sample);
+            }
+        }
+    }
+}
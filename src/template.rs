@@ -0,0 +1,203 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use glob_parser::{parse_events, GlobParseError};
+use crate::{OwnedGlobParseError, ParsedGlobString};
+
+/// returned if creating a [`PatternTemplate`] or filling in its placeholders fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    /// returned when a `{{` isn't closed by `}}`. Encapsulates the byte index of the `{{` in the
+    /// template string.
+    UnterminatedPlaceholder(usize), // index
+    /// returned when the name of a placeholder is empty or contains characters other than ASCII
+    /// letters, digits and `_`. Encapsulates the byte index of the `{{` in the template string.
+    InvalidPlaceholderName(usize), // index
+    /// returned when the pattern text around the placeholders is malformed. The position of the
+    /// error is a byte index in the template string.
+    Pattern(OwnedGlobParseError),
+    /// returned when no value is given for a placeholder. Encapsulates its name.
+    MissingValue(String),
+}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateError::UnterminatedPlaceholder(index) => write!(f, "unterminated `{{{{` at index {}", index),
+            TemplateError::InvalidPlaceholderName(index) => write!(f, "invalid placeholder name at index {}", index),
+            TemplateError::Pattern(error) => write!(f, "{}", error),
+            TemplateError::MissingValue(name) => write!(f, "no value for placeholder `{}`", name),
+        }
+    }
+}
+
+impl Error for TemplateError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        return match self {
+            TemplateError::Pattern(error) => Some(error),
+            _ => None,
+        };
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TemplatePart {
+    Pattern(String), // pattern syntax, copied into the result as it is
+    Placeholder(String), // name
+}
+
+/// checks that `text`, found at `offset` in the template string, parses on its own, and reports
+/// errors with their position in the template string.
+fn check_pattern_text(template: &str, offset: usize, text: &str) -> Result<(), TemplateError> {
+    let shift = |error: GlobParseError<'_>| match error {
+        GlobParseError::UnknownEscapeSequence(index, sequence) => {
+            GlobParseError::UnknownEscapeSequence(offset + index, &template[offset + index..offset + index + sequence.len()])
+        },
+        GlobParseError::UnterminatedEscapeSequence(index) => GlobParseError::UnterminatedEscapeSequence(offset + index),
+    };
+    return parse_events(text, |_| {}).map_err(|error| TemplateError::Pattern(shift(error).into_owned()));
+}
+
+/// appends `value` to `pattern` escaped, so it matches `value` literally.
+fn push_escaped(pattern: &mut String, value: &str) {
+    for c in value.chars() {
+        if matches!(c, '*' | '?' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+}
+
+/// a pattern string with named placeholders like `{{ext}}`, from which related patterns are
+/// generated by filling in values, e.g. one template in a configuration file for many file types.
+///
+/// The values are escaped before they are substituted, so they always match literally: a value
+/// can't add wildcards to the pattern or make it malformed. Names consist of ASCII letters, digits
+/// and `_`, and the same placeholder may occur several times. The pattern text around the
+/// placeholders is checked when the template is created.
+/// ```
+/// use glob::PatternTemplate;
+/// let template = PatternTemplate::new("logs/{{service}}-*.{{ext}}").unwrap();
+/// let pattern = template.build(&[("service", "api"), ("ext", "log")]).unwrap();
+/// assert!(pattern.matches_completely("logs/api-2024-01-01.log"));
+/// let pattern = template.build(&[("service", "w*"), ("ext", "txt")]).unwrap();
+/// assert_eq!(pattern.to_string(), "logs/w\\*-*.txt");
+/// assert!(!pattern.matches_completely("logs/web-1.txt"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternTemplate {
+    parts: Vec<TemplatePart>,
+}
+
+impl PatternTemplate {
+    /// parses a template string.
+    pub fn new(template: &str) -> Result<Self, TemplateError> {
+        let mut parts = Vec::new();
+        let mut position = 0;
+        while let Option::Some(offset) = template[position..].find("{{") {
+            let start = position + offset;
+            check_pattern_text(template, position, &template[position..start])?;
+            parts.push(TemplatePart::Pattern(String::from(&template[position..start])));
+            let Option::Some(length) = template[start + 2..].find("}}") else {
+                return Err(TemplateError::UnterminatedPlaceholder(start));
+            };
+            let name = &template[start + 2..start + 2 + length];
+            if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                return Err(TemplateError::InvalidPlaceholderName(start));
+            }
+            parts.push(TemplatePart::Placeholder(String::from(name)));
+            position = start + 2 + length + 2;
+        }
+        check_pattern_text(template, position, &template[position..])?;
+        parts.push(TemplatePart::Pattern(String::from(&template[position..])));
+        parts.retain(|part| *part != TemplatePart::Pattern(String::new()));
+        return Ok(PatternTemplate { parts: parts });
+    }
+
+    /// returns the names of the placeholders in the order they occur, with repetitions.
+    pub fn placeholders(&self) -> impl Iterator<Item = &str> + '_ {
+        return self.parts.iter().filter_map(|part| match part {
+            TemplatePart::Placeholder(name) => Some(name.as_str()),
+            TemplatePart::Pattern(_) => None,
+        });
+    }
+
+    /// returns the pattern string with the placeholders replaced by the escaped values given for
+    /// their names. If a name is given several times, the first value is used.
+    /// ```
+    /// use glob::PatternTemplate;
+    /// let template = PatternTemplate::new("{{dir}}/*").unwrap();
+    /// assert_eq!(template.render(&[("dir", "a?b")]).unwrap(), "a\\?b/*");
+    /// assert_eq!(template.render(&[]).unwrap_err().to_string(), "no value for placeholder `dir`");
+    /// ```
+    pub fn render(&self, values: &[(&str, &str)]) -> Result<String, TemplateError> {
+        let mut pattern = String::new();
+        for part in &self.parts {
+            match part {
+                TemplatePart::Pattern(text) => pattern.push_str(text),
+                TemplatePart::Placeholder(name) => {
+                    let Option::Some((_, value)) = values.iter().find(|(key, _)| key == name) else {
+                        return Err(TemplateError::MissingValue(name.clone()));
+                    };
+                    push_escaped(&mut pattern, value);
+                },
+            }
+        }
+        return Ok(pattern);
+    }
+
+    /// renders the template with the given values, see [`render`](Self::render), and parses the
+    /// result like [`ParsedGlobString::try_from`]. Use [`GlobBuilder`](crate::GlobBuilder) with
+    /// the rendered string for other options.
+    pub fn build(&self, values: &[(&str, &str)]) -> Result<ParsedGlobString<'static>, TemplateError> {
+        let pattern = self.render(values)?;
+        let parsed = ParsedGlobString::try_from(pattern.as_str()).expect("the pattern text was checked and the values are escaped");
+        return Ok(parsed.into_owned());
+    }
+}
+
+impl TryFrom<&str> for PatternTemplate {
+    type Error = TemplateError;
+    /// parses a template string like [`new`](PatternTemplate::new).
+    fn try_from(template: &str) -> Result<Self, Self::Error> {
+        return PatternTemplate::new(template);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::GlobParseError;
+    use super::{PatternTemplate, TemplateError};
+
+    #[test]
+    fn test_placeholders() {
+        let template = PatternTemplate::new("{{a}}*{{b_2}}{{a}}").unwrap();
+        assert_eq!(template.placeholders().collect::<Vec<_>>(), vec!["a", "b_2", "a"]);
+        assert_eq!(template.render(&[("b_2", "\\"), ("a", "x"), ("a", "y")]).unwrap(), "x*\\\\x");
+        assert_eq!(PatternTemplate::new("*.rs").unwrap().placeholders().count(), 0);
+        assert_eq!(PatternTemplate::new("").unwrap().render(&[]).unwrap(), "");
+        assert_eq!(PatternTemplate::new("{a}}").unwrap().render(&[]).unwrap(), "{a}}");
+    }
+
+    #[test]
+    fn test_values_match_literally() {
+        let template = PatternTemplate::new("*.{{ext}}").unwrap();
+        for value in ["*", "?", "\\", "\\b", "ä?*"] {
+            let pattern = template.build(&[("ext", value)]).unwrap();
+            assert!(pattern.matches_completely(&format!("file.{}", value)), "{:?}", value);
+            assert!(!pattern.matches_completely("file.xyz"), "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn test_invalid_templates() {
+        assert_eq!(PatternTemplate::new("a{{b"), Err(TemplateError::UnterminatedPlaceholder(1)));
+        assert_eq!(PatternTemplate::new("a{{}}"), Err(TemplateError::InvalidPlaceholderName(1)));
+        assert_eq!(PatternTemplate::new("{{a b}}"), Err(TemplateError::InvalidPlaceholderName(0)));
+        let error = GlobParseError::UnknownEscapeSequence(7, "\\x").into_owned();
+        assert_eq!(PatternTemplate::new("{{ab}}*\\x"), Err(TemplateError::Pattern(error)));
+        // an escape can't continue in a value
+        let error = GlobParseError::UnterminatedEscapeSequence(1).into_owned();
+        assert_eq!(PatternTemplate::try_from("a\\{{ab}}"), Err(TemplateError::Pattern(error)));
+        assert_eq!(PatternTemplate::new("a{{b").unwrap_err().to_string(), "unterminated `{{` at index 1");
+    }
+}
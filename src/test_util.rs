@@ -0,0 +1,175 @@
+// Support for the assertion macros of the `test-util` feature. The functions are public because
+// the macros expand to calls of them in other crates, but they are not part of the API.
+use std::fmt::Write;
+use glob_parser::Token;
+use crate::matcher::find_match_with_captures_from;
+use crate::{ParsedGlobString, WildcardPolicy};
+
+/// asserts that a pattern matches a string with the options of the pattern, like
+/// [`is_match`](crate::ParsedGlobString::is_match) checks it.
+///
+/// If it doesn't, the panic message shows the pattern, the string, how far into the string the
+/// pattern could be matched, and the token of the pattern that failed to match there. Like with
+/// `assert!`, a custom message can follow the arguments. Requires the `test-util` feature.
+/// ```
+/// use glob::{assert_glob_matches, ParsedGlobString};
+/// let pattern = ParsedGlobString::try_from("*.rs").unwrap();
+/// assert_glob_matches!(pattern, "src/lib.rs");
+/// ```
+/// ```should_panic
+/// # use glob::{assert_glob_matches, GlobBuilder, MatchOptions, Anchor};
+/// let options = MatchOptions { anchor: Anchor::Both, ..MatchOptions::default() };
+/// let pattern = GlobBuilder::new("src/*.rs").options(&options).build().unwrap();
+/// assert_glob_matches!(pattern, "src/lib.rs.orig", "checking the backup files");
+/// // panics with:
+/// // pattern `src/*.rs` does not match "src/lib.rs.orig": checking the backup files
+/// //   matched up to byte 10, then expected the end of the string
+/// ```
+#[macro_export]
+macro_rules! assert_glob_matches {
+    ($pattern:expr, $haystack:expr $(,)?) => {
+        $crate::assert_glob_matches!($pattern, $haystack, "")
+    };
+    ($pattern:expr, $haystack:expr, $($message:tt)+) => {
+        match (&$pattern, &$haystack) {
+            (pattern, haystack) => {
+                let pattern: &$crate::ParsedGlobString<'_> = pattern;
+                let haystack: &str = ::core::convert::AsRef::<str>::as_ref(haystack);
+                if let ::core::option::Option::Some(failure) = $crate::test_util::explain_mismatch(pattern, haystack, &format!($($message)+)) {
+                    panic!("{}", failure);
+                }
+            },
+        }
+    };
+}
+
+/// asserts that a pattern doesn't match a string with the options of the pattern, the opposite
+/// of [`assert_glob_matches!`].
+///
+/// If it does, the panic message shows the pattern, the string and the range of the match.
+/// Requires the `test-util` feature.
+/// ```
+/// use glob::{assert_glob_not_matches, ParsedGlobString};
+/// let pattern = ParsedGlobString::try_from("*.rs").unwrap();
+/// assert_glob_not_matches!(pattern, "Cargo.toml");
+/// ```
+#[macro_export]
+macro_rules! assert_glob_not_matches {
+    ($pattern:expr, $haystack:expr $(,)?) => {
+        $crate::assert_glob_not_matches!($pattern, $haystack, "")
+    };
+    ($pattern:expr, $haystack:expr, $($message:tt)+) => {
+        match (&$pattern, &$haystack) {
+            (pattern, haystack) => {
+                let pattern: &$crate::ParsedGlobString<'_> = pattern;
+                let haystack: &str = ::core::convert::AsRef::<str>::as_ref(haystack);
+                if let ::core::option::Option::Some(failure) = $crate::test_util::explain_match(pattern, haystack, &format!($($message)+)) {
+                    panic!("{}", failure);
+                }
+            },
+        }
+    };
+}
+
+/// describes a token in pattern syntax, like the `Display` implementation of patterns does.
+fn describe(token: &Token<'_>) -> String {
+    return match token {
+        Token::ExactLengthWildcard(length) => format!("wildcard `{}`", "?".repeat(*length)),
+        Token::MinLengthWildcard(length) => format!("wildcard `{}*`", "?".repeat(*length)),
+        Token::WordBoundary => String::from("word boundary `\\b`"),
+        Token::Literal(literal) => format!("literal {:?}", literal.chars().collect::<String>()),
+    };
+}
+
+/// returns the number of leading tokens of `pattern` that match somewhere in `haystack`, and the
+/// offset after the longest of their matches.
+fn furthest_match(pattern: &ParsedGlobString<'_>, haystack: &str) -> (usize, usize) {
+    let anchor_start = pattern.anchor.is_anchored_at_start();
+    let mut config = pattern.config;
+    config.wildcard_policy = WildcardPolicy::Greedy;
+    config.leftmost_longest = true;
+    let mut matched = (0, 0);
+    for count in 1..=pattern.tokens.len() {
+        let starts = haystack.char_indices().map(|(i, _)| i).chain([haystack.len()]).take(if anchor_start { 1 } else { usize::MAX });
+        let end = starts.filter_map(|start| find_match_with_captures_from(&pattern.tokens[..count], haystack, start, true, false, &config))
+            .map(|(range, _)| range.end)
+            .max();
+        match end {
+            Option::Some(end) => matched = (count, end),
+            Option::None => break,
+        }
+    }
+    return matched;
+}
+
+/// returns the panic message of [`assert_glob_matches!`], or `None` if the pattern matches.
+#[doc(hidden)]
+pub fn explain_mismatch(pattern: &ParsedGlobString<'_>, haystack: &str, message: &str) -> Option<String> {
+    if pattern.is_match(haystack) {
+        return None;
+    }
+    let mut failure = format!("pattern `{}` does not match {:?}", pattern, haystack);
+    if !message.is_empty() {
+        write!(failure, ": {}", message).unwrap();
+    }
+    let (count, end) = furthest_match(pattern, haystack);
+    let expected = match pattern.tokens.get(count) {
+        Option::Some(token) => format!("token {} ({})", count, describe(token)),
+        Option::None => String::from("the end of the string"),
+    };
+    if count == 0 {
+        write!(failure, "\n  no part of the string matched, the first token is {}", expected).unwrap();
+    } else {
+        write!(failure, "\n  matched up to byte {}, then expected {}", end, expected).unwrap();
+    }
+    return Some(failure);
+}
+
+/// returns the panic message of [`assert_glob_not_matches!`], or `None` if the pattern doesn't
+/// match.
+#[doc(hidden)]
+pub fn explain_match(pattern: &ParsedGlobString<'_>, haystack: &str, message: &str) -> Option<String> {
+    let range = pattern.captures_anchored(haystack, pattern.anchor)?.range();
+    let mut failure = format!("pattern `{}` unexpectedly matches {:?}", pattern, haystack);
+    if !message.is_empty() {
+        write!(failure, ": {}", message).unwrap();
+    }
+    write!(failure, "\n  matched bytes {:?}: {:?}", range, &haystack[range.clone()]).unwrap();
+    return Some(failure);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Anchor, GlobBuilder, MatchOptions, ParsedGlobString};
+    use super::{explain_match, explain_mismatch};
+
+    #[test]
+    fn test_explain_mismatch() {
+        let pattern = ParsedGlobString::try_from("src/*.rs").unwrap();
+        assert_eq!(explain_mismatch(&pattern, "src/lib.rs", ""), None);
+        assert_eq!(explain_mismatch(&pattern, "src/lib.py", "").unwrap(), "pattern `src/*.rs` does not match \"src/lib.py\"\n  matched up to byte 10, then expected token 2 (literal \".rs\")");
+        assert_eq!(explain_mismatch(&pattern, "tests/a.rs", "in tests").unwrap(), "pattern `src/*.rs` does not match \"tests/a.rs\": in tests\n  no part of the string matched, the first token is token 0 (literal \"src/\")");
+        let options = MatchOptions { anchor: Anchor::Both, ..MatchOptions::default() };
+        let pattern = GlobBuilder::new("?\\b").options(&options).build().unwrap();
+        assert_eq!(explain_mismatch(&pattern, "ab", "").unwrap(), "pattern `?\\b` does not match \"ab\"\n  matched up to byte 1, then expected token 1 (word boundary `\\b`)");
+        let pattern = GlobBuilder::new("a?").options(&options).build().unwrap();
+        assert_eq!(explain_mismatch(&pattern, "abc", "").unwrap(), "pattern `a?` does not match \"abc\"\n  matched up to byte 2, then expected the end of the string");
+    }
+
+    #[test]
+    fn test_explain_match() {
+        let pattern = ParsedGlobString::try_from("b?").unwrap();
+        assert_eq!(explain_match(&pattern, "xyz", ""), None);
+        assert_eq!(explain_match(&pattern, "abc", "").unwrap(), "pattern `b?` unexpectedly matches \"abc\"\n  matched bytes 1..3: \"bc\"");
+    }
+
+    #[test]
+    fn test_macros() {
+        let pattern = ParsedGlobString::try_from("*.txt").unwrap();
+        crate::assert_glob_matches!(pattern, "a.txt");
+        crate::assert_glob_matches!(&pattern, String::from("b.txt"), "with {}", "a reference");
+        crate::assert_glob_not_matches!(pattern, "a.md");
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| crate::assert_glob_not_matches!(pattern, "a.txt", "oops")));
+        assert!(result.is_err());
+    }
+}
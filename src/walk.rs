@@ -0,0 +1,774 @@
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::Duration;
+use crate::cancel::is_cancelled;
+use crate::progress::ProgressReporter;
+use crate::{Cancelled, GlobParseError, WalkStats, IgnoreRules, IgnoreStack, OverrideMatch, Overrides, ParsedGlobString, PathPattern};
+
+/// iterates over all files and directories below a root directory whose path matches a pattern.
+///
+/// The path of each entry relative to the root directory, with components separated by `/`, must
+/// match the pattern [completely](ParsedGlobString::matches_completely). Since wildcards also match
+/// `/`, `*.rs` matches Rust files in all subdirectories. The yielded paths are the root directory
+/// joined with the relative path.
+///
+/// Directories are traversed depth-first, entries of the same directory in the order of their file
+/// names. Symbolic links are yielded, but not followed. Errors while reading a directory or one of
+/// its entries are yielded as well, the traversal continues with the next entry afterwards.
+/// Entries whose names aren't valid Unicode are matched with the invalid parts replaced by `�`,
+/// but yielded with their actual names.
+/// ```no_run
+/// use glob::{GlobWalker, ParsedGlobString};
+/// let pattern = ParsedGlobString::try_from("src/*.rs").unwrap();
+/// for path in GlobWalker::new(".", &pattern) {
+///     println!("{}", path.unwrap().display());
+/// }
+/// ```
+///
+/// A walker created with [`with_path_pattern`](Self::with_path_pattern) matches the relative paths
+/// component by component instead, and skips directories below which the pattern can't match.
+/// A path pattern that starts with a Windows prefix, like `C:/Users/*/Documents/**`, see
+/// [`PathPattern::windows_paths`], is matched against the paths joined with the root instead.
+pub struct GlobWalker<'p, 'g> {
+    pattern: WalkPattern<'p, 'g>,
+    root: PathBuf,
+    pending: Vec<PendingEntry>,
+    pending_errors: VecDeque<io::Error>,
+    relative: bool, // if the yielded paths are relative to the root
+    started: bool, // if the root directory was read
+    ignore_file_names: Vec<String>, // in the order of increasing precedence
+    ignores: IgnoreStack,
+    overrides: Overrides<'g>,
+    one_file_system: bool,
+    root_device: Option<u64>, // read when the walk starts, if the walk stays on one file system
+    device_of: fn(&str, &fs::Metadata) -> Option<u64>, // replaced by the tests to fake mount points
+    stats: WalkStats,
+    progress: Option<ProgressReporter<'p>>,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+enum WalkPattern<'p, 'g> {
+    Glob(&'p ParsedGlobString<'g>),
+    Path(&'p PathPattern<'g>),
+    OwnedPath(PathPattern<'g>),
+    Includes(Vec<PathPattern<'g>>), // matches everything if empty
+}
+
+/// returns the path that `pattern` is matched against for the entry at `relative_path`: the path
+/// joined with the root if the pattern starts with a Windows prefix like `C:`, else the relative
+/// path itself.
+fn matched_path<'r>(pattern: &PathPattern<'_>, root: &Path, relative_path: &'r str) -> Cow<'r, str> {
+    if !pattern.has_windows_prefix() {
+        return Cow::Borrowed(relative_path);
+    }
+    let root = root.to_string_lossy();
+    return Cow::Owned(format!("{}/{}", root.trim_end_matches(['/', '\\']), relative_path));
+}
+
+impl<'p, 'g> WalkPattern<'p, 'g> {
+    fn matches(&self, root: &Path, relative_path: &str) -> bool {
+        return match self {
+            WalkPattern::Glob(pattern) => pattern.matches_completely(relative_path),
+            WalkPattern::Path(pattern) => pattern.matches(&matched_path(pattern, root, relative_path)),
+            WalkPattern::OwnedPath(pattern) => pattern.matches(&matched_path(pattern, root, relative_path)),
+            WalkPattern::Includes(patterns) => patterns.is_empty() || patterns.iter().any(|pattern| pattern.matches(&matched_path(pattern, root, relative_path))),
+        };
+    }
+
+    fn could_match_below(&self, root: &Path, relative_path: &str) -> bool {
+        return match self {
+            WalkPattern::Glob(_) => true,
+            WalkPattern::Path(pattern) => pattern.could_match_below(&matched_path(pattern, root, relative_path)),
+            WalkPattern::OwnedPath(pattern) => pattern.could_match_below(&matched_path(pattern, root, relative_path)),
+            WalkPattern::Includes(patterns) => patterns.is_empty() || patterns.iter().any(|pattern| pattern.could_match_below(&matched_path(pattern, root, relative_path))),
+        };
+    }
+}
+
+struct PendingEntry {
+    relative_path: String, // components separated by '/', for matching
+    path: PathBuf, // relative to the root, with the actual names of the components
+    metadata: fs::Metadata, // of the entry itself, not of the target of a symbolic link
+}
+
+/// an entry found by a [`GlobWalker`], together with the metadata the walker read anyway, so
+/// callers filtering e.g. by size or modification time don't need to read it again.
+#[derive(Debug, Clone)]
+pub struct MatchedEntry {
+    path: PathBuf,
+    metadata: fs::Metadata,
+}
+
+impl MatchedEntry {
+    /// returns the path of the entry, as it would be yielded by the walker.
+    pub fn path(&self) -> &Path {
+        return &self.path;
+    }
+
+    /// returns the metadata of the entry. For symbolic links, this is the metadata of the link
+    /// itself, not of its target.
+    pub fn metadata(&self) -> &fs::Metadata {
+        return &self.metadata;
+    }
+
+    /// returns the type of the entry.
+    pub fn file_type(&self) -> fs::FileType {
+        return self.metadata.file_type();
+    }
+
+    /// returns the path of the entry, consuming the entry.
+    pub fn into_path(self) -> PathBuf {
+        return self.path;
+    }
+}
+
+/// the iterator returned by [`GlobWalker::entries`].
+pub struct MatchedEntries<'p, 'g> {
+    walker: GlobWalker<'p, 'g>,
+}
+
+impl<'p, 'g> Iterator for MatchedEntries<'p, 'g> {
+    type Item = io::Result<MatchedEntry>;
+    fn next(&mut self) -> Option<Self::Item> {
+        return self.walker.next_entry();
+    }
+}
+
+impl<'p, 'g> GlobWalker<'p, 'g> {
+    /// creates a walker for all entries below `root` that match `pattern`.
+    pub fn new(root: impl Into<PathBuf>, pattern: &'p ParsedGlobString<'g>) -> Self {
+        return GlobWalker::with_pattern(root.into(), WalkPattern::Glob(pattern));
+    }
+
+    /// creates a walker for all entries below `root` that match the path pattern. Directories are
+    /// only read if the pattern could match an entry below them.
+    /// ```no_run
+    /// use glob::{GlobWalker, PathPattern};
+    /// let pattern = PathPattern::try_from("src/**/*.rs").unwrap();
+    /// // doesn't descend into `target` or `.git`
+    /// for path in GlobWalker::with_path_pattern(".", &pattern) {
+    ///     println!("{}", path.unwrap().display());
+    /// }
+    /// ```
+    pub fn with_path_pattern(root: impl Into<PathBuf>, pattern: &'p PathPattern<'g>) -> Self {
+        return GlobWalker::with_pattern(root.into(), WalkPattern::Path(pattern));
+    }
+
+    /// creates a walker that owns its path pattern. If `relative` is `true`, the yielded paths
+    /// are relative to `root`.
+    pub(crate) fn with_owned_path_pattern(root: PathBuf, pattern: PathPattern<'g>, relative: bool) -> Self {
+        let mut walker = GlobWalker::with_pattern(root, WalkPattern::OwnedPath(pattern));
+        walker.relative = relative;
+        return walker;
+    }
+
+    fn with_pattern(root: PathBuf, pattern: WalkPattern<'p, 'g>) -> Self {
+        return GlobWalker {
+            pattern: pattern,
+            root: root,
+            pending: Vec::new(),
+            pending_errors: VecDeque::new(),
+            relative: false,
+            started: false,
+            ignore_file_names: Vec::new(),
+            ignores: IgnoreStack::new(),
+            overrides: Overrides::new(),
+            one_file_system: false,
+            root_device: None,
+            device_of: device,
+            stats: WalkStats::default(),
+            progress: None,
+            cancel: None,
+        };
+    }
+
+    /// makes the walker stay on the file system of the root directory, like `find -xdev`:
+    /// directories on other file systems, like mounted network shares or `/proc`, are yielded if
+    /// they match, but not entered. Disabled by default. Only has an effect on Unix.
+    /// ```no_run
+    /// use glob::{GlobWalker, ParsedGlobString};
+    /// let pattern = ParsedGlobString::try_from("*.conf").unwrap();
+    /// for path in GlobWalker::new("/", &pattern).one_file_system(true) {
+    ///     println!("{}", path.unwrap().display());
+    /// }
+    /// ```
+    pub fn one_file_system(mut self, yes: bool) -> Self {
+        self.one_file_system = yes;
+        return self;
+    }
+
+    /// calls `callback` with the counts of the walk so far while it runs, at most once per
+    /// `interval`, and once more when it is done, so command line tools can render progress.
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use glob::{GlobWalker, ParsedGlobString};
+    /// let pattern = ParsedGlobString::try_from("*.jpg").unwrap();
+    /// let photos: Vec<_> = GlobWalker::new("/home", &pattern)
+    ///     .on_progress(Duration::from_millis(100), |stats| {
+    ///         eprint!("\r{} directories, {} photos", stats.directories_visited(), stats.files_matched());
+    ///     })
+    ///     .collect();
+    /// ```
+    pub fn on_progress(mut self, interval: Duration, callback: impl FnMut(&WalkStats) + Send + 'p) -> Self {
+        self.progress = Some(ProgressReporter::new(interval, callback));
+        return self;
+    }
+
+    /// makes the walk stop once `cancel` is set, e.g. by another thread when the user aborts a long
+    /// scan. The flag is checked before each entry; once it is set, the walker yields a [`Cancelled`]
+    /// error of kind [`Interrupted`](io::ErrorKind::Interrupted) and then ends.
+    /// ```no_run
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::sync::Arc;
+    /// use glob::{GlobWalker, ParsedGlobString};
+    /// let cancel = Arc::new(AtomicBool::new(false));
+    /// let abort = Arc::clone(&cancel);
+    /// std::thread::spawn(move || {
+    ///     std::thread::sleep(std::time::Duration::from_secs(10));
+    ///     abort.store(true, Ordering::Relaxed);
+    /// });
+    /// let pattern = ParsedGlobString::try_from("*.iso").unwrap();
+    /// for path in GlobWalker::new("/", &pattern).cancel_on(cancel) {
+    ///     match path {
+    ///         Ok(path) => println!("{}", path.display()),
+    ///         Err(error) => eprintln!("{}", error),
+    ///     }
+    /// }
+    /// ```
+    pub fn cancel_on(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(cancel);
+        return self;
+    }
+
+    /// returns the counts of the walk so far.
+    pub fn stats(&self) -> WalkStats {
+        return self.stats;
+    }
+
+    fn report_progress(&mut self) {
+        if let Option::Some(progress) = &mut self.progress {
+            progress.tick(&self.stats);
+        }
+    }
+
+    /// checks if the walker must not enter the directory at `relative_path` with the given
+    /// metadata, because it is on another file system than the root.
+    fn is_on_other_file_system(&self, relative_path: &str, metadata: &fs::Metadata) -> bool {
+        return self.one_file_system && self.root_device.is_some() && (self.device_of)(relative_path, metadata) != self.root_device;
+    }
+
+    /// makes the walker skip the entries excluded by the ignore files with the given name, like
+    /// `.gitignore`, see [`IgnoreRules`]. An ignore file applies to the directory containing it
+    /// and all directories below; the rules of deeper files take precedence. Ignored directories
+    /// are not entered. An ignore file that can't be read or parsed is reported as an error.
+    ///
+    /// Each ignore file is read once, when the walker enters its directory, and its rules are
+    /// dropped when the walker leaves the directory. Calling this again adds another file name,
+    /// like ripgrep reads `.gitignore`, `.ignore` and `.rgignore`: the ignore files of one
+    /// directory are merged, and the rules of names added later take precedence.
+    /// ```no_run
+    /// use glob::{GlobWalker, ParsedGlobString};
+    /// let pattern = ParsedGlobString::try_from("*.rs").unwrap();
+    /// for path in GlobWalker::new(".", &pattern).ignore_files(".gitignore").ignore_files(".ignore") {
+    ///     println!("{}", path.unwrap().display());
+    /// }
+    /// ```
+    pub fn ignore_files(mut self, file_name: &str) -> Self {
+        self.ignore_file_names.push(String::from(file_name));
+        return self;
+    }
+
+    /// loads the ignore files of the directory at `relative_path`, if there are any.
+    fn load_ignore_files(&mut self, relative_path: &str, path: &Path, entries: &[(OsString, fs::Metadata)]) -> io::Result<()> {
+        self.ignores.leave(relative_path);
+        let mut rules = IgnoreRules::default();
+        for file_name in &self.ignore_file_names {
+            if entries.iter().any(|(name, metadata)| name == file_name.as_str() && !metadata.is_dir()) {
+                rules.append(IgnoreRules::load(self.root.join(path).join(file_name))?);
+            }
+        }
+        if !rules.is_empty() {
+            self.ignores.push(relative_path, rules);
+        }
+        return Ok(());
+    }
+
+    /// checks if the entry at `relative_path` is neither entered nor yielded: overrides exclude it
+    /// explicitly, or an ignore file excludes it and no include rule of the overrides matches it.
+    fn is_skipped(&self, relative_path: &str, is_dir: bool) -> bool {
+        return match self.overrides.matched_by_rule(relative_path, is_dir) {
+            OverrideMatch::Whitelist => false,
+            OverrideMatch::Ignore => true,
+            OverrideMatch::None => self.ignores.is_ignored(relative_path, is_dir),
+        };
+    }
+
+    /// reads the directory at `path`, which is matched as `relative_path`, and schedules its
+    /// entries that aren't ignored, so they are visited in the order of their names.
+    fn push_children(&mut self, relative_path: &str, path: &Path) {
+        self.stats.directories_visited += 1;
+        self.report_progress();
+        match read_sorted_dir(&self.root.join(path)) {
+            Err(error) => self.pending_errors.push_back(error),
+            Ok((entries, errors)) => {
+                self.pending_errors.extend(errors);
+                if let Err(error) = self.load_ignore_files(relative_path, path, &entries) {
+                    self.pending_errors.push_back(error);
+                }
+                for (name, metadata) in entries.into_iter().rev() {
+                    let lossy_name = name.to_string_lossy();
+                    let child_relative_path = if relative_path.is_empty() {
+                        lossy_name.into_owned()
+                    } else {
+                        format!("{}/{}", relative_path, lossy_name)
+                    };
+                    if self.is_skipped(&child_relative_path, metadata.is_dir()) {
+                        continue;
+                    }
+                    self.pending.push(PendingEntry { relative_path: child_relative_path, path: path.join(name), metadata: metadata });
+                }
+            }
+        }
+    }
+
+    /// turns the walker into an iterator over the matched entries together with their metadata.
+    /// ```no_run
+    /// use glob::{GlobWalker, ParsedGlobString};
+    /// let pattern = ParsedGlobString::try_from("*.log").unwrap();
+    /// let large_logs = GlobWalker::new("/var/log", &pattern).entries()
+    ///     .filter_map(Result::ok)
+    ///     .filter(|entry| entry.metadata().len() > 1 << 20);
+    /// for entry in large_logs {
+    ///     println!("{}", entry.path().display());
+    /// }
+    /// ```
+    pub fn entries(self) -> MatchedEntries<'p, 'g> {
+        return MatchedEntries { walker: self };
+    }
+
+    /// returns the next entry that matches, or the next error.
+    pub(crate) fn next_entry(&mut self) -> Option<io::Result<MatchedEntry>> {
+        if !self.started {
+            self.started = true;
+            if self.one_file_system {
+                self.root_device = fs::metadata(&self.root).ok().and_then(|metadata| (self.device_of)("", &metadata));
+            }
+            self.push_children("", Path::new(""));
+        }
+        loop {
+            if let Some(error) = self.pending_errors.pop_front() {
+                return Some(Err(error));
+            }
+            if is_cancelled(&self.cancel) {
+                // ends the walk after the error
+                self.pending.clear();
+                self.cancel = None;
+                return Some(Err(Cancelled.into()));
+            }
+            let Option::Some(entry) = self.pending.pop() else {
+                if let Option::Some(progress) = &mut self.progress {
+                    progress.finish(&self.stats);
+                }
+                return None;
+            };
+            if entry.metadata.is_dir() && self.pattern.could_match_below(&self.root, &entry.relative_path) && !self.is_on_other_file_system(&entry.relative_path, &entry.metadata) {
+                self.push_children(&entry.relative_path, &entry.path);
+            }
+            // directories that no include rule of the overrides matches are entered, but not yielded
+            if self.pattern.matches(&self.root, &entry.relative_path) && !self.overrides.is_ignored(&entry.relative_path, entry.metadata.is_dir()) {
+                let path = if self.relative {
+                    entry.path
+                } else {
+                    self.root.join(&entry.path)
+                };
+                self.stats.files_matched += 1;
+                self.report_progress();
+                return Some(Ok(MatchedEntry { path: path, metadata: entry.metadata }));
+            }
+        }
+    }
+}
+
+/// configures a [`GlobWalker`] that combines include patterns, ignore files and [`Overrides`] in
+/// one traversal, so tools don't have to layer them by hand. They are applied in this order:
+///
+/// 1. A rule of the overrides that matches an entry decides first: an excluded entry is skipped,
+///    an included one is kept even if an ignore file excludes it.
+/// 2. Otherwise, the ignore files decide, see [`GlobWalker::ignore_files`].
+/// 3. Kept entries are yielded if one of the include patterns matches them, or if there are none.
+///    If the overrides have include rules, entries that none of them matches are not yielded
+///    either, but directories are still entered.
+///
+/// Skipped directories are not entered, nor are directories below which no include pattern could
+/// match.
+/// ```no_run
+/// use glob::{Overrides, PathPattern, WalkBuilder};
+/// let mut overrides = Overrides::new();
+/// overrides.add("!*.min.js").unwrap();
+/// overrides.add("!vendor/").unwrap();
+/// let walker = WalkBuilder::new("web")
+///     .include(PathPattern::try_from("src/**/*.js").unwrap())
+///     .include(PathPattern::try_from("tests/**/*.js").unwrap())
+///     .ignore_files(".gitignore")
+///     .overrides(overrides)
+///     .build();
+/// for path in walker {
+///     println!("{}", path.unwrap().display());
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct WalkBuilder<'g> {
+    root: PathBuf,
+    includes: Vec<PathPattern<'g>>,
+    ignore_file_names: Vec<String>,
+    overrides: Overrides<'g>,
+    relative: bool,
+    one_file_system: bool,
+}
+
+impl<'g> WalkBuilder<'g> {
+    /// creates a builder for a walk of all entries below `root`.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        return WalkBuilder { root: root.into(), includes: Vec::new(), ignore_file_names: Vec::new(), overrides: Overrides::new(), relative: false, one_file_system: false };
+    }
+
+    /// adds a pattern for the paths to yield, relative to the root. Entries are yielded if any of
+    /// the patterns matches them.
+    pub fn include(mut self, pattern: PathPattern<'g>) -> Self {
+        self.includes.push(pattern);
+        return self;
+    }
+
+    /// makes the walk honor the ignore files with the given name, like `.gitignore`. Can be called
+    /// several times, see [`GlobWalker::ignore_files`].
+    pub fn ignore_files(mut self, file_name: &str) -> Self {
+        self.ignore_file_names.push(String::from(file_name));
+        return self;
+    }
+
+    /// sets the overrides, which take precedence over the ignore files.
+    pub fn overrides(mut self, overrides: Overrides<'g>) -> Self {
+        self.overrides = overrides;
+        return self;
+    }
+
+    /// sets whether the yielded paths are relative to the root instead of joined with it. Defaults
+    /// to `false`.
+    pub fn relative_paths(mut self, yes: bool) -> Self {
+        self.relative = yes;
+        return self;
+    }
+
+    /// makes the walk stay on the file system of the root, see [`GlobWalker::one_file_system`].
+    pub fn one_file_system(mut self, yes: bool) -> Self {
+        self.one_file_system = yes;
+        return self;
+    }
+
+    /// creates the walker.
+    pub fn build(self) -> GlobWalker<'static, 'g> {
+        let mut walker = GlobWalker::with_pattern(self.root, WalkPattern::Includes(self.includes));
+        walker.ignore_file_names = self.ignore_file_names;
+        walker.overrides = self.overrides;
+        walker.relative = self.relative;
+        walker.one_file_system = self.one_file_system;
+        return walker;
+    }
+}
+
+/// iterates over all entries below the directory `base` that match the [path pattern](PathPattern)
+/// `pattern`, which is relative to `base`. Unlike with [`GlobWalker`], the yielded paths are
+/// relative to `base` as well, e.g. `src/lib.rs` rather than `base/src/lib.rs`.
+/// ```no_run
+/// let rust_files: Vec<_> = glob::glob_in("project", "src/**/*.rs").unwrap().collect();
+/// ```
+pub fn glob_in<'g>(base: impl Into<PathBuf>, pattern: &'g str) -> Result<GlobWalker<'static, 'g>, GlobParseError<'g>> {
+    let pattern = PathPattern::try_from(pattern)?;
+    return Ok(GlobWalker::with_owned_path_pattern(base.into(), pattern, true));
+}
+
+/// returns the device of the file system an entry is on, if the platform tells. The relative path
+/// of the entry is only used by the tests.
+#[cfg(unix)]
+fn device(_relative_path: &str, metadata: &fs::Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    return Some(metadata.dev());
+}
+
+#[cfg(not(unix))]
+fn device(_relative_path: &str, _metadata: &fs::Metadata) -> Option<u64> {
+    return None;
+}
+
+/// the names of the entries of a directory together with their metadata.
+type DirEntries = Vec<(OsString, fs::Metadata)>;
+
+/// returns the entries of the directory at `path`, sorted by name (symbolic links are not
+/// followed), and the errors for entries that couldn't be read.
+fn read_sorted_dir(path: &Path) -> io::Result<(DirEntries, Vec<io::Error>)> {
+    let mut entries = Vec::new();
+    let mut errors = Vec::new();
+    for entry in fs::read_dir(path)? {
+        match entry.and_then(|entry| Ok((entry.file_name(), entry.metadata()?))) {
+            Ok(entry) => entries.push(entry),
+            Err(error) => errors.push(error),
+        }
+    }
+    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+    return Ok((entries, errors));
+}
+
+impl<'p, 'g> Iterator for GlobWalker<'p, 'g> {
+    type Item = io::Result<PathBuf>;
+    fn next(&mut self) -> Option<Self::Item> {
+        return self.next_entry().map(|entry| entry.map(MatchedEntry::into_path));
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::fs;
+    use std::io;
+    use std::path::{Path, PathBuf};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use crate::{Cancelled, Overrides, ParsedGlobString, PathPattern, WalkStats};
+    use super::{glob_in, GlobWalker, WalkBuilder};
+
+    /// creates a fresh directory below the system's temporary directory, containing the given
+    /// files (and their parent directories).
+    pub fn create_tree(name: &str, files: &[&str]) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("libglob-test-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&root);
+        for file in files {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, file).unwrap();
+        }
+        return root;
+    }
+
+    fn walk(root: &Path, pattern: &str) -> Vec<String> {
+        let pattern = ParsedGlobString::try_from(pattern).unwrap();
+        return GlobWalker::new(root, &pattern)
+            .map(|path| path.unwrap().strip_prefix(root).unwrap().to_string_lossy().replace('\\', "/"))
+            .collect();
+    }
+
+    #[test]
+    fn test_walk_finds_nested_files_in_order() {
+        let root = create_tree("walk-nested", &["b.rs", "a.rs", "src/lib.rs", "src/main.txt", "src/bin/cli.rs"]);
+        assert_eq!(walk(&root, "*.rs"), vec!["a.rs", "b.rs", "src/bin/cli.rs", "src/lib.rs"]);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_walk_matches_relative_path_completely() {
+        let root = create_tree("walk-complete", &["src/lib.rs", "src/lib.rs.orig", "lib.rs"]);
+        assert_eq!(walk(&root, "src/*"), vec!["src/lib.rs", "src/lib.rs.orig"]);
+        assert_eq!(walk(&root, "lib.rs"), vec!["lib.rs"]);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_walk_yields_directories() {
+        let root = create_tree("walk-dirs", &["src/bin/cli.rs"]);
+        assert_eq!(walk(&root, "src*"), vec!["src", "src/bin", "src/bin/cli.rs"]);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_walk_with_path_pattern() {
+        let root = create_tree("walk-path-pattern", &["a.rs", "src/lib.rs", "src/bin/cli.rs", "target/debug/build.rs"]);
+        let pattern = PathPattern::try_from("src/**/*.rs").unwrap();
+        let paths: Vec<_> = GlobWalker::with_path_pattern(&root, &pattern)
+            .map(|path| path.unwrap().strip_prefix(&root).unwrap().to_string_lossy().replace('\\', "/"))
+            .collect();
+        assert_eq!(paths, vec!["src/bin/cli.rs", "src/lib.rs"]);
+        let pattern = PathPattern::try_from("*.rs").unwrap();
+        assert_eq!(GlobWalker::with_path_pattern(&root, &pattern).count(), 1);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_glob_in_yields_relative_paths() {
+        let root = create_tree("glob-in", &["Cargo.toml", "src/lib.rs", "src/bin/cli.rs"]);
+        let paths: Vec<_> = glob_in(&root, "src/**/*.rs").unwrap().map(|path| path.unwrap()).collect();
+        assert_eq!(paths, vec![PathBuf::from("src/bin/cli.rs"), PathBuf::from("src/lib.rs")]);
+        assert!(glob_in(&root, "src/\\x").is_err());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_entries_carry_metadata() {
+        let root = create_tree("walk-entries", &["src/lib.rs", "src/main.rs.orig"]);
+        let pattern = ParsedGlobString::try_from("src*").unwrap();
+        let entries: Vec<_> = GlobWalker::new(&root, &pattern).entries().map(|entry| entry.unwrap()).collect();
+        assert_eq!(entries.len(), 3);
+        assert!(entries[0].file_type().is_dir());
+        assert_eq!(entries[1].path(), root.join("src/lib.rs"));
+        assert_eq!(entries[1].metadata().len(), "src/lib.rs".len() as u64);
+        assert!(entries[2].metadata().is_file());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_walk_with_nested_ignore_files() {
+        let root = create_tree("walk-ignore", &[
+            ".gitignore",
+            "a.log",
+            "target/debug/a.rs",
+            "src/lib.rs",
+            "src/gen/.gitignore",
+            "src/gen/keep.log",
+            "src/gen/out.rs",
+            "src/debug.log",
+        ]);
+        fs::write(root.join(".gitignore"), "/target/\n*.log\n").unwrap();
+        fs::write(root.join("src/gen/.gitignore"), "*.rs\n!keep.log\n").unwrap();
+        let pattern = ParsedGlobString::try_from("*").unwrap();
+        let paths: Vec<_> = GlobWalker::new(&root, &pattern).ignore_files(".gitignore")
+            .map(|path| path.unwrap().strip_prefix(&root).unwrap().to_string_lossy().replace('\\', "/"))
+            .collect();
+        assert_eq!(paths, vec![".gitignore", "src", "src/gen", "src/gen/.gitignore", "src/gen/keep.log", "src/lib.rs"]);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_walk_merges_several_ignore_files_per_directory() {
+        let root = create_tree("walk-ignore-merge", &[
+            ".gitignore",
+            ".ignore",
+            "a.log",
+            "b.log",
+            "docs/.ignore",
+            "docs/a.log",
+            "docs/b.log",
+            "docs/c.md",
+        ]);
+        fs::write(root.join(".gitignore"), "*.log\n*.md\n").unwrap();
+        fs::write(root.join(".ignore"), "!a.log\n").unwrap();
+        fs::write(root.join("docs/.ignore"), "!b.log\n").unwrap();
+        let pattern = ParsedGlobString::try_from("*").unwrap();
+        let walk = |walker: GlobWalker<'_, '_>| -> Vec<String> {
+            return walker.map(|path| path.unwrap().strip_prefix(&root).unwrap().to_string_lossy().replace('\\', "/")).collect();
+        };
+        let paths = walk(GlobWalker::new(&root, &pattern).ignore_files(".gitignore").ignore_files(".ignore"));
+        assert_eq!(paths, vec![".gitignore", ".ignore", "a.log", "docs", "docs/.ignore", "docs/a.log", "docs/b.log"]);
+        // the rules of names added later take precedence
+        let paths = walk(GlobWalker::new(&root, &pattern).ignore_files(".ignore").ignore_files(".gitignore"));
+        assert_eq!(paths, vec![".gitignore", ".ignore", "docs", "docs/.ignore", "docs/b.log"]);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_walk_builder_layers_overrides_over_ignore_files() {
+        let root = create_tree("walk-builder", &[
+            ".gitignore",
+            "a.log",
+            "README.md",
+            "docs/guide.md",
+            "src/lib.rs",
+            "src/gen/out.rs",
+            "target/x.rs",
+        ]);
+        fs::write(root.join(".gitignore"), "*.log\n/target/\n").unwrap();
+        let mut overrides = Overrides::new();
+        for rule in ["*.rs", "*.log", "!src/gen/"] {
+            overrides.add(rule).unwrap();
+        }
+        let walk = |builder: WalkBuilder<'static>| -> Vec<PathBuf> {
+            return builder.ignore_files(".gitignore").overrides(overrides.clone()).relative_paths(true).build().map(|path| path.unwrap()).collect();
+        };
+        assert_eq!(walk(WalkBuilder::new(&root)), vec![PathBuf::from("a.log"), PathBuf::from("src/lib.rs")]);
+        assert_eq!(walk(WalkBuilder::new(&root).include(PathPattern::try_from("src/**").unwrap())), vec![PathBuf::from("src/lib.rs")]);
+        let everything: Vec<_> = WalkBuilder::new(&root).ignore_files(".gitignore").relative_paths(true).build().map(|path| path.unwrap()).collect();
+        assert_eq!(everything.len(), 8);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_one_file_system_enters_directories_on_the_same_file_system() {
+        let root = create_tree("walk-one-file-system", &["a.rs", "src/lib.rs", "src/bin/cli.rs"]);
+        let pattern = ParsedGlobString::try_from("*.rs").unwrap();
+        assert_eq!(GlobWalker::new(&root, &pattern).one_file_system(true).count(), 3);
+        assert_eq!(WalkBuilder::new(&root).one_file_system(true).build().count(), 5);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_one_file_system_skips_directories_on_other_file_systems() {
+        let root = create_tree("walk-other-file-system", &["a.rs", "mnt/b.rs", "src/lib.rs", "src/proc/c.rs"]);
+        let pattern = ParsedGlobString::try_from("*").unwrap();
+        let walk = |one_file_system: bool| -> Vec<String> {
+            let mut walker = GlobWalker::new(&root, &pattern).one_file_system(one_file_system);
+            walker.device_of = |relative_path, _metadata| {
+                return if relative_path == "mnt" || relative_path == "src/proc" { Some(2) } else { Some(1) };
+            };
+            return walker.map(|path| path.unwrap().strip_prefix(&root).unwrap().to_string_lossy().replace('\\', "/")).collect();
+        };
+        // the directories are yielded, but not entered
+        assert_eq!(walk(true), vec!["a.rs", "mnt", "src", "src/lib.rs", "src/proc"]);
+        assert_eq!(walk(false).len(), 7);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_walk_keeps_names_that_are_not_unicode() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+        let root = create_tree("walk-not-unicode", &["a.rs"]);
+        let name = OsStr::from_bytes(b"caf\xE9");
+        fs::create_dir(root.join(name)).unwrap();
+        fs::write(root.join(name).join("lib.rs"), "").unwrap();
+        let pattern = ParsedGlobString::try_from("caf\u{FFFD}/*.rs").unwrap();
+        let paths: Vec<PathBuf> = GlobWalker::new(&root, &pattern).map(Result::unwrap).collect();
+        assert_eq!(paths, vec![root.join(name).join("lib.rs")]);
+        assert!(paths[0].exists());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_walk_reports_missing_root() {
+        let pattern = ParsedGlobString::try_from("*").unwrap();
+        let mut walker = GlobWalker::new("/this/path/does/not/exist", &pattern);
+        assert!(walker.next().unwrap().is_err());
+        assert!(walker.next().is_none());
+    }
+
+    #[test]
+    fn test_walk_stops_when_cancelled() {
+        let root = create_tree("walk-cancel", &["a.txt", "b.txt", "c/d.txt"]);
+        let pattern = ParsedGlobString::try_from("*").unwrap();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let mut walker = GlobWalker::new(&root, &pattern).cancel_on(Arc::clone(&cancel));
+        assert!(walker.next().unwrap().unwrap().ends_with("a.txt"));
+        cancel.store(true, Ordering::Relaxed);
+        let error = walker.next().unwrap().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::Interrupted);
+        assert_eq!(error.get_ref().unwrap().downcast_ref::<Cancelled>(), Some(&Cancelled));
+        assert!(walker.next().is_none());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_walk_reports_progress() {
+        let root = create_tree("walk-progress", &["a/x.txt", "a/y.txt", "b/z.txt"]);
+        let reports = Arc::new(Mutex::new(Vec::<WalkStats>::new()));
+        let pattern = ParsedGlobString::try_from("*.txt").unwrap();
+        let log = Arc::clone(&reports);
+        let walker = GlobWalker::new(&root, &pattern).on_progress(Duration::ZERO, move |stats| log.lock().unwrap().push(*stats));
+        assert_eq!(walker.count(), 3);
+        let last = *reports.lock().unwrap().last().unwrap();
+        assert_eq!((last.directories_visited(), last.files_matched(), last.bytes_scanned()), (3, 3, 0));
+        fs::remove_dir_all(root).unwrap();
+    }
+}
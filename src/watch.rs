@@ -0,0 +1,359 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard, RwLock};
+use std::time::SystemTime;
+use crate::{GlobSet, Matcher};
+#[cfg(feature = "notify")]
+use std::collections::VecDeque;
+#[cfg(feature = "notify")]
+use std::sync::mpsc;
+#[cfg(feature = "notify")]
+use std::time::{Duration, Instant};
+#[cfg(feature = "notify")]
+use crate::PathPattern;
+
+/// a [`GlobSet`] loaded from a file with one pattern per line (see [`GlobSet::from_lines`]) that is
+/// reloaded when the file changes, so long-running services pick up changed filters without a
+/// restart.
+///
+/// [`reload_if_changed`](Self::reload_if_changed) polls the modification time of the file; with the
+/// `notify` feature, `watch` reloads the set as soon as the file changes. A reload
+/// parses the whole file before it swaps the set, so matching never sees a partially loaded set,
+/// and a file with an invalid pattern leaves the previous set in place.
+/// ```no_run
+/// use glob::WatchedPatternSet;
+/// let deny = WatchedPatternSet::open("/etc/uploads/deny.txt").unwrap();
+/// loop {
+///     if let Err(error) = deny.reload_if_changed() {
+///         eprintln!("keeping the old patterns: {}", error);
+///     }
+///     let patterns = deny.current();
+///     // ... serve requests with `patterns` ...
+/// #   break;
+/// }
+/// ```
+#[derive(Debug)]
+pub struct WatchedPatternSet {
+    path: PathBuf,
+    current: RwLock<Arc<GlobSet<'static>>>,
+    version: Mutex<FileVersion>, // of the file the current set was loaded from
+}
+
+/// what identifies a version of a file when polling it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileVersion {
+    modified: SystemTime,
+    length: u64,
+}
+
+impl FileVersion {
+    fn of(path: &Path) -> io::Result<Self> {
+        let metadata = fs::metadata(path)?;
+        return Ok(FileVersion { modified: metadata.modified()?, length: metadata.len() });
+    }
+}
+
+fn load(path: &Path) -> io::Result<GlobSet<'static>> {
+    let contents = fs::read_to_string(path)?;
+    let set = GlobSet::from_lines(&path.display().to_string(), &contents)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error.to_string()))?;
+    return Ok(set.into_owned());
+}
+
+impl WatchedPatternSet {
+    /// loads the patterns from the file. Fails if the file can't be read or contains an invalid
+    /// pattern, with an error of kind [`InvalidData`](io::ErrorKind::InvalidData) in the latter case.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let version = FileVersion::of(&path)?;
+        let set = load(&path)?;
+        return Ok(WatchedPatternSet { path: path, current: RwLock::new(Arc::new(set)), version: Mutex::new(version) });
+    }
+
+    /// returns the path of the file.
+    pub fn path(&self) -> &Path {
+        return &self.path;
+    }
+
+    /// returns the current set. It stays valid, and unchanged, while the file is reloaded.
+    pub fn current(&self) -> Arc<GlobSet<'static>> {
+        // the lock only guards swapping an `Arc`, which can't leave it in an inconsistent state
+        let current = self.current.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+        return Arc::clone(&current);
+    }
+
+    fn version(&self) -> MutexGuard<'_, FileVersion> {
+        return self.version.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    }
+
+    fn swap(&self, set: GlobSet<'static>) {
+        *self.current.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = Arc::new(set);
+    }
+
+    /// reloads the patterns if the modification time or the size of the file changed since they
+    /// were loaded, and returns whether it did. If reloading fails, the current set is kept.
+    pub fn reload_if_changed(&self) -> io::Result<bool> {
+        // holding the lock while loading keeps concurrent reloads from swapping in an older set
+        let mut version = self.version();
+        let new_version = FileVersion::of(&self.path)?;
+        if new_version == *version {
+            return Ok(false);
+        }
+        self.swap(load(&self.path)?);
+        *version = new_version;
+        return Ok(true);
+    }
+
+    /// reloads the patterns, whether the file changed or not. If reloading fails, the current set is
+    /// kept.
+    pub fn reload(&self) -> io::Result<()> {
+        let mut version = self.version();
+        let new_version = FileVersion::of(&self.path)?;
+        self.swap(load(&self.path)?);
+        *version = new_version;
+        return Ok(());
+    }
+}
+
+#[cfg(feature = "notify")]
+impl WatchedPatternSet {
+    /// reloads the patterns whenever the file changes, until the returned watcher is dropped.
+    /// Errors while reloading keep the current set, like with
+    /// [`reload_if_changed`](Self::reload_if_changed).
+    /// ```no_run
+    /// use std::sync::Arc;
+    /// use glob::WatchedPatternSet;
+    /// let deny = Arc::new(WatchedPatternSet::open("deny.txt").unwrap());
+    /// let _watcher = deny.watch().unwrap();
+    /// assert!(!deny.current().is_match("report.pdf"));
+    /// ```
+    pub fn watch(self: &Arc<Self>) -> notify::Result<notify::RecommendedWatcher> {
+        use notify::Watcher;
+        let set = Arc::clone(self);
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            if let Ok(event) = event {
+                if event.paths.iter().any(|path| path.file_name() == set.path.file_name()) {
+                    let _ = set.reload_if_changed();
+                }
+            }
+        })?;
+        // editors often replace the file instead of writing to it, which ends a watch of the file
+        // itself, so the directory is watched instead
+        let directory = match self.path.parent() {
+            Option::Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        watcher.watch(directory, notify::RecursiveMode::NonRecursive)?;
+        return Ok(watcher);
+    }
+}
+
+/// the created and modified files and directories below a root directory whose path matches a
+/// [path pattern](PathPattern), as they are reported by the file system, for tools that rebuild
+/// when sources change. Created by [`watch_glob`]; the watch ends when it is dropped.
+///
+/// Like [`GlobWalker::with_path_pattern`](crate::GlobWalker::with_path_pattern), only the
+/// directories below which the pattern could match are watched. When such a directory is
+/// created, or moved into a watched one, it is watched as well, and the entries that were created
+/// in it before the watch started are reported as created. The yielded paths are the root
+/// directory, canonicalized, joined with the relative paths. A change can be reported more than
+/// once, e.g. as the creation of a file and as writes to it, so tools usually wait for a short
+/// pause in the changes before they act on them.
+#[cfg(feature = "notify")]
+pub struct GlobWatch<'g> {
+    root: PathBuf,
+    pattern: PathPattern<'g>,
+    watcher: notify::RecommendedWatcher,
+    events: mpsc::Receiver<notify::Result<notify::Event>>,
+    pending: VecDeque<notify::Result<PathBuf>>,
+}
+
+/// starts watching the directory `root` for created and modified entries whose path relative to
+/// `root` matches `pattern`, see [`GlobWatch`]. Requires the `notify` feature.
+/// ```no_run
+/// use glob::{watch_glob, PathPattern};
+/// let pattern = PathPattern::try_from("src/**/*.rs").unwrap();
+/// for path in watch_glob(".", pattern).unwrap() {
+///     println!("changed: {}", path.unwrap().display());
+/// }
+/// ```
+#[cfg(feature = "notify")]
+pub fn watch_glob<'g>(root: impl AsRef<Path>, pattern: PathPattern<'g>) -> notify::Result<GlobWatch<'g>> {
+    let root = fs::canonicalize(root)?;
+    let (sender, events) = mpsc::channel();
+    let watcher = notify::recommended_watcher(sender)?;
+    let mut watch = GlobWatch { root: root, pattern: pattern, watcher: watcher, events: events, pending: VecDeque::new() };
+    watch.watch_directory("", false)?;
+    return Ok(watch);
+}
+
+#[cfg(feature = "notify")]
+impl<'g> GlobWatch<'g> {
+    /// returns the canonicalized root directory.
+    pub fn root(&self) -> &Path {
+        return &self.root;
+    }
+
+    /// returns the next matching path like [`next`](Iterator::next), but gives up and returns
+    /// `None` if there is none within `timeout`.
+    pub fn next_timeout(&mut self, timeout: Duration) -> Option<notify::Result<PathBuf>> {
+        return self.next_until(Some(Instant::now() + timeout));
+    }
+
+    fn next_until(&mut self, deadline: Option<Instant>) -> Option<notify::Result<PathBuf>> {
+        loop {
+            if let Option::Some(path) = self.pending.pop_front() {
+                return Some(path);
+            }
+            let event = match deadline {
+                Option::Some(deadline) => self.events.recv_timeout(deadline.saturating_duration_since(Instant::now())).ok()?,
+                // the watcher, which holds the sender, lives as long as `self`
+                Option::None => self.events.recv().ok()?,
+            };
+            match event {
+                Ok(event) => self.handle(event),
+                Err(error) => return Some(Err(error)),
+            }
+        }
+    }
+
+    /// watches the directory at `relative_path` and the directories below it that the pattern
+    /// could match in. If `report` is `true`, the entries found in them that match are queued.
+    fn watch_directory(&mut self, relative_path: &str, report: bool) -> notify::Result<()> {
+        use notify::Watcher;
+        let path = self.root.join(relative_path);
+        // watching first reports the entries that are created while the directory is read
+        self.watcher.watch(&path, notify::RecursiveMode::NonRecursive)?;
+        for entry in fs::read_dir(&path)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let child = if relative_path.is_empty() { name } else { format!("{}/{}", relative_path, name) };
+            if report && self.pattern.matches(&child) {
+                self.pending.push_back(Ok(entry.path()));
+            }
+            if entry.file_type()?.is_dir() && self.pattern.could_match_below(&child) {
+                self.watch_directory(&child, report)?;
+            }
+        }
+        return Ok(());
+    }
+
+    /// queues the paths of an event that match, and watches the directories it creates.
+    fn handle(&mut self, event: notify::Event) {
+        use notify::event::{EventKind, ModifyKind};
+        let creates = match event.kind {
+            EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(_)) => true,
+            EventKind::Modify(ModifyKind::Metadata(_)) => return,
+            EventKind::Modify(_) => false,
+            _ => return,
+        };
+        for path in event.paths {
+            let Ok(relative) = path.strip_prefix(&self.root) else {
+                continue;
+            };
+            let relative: Vec<String> = relative.components().map(|component| component.as_os_str().to_string_lossy().into_owned()).collect();
+            let relative = relative.join("/");
+            if relative.is_empty() {
+                continue;
+            }
+            // the entry is gone again, or the event reports the old name of a renamed entry
+            let Ok(metadata) = fs::symlink_metadata(&path) else {
+                continue;
+            };
+            if self.pattern.matches(&relative) {
+                self.pending.push_back(Ok(path.clone()));
+            }
+            if creates && metadata.is_dir() && self.pattern.could_match_below(&relative) {
+                if let Err(error) = self.watch_directory(&relative, true) {
+                    self.pending.push_back(Err(error));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "notify")]
+impl<'g> Iterator for GlobWatch<'g> {
+    type Item = notify::Result<PathBuf>;
+    /// waits for the next created or modified path that matches.
+    fn next(&mut self) -> Option<Self::Item> {
+        return self.next_until(None);
+    }
+}
+
+impl Matcher for WatchedPatternSet {
+    fn is_match(&self, haystack: &str) -> bool {
+        return self.current().is_match(haystack);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io;
+    use std::time::{Duration, SystemTime};
+    use crate::walk::tests::create_tree;
+    use crate::Matcher;
+    use super::WatchedPatternSet;
+
+    fn write(path: &std::path::Path, contents: &str, modified: SystemTime) {
+        fs::write(path, contents).unwrap();
+        fs::File::options().write(true).open(path).unwrap().set_modified(modified).unwrap();
+    }
+
+    #[test]
+    fn test_reload_if_changed() {
+        let root = create_tree("watch-reload", &[]);
+        fs::create_dir_all(&root).unwrap();
+        let path = root.join("deny.txt");
+        let start = SystemTime::now();
+        write(&path, "*.exe\n", start);
+        let set = WatchedPatternSet::open(&path).unwrap();
+        let old = set.current();
+        assert!(set.is_match("setup.exe"));
+        assert!(!set.reload_if_changed().unwrap());
+
+        write(&path, "*.exe\n*.bat\n", start + Duration::from_secs(1));
+        assert!(set.reload_if_changed().unwrap());
+        assert!(set.is_match("run.bat"));
+        assert_eq!(old.len(), 1);
+
+        write(&path, "*.exe\nbad\\", start + Duration::from_secs(2));
+        let error = set.reload_if_changed().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error.to_string().ends_with("deny.txt:2: unterminated escape sequence at index 3"), "{}", error);
+        assert_eq!(set.current().len(), 2);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[cfg(feature = "notify")]
+    #[test]
+    fn test_watch_glob() {
+        use std::collections::BTreeSet;
+        use crate::{watch_glob, PathPattern};
+        let root = create_tree("watch-glob", &["src/lib.rs", "docs/a.md"]);
+        let mut watch = watch_glob(&root, PathPattern::try_from("src/**/*.rs").unwrap()).unwrap();
+        let root = watch.root().to_path_buf();
+        fs::write(root.join("src/main.rs"), "").unwrap();
+        fs::write(root.join("src/notes.txt"), "").unwrap();
+        fs::write(root.join("docs/b.rs"), "").unwrap();
+        fs::create_dir_all(root.join("src/bin/cli")).unwrap();
+        fs::write(root.join("src/bin/cli/main.rs"), "").unwrap();
+        fs::write(root.join("src/lib.rs"), "fn main() {}").unwrap();
+        let expected = BTreeSet::from(["src/bin/cli/main.rs", "src/lib.rs", "src/main.rs"].map(|path| root.join(path)));
+        let mut changed = BTreeSet::new();
+        while changed != expected {
+            let Option::Some(path) = watch.next_timeout(Duration::from_secs(5)) else {
+                break;
+            };
+            changed.insert(path.unwrap());
+        }
+        assert_eq!(changed, expected);
+        // nothing else is reported
+        while let Option::Some(path) = watch.next_timeout(Duration::from_millis(100)) {
+            assert!(expected.contains(&path.unwrap()));
+        }
+        fs::remove_dir_all(root).unwrap();
+    }
+}